@@ -0,0 +1,17 @@
+use web_sys::HtmlElement;
+
+use super::RenderBackend;
+
+impl RenderBackend for HtmlElement {
+    fn set_style(&mut self, property: &str, value: &str) {
+        self.style().set_property(property, value).unwrap();
+    }
+
+    fn set_class(&mut self, class: &str, is: bool) {
+        if is {
+            self.class_list().add_1(class).unwrap();
+        } else {
+            self.class_list().remove_1(class).unwrap();
+        }
+    }
+}
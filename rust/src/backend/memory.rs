@@ -0,0 +1,44 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::RenderBackend;
+
+/// Backend that just remembers what was rendered, used in native tests
+#[derive(Debug, Default)]
+pub struct MemoryBackend {
+    styles: BTreeMap<String, String>,
+    classes: BTreeSet<String>,
+}
+
+impl MemoryBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the value of an inline style property
+    pub fn style(&self, property: &str) -> Option<&str> {
+        self.styles.get(property).map(|value| value.as_str())
+    }
+
+    /// Determines whether the class was added
+    pub fn has_class(&self, class: &str) -> bool {
+        self.classes.contains(class)
+    }
+}
+
+impl RenderBackend for MemoryBackend {
+    fn set_style(&mut self, property: &str, value: &str) {
+        if value.is_empty() {
+            self.styles.remove(property);
+        } else {
+            self.styles.insert(property.into(), value.into());
+        }
+    }
+
+    fn set_class(&mut self, class: &str, is: bool) {
+        if is {
+            self.classes.insert(class.into());
+        } else {
+            self.classes.remove(class);
+        }
+    }
+}
@@ -0,0 +1,18 @@
+mod dom;
+
+#[cfg(test)]
+mod memory;
+#[cfg(test)]
+pub use memory::MemoryBackend;
+
+/// Visual representation of a single node of the editor tree (page, layout, component)
+///
+/// The model does not talk to the DOM directly, instead it describes its visual state through this trait.
+/// In the browser it is implemented by `HtmlElement`, in native tests `MemoryBackend` is used instead.
+pub trait RenderBackend {
+    /// Set an inline style property, an empty value removes it
+    fn set_style(&mut self, property: &str, value: &str);
+
+    /// Add or remove a class
+    fn set_class(&mut self, class: &str, is: bool);
+}
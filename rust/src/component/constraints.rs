@@ -0,0 +1,203 @@
+use serde::Deserialize;
+
+/// Size constraints of a kind of component, declared in the `constraints` of its descriptor
+///
+/// ```js
+/// editor.register_component({
+///   tag_name: "launch-table",
+///   parameters: {},
+///   constraints: { default_span: [4, 2], min_span: [2, 2], max_size: [800, 600] },
+/// });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(default)]
+pub struct SizeConstraints {
+    /// Span in cells of a component placed in a grid layout, `None` to fit its size in px
+    pub default_span: Option<(usize, usize)>,
+    /// Smallest span in cells in grid layouts
+    pub min_span: Option<(usize, usize)>,
+    /// Largest span in cells in grid layouts
+    pub max_span: Option<(usize, usize)>,
+
+    /// Smallest size in px in free and flex layouts
+    pub min_size: Option<(f64, f64)>,
+    /// Largest size in px in free and flex layouts
+    pub max_size: Option<(f64, f64)>,
+}
+
+impl SizeConstraints {
+    /// Parse the constraints from their JSON representation
+    ///
+    /// Invalid constraints are ignored, so that a typo in a descriptor does not break the editor
+    pub fn from_json(json: &str) -> Self {
+        let mut constraints: Self = serde_json::from_str(json).unwrap_or_default();
+
+        let spans = [
+            ("default_span", &mut constraints.default_span),
+            ("min_span", &mut constraints.min_span),
+            ("max_span", &mut constraints.max_span),
+        ];
+        for (name, span) in spans {
+            if span.is_some_and(|(width, height)| width == 0 || height == 0) {
+                log::warn!("Ignoring {}, spans have to be at least a single cell", name);
+                *span = None;
+            }
+        }
+
+        if let (Some(min), Some(max)) = (constraints.min_span, constraints.max_span) {
+            if min.0 > max.0 || min.1 > max.1 {
+                log::warn!("Ignoring span constraints, min span is larger than max span");
+                constraints.min_span = None;
+                constraints.max_span = None;
+            }
+        }
+
+        if let (Some(min), Some(max)) = (constraints.min_size, constraints.max_size) {
+            if min.0 > max.0 || min.1 > max.1 {
+                log::warn!("Ignoring size constraints, min size is larger than max size");
+                constraints.min_size = None;
+                constraints.max_size = None;
+            }
+        }
+
+        constraints
+    }
+
+    /// Span limits in grid layouts
+    pub fn span_limits(&self) -> SpanLimits {
+        SpanLimits {
+            min: self.min_span.unwrap_or((1, 1)),
+            max: self.max_span,
+        }
+    }
+
+    /// Span of a component newly placed in a grid layout
+    ///
+    /// # Arguments
+    /// * `fit` - span that fits the size of the component in px
+    pub fn initial_span(&self, fit: (usize, usize)) -> (usize, usize) {
+        self.span_limits().clamp(self.default_span.unwrap_or(fit))
+    }
+}
+
+/// Minimum and maximum span of a component in cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpanLimits {
+    pub min: (usize, usize),
+    /// `None` for no limit
+    pub max: Option<(usize, usize)>,
+}
+
+impl Default for SpanLimits {
+    fn default() -> Self {
+        Self {
+            min: (1, 1),
+            max: None,
+        }
+    }
+}
+
+impl SpanLimits {
+    /// Whether the span is within the limits
+    pub fn contains(&self, span: (usize, usize)) -> bool {
+        self.clamp(span) == span
+    }
+
+    /// Closest span within the limits
+    pub fn clamp(&self, (width, height): (usize, usize)) -> (usize, usize) {
+        let (max_width, max_height) = self.max.unwrap_or((usize::MAX, usize::MAX));
+
+        (
+            width.max(self.min.0).min(max_width),
+            height.max(self.min.1).min(max_height),
+        )
+    }
+
+    /// Whether a single axis of the span is within the limits
+    ///
+    /// # Arguments
+    /// * `axis` - 0 for the width, 1 for the height
+    pub fn contains_axis(&self, axis: usize, span: usize) -> bool {
+        let (min, max) = match axis {
+            0 => (self.min.0, self.max.map(|max| max.0)),
+            _ => (self.min.1, self.max.map(|max| max.1)),
+        };
+
+        span >= min && max.is_none_or(|max| span <= max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constraints_are_optional() {
+        let constraints = SizeConstraints::from_json("{}");
+        assert_eq!(constraints, SizeConstraints::default());
+        assert_eq!(constraints.initial_span((3, 1)), (3, 1));
+
+        let constraints = SizeConstraints::from_json("not json");
+        assert_eq!(constraints, SizeConstraints::default());
+    }
+
+    #[test]
+    fn initial_span_is_clamped() {
+        let constraints =
+            SizeConstraints::from_json(r#"{ "default_span": [4, 1], "min_span": [2, 2] }"#);
+        assert_eq!(constraints.initial_span((1, 1)), (4, 2));
+
+        let constraints = SizeConstraints::from_json(r#"{ "max_span": [2, 2] }"#);
+        assert_eq!(constraints.initial_span((5, 1)), (2, 1));
+    }
+
+    #[test]
+    fn inverted_span_limits_are_ignored() {
+        let constraints = SizeConstraints::from_json(
+            r#"{ "min_span": [3, 1], "max_span": [2, 2], "max_size": [100, 50] }"#,
+        );
+
+        assert_eq!(constraints.span_limits(), SpanLimits::default());
+        assert_eq!(constraints.max_size, Some((100.0, 50.0)));
+    }
+
+    #[test]
+    fn inverted_size_limits_are_ignored() {
+        let constraints = SizeConstraints::from_json(
+            r#"{ "min_size": [100, 10], "max_size": [200, 5], "min_span": [2, 1] }"#,
+        );
+
+        assert_eq!(constraints.min_size, None);
+        assert_eq!(constraints.max_size, None);
+        assert_eq!(constraints.min_span, Some((2, 1)));
+    }
+
+    #[test]
+    fn empty_spans_are_ignored() {
+        let constraints = SizeConstraints::from_json(
+            r#"{ "default_span": [0, 2], "min_span": [1, 0], "max_span": [3, 3] }"#,
+        );
+
+        assert_eq!(constraints.default_span, None);
+        assert_eq!(constraints.min_span, None);
+        assert_eq!(constraints.max_span, Some((3, 3)));
+        assert_eq!(constraints.initial_span((5, 1)), (3, 1));
+
+        let constraints = SizeConstraints::from_json(r#"{ "max_span": [0, 0] }"#);
+        assert_eq!(constraints.span_limits(), SpanLimits::default());
+    }
+
+    #[test]
+    fn span_limits_per_axis() {
+        let limits = SpanLimits {
+            min: (2, 1),
+            max: Some((4, 2)),
+        };
+
+        assert!(limits.contains((2, 2)));
+        assert!(!limits.contains((5, 2)));
+        assert!(limits.contains_axis(0, 4));
+        assert!(!limits.contains_axis(0, 1));
+        assert!(!limits.contains_axis(1, 3));
+    }
+}
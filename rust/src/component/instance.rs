@@ -1,5 +1,6 @@
 use std::{
     cell::{Ref, RefCell, RefMut},
+    collections::BTreeMap,
     rc::Rc,
 };
 
@@ -8,15 +9,40 @@ use wasm_bindgen::JsCast;
 use web_sys::{Element, HtmlElement};
 
 use crate::{
-    editor::hierarchy::HierarchyItemData, html_elements::component::EditorComponent, utils,
+    component::{ParameterError, ParameterSchema, SizeConstraints},
+    document::{is_attribute_name, ComponentDocument, PlacementDocument},
+    drag_controller::resize_controller::ResizeHandle,
+    editor::hierarchy::HierarchyItemData,
+    html_elements::component::EditorComponent,
+    ids,
+    page::{
+        breakpoint::BreakpointPlacement,
+        layout::{flex::FlexItem, grid::GridItem, LayoutKind},
+    },
+    utils,
 };
 
 struct InnerData {
     name: RefCell<String>,
+    /// Stable user-facing id, unlike `index` it is kept when the component is saved and loaded
+    uid: RefCell<String>,
 
     grid_size: RefCell<Option<(usize, usize)>>,
     grid_pos: RefCell<Option<(usize, usize)>>,
 
+    /// Absolute position in px, set with `Component::set_position`
+    position: RefCell<Option<(i32, i32)>>,
+    /// Size in px, set with `Component::set_size`
+    size: RefCell<Option<(f64, f64)>>,
+
+    /// Flex properties, used when the component is placed in a flex layout
+    flex_item: RefCell<FlexItem>,
+
+    /// Whether the component is hidden at the current breakpoint
+    hidden: RefCell<bool>,
+    /// Placement of the component at each breakpoint of the page [breakpoint name -> placement]
+    breakpoints: RefCell<BTreeMap<String, BreakpointPlacement>>,
+
     /// The index of a componetn
     ///
     /// Despite the fact that this is an `Option`, it is guaranteed to be initialized
@@ -42,12 +68,38 @@ impl Component {
 
         document.body().unwrap().append_child(&element).unwrap();
 
+        for handle in ResizeHandle::ALL.iter() {
+            let elm = document.create_element("div").unwrap();
+            elm.class_list().add_1("resize-handle").unwrap();
+            elm.set_attribute("data-handle", handle.name()).unwrap();
+            element.append_child(&elm).unwrap();
+        }
+
+        let descriptor = element.descriptor();
+        let name = descriptor.display_name();
+        let uid = ids::new_id(&descriptor.tag_name());
+
+        // New components start with the defaults declared in the descriptor
+        for schema in descriptor.parameter_schemas() {
+            if let Some(default) = &schema.default {
+                if !element.has_attribute(&schema.name) {
+                    element.set_attribute(&schema.name, default).unwrap();
+                }
+            }
+        }
+
         Self {
             element,
             data: Rc::new(InnerData {
-                name: RefCell::new("Component".into()),
+                name: RefCell::new(name),
+                uid: RefCell::new(uid),
                 grid_size: Default::default(),
                 grid_pos: Default::default(),
+                position: Default::default(),
+                size: Default::default(),
+                flex_item: Default::default(),
+                hidden: Default::default(),
+                breakpoints: Default::default(),
                 index: Default::default(),
                 layout: Default::default(),
 
@@ -73,10 +125,30 @@ impl Component {
         self.data.index.replace(Some(id));
     }
 
+    /// Key of the component in the cells of grid layouts
+    pub fn grid_item(&self) -> GridItem {
+        GridItem::Component(self.index())
+    }
+
     pub fn name(&self) -> Ref<str> {
         Ref::map(self.data.name.borrow(), |name| name.as_ref())
     }
 
+    pub fn set_name(&mut self, name: &str) {
+        self.data.name.replace(name.into());
+    }
+
+    /// Stable user-facing id of the component, for example `launch-table-3`
+    pub fn uid(&self) -> Ref<'_, str> {
+        Ref::map(self.data.uid.borrow(), |uid| uid.as_ref())
+    }
+
+    /// Replace the id, used when a component is loaded from a document
+    pub fn set_uid(&mut self, uid: &str) {
+        ids::reserve_id(uid);
+        self.data.uid.replace(uid.into());
+    }
+
     pub fn layout(&self) -> Option<HtmlElement> {
         self.data.layout.borrow().clone()
     }
@@ -160,7 +232,13 @@ impl Component {
         }
     }
 
+    /// Get the last position set with `Component::set_position`
+    pub fn position(&self) -> Option<(i32, i32)> {
+        *self.data.position.borrow()
+    }
+
     pub fn set_position(&self, (x, y): (i32, i32)) {
+        self.data.position.replace(Some((x, y)));
         self.element
             .style()
             .set_property("left", &format!("{}px", x))
@@ -171,14 +249,25 @@ impl Component {
             .unwrap();
     }
 
+    /// Size constraints declared in the descriptor of the component
+    pub fn constraints(&self) -> SizeConstraints {
+        self.element.descriptor().constraints()
+    }
+
     /// Get size in px
     pub fn size(&self) -> (f64, f64) {
         let bbox = self.element.get_bounding_client_rect();
         (bbox.width(), bbox.height())
     }
 
+    /// Get the last size set with `Component::set_size`
+    pub fn stored_size(&self) -> Option<(f64, f64)> {
+        *self.data.size.borrow()
+    }
+
     /// Set size in px
     pub fn set_size(&self, w: f64, h: f64) {
+        self.data.size.replace(Some((w, h)));
         self.element
             .style()
             .set_property("width", &format!("{}px", w))
@@ -191,6 +280,7 @@ impl Component {
 
     /// Unsets absolute pos
     pub fn unset_absolute_pos(&self) {
+        self.data.position.replace(None);
         self.element.style().remove_property("top").unwrap();
         self.element.style().remove_property("left").unwrap();
         self.element.style().remove_property("position").unwrap();
@@ -198,10 +288,206 @@ impl Component {
 
     /// Unsets absolute pos
     pub fn unset_size(&self) {
+        self.data.size.replace(None);
         self.element.style().remove_property("width").unwrap();
         self.element.style().remove_property("height").unwrap();
     }
 
+    /// Flex properties of the component
+    pub fn flex_item(&self) -> FlexItem {
+        *self.data.flex_item.borrow()
+    }
+
+    pub fn set_flex_item(&self, item: FlexItem) {
+        self.data.flex_item.replace(item);
+
+        let mut element: HtmlElement = self.element.clone().into();
+        item.render(&mut element);
+    }
+
+    /// Whether the component is hidden at the current breakpoint
+    pub fn is_hidden(&self) -> bool {
+        *self.data.hidden.borrow()
+    }
+
+    pub fn set_hidden(&self, is: bool) {
+        self.data.hidden.replace(is);
+
+        if is {
+            self.element
+                .class_list()
+                .add_1("breakpoint-hidden")
+                .unwrap();
+        } else {
+            self.element
+                .class_list()
+                .remove_1("breakpoint-hidden")
+                .unwrap();
+        }
+    }
+
+    /// Current placement of the component, as it would be stored for a breakpoint
+    ///
+    /// # Arguments
+    /// * `kind` - kind of the layout that the component is placed in
+    pub fn current_placement(&self, kind: &LayoutKind) -> BreakpointPlacement {
+        match (kind, self.grid_pos(), self.grid_size()) {
+            _ if self.is_hidden() => BreakpointPlacement::Hidden,
+            (LayoutKind::Grid { .. }, Some(pos), Some(size)) => {
+                BreakpointPlacement::Grid { pos, size }
+            }
+            _ => BreakpointPlacement::Visible,
+        }
+    }
+
+    /// Placement of the component at a breakpoint, `None` if it was never arranged there
+    pub fn breakpoint_placement(&self, breakpoint: &str) -> Option<BreakpointPlacement> {
+        self.data.breakpoints.borrow().get(breakpoint).copied()
+    }
+
+    /// Remember the placement of the component at a breakpoint
+    pub fn set_breakpoint_placement(&self, breakpoint: &str, placement: BreakpointPlacement) {
+        self.data
+            .breakpoints
+            .borrow_mut()
+            .insert(breakpoint.into(), placement);
+    }
+
+    /// Forget the placement of the component at a breakpoint, returns the forgotten one
+    pub fn remove_breakpoint_placement(&self, breakpoint: &str) -> Option<BreakpointPlacement> {
+        self.data.breakpoints.borrow_mut().remove(breakpoint)
+    }
+
+    /// Values of all descriptor parameters set on the component
+    pub fn attributes(&self) -> BTreeMap<String, String> {
+        self.element
+            .descriptor()
+            .parameters()
+            .iter()
+            .filter_map(|param| {
+                let name = param.name();
+                self.element.get_attribute(&name).map(|value| (name, value))
+            })
+            .collect()
+    }
+
+    /// Validate a value of a descriptor parameter, and write it as an attribute of the component
+    ///
+    /// Empty values of optional parameters remove the attribute
+    pub fn set_parameter(
+        &self,
+        schema: &ParameterSchema,
+        value: &str,
+    ) -> Result<(), ParameterError> {
+        let value = schema.validate(value)?;
+
+        if value.is_empty() {
+            self.element.remove_attribute(&schema.name).unwrap();
+        } else {
+            self.element.set_attribute(&schema.name, &value).unwrap();
+        }
+
+        Ok(())
+    }
+
+    /// Take a serializable snapshot of the component
+    ///
+    /// # Arguments
+    /// * `kind` - kind of the layout that the component is placed in
+    pub fn to_document(&self, kind: &LayoutKind) -> ComponentDocument {
+        ComponentDocument {
+            id: Some(self.uid().to_owned()),
+            name: self.name().to_owned(),
+            tag_name: self.element.descriptor().tag_name(),
+            attributes: self.attributes(),
+            placement: self.placement(kind),
+            breakpoints: self.data.breakpoints.borrow().clone(),
+        }
+    }
+
+    /// Serializable position of the component inside of a layout of the kind
+    pub fn placement(&self, kind: &LayoutKind) -> PlacementDocument {
+        match kind {
+            LayoutKind::Grid { .. } => PlacementDocument::Grid {
+                pos: self.grid_pos().unwrap_or((1, 1)),
+                size: self.grid_size().unwrap_or((1, 1)),
+            },
+            LayoutKind::Free { .. } => PlacementDocument::Free {
+                position: self.position().unwrap_or((0, 0)),
+                size: self.stored_size(),
+            },
+            LayoutKind::Flex { .. } => PlacementDocument::Flex {
+                item: self.flex_item(),
+            },
+        }
+    }
+
+    /// Apply attributes and placement from a serialized snapshot
+    pub fn apply_document(&mut self, document: &ComponentDocument) {
+        self.set_name(&document.name);
+        if let Some(uid) = &document.id {
+            self.set_uid(uid);
+        }
+
+        let schemas = self.element.descriptor().parameter_schemas();
+        for (name, value) in document.attributes.iter() {
+            let result = match schemas.iter().find(|schema| &schema.name == name) {
+                Some(schema) => self.set_parameter(schema, value),
+                // Attributes of parameters that are no longer declared are kept as they are
+                None if is_attribute_name(name) => {
+                    self.element.set_attribute(name, value).unwrap();
+                    Ok(())
+                }
+                None => {
+                    log::warn!(
+                        "Ignoring `{}` of component `{}`: not a valid attribute name",
+                        name,
+                        document.name
+                    );
+                    continue;
+                }
+            };
+
+            if let Err(err) = result {
+                log::warn!(
+                    "Ignoring `{}` of component `{}`: {}",
+                    name,
+                    document.name,
+                    err
+                );
+            }
+        }
+
+        self.data.breakpoints.replace(document.breakpoints.clone());
+
+        self.apply_placement(&document.placement);
+    }
+
+    /// Apply a serializable position of the component
+    pub fn apply_placement(&mut self, placement: &PlacementDocument) {
+        match *placement {
+            PlacementDocument::Grid { pos, size } => {
+                self.set_grid_pos(pos);
+                self.set_grid_size(size);
+            }
+            PlacementDocument::Free { position, size } => {
+                self.set_position(position);
+                if let Some((w, h)) = size {
+                    self.set_size(w, h);
+                }
+            }
+            PlacementDocument::Flex { item } => self.set_flex_item(item),
+        }
+    }
+
+    /// Revert the `Component::remove`, used when removal gets undone
+    pub fn restore(&self) {
+        self.element
+            .class_list()
+            .remove_1("death-animation")
+            .unwrap();
+    }
+
     pub fn remove(&self) {
         self.element.class_list().add_1("death-animation").unwrap();
 
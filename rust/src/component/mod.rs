@@ -3,3 +3,12 @@ pub use source::ComponentSource;
 
 mod instance;
 pub use instance::Component;
+
+mod constraints;
+pub use constraints::{SizeConstraints, SpanLimits};
+
+mod schema;
+pub use schema::{ParameterError, ParameterKind, ParameterSchema};
+
+/// Descriptor parameter that holds the text content of a component
+pub const TEXT_PARAMETER: &str = "innertext";
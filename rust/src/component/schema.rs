@@ -0,0 +1,407 @@
+use std::fmt;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Kind of value that a descriptor parameter holds
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ParameterKind {
+    /// Hex color, `#rgb` or `#rrggbb`
+    Color,
+    Text {
+        /// Whether the text is edited in a text area
+        #[serde(default)]
+        multiline: bool,
+    },
+    Number {
+        min: Option<f64>,
+        max: Option<f64>,
+        /// Values are multiples of the step, counted from `min` (or from 0)
+        step: Option<f64>,
+    },
+    /// `true` or `false`
+    Boolean,
+    LayoutStyle,
+    /// One of the options
+    Select {
+        options: Vec<String>,
+    },
+    /// Absolute `http(s)`, `mailto` or `tel` url, or a relative one
+    Url,
+    /// Url of an image, data urls of images included
+    Image,
+}
+
+/// Parameter of a kind of component, declared in the `parameters` of its descriptor
+///
+/// A parameter is either declared by its type alone, or by an object with the type and its options
+///
+/// ```js
+/// editor.register_component({
+///   tag_name: "launch-pagination",
+///   parameters: {
+///     label: "text",
+///     pages: { type: "number", default: 5, min: 1, max: 20, step: 1, required: true },
+///     size: { type: "select", options: ["small", "medium", "large"], default: "medium" },
+///   },
+/// });
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterSchema {
+    /// Name of the attribute that holds the value
+    pub name: String,
+    pub kind: ParameterKind,
+    /// Value that new components get, already validated
+    pub default: Option<String>,
+    /// Whether the value can not be empty
+    pub required: bool,
+}
+
+/// Parameter as it is written in the descriptor
+#[derive(Deserialize)]
+struct RawSchema {
+    #[serde(flatten)]
+    kind: ParameterKind,
+    default: Option<Value>,
+    #[serde(default)]
+    required: bool,
+}
+
+/// Reasons for a value to be rejected by a parameter
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParameterError {
+    /// The parameter is required, but the value is empty
+    Required,
+    /// The value is not of the kind of the parameter, like `abc` for a number
+    Invalid(&'static str),
+    /// The number is outside of the range of the parameter
+    OutOfRange { min: Option<f64>, max: Option<f64> },
+    /// The number is not a multiple of the step
+    NotOnStep(f64),
+    /// The value is not one of the options
+    UnknownOption(Vec<String>),
+}
+
+impl fmt::Display for ParameterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Required => write!(f, "A value is required"),
+            Self::Invalid(expected) => write!(f, "Expected {}", expected),
+            Self::OutOfRange { min, max } => match (min, max) {
+                (Some(min), Some(max)) => write!(f, "Expected a number from {} to {}", min, max),
+                (Some(min), None) => write!(f, "Expected a number of at least {}", min),
+                (None, Some(max)) => write!(f, "Expected a number of at most {}", max),
+                (None, None) => write!(f, "Expected a number"),
+            },
+            Self::NotOnStep(step) => write!(f, "Expected a multiple of {}", step),
+            Self::UnknownOption(options) => {
+                write!(f, "Expected one of `{}`", options.join("`, `"))
+            }
+        }
+    }
+}
+
+impl ParameterSchema {
+    /// Parse a parameter from the JSON representation of its declaration
+    ///
+    /// Invalid declarations and defaults are ignored, so that a typo in a descriptor does not break the editor,
+    /// the parameter becomes a plain text one
+    pub fn from_json(name: &str, json: &str) -> Self {
+        let value = match serde_json::from_str(json) {
+            // Shorthand with the type alone, `"color"`
+            Ok(Value::String(kind)) => serde_json::json!({ "type": kind }),
+            Ok(value) => value,
+            Err(_) => Value::Null,
+        };
+
+        let raw: RawSchema = match serde_json::from_value(value) {
+            Ok(raw) => raw,
+            Err(err) => {
+                log::warn!("Invalid parameter `{}`, treated as text: {}", name, err);
+                RawSchema {
+                    kind: ParameterKind::Text { multiline: false },
+                    default: None,
+                    required: false,
+                }
+            }
+        };
+
+        let mut schema = Self {
+            name: name.to_owned(),
+            kind: raw.kind,
+            default: None,
+            required: raw.required,
+        };
+        schema.check_kind();
+
+        schema.default = raw.default.and_then(|default| {
+            let default = match default {
+                Value::String(text) => text,
+                Value::Null => return None,
+                value => value.to_string(),
+            };
+
+            match schema.validate(&default) {
+                Ok(default) => Some(default),
+                Err(err) => {
+                    log::warn!("Ignoring default of parameter `{}`: {}", name, err);
+                    None
+                }
+            }
+        });
+
+        schema
+    }
+
+    /// Drop the options that contradict each other, like `constraints` of a descriptor do
+    fn check_kind(&mut self) {
+        match &mut self.kind {
+            ParameterKind::Number { min, max, step } => {
+                if let (Some(lower), Some(upper)) = (*min, *max) {
+                    if lower > upper {
+                        log::warn!(
+                            "Ignoring range of parameter `{}`, min is larger than max",
+                            self.name
+                        );
+                        *min = None;
+                        *max = None;
+                    }
+                }
+
+                if step.is_some_and(|step| step <= 0.0) {
+                    log::warn!(
+                        "Ignoring step of parameter `{}`, it is not positive",
+                        self.name
+                    );
+                    *step = None;
+                }
+            }
+            ParameterKind::Select { options } if options.is_empty() => {
+                log::warn!("Parameter `{}` has no options, treated as text", self.name);
+                self.kind = ParameterKind::Text { multiline: false };
+            }
+            _ => {}
+        }
+    }
+
+    /// Value that an input shows for a component without the attribute
+    pub fn initial_value(&self) -> String {
+        if let Some(default) = &self.default {
+            return default.clone();
+        }
+
+        match &self.kind {
+            ParameterKind::Color => "#000000".into(),
+            ParameterKind::Number { min, max, .. } => {
+                let number = min.unwrap_or(0.0);
+                max.map_or(number, |max| number.min(max)).to_string()
+            }
+            ParameterKind::Boolean => "false".into(),
+            ParameterKind::Select { options } => options[0].clone(),
+            _ => String::new(),
+        }
+    }
+
+    /// Check a value before it is written as an attribute of a component
+    ///
+    /// Returns the value as it should be written, colors in lower case and numbers without spaces
+    pub fn validate(&self, value: &str) -> Result<String, ParameterError> {
+        let trimmed = value.trim();
+
+        if trimmed.is_empty() {
+            return if self.required {
+                Err(ParameterError::Required)
+            } else {
+                Ok(String::new())
+            };
+        }
+
+        match &self.kind {
+            ParameterKind::Text { .. } | ParameterKind::LayoutStyle => Ok(value.to_owned()),
+            ParameterKind::Color => {
+                let hex = trimmed
+                    .strip_prefix('#')
+                    .filter(|hex| hex.len() == 3 || hex.len() == 6)
+                    .filter(|hex| hex.chars().all(|c| c.is_ascii_hexdigit()));
+
+                match hex {
+                    Some(_) => Ok(trimmed.to_ascii_lowercase()),
+                    None => Err(ParameterError::Invalid("a color like `#2ecc71`")),
+                }
+            }
+            ParameterKind::Number { min, max, step } => {
+                let number: f64 = trimmed
+                    .parse()
+                    .ok()
+                    .filter(|number: &f64| number.is_finite())
+                    .ok_or(ParameterError::Invalid("a number"))?;
+
+                if min.is_some_and(|min| number < min) || max.is_some_and(|max| number > max) {
+                    return Err(ParameterError::OutOfRange {
+                        min: *min,
+                        max: *max,
+                    });
+                }
+
+                if let Some(step) = *step {
+                    let steps = (number - min.unwrap_or(0.0)) / step;
+                    if (steps - steps.round()).abs() > 1e-9 {
+                        return Err(ParameterError::NotOnStep(step));
+                    }
+                }
+
+                Ok(trimmed.to_owned())
+            }
+            ParameterKind::Boolean => match trimmed {
+                "true" | "false" => Ok(trimmed.to_owned()),
+                _ => Err(ParameterError::Invalid("`true` or `false`")),
+            },
+            ParameterKind::Select { options } => {
+                if options.iter().any(|option| option == trimmed) {
+                    Ok(trimmed.to_owned())
+                } else {
+                    Err(ParameterError::UnknownOption(options.clone()))
+                }
+            }
+            ParameterKind::Url | ParameterKind::Image => {
+                let image = self.kind == ParameterKind::Image;
+
+                if is_url(trimmed, image) {
+                    Ok(trimmed.to_owned())
+                } else if image {
+                    Err(ParameterError::Invalid(
+                        "an image address or a relative path",
+                    ))
+                } else {
+                    Err(ParameterError::Invalid("a web address or a relative path"))
+                }
+            }
+        }
+    }
+}
+
+/// Whether a value is an url that components can link to
+///
+/// # Arguments
+/// * `image` - whether data urls of images are allowed too
+fn is_url(value: &str, image: bool) -> bool {
+    if value.contains(char::is_whitespace) {
+        return false;
+    }
+
+    // The scheme ends at the first `:`, unless a path starts before it
+    let scheme = value
+        .find(':')
+        .filter(|end| !value[..*end].contains(['/', '?', '#']))
+        .map(|end| value[..end].to_ascii_lowercase());
+
+    match scheme.as_deref() {
+        None => true,
+        Some("http") | Some("https") => value.len() > value.find("//").map_or(0, |start| start + 2),
+        Some("mailto") | Some("tel") => !image,
+        Some("data") => image && value[5..].to_ascii_lowercase().starts_with("image/"),
+        Some(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parameters_are_declared_by_type_or_by_schema() {
+        let schema = ParameterSchema::from_json("color", r#""color""#);
+        assert_eq!(schema.kind, ParameterKind::Color);
+        assert_eq!(schema.default, None);
+        assert_eq!(schema.initial_value(), "#000000");
+
+        let schema = ParameterSchema::from_json(
+            "pages",
+            r#"{ "type": "number", "default": 5, "min": 1, "max": 20, "step": 1, "required": true }"#,
+        );
+        assert_eq!(
+            schema.kind,
+            ParameterKind::Number {
+                min: Some(1.0),
+                max: Some(20.0),
+                step: Some(1.0)
+            }
+        );
+        assert_eq!(schema.default.as_deref(), Some("5"));
+        assert!(schema.required);
+
+        let schema = ParameterSchema::from_json(
+            "size",
+            r#"{ "type": "select", "options": ["small", "large"] }"#,
+        );
+        assert_eq!(schema.initial_value(), "small");
+    }
+
+    #[test]
+    fn invalid_declarations_are_ignored() {
+        let schema = ParameterSchema::from_json("label", r#""colour""#);
+        assert_eq!(schema.kind, ParameterKind::Text { multiline: false });
+
+        let schema = ParameterSchema::from_json(
+            "size",
+            r#"{ "type": "select", "options": ["small", "large"], "default": "huge" }"#,
+        );
+        assert_eq!(schema.default, None);
+
+        let schema = ParameterSchema::from_json(
+            "count",
+            r#"{ "type": "number", "min": 10, "max": 1, "step": -1 }"#,
+        );
+        assert_eq!(
+            schema.kind,
+            ParameterKind::Number {
+                min: None,
+                max: None,
+                step: None
+            }
+        );
+    }
+
+    #[test]
+    fn values_are_validated() {
+        let color = ParameterSchema::from_json("color", r#""color""#);
+        assert_eq!(color.validate("#2ECC71"), Ok("#2ecc71".into()));
+        assert_eq!(color.validate(""), Ok("".into()));
+        assert!(color.validate("green").is_err());
+
+        let pages = ParameterSchema::from_json(
+            "pages",
+            r#"{ "type": "number", "min": 1, "max": 20, "step": 0.5, "required": true }"#,
+        );
+        assert_eq!(pages.validate(" 2.5 "), Ok("2.5".into()));
+        assert_eq!(pages.validate(""), Err(ParameterError::Required));
+        assert_eq!(
+            pages.validate("30"),
+            Err(ParameterError::OutOfRange {
+                min: Some(1.0),
+                max: Some(20.0)
+            })
+        );
+        assert_eq!(pages.validate("2.25"), Err(ParameterError::NotOnStep(0.5)));
+        assert_eq!(
+            pages.validate("NaN").unwrap_err().to_string(),
+            "Expected a number"
+        );
+
+        let disabled = ParameterSchema::from_json("disabled", r#""boolean""#);
+        assert!(disabled.validate("yes").is_err());
+
+        let link = ParameterSchema::from_json("href", r#""url""#);
+        assert!(link.validate("https://example.com/a?b=c").is_ok());
+        assert!(link.validate("../blog/first-post").is_ok());
+        assert!(link.validate("mailto:hello@example.com").is_ok());
+        assert!(link.validate("javascript:alert(1)").is_err());
+        assert!(link.validate("https://").is_err());
+
+        let image = ParameterSchema::from_json("src", r#""image""#);
+        assert!(image.validate("data:image/png;base64,AAAA").is_ok());
+        assert!(image.validate("data:text/html,hi").is_err());
+        assert!(image.validate("mailto:hello@example.com").is_err());
+    }
+}
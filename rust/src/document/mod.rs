@@ -0,0 +1,558 @@
+use std::{collections::BTreeMap, fmt};
+
+use serde::{Deserialize, Serialize};
+
+use crate::page::{
+    breakpoint::{Breakpoint, BreakpointPlacement},
+    layout::{
+        flex::{FlexDirection, FlexItem},
+        free::SNAP_GRID,
+        grid::CollisionStrategy,
+        ChildPlacement,
+    },
+    metadata::PageMetadata,
+};
+
+/// Version of the document format written by `Document::to_json`
+///
+/// Bump it whenever the layout of the structs below changes in a non backwards compatible way
+pub const DOCUMENT_VERSION: u32 = 1;
+
+/// Serializable snapshot of the whole workspace
+///
+/// Unlike `Workspace`, it does not reference any DOM nodes,
+/// so it can be saved to a file and used to rebuild the editor later on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Document {
+    /// Version of the format, see `DOCUMENT_VERSION`
+    pub version: u32,
+    /// List of all pages in the workspace
+    pub pages: Vec<PageDocument>,
+}
+
+/// Serializable snapshot of a `Page`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageDocument {
+    /// The name of a page
+    pub name: String,
+    /// Width of a page in px
+    pub width: u32,
+    /// Breakpoints of a page, sorted by width
+    #[serde(default)]
+    pub breakpoints: Vec<Breakpoint>,
+    /// Name of the breakpoint that the page was saved at, `layouts` are arranged for it
+    #[serde(default)]
+    pub breakpoint: Option<String>,
+    /// Route, title and description of a page
+    #[serde(default)]
+    pub metadata: PageMetadata,
+    /// List of layouts inside of a page, laid out one under the other
+    pub layouts: Vec<LayoutDocument>,
+}
+
+/// Serializable snapshot of a `Layout`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayoutDocument {
+    /// Stable id of a layout, `None` to generate a new one
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Name of a layout
+    pub name: String,
+    /// Width of a layout in px
+    pub width: u32,
+    /// Height of a layout in px
+    pub height: u32,
+    /// Layout kind specyfic data
+    pub kind: LayoutKindDocument,
+    /// Children of a layout
+    pub components: Vec<ComponentDocument>,
+    /// Placement inside of the parent layout, `None` for layouts placed directly in a page
+    #[serde(default)]
+    pub placement: Option<ChildPlacement>,
+    /// Placement inside of a parent grid at each breakpoint of the page
+    #[serde(default)]
+    pub breakpoints: BTreeMap<String, ChildPlacement>,
+    /// Layouts nested inside of this one
+    #[serde(default)]
+    pub children: Vec<LayoutDocument>,
+}
+
+/// Serializable counterpart of `LayoutKind`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LayoutKindDocument {
+    /// Layout based on absolute position
+    Free {
+        /// Size of the pixel grid that components snap to, 0 turns it off
+        #[serde(default = "default_snap_grid")]
+        snap_grid: u32,
+        /// Whether components snap to the edges and centers of other components and of the layout
+        #[serde(default = "default_snap_to_items")]
+        snap_to_items: bool,
+    },
+    /// CSS Flexbox based layout
+    Flex {
+        /// Tailwind `justify-*` class of the layout
+        justify: String,
+        /// Tailwind `items-*` class of the layout
+        align: String,
+        /// Main axis of the layout
+        #[serde(default)]
+        direction: FlexDirection,
+        /// Whether items that do not fit are moved to the next line
+        ///
+        /// Documents written before it could be turned off always wrap
+        #[serde(default = "default_flex_wrap")]
+        wrap: bool,
+        /// Space between the items in px
+        #[serde(default)]
+        gap: u32,
+    },
+    /// CSS Grid based layout
+    Grid {
+        /// Width of a grid column in px
+        #[serde(alias = "cell_size")]
+        column_width: u32,
+        /// Height of a grid row in px
+        ///
+        /// Documents written before rows had their own height use square cells
+        #[serde(default)]
+        row_height: Option<u32>,
+        /// Space between columns and rows in px
+        #[serde(default)]
+        gap: u32,
+        /// Total width of the grid in cells
+        columns: usize,
+        /// Total height of the grid in cells
+        rows: usize,
+        /// What happens when a component is dropped onto occupied cells
+        #[serde(default)]
+        collision: CollisionStrategy,
+        /// Add rows when components are dropped or resized past the last row
+        #[serde(default)]
+        auto_grow: bool,
+        /// Remove trailing empty rows after a drop
+        #[serde(default)]
+        auto_shrink: bool,
+        /// Column count of the grid at each breakpoint of the page
+        #[serde(default)]
+        breakpoint_columns: BTreeMap<String, usize>,
+    },
+}
+
+/// Serializable snapshot of a `Component`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComponentDocument {
+    /// Stable id of a component, `None` to generate a new one
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Name of a component
+    pub name: String,
+    /// Tag name of the component descriptor
+    pub tag_name: String,
+    /// Values of the descriptor parameters
+    pub attributes: BTreeMap<String, String>,
+    /// Position of the component inside of its layout
+    pub placement: PlacementDocument,
+    /// Placement of the component at each breakpoint of the page
+    #[serde(default)]
+    pub breakpoints: BTreeMap<String, BreakpointPlacement>,
+}
+
+/// Whether an attribute of a component can be set on its element
+///
+/// Names follow the XML `Name` rule that `Element.setAttribute` checks,
+/// a document can contain anything, like `on click` or `"x"`
+pub fn is_attribute_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    let start = |c: char| c.is_ascii_alphabetic() || c == '_' || c == ':' || !c.is_ascii();
+
+    match chars.next() {
+        Some(first) if start(first) => {
+            chars.all(|c| start(c) || c.is_ascii_digit() || c == '-' || c == '.')
+        }
+        _ => false,
+    }
+}
+
+/// Position of a component inside of a layout
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PlacementDocument {
+    /// Component placed in a grid layout
+    Grid {
+        /// Top left cell of a component (starting from 1)
+        pos: (usize, usize),
+        /// Size of a component in cells
+        size: (usize, usize),
+    },
+    /// Component placed in a free layout
+    Free {
+        /// Position in px, relative to the left-top point of the layout
+        position: (i32, i32),
+        /// Size in px, `None` if the component was never resized
+        size: Option<(f64, f64)>,
+    },
+    /// Component placed in a flex layout, positioned by the flexbox itself
+    Flex {
+        /// Flex properties of the component
+        #[serde(default)]
+        item: FlexItem,
+    },
+}
+
+fn default_flex_wrap() -> bool {
+    true
+}
+
+fn default_snap_grid() -> u32 {
+    SNAP_GRID
+}
+
+fn default_snap_to_items() -> bool {
+    true
+}
+
+/// Errors that can occur while loading a document
+#[derive(Debug)]
+pub enum DocumentError {
+    /// The json could not be parsed
+    Json(serde_json::Error),
+    /// The document was written by a newer version of the editor
+    UnsupportedVersion(u32),
+    /// The document uses a component that was not registered in the editor
+    UnknownComponent(String),
+    /// Placement of a component is of a different kind than the layout it is in,
+    /// like a free position inside of a grid layout
+    PlacementMismatch {
+        /// Name of the component
+        component: String,
+        /// Name of the layout
+        layout: String,
+    },
+}
+
+impl fmt::Display for DocumentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(err) => write!(f, "Invalid document: {}", err),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "Unsupported document version {} (expected at most {})",
+                version, DOCUMENT_VERSION
+            ),
+            Self::UnknownComponent(tag_name) => {
+                write!(f, "Unknown component `{}`", tag_name)
+            }
+            Self::PlacementMismatch { component, layout } => write!(
+                f,
+                "Placement of component `{}` does not match the kind of layout `{}`",
+                component, layout
+            ),
+        }
+    }
+}
+
+impl From<serde_json::Error> for DocumentError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+impl Document {
+    /// Create a new document, with current version
+    pub fn new(pages: Vec<PageDocument>) -> Self {
+        Self {
+            version: DOCUMENT_VERSION,
+            pages,
+        }
+    }
+
+    /// Serialize the document into json
+    pub fn to_json(&self) -> String {
+        // Serialization of these structs can not fail, as all of the map keys are strings
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    /// Deserialize the document from json
+    pub fn from_json(json: &str) -> Result<Self, DocumentError> {
+        let document: Self = serde_json::from_str(json)?;
+
+        if document.version > DOCUMENT_VERSION {
+            return Err(DocumentError::UnsupportedVersion(document.version));
+        }
+
+        document.validate()?;
+
+        Ok(document)
+    }
+
+    /// Check that the components are placed the way their layouts place them
+    ///
+    /// Layouts rely on the placements of their components, a grid layout can not place
+    /// a component without cells, so such documents are rejected as a whole
+    pub fn validate(&self) -> Result<(), DocumentError> {
+        self.pages
+            .iter()
+            .flat_map(|page| page.layouts.iter())
+            .try_for_each(LayoutDocument::validate)
+    }
+
+    /// Iterate over all of the components in the document, including the ones in nested layouts
+    pub fn components(&self) -> impl Iterator<Item = &ComponentDocument> {
+        self.pages
+            .iter()
+            .flat_map(|page| page.layouts.iter())
+            .flat_map(|layout| layout.all_components())
+    }
+}
+
+impl PageDocument {
+    /// Forget the ids of all layouts and components, so that a copy of the page gets new ones
+    pub fn clear_ids(&mut self) {
+        for layout in self.layouts.iter_mut() {
+            layout.clear_ids();
+        }
+    }
+}
+
+impl LayoutDocument {
+    /// Forget the ids of the layout, its components and nested layouts,
+    /// so that a copy of the layout gets new ones
+    pub fn clear_ids(&mut self) {
+        self.id = None;
+
+        for component in self.components.iter_mut() {
+            component.id = None;
+        }
+
+        for child in self.children.iter_mut() {
+            child.clear_ids();
+        }
+    }
+
+    /// Check that the placements of the components (and of the ones in nested layouts) match the layout kind
+    fn validate(&self) -> Result<(), DocumentError> {
+        for component in self.components.iter() {
+            let matches = matches!(
+                (&self.kind, &component.placement),
+                (
+                    LayoutKindDocument::Free { .. },
+                    PlacementDocument::Free { .. }
+                ) | (
+                    LayoutKindDocument::Flex { .. },
+                    PlacementDocument::Flex { .. }
+                ) | (
+                    LayoutKindDocument::Grid { .. },
+                    PlacementDocument::Grid { .. }
+                )
+            );
+
+            if !matches {
+                return Err(DocumentError::PlacementMismatch {
+                    component: component.name.clone(),
+                    layout: self.name.clone(),
+                });
+            }
+        }
+
+        self.children.iter().try_for_each(LayoutDocument::validate)
+    }
+
+    /// Components of the layout, followed by the components of the layouts nested inside of it
+    pub fn all_components(&self) -> Vec<&ComponentDocument> {
+        let mut components: Vec<&ComponentDocument> = self.components.iter().collect();
+
+        for child in self.children.iter() {
+            components.extend(child.all_components());
+        }
+
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn component(id: &str, placement: PlacementDocument) -> ComponentDocument {
+        ComponentDocument {
+            id: Some(id.into()),
+            name: "Button".into(),
+            tag_name: "launch-button".into(),
+            attributes: vec![("innertext".to_owned(), "Save".to_owned())]
+                .into_iter()
+                .collect(),
+            placement,
+            breakpoints: BTreeMap::new(),
+        }
+    }
+
+    fn document() -> Document {
+        let mut grid = component(
+            "launch-button-1",
+            PlacementDocument::Grid {
+                pos: (2, 1),
+                size: (3, 1),
+            },
+        );
+        grid.breakpoints
+            .insert("mobile".into(), BreakpointPlacement::Hidden);
+
+        let free = component(
+            "launch-button-2",
+            PlacementDocument::Free {
+                position: (16, -8),
+                size: Some((120.0, 40.5)),
+            },
+        );
+
+        let child = LayoutDocument {
+            id: Some("layout-2".into()),
+            name: "Free".into(),
+            width: 300,
+            height: 120,
+            kind: LayoutKindDocument::Free {
+                snap_grid: SNAP_GRID,
+                snap_to_items: false,
+            },
+            components: vec![free],
+            placement: Some(ChildPlacement::Grid {
+                pos: (1, 2),
+                size: (4, 2),
+            }),
+            breakpoints: BTreeMap::new(),
+            children: Vec::new(),
+        };
+
+        let layout = LayoutDocument {
+            id: Some("layout-1".into()),
+            name: "Grid".into(),
+            width: 908,
+            height: 255,
+            kind: LayoutKindDocument::Grid {
+                column_width: 80,
+                row_height: Some(76),
+                gap: 8,
+                columns: 10,
+                rows: 3,
+                collision: CollisionStrategy::default(),
+                auto_grow: true,
+                auto_shrink: false,
+                breakpoint_columns: vec![("mobile".to_owned(), 4)].into_iter().collect(),
+            },
+            components: vec![grid],
+            placement: None,
+            breakpoints: BTreeMap::new(),
+            children: vec![child],
+        };
+
+        Document::new(vec![PageDocument {
+            name: "Home".into(),
+            width: 908,
+            breakpoints: vec![Breakpoint::new("mobile", 375)],
+            breakpoint: Some("mobile".into()),
+            metadata: PageMetadata::default(),
+            layouts: vec![layout],
+        }])
+    }
+
+    #[test]
+    fn documents_survive_a_json_round_trip() {
+        let document = document();
+        let loaded = Document::from_json(&document.to_json()).unwrap();
+
+        assert_eq!(loaded, document);
+        assert_eq!(loaded.version, DOCUMENT_VERSION);
+        assert_eq!(
+            loaded
+                .components()
+                .map(|component| component.id.as_deref().unwrap())
+                .collect::<Vec<_>>(),
+            vec!["launch-button-1", "launch-button-2"]
+        );
+    }
+
+    #[test]
+    fn newer_versions_are_rejected() {
+        let mut document = document();
+        document.version = DOCUMENT_VERSION + 1;
+
+        match Document::from_json(&document.to_json()) {
+            Err(DocumentError::UnsupportedVersion(version)) => {
+                assert_eq!(version, DOCUMENT_VERSION + 1)
+            }
+            result => panic!("expected an unsupported version, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn placements_have_to_match_the_layout_kind() {
+        let mut document = document();
+        document.pages[0].layouts[0].components[0].placement = PlacementDocument::Flex {
+            item: FlexItem::default(),
+        };
+
+        match Document::from_json(&document.to_json()) {
+            Err(DocumentError::PlacementMismatch { component, layout }) => {
+                assert_eq!((component.as_str(), layout.as_str()), ("Button", "Grid"))
+            }
+            result => panic!("expected a placement mismatch, got {:?}", result),
+        }
+
+        // Components of nested layouts are checked too
+        let mut document = self::document();
+        document.pages[0].layouts[0].children[0].components[0].placement =
+            PlacementDocument::Grid {
+                pos: (1, 1),
+                size: (1, 1),
+            };
+
+        assert!(matches!(
+            Document::from_json(&document.to_json()),
+            Err(DocumentError::PlacementMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn invalid_attribute_names_are_loaded_but_recognized() {
+        let mut document = document();
+        let attributes = &mut document.pages[0].layouts[0].components[0].attributes;
+        attributes.insert("on click".into(), "alert(1)".into());
+        attributes.insert("\"x\"".into(), "1".into());
+
+        // Components skip the invalid attributes when the document is applied
+        let loaded = Document::from_json(&document.to_json()).unwrap();
+        let names: Vec<_> = loaded.pages[0].layouts[0].components[0]
+            .attributes
+            .keys()
+            .filter(|name| !is_attribute_name(name))
+            .collect();
+        assert_eq!(names, vec!["\"x\"", "on click"]);
+
+        for name in ["innertext", "data-x", "xlink:href", "_a.b", "größe"] {
+            assert!(is_attribute_name(name), "{}", name);
+        }
+        for name in ["", "1st", "-a", "a b", "a=b", "a/b", "<a>"] {
+            assert!(!is_attribute_name(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn malformed_documents_are_rejected() {
+        let json = document().to_json();
+
+        for malformed in [
+            "",
+            "[]",
+            &json[..json.len() / 2],
+            &json.replace("\"grid\"", "\"table\""),
+        ] {
+            assert!(
+                matches!(Document::from_json(malformed), Err(DocumentError::Json(_))),
+                "{} was loaded",
+                malformed
+            );
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use wasm_bindgen::JsCast;
+use web_sys::{Document, HtmlElement};
+
+use crate::{
+    component::Component,
+    page::layout::{Layout, Rect},
+};
+
+/// Distance in px that the mouse has to travel before the marquee shows up,
+/// so that a simple click still selects the layout
+const MARQUEE_THRESHOLD: i32 = 4;
+
+/// Rubber-band selection of components inside of a layout
+pub struct MarqueeController {
+    document: Document,
+
+    layout: Layout,
+
+    start_x: i32,
+    start_y: i32,
+
+    /// Rectangle shown while dragging, `None` until the mouse passes the threshold
+    element: Option<HtmlElement>,
+}
+
+impl MarqueeController {
+    /// Init the marquee controler for a layout
+    pub fn new(layout: Layout, event: &web_sys::MouseEvent) -> Self {
+        let document = layout.html_element.owner_document().unwrap();
+
+        Self {
+            document,
+
+            layout,
+
+            start_x: event.client_x(),
+            start_y: event.client_y(),
+
+            element: None,
+        }
+    }
+
+    /// Rectangle between the start point and the mouse
+    fn rect(&self, event: &web_sys::MouseEvent) -> Rect {
+        marquee_rect(
+            (self.start_x, self.start_y),
+            (event.client_x(), event.client_y()),
+        )
+    }
+
+    /// Called when mouse moves
+    pub fn mouse_move(&mut self, event: &web_sys::MouseEvent) {
+        let ((x, y), (w, h)) = self.rect(event);
+
+        if self.element.is_none() {
+            if (w as i32).max(h as i32) < MARQUEE_THRESHOLD {
+                return;
+            }
+
+            let element = self.document.create_element("div").unwrap();
+            let element: HtmlElement = element.dyn_into().unwrap();
+            element.class_list().add_1("marquee").unwrap();
+
+            self.document
+                .body()
+                .unwrap()
+                .append_child(&element)
+                .unwrap();
+            self.element = Some(element);
+        }
+
+        if let Some(element) = self.element.as_ref() {
+            let style = element.style();
+            style.set_property("left", &format!("{}px", x)).unwrap();
+            style.set_property("top", &format!("{}px", y)).unwrap();
+            style.set_property("width", &format!("{}px", w)).unwrap();
+            style.set_property("height", &format!("{}px", h)).unwrap();
+        }
+    }
+
+    /// Abort the selection
+    pub fn cancel(self) {
+        if let Some(element) = self.element {
+            element.remove();
+        }
+    }
+
+    /// Called when mouse is up
+    ///
+    /// Returns the components of the layout touched by the marquee,
+    /// `None` if the mouse did not travel far enough to show it
+    pub fn mouse_up(self, event: &web_sys::MouseEvent) -> Option<Vec<Component>> {
+        let element = self.element.as_ref()?;
+        element.remove();
+
+        let rect = self.rect(event);
+
+        let components = self
+            .layout
+            .components()
+            .iter()
+            .filter(|component| !component.is_hidden())
+            .filter(|component| overlaps(rect, component.bounding_client_rect()))
+            .cloned()
+            .collect();
+
+        Some(components)
+    }
+}
+
+/// Rectangle between two corner points, in any order
+fn marquee_rect(start: (i32, i32), end: (i32, i32)) -> Rect {
+    (
+        (start.0.min(end.0) as f64, start.1.min(end.1) as f64),
+        (
+            (start.0 - end.0).abs() as f64,
+            (start.1 - end.1).abs() as f64,
+        ),
+    )
+}
+
+/// Whether two rects share some area, rects that only touch do not
+fn overlaps(a: Rect, b: Rect) -> bool {
+    let ((ax, ay), (aw, ah)) = a;
+    let ((bx, by), (bw, bh)) = b;
+
+    bx < ax + aw && ax < bx + bw && by < ay + ah && ay < by + bh
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marquee_spans_between_the_points() {
+        assert_eq!(
+            marquee_rect((10, 40), (30, 20)),
+            ((10.0, 20.0), (20.0, 20.0))
+        );
+        assert_eq!(marquee_rect((5, 5), (5, 5)), ((5.0, 5.0), (0.0, 0.0)));
+    }
+
+    #[test]
+    fn only_overlapping_rects_are_selected() {
+        let marquee = ((10.0, 10.0), (100.0, 50.0));
+
+        assert!(overlaps(marquee, ((100.0, 50.0), (40.0, 40.0))));
+        assert!(overlaps(marquee, ((0.0, 0.0), (200.0, 200.0))));
+        // Touching the right edge only
+        assert!(!overlaps(marquee, ((110.0, 10.0), (40.0, 40.0))));
+        assert!(!overlaps(marquee, ((10.0, 70.0), (40.0, 40.0))));
+    }
+}
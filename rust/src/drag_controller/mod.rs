@@ -1,2 +1,3 @@
+pub mod marquee_controller;
 pub mod move_controller;
 pub mod resize_controller;
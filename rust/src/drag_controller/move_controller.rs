@@ -4,7 +4,11 @@ use web_sys::{Document, HtmlElement};
 use crate::{
     component::Component,
     editor::workspace::Workspace,
-    page::layout::{Layout, LayoutKind},
+    page::layout::{
+        grid::{Block, GridItem},
+        model::DropTarget,
+        Layout, LayoutKind,
+    },
 };
 
 mod css_transform;
@@ -14,6 +18,12 @@ pub enum DragMoveResult {
     MovedToLayout {
         component: Component,
         layout: Layout,
+        /// Other components of the layout that have to be moved to make room for this one
+        displaced: Vec<(GridItem, Block)>,
+        /// Height of the layout before the drag, grid layouts can grow when a component is dropped
+        previous_height: u32,
+        /// Position among the other components of a flex layout that the component was dropped at
+        order: Option<usize>,
     },
     Removed {
         component: Component,
@@ -30,13 +40,16 @@ pub struct MoveController {
 
     drag_css_transform: Option<CssMoveTransform>,
     layout: Option<Layout>,
+    /// Where the component would be dropped in the hovered layout
+    target: Option<DropTarget>,
+    /// Height of the hovered layout before it was grown by the drag
+    layout_height: u32,
 }
 
 impl MoveController {
     /// Init the move controler for a component
     pub fn new(component: Component) -> Self {
-        let window = web_sys::window().unwrap();
-        let document = window.document().unwrap();
+        let document = component.element().owner_document().unwrap();
 
         Self {
             document,
@@ -45,6 +58,8 @@ impl MoveController {
 
             drag_css_transform: None,
             layout: None,
+            target: None,
+            layout_height: 0,
         }
     }
 
@@ -106,8 +121,6 @@ impl MoveController {
                 let container = containers.first();
 
                 if let Some(container) = container {
-                    let component = &self.component;
-
                     // Finda a page that it belongs to
                     let page = workspace
                         .pages()
@@ -115,65 +128,63 @@ impl MoveController {
                         .find(|page| page.contains(container));
 
                     if let Some(page) = page {
-                        if let Some(layout) = page.find_layout_by_element(container) {
-                            let (bbox_pos, _bbox_size) = layout.bounding_client_rect();
-
-                            if let LayoutKind::Grid {
-                                grid_data,
-                                grid_background,
-                                cell_size,
-                                ..
-                            } = &mut *layout.kind_mut()
-                            {
-                                let grid_w = grid_data.width();
-                                let grid_h = grid_data.height();
-
-                                let sub_x = component_bbox_pos.0 - bbox_pos.0;
-                                let sub_y = component_bbox_pos.1 - bbox_pos.1;
-
-                                let div_x = sub_x / *cell_size as f64;
-                                let div_y = sub_y / *cell_size as f64;
-
-                                let grid_x = div_x.floor() as usize + 1;
-                                let grid_y = div_y.floor() as usize + 1;
-
-                                let placeholder_size =
-                                    self.component.grid_size().unwrap_or_else(|| {
-                                        let width = component_bbox_size.0;
-                                        let height = component_bbox_size.1;
-
-                                        let w = width / *cell_size as f64;
-                                        let h = height / *cell_size as f64;
-
-                                        let w = w.ceil() as usize;
-                                        let h = h.ceil() as usize;
-
-                                        (w, h)
-                                    });
-
-                                let grid_x = grid_x.min(grid_w - placeholder_size.0 + 1).max(0);
-                                let grid_y = grid_y.min(grid_h - placeholder_size.1 + 1).max(0);
-
-                                grid_background.update_placeholder(
-                                    workspace,
-                                    grid_data,
-                                    component,
-                                    (grid_x, grid_y),
-                                    placeholder_size,
-                                );
-                            }
-
-                            if let Some(l) = self.layout.take() {
+                        if let Some(mut layout) = page.find_layout_by_element(container) {
+                            if let Some(mut l) = self.layout.take() {
                                 if l != layout {
-                                    if let LayoutKind::Grid {
-                                        grid_background, ..
-                                    } = &mut *l.kind_mut()
-                                    {
+                                    if let Some(mut grid_background) = l.grid_background() {
                                         grid_background.set_placeholder_visible(false);
                                     }
+                                    if let Some(free_guides) = l.free_guides() {
+                                        free_guides.hide();
+                                    }
+
+                                    // The component is no longer dropped there, so undo the growth
+                                    restore_height(&mut l, self.layout_height);
+                                    self.layout_height = layout.size().1;
                                 }
+                            } else {
+                                self.layout_height = layout.size().1;
                             }
 
+                            let target = layout.drop_target(
+                                &self.component,
+                                (component_bbox_pos, component_bbox_size),
+                            );
+
+                            match &target {
+                                DropTarget::Grid(placeholder) => {
+                                    if let Some(mut grid_background) = layout.grid_background() {
+                                        grid_background.show_placeholder(placeholder.clone());
+                                    }
+
+                                    // Grow (or shrink) the grid to fit the placeholder
+                                    let rows = match &*layout.kind() {
+                                        LayoutKind::Grid { grid_data, .. } => grid_data.height(),
+                                        _ => placeholder.rows,
+                                    };
+                                    if rows != placeholder.rows {
+                                        layout.set_grid_rows(placeholder.rows);
+                                    }
+                                }
+                                DropTarget::Free(snap) => {
+                                    let (bbox_pos, _) = layout.bounding_client_rect();
+
+                                    // Move the component from under the cursor to the snapped position
+                                    drag_transform.set_snap_offset((
+                                        (bbox_pos.0 + snap.position.0 - component_bbox_pos.0)
+                                            .round() as i32,
+                                        (bbox_pos.1 + snap.position.1 - component_bbox_pos.1)
+                                            .round() as i32,
+                                    ));
+
+                                    if let Some(free_guides) = layout.free_guides() {
+                                        free_guides.show(snap);
+                                    }
+                                }
+                                DropTarget::Flex { .. } => {}
+                            }
+
+                            self.target = Some(target);
                             self.layout = Some(layout);
                         }
                     }
@@ -197,12 +208,12 @@ impl MoveController {
         self.component.set_is_dragged(false);
 
         if let Some(layout) = self.layout.as_ref() {
-            if let LayoutKind::Grid {
-                grid_background, ..
-            } = &mut *layout.kind_mut()
-            {
+            if let Some(mut grid_background) = layout.grid_background() {
                 grid_background.set_placeholder_visible(false);
             }
+            if let Some(free_guides) = layout.free_guides() {
+                free_guides.hide();
+            }
         }
 
         if let Some(drag_transform) = self.drag_css_transform.as_mut() {
@@ -226,49 +237,55 @@ impl MoveController {
                 .remove_property("pointer-events")
                 .unwrap();
 
-            if let (Some(layout), true) = (self.layout, container.is_some()) {
-                match &*layout.kind() {
-                    LayoutKind::Grid {
-                        grid_background, ..
-                    } => {
-                        let placeholder_pos = grid_background.placeholder_pos();
-                        let placeholder_size = grid_background.placeholder_size();
+            if let (Some(mut layout), Some(target), true) =
+                (self.layout.clone(), self.target.take(), container.is_some())
+            {
+                let mut displaced = Vec::new();
+                let mut denied = false;
+                let mut order = None;
+
+                drag_transform.stop();
+
+                match target {
+                    DropTarget::Grid(placeholder) => {
+                        denied = placeholder.denied;
+
+                        if !denied {
+                            let block = placeholder.block;
 
-                        if !grid_background.is_placeholder_denied() {
                             self.component.unset_absolute_pos();
                             self.component.unset_size();
 
-                            self.component.set_grid_pos(placeholder_pos);
-                            self.component.set_grid_size(placeholder_size);
-                        }
+                            self.component.set_grid_pos((block.x, block.y));
+                            self.component.set_grid_size((block.width, block.height));
 
-                        // The component does not have grid size, so this is initial drag and drop
-                        // And component was droped into ocupied spot
-                        if self.component.grid_pos().is_none()
-                            || self.component.grid_size().is_none()
-                        {
-                            return DragMoveResult::Removed {
-                                component: self.component,
-                            };
+                            displaced = placeholder.displaced;
                         }
-
-                        drag_transform.stop();
                     }
-                    LayoutKind::Flex { .. } => {
-                        drag_transform.stop();
+                    DropTarget::Flex { order: index } => {
                         self.component.unset_absolute_pos();
-                    }
-                    LayoutKind::Free { .. } => {
-                        let new_absolute_pos = drag_transform.stop();
 
-                        let (bpos, _) = layout.bounding_client_rect();
+                        order = Some(index);
+                    }
+                    DropTarget::Free(snap) => {
+                        let (x, y) = snap.position;
+                        self.component
+                            .set_position((x.round() as i32, y.round() as i32));
+                    }
+                };
 
-                        let offset = (bpos.0 as i32, bpos.1 as i32);
-                        let pos = (new_absolute_pos.0 - offset.0, new_absolute_pos.1 - offset.1);
+                if denied {
+                    // Rows added while hovering are not needed anymore
+                    restore_height(&mut layout, self.layout_height);
 
-                        self.component.set_position(pos);
+                    // The component does not have grid size, so this is initial drag and drop
+                    // And component was droped into ocupied spot
+                    if self.component.grid_pos().is_none() || self.component.grid_size().is_none() {
+                        return DragMoveResult::Removed {
+                            component: self.component,
+                        };
                     }
-                };
+                }
 
                 // Move has ended so now the layout is responsible for positioning
                 // So we remove the position property
@@ -281,8 +298,15 @@ impl MoveController {
                 DragMoveResult::MovedToLayout {
                     component: self.component,
                     layout,
+                    displaced,
+                    previous_height: self.layout_height,
+                    order,
                 }
             } else {
+                if let Some(layout) = self.layout.as_mut() {
+                    restore_height(layout, self.layout_height);
+                }
+
                 DragMoveResult::Removed {
                     component: self.component,
                 }
@@ -293,4 +317,39 @@ impl MoveController {
             }
         }
     }
+
+    /// Abort the drag
+    ///
+    /// Returns the dragged component, it has to be put back into its layout by the caller
+    pub fn cancel(mut self) -> Component {
+        self.component.set_is_dragged(false);
+
+        if let Some(layout) = self.layout.as_mut() {
+            if let Some(mut grid_background) = layout.grid_background() {
+                grid_background.set_placeholder_visible(false);
+            }
+            if let Some(free_guides) = layout.free_guides() {
+                free_guides.hide();
+            }
+
+            restore_height(layout, self.layout_height);
+        }
+
+        if let Some(drag_transform) = self.drag_css_transform.as_mut() {
+            drag_transform.stop();
+        }
+
+        let style = self.component.element().style();
+        style.remove_property("pointer-events").unwrap();
+        style.remove_property("position").unwrap();
+
+        self.component
+    }
+}
+
+/// Bring back the height a layout had before it was grown by a drag
+fn restore_height(layout: &mut Layout, height: u32) {
+    if layout.size().1 != height {
+        layout.resize(None, Some(height));
+    }
 }
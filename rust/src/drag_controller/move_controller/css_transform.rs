@@ -11,6 +11,9 @@ pub struct CssMoveTransform {
     last_pos: (i32, i32),
     /// Starting pos
     start_pos: (i32, i32),
+
+    /// Offset from the cursor, applied when the component snaps to something
+    snap_offset: (i32, i32),
 }
 
 impl CssMoveTransform {
@@ -30,10 +33,13 @@ impl CssMoveTransform {
             last_pos: (last_x, last_y),
             absolute_pos: (absolute_x, absolute_y),
             start_pos: (start_x, start_y),
+            snap_offset: (0, 0),
         }
     }
 
     /// Called when mouse is being draged
+    ///
+    /// The component follows the cursor exactly, any previous snap offset is dropped
     pub fn drag(&mut self, x: i32, y: i32) {
         self.absolute_pos.0 -= self.last_pos.0 - x;
         self.absolute_pos.1 -= self.last_pos.1 - y;
@@ -41,6 +47,17 @@ impl CssMoveTransform {
         self.last_pos.0 = x;
         self.last_pos.1 = y;
 
+        self.snap_offset = (0, 0);
+        self.update_transform();
+    }
+
+    /// Move the component away from the cursor, so that it snaps to something
+    pub fn set_snap_offset(&mut self, offset: (i32, i32)) {
+        self.snap_offset = offset;
+        self.update_transform();
+    }
+
+    fn update_transform(&self) {
         self.component
             .element()
             .style()
@@ -48,8 +65,8 @@ impl CssMoveTransform {
                 "transform",
                 &format!(
                     "translate({}px, {}px)",
-                    x - self.start_pos.0,
-                    y - self.start_pos.1
+                    self.last_pos.0 - self.start_pos.0 + self.snap_offset.0,
+                    self.last_pos.1 - self.start_pos.1 + self.snap_offset.1
                 ),
             )
             .unwrap();
@@ -65,6 +82,9 @@ impl CssMoveTransform {
             .remove_property("transform")
             .unwrap();
 
-        self.absolute_pos
+        (
+            self.absolute_pos.0 + self.snap_offset.0,
+            self.absolute_pos.1 + self.snap_offset.1,
+        )
     }
 }
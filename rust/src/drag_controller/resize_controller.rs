@@ -2,15 +2,147 @@ use wasm_bindgen::JsCast;
 use web_sys::{Document, HtmlElement};
 
 use crate::{
-    component::Component,
+    component::{Component, SizeConstraints, SpanLimits},
     editor::workspace::Workspace,
-    page::layout::{Layout, LayoutKind},
+    page::layout::{
+        grid::{Block, GridItem},
+        Layout, LayoutKind, Rect,
+    },
 };
 
+/// Smallest size in px that a component can be resized to outside of grid layouts
+pub const MIN_SIZE: f64 = 16.0;
+
+/// Side or corner of a component that it is resized from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResizeHandle {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl ResizeHandle {
+    pub const ALL: [ResizeHandle; 8] = [
+        ResizeHandle::N,
+        ResizeHandle::NE,
+        ResizeHandle::E,
+        ResizeHandle::SE,
+        ResizeHandle::S,
+        ResizeHandle::SW,
+        ResizeHandle::W,
+        ResizeHandle::NW,
+    ];
+
+    /// Name of the handle, used as the `data-handle` attribute of its html element
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::N => "n",
+            Self::NE => "ne",
+            Self::E => "e",
+            Self::SE => "se",
+            Self::S => "s",
+            Self::SW => "sw",
+            Self::W => "w",
+            Self::NW => "nw",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|handle| handle.name() == name)
+    }
+
+    /// Find the handle that the html element belongs to
+    pub fn from_element(elm: &HtmlElement) -> Option<Self> {
+        let handle = elm.closest(".resize-handle").ok()??;
+        Self::from_name(&handle.get_attribute("data-handle")?)
+    }
+
+    /// Dragged (horizontal, vertical) edges, -1 for the left/top one, 1 for the right/bottom one, 0 for none
+    pub fn edges(self) -> (i32, i32) {
+        match self {
+            Self::N => (0, -1),
+            Self::NE => (1, -1),
+            Self::E => (1, 0),
+            Self::SE => (1, 1),
+            Self::S => (0, 1),
+            Self::SW => (-1, 1),
+            Self::W => (-1, 0),
+            Self::NW => (-1, -1),
+        }
+    }
+}
+
+/// Minimum and maximum size of a resized component in px
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SizeLimits {
+    pub min: (f64, f64),
+    pub max: (f64, f64),
+}
+
+impl Default for SizeLimits {
+    fn default() -> Self {
+        Self {
+            min: (MIN_SIZE, MIN_SIZE),
+            max: (f64::INFINITY, f64::INFINITY),
+        }
+    }
+}
+
+impl SizeLimits {
+    /// Limits that keep a component resized from a handle inside of the layout bounds
+    pub fn inside(rect: Rect, handle: ResizeHandle, bounds: (f64, f64)) -> Self {
+        let ((x, y), (width, height)) = rect;
+
+        // Space left for the dragged edge
+        let max = |edge: i32, start: f64, length: f64, bound: f64| match edge {
+            1 => bound - start,
+            -1 => start + length,
+            _ => f64::INFINITY,
+        };
+
+        let (h, v) = handle.edges();
+        let limits = Self::default();
+
+        Self {
+            max: (
+                max(h, x, width, bounds.0).max(limits.min.0),
+                max(v, y, height, bounds.1).max(limits.min.1),
+            ),
+            ..limits
+        }
+    }
+
+    /// Narrow the limits down to the size constraints of a component
+    pub fn constrained(self, constraints: &SizeConstraints) -> Self {
+        let (min_width, min_height) = constraints.min_size.unwrap_or(self.min);
+        let min = (self.min.0.max(min_width), self.min.1.max(min_height));
+
+        let (max_width, max_height) = constraints.max_size.unwrap_or(self.max);
+        let max = (
+            self.max.0.min(max_width).max(min.0),
+            self.max.1.min(max_height).max(min.1),
+        );
+
+        Self { min, max }
+    }
+}
+
 pub enum DragResizeResult {
     Resized {
         layout: Layout,
         component: Component,
+        /// Other components of the layout that have to be moved to make room for this one
+        displaced: Vec<(GridItem, Block)>,
+        /// Height of the layout before the resize, grid layouts can grow with the component
+        previous_height: u32,
     },
     NotResized,
 }
@@ -18,149 +150,347 @@ pub enum DragResizeResult {
 struct ResizeState {
     layout: Layout,
     component: Component,
+    handle: ResizeHandle,
 
     last_x: i32,
     last_y: i32,
+
+    /// Mouse position at the start of the resize
+    start_mouse: (i32, i32),
+    /// Rect of the component before the resize, relative to the layout
+    start_rect: Rect,
+    limits: SizeLimits,
+    /// Span limits of the component in grid layouts
+    span_limits: SpanLimits,
+
+    /// Grid position of the component before the resize
+    start_grid_pos: Option<(usize, usize)>,
+    /// Grid size of the component before the resize
+    start_grid_size: Option<(usize, usize)>,
+    /// Height of the layout before the resize
+    start_height: u32,
 }
 
 impl ResizeState {
     /// Start the resize of a component
-    pub fn start(layout: Layout, component: Component, x: i32, y: i32) -> Self {
+    pub fn start(
+        layout: Layout,
+        component: Component,
+        handle: ResizeHandle,
+        x: i32,
+        y: i32,
+    ) -> Self {
         let last_x = x;
         let last_y = y;
 
-        if let LayoutKind::Grid {
-            grid_background, ..
-        } = &mut *layout.kind_mut()
+        let ((left, top), bounds) = layout.bounding_client_rect();
+        let ((component_x, component_y), size) = component.bounding_client_rect();
+        let position = component
+            .position()
+            .map(|(x, y)| (x as f64, y as f64))
+            .unwrap_or((component_x - left, component_y - top));
+        let start_rect = (position, size);
+
+        let constraints = component.constraints();
+        let limits = match &*layout.kind() {
+            LayoutKind::Free { .. } => SizeLimits::inside(start_rect, handle, bounds),
+            _ => SizeLimits::default(),
+        }
+        .constrained(&constraints);
+        let span_limits = constraints.span_limits();
+
+        if let (LayoutKind::Grid { grid_data, .. }, Some(mut grid_background)) =
+            (&*layout.kind(), layout.grid_background())
         {
-            grid_background.set_placeholder_visible(true);
+            if let (Some((x, y)), Some((width, height))) =
+                (component.grid_pos(), component.grid_size())
+            {
+                let block = Block {
+                    x,
+                    y,
+                    width,
+                    height,
+                };
+                grid_background
+                    .show_placeholder(grid_data.placeholder(component.grid_item(), block));
+            }
         }
 
+        let start_grid_pos = component.grid_pos();
+        let start_grid_size = component.grid_size();
+        let start_height = layout.size().1;
+
         Self {
             layout,
             component,
+            handle,
             last_x,
             last_y,
+            start_mouse: (x, y),
+            start_rect,
+            limits,
+            span_limits,
+            start_grid_pos,
+            start_grid_size,
+            start_height,
         }
     }
 
     /// Called when mouse is being draged
-    pub fn drag(&mut self, workspace: &mut Workspace, x: i32, y: i32) {
+    ///
+    /// # Arguments
+    /// * `keep_aspect` - keep the aspect ratio the component had before the resize
+    pub fn drag(&mut self, x: i32, y: i32, keep_aspect: bool) {
         let dx = -(self.last_x - x);
         let dy = -(self.last_y - y);
 
-        let (w, h) = self.component.size();
+        let mut rows = None;
 
         // Check if we are in a gird
         // If so resize cell by cell
         // Otherwise just resize freely
-        if let LayoutKind::Grid {
-            cell_size,
-            grid_data,
-            grid_background,
-            ..
-        } = &mut *self.layout.kind_mut()
+        if let (LayoutKind::Grid { grid_data, .. }, Some(mut grid_background)) =
+            (&*self.layout.kind(), self.layout.grid_background())
         {
             let (pos_x, pos_y) = self.component.grid_pos().unwrap();
             let (size_x, size_y) = self.component.grid_size().unwrap();
+            let block = Block {
+                x: pos_x,
+                y: pos_y,
+                width: size_x,
+                height: size_y,
+            };
+
+            let step = self.layout.grid_resize_step(
+                block,
+                self.handle.edges(),
+                (dx, dy),
+                self.span_limits,
+            );
+
+            if let Some(step) = step {
+                let (horizontal, vertical) = step.changed;
+                if horizontal {
+                    self.last_x = x;
+                }
+                if vertical {
+                    self.last_y = y;
+                }
 
-            let cell_size = *cell_size as i32;
-
-            let horizontal = dx.abs() > cell_size;
-            let vertical = dy.abs() > cell_size;
+                let block = step.block;
+                self.component.set_grid_pos((block.x, block.y));
+                self.component.set_grid_size((block.width, block.height));
 
-            if horizontal {
-                if dx > cell_size && pos_x + size_x < grid_data.width() + 1 {
-                    self.last_x = x;
+                let placeholder = grid_data.placeholder(self.component.grid_item(), block);
+                if placeholder.rows != grid_data.height() {
+                    rows = Some(placeholder.rows);
+                }
+                grid_background.show_placeholder(placeholder);
+            }
+        } else {
+            self.last_x = x;
+            self.last_y = y;
 
-                    self.component.set_grid_size((size_x + 1, size_y));
+            let delta = (
+                (x - self.start_mouse.0) as f64,
+                (y - self.start_mouse.1) as f64,
+            );
+            let rect = resize_rect(
+                self.start_rect,
+                self.handle,
+                delta,
+                self.limits,
+                keep_aspect,
+            );
+
+            // Edges snap only when they are free to, as snapping would break the aspect ratio
+            let snapped = if keep_aspect {
+                None
+            } else {
+                self.layout
+                    .free_resize_snap(&self.component, rect, self.handle.edges())
+            };
+
+            let rect = match snapped {
+                Some(snap) if fits(snap.size, self.limits) => {
+                    if let Some(free_guides) = self.layout.free_guides() {
+                        free_guides.show(&snap);
+                    }
+                    (snap.position, snap.size)
+                }
+                _ => {
+                    if let Some(free_guides) = self.layout.free_guides() {
+                        free_guides.hide();
+                    }
+                    rect
+                }
+            };
 
-                    self.component
-                        .element()
-                        .style()
-                        .set_property("grid-column", &format!("{}/span {}", pos_x, size_x + 1))
-                        .unwrap();
-                } else if dx < -cell_size && size_x > 1 {
-                    self.last_x = x;
+            let ((left, top), (width, height)) = rect;
+            if let LayoutKind::Free { .. } = &*self.layout.kind() {
+                self.component
+                    .set_position((left.round() as i32, top.round() as i32));
+            }
+            self.component.set_size(width.round(), height.round());
+        }
 
-                    self.component.set_grid_size((size_x - 1, size_y));
+        if let Some(rows) = rows {
+            self.layout.set_grid_rows(rows);
+        }
+    }
 
-                    self.component
-                        .element()
-                        .style()
-                        .set_property("grid-column", &format!("{}/span {}", pos_x, size_x - 1))
-                        .unwrap();
+    /// Finish the resize
+    ///
+    /// Returns the components that have to be moved to make room for the resized one
+    pub fn stop(&mut self) -> Vec<(GridItem, Block)> {
+        let mut displaced = Vec::new();
+        let mut denied = false;
+
+        if let Some(mut grid_background) = self.layout.grid_background() {
+            let placeholder = grid_background.placeholder().clone();
+            denied = placeholder.denied;
+
+            if denied {
+                // The new size collides with other components, so go back to the old one
+                if let Some(pos) = self.start_grid_pos {
+                    self.component.set_grid_pos(pos);
+                }
+                if let Some(size) = self.start_grid_size {
+                    self.component.set_grid_size(size);
                 }
+            } else {
+                // Compacting grids can also move the resized component itself
+                let block = placeholder.block;
+                self.component.set_grid_pos((block.x, block.y));
+                self.component.set_grid_size((block.width, block.height));
+
+                displaced = placeholder.displaced;
             }
 
-            if vertical {
-                if dy > cell_size && pos_y + size_y < grid_data.height() + 1 {
-                    self.last_y = y;
+            grid_background.set_placeholder_visible(false);
+        }
 
-                    self.component.set_grid_size((size_x, size_y + 1));
+        if let Some(free_guides) = self.layout.free_guides() {
+            free_guides.hide();
+        }
 
-                    self.component
-                        .element()
-                        .style()
-                        .set_property("grid-row", &format!("{}/span {}", pos_y, size_y + 1))
-                        .unwrap();
-                } else if dy < -cell_size && size_y > 1 {
-                    self.last_y = y;
+        if denied && self.layout.size().1 != self.start_height {
+            self.layout.resize(None, Some(self.start_height));
+        }
 
-                    self.component.set_grid_size((size_x, size_y - 1));
+        displaced
+    }
 
-                    self.component
-                        .element()
-                        .style()
-                        .set_property("grid-row", &format!("{}/span {}", pos_y, size_y - 1))
-                        .unwrap();
-                }
-            }
+    /// Abort the resize, bringing back the grid block of the component and the height of the layout
+    pub fn cancel(&mut self) {
+        if let Some(pos) = self.start_grid_pos {
+            self.component.set_grid_pos(pos);
+        }
+        if let Some(size) = self.start_grid_size {
+            self.component.set_grid_size(size);
+        }
 
-            if horizontal || vertical {
-                grid_background.update_placeholder(
-                    workspace,
-                    grid_data,
-                    &self.component,
-                    self.component.grid_pos().unwrap(),
-                    self.component.grid_size().unwrap(),
-                );
-            }
-        } else {
-            self.last_x = x;
-            self.last_y = y;
+        if let Some(mut grid_background) = self.layout.grid_background() {
+            grid_background.set_placeholder_visible(false);
+        }
+        if let Some(free_guides) = self.layout.free_guides() {
+            free_guides.hide();
+        }
 
-            self.component.set_size(w + dx as f64, h + dy as f64);
+        if self.layout.size().1 != self.start_height {
+            self.layout.resize(None, Some(self.start_height));
         }
     }
+}
 
-    pub fn stop(&mut self) {
-        if let LayoutKind::Grid {
-            grid_background, ..
-        } = &mut *self.layout.kind_mut()
-        {
-            grid_background.set_placeholder_visible(false);
+/// Rect of a component resized from a handle
+///
+/// The edges opposite to the handle stay in place.
+/// With a locked aspect ratio, the axis that changed relatively more drives the other one
+///
+/// # Arguments
+/// * `start` - rect of the component before the resize
+/// * `handle` - handle that is dragged
+/// * `delta` - mouse movement since the start of the resize in px
+/// * `limits` - minimum and maximum size of the component
+/// * `keep_aspect` - keep the aspect ratio of the `start` rect
+fn resize_rect(
+    start: Rect,
+    handle: ResizeHandle,
+    delta: (f64, f64),
+    limits: SizeLimits,
+    keep_aspect: bool,
+) -> Rect {
+    let ((x, y), (width, height)) = start;
+    let (edge_x, edge_y) = handle.edges();
+
+    let clamp = |value: f64, min: f64, max: f64| value.max(min).min(max.max(min));
+
+    let mut new_width = clamp(width + delta.0 * edge_x as f64, limits.min.0, limits.max.0);
+    let mut new_height = clamp(height + delta.1 * edge_y as f64, limits.min.1, limits.max.1);
+
+    if keep_aspect && width > 0.0 && height > 0.0 {
+        let ratio = width / height;
+
+        // Edge handles only drive their own axis
+        let width_drives = match (edge_x, edge_y) {
+            (_, 0) => true,
+            (0, _) => false,
+            _ => (new_width / width - 1.0).abs() >= (new_height / height - 1.0).abs(),
+        };
+
+        if width_drives {
+            new_height = clamp(new_width / ratio, limits.min.1, limits.max.1);
+            new_width = new_height * ratio;
+        } else {
+            new_width = clamp(new_height * ratio, limits.min.0, limits.max.0);
+            new_height = new_width / ratio;
         }
     }
+
+    let new_x = if edge_x == -1 {
+        x + width - new_width
+    } else {
+        x
+    };
+    let new_y = if edge_y == -1 {
+        y + height - new_height
+    } else {
+        y
+    };
+
+    ((new_x, new_y), (new_width, new_height))
+}
+
+/// Whether the size is within the limits
+fn fits(size: (f64, f64), limits: SizeLimits) -> bool {
+    size.0 >= limits.min.0
+        && size.0 <= limits.max.0
+        && size.1 >= limits.min.1
+        && size.1 <= limits.max.1
 }
 
 pub struct ResizeController {
     document: Document,
 
     pub component: Component,
+    handle: ResizeHandle,
     drag_state: Option<ResizeState>,
 }
 
 impl ResizeController {
-    /// Init the move controler for a component
-    pub fn new(component: Component) -> Self {
-        let window = web_sys::window().unwrap();
-        let document = window.document().unwrap();
+    /// Init the resize controler for a component
+    ///
+    /// # Arguments
+    /// * `handle` - side or corner that the component is resized from
+    pub fn new(component: Component, handle: ResizeHandle) -> Self {
+        let document = component.element().owner_document().unwrap();
 
         Self {
             document,
 
             component,
+            handle,
             drag_state: None,
         }
     }
@@ -207,6 +537,7 @@ impl ResizeController {
             ResizeState::start(
                 layout,
                 self.component.clone(),
+                self.handle,
                 event.client_x(),
                 event.client_y(),
             )
@@ -216,7 +547,7 @@ impl ResizeController {
     /// Called when mouse moves
     pub fn mouse_move(&mut self, workspace: &mut Workspace, event: &web_sys::MouseEvent) {
         if let Some(drag_state) = self.drag_state.as_mut() {
-            drag_state.drag(workspace, event.client_x(), event.client_y());
+            drag_state.drag(event.client_x(), event.client_y(), event.shift_key());
         } else {
             self.drag_start(workspace, event);
         }
@@ -236,14 +567,90 @@ impl ResizeController {
             .unwrap();
 
         if let Some(mut drag_state) = self.drag_state {
-            drag_state.stop();
+            let displaced = drag_state.stop();
 
             DragResizeResult::Resized {
                 component: self.component,
                 layout: drag_state.layout,
+                displaced,
+                previous_height: drag_state.start_height,
             }
         } else {
             DragResizeResult::NotResized
         }
     }
+
+    /// Abort the resize
+    ///
+    /// Returns the resized component, its size in px has to be restored by the caller
+    pub fn cancel(self) -> Component {
+        self.component.set_is_selected(false);
+
+        self.component
+            .element()
+            .style()
+            .remove_property("pointer-events")
+            .unwrap();
+
+        if let Some(mut drag_state) = self.drag_state {
+            drag_state.cancel();
+        }
+
+        self.component
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START: Rect = ((100.0, 100.0), (200.0, 100.0));
+
+    #[test]
+    fn resize_keeps_the_opposite_edges() {
+        let limits = SizeLimits::default();
+
+        let rect = resize_rect(START, ResizeHandle::NW, (-20.0, 10.0), limits, false);
+        assert_eq!(rect, ((80.0, 110.0), (220.0, 90.0)));
+
+        let rect = resize_rect(START, ResizeHandle::E, (30.0, 50.0), limits, false);
+        assert_eq!(rect, ((100.0, 100.0), (230.0, 100.0)));
+    }
+
+    #[test]
+    fn resize_respects_the_limits() {
+        let limits = SizeLimits::inside(START, ResizeHandle::SE, (350.0, 400.0));
+
+        let rect = resize_rect(START, ResizeHandle::SE, (500.0, -500.0), limits, false);
+        assert_eq!(rect, ((100.0, 100.0), (250.0, MIN_SIZE)));
+
+        let limits = SizeLimits::inside(START, ResizeHandle::W, (350.0, 400.0));
+        let rect = resize_rect(START, ResizeHandle::W, (-500.0, 0.0), limits, false);
+        assert_eq!(rect, ((0.0, 100.0), (300.0, 100.0)));
+    }
+
+    #[test]
+    fn limits_are_narrowed_by_constraints() {
+        let constraints = SizeConstraints {
+            min_size: Some((40.0, 10.0)),
+            max_size: Some((120.0, 500.0)),
+            ..Default::default()
+        };
+        let limits =
+            SizeLimits::inside(START, ResizeHandle::SE, (350.0, 400.0)).constrained(&constraints);
+
+        assert_eq!(limits.min, (40.0, MIN_SIZE));
+        assert_eq!(limits.max, (120.0, 300.0));
+    }
+
+    #[test]
+    fn resize_can_keep_the_aspect_ratio() {
+        let limits = SizeLimits::default();
+
+        let rect = resize_rect(START, ResizeHandle::SE, (100.0, 10.0), limits, true);
+        assert_eq!(rect, ((100.0, 100.0), (300.0, 150.0)));
+
+        let rect = resize_rect(START, ResizeHandle::N, (0.0, -50.0), limits, true);
+        assert_eq!(rect, ((100.0, 50.0), (300.0, 150.0)));
+    }
 }
@@ -0,0 +1,17 @@
+use crate::document::{ComponentDocument, LayoutDocument};
+
+/// Offset in px of components pasted into free layouts, so that they do not hide the copied ones
+pub const PASTE_OFFSET: i32 = 16;
+
+/// Copied items of the workspace
+///
+/// Items are kept as serializable snapshots, so that changing or removing the copied items
+/// does not affect the clipboard, and it can be pasted into any page
+#[derive(Clone)]
+pub enum Clipboard {
+    /// One or more components, pasted into a single layout
+    Components(Vec<ComponentDocument>),
+    /// A whole layout with all of its components
+    Layout(Box<LayoutDocument>),
+    Empty,
+}
@@ -1,20 +1,20 @@
 #![allow(unused)]
 /// Include relevent crates and modules
 use super::Workspace;
-use gloo_events::EventListener;
-use wasm_bindgen::JsCast;
-use web_sys::{Element, HtmlElement};
 use crate::{
     component::Component,
     page::{layout::Layout, Page},
 };
+use gloo_events::EventListener;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlElement};
 
 /// Enum to identify the type of html element
 pub enum ElementType {
     PageElement,
     LayoutElement,
     ComponentElement,
-    None
+    None,
 }
 
 /// Enum to return the result for on click event on html element inside hirarchy tree
@@ -22,21 +22,21 @@ pub enum ClickResult {
     Page(Page),
     Layout(Layout),
     Component(Component),
-    None
+    None,
 }
 
 /// Hierarchy Item Data Struct to represent hierarchy tree related data
 pub struct HierarchyItemData {
-     /// Html element of the item
-     item_html_element: Option<HtmlElement>,
-     /// Html element of the arrow
-     arrow_html_element: Option<HtmlElement>,
-     /// Html element of the children container
-     children_html_element: Option<HtmlElement>,
-     /// Collapse status of the item
-     collapse_status: bool,
-     /// Type of element
-     element_type: ElementType,
+    /// Html element of the item
+    item_html_element: Option<HtmlElement>,
+    /// Html element of the arrow
+    arrow_html_element: Option<HtmlElement>,
+    /// Html element of the children container
+    children_html_element: Option<HtmlElement>,
+    /// Collapse status of the item
+    collapse_status: bool,
+    /// Type of element
+    element_type: ElementType,
 }
 
 /// Methods for Hierarchy Item Data Struct
@@ -102,21 +102,25 @@ impl Hierarchy {
             page_item_element.set_class_name("page-item");
 
             let page_item_header_element = document.create_element("header").unwrap();
-            let page_item_header_element: HtmlElement = page_item_header_element.dyn_into().unwrap();
+            let page_item_header_element: HtmlElement =
+                page_item_header_element.dyn_into().unwrap();
 
             let page_item_children_element = document.create_element("div").unwrap();
-            let page_item_children_element: HtmlElement = page_item_children_element.dyn_into().unwrap();
+            let page_item_children_element: HtmlElement =
+                page_item_children_element.dyn_into().unwrap();
 
             let page_item_arrow_element = document.create_element("div").unwrap();
             let page_item_arrow_element: HtmlElement = page_item_arrow_element.dyn_into().unwrap();
             page_item_arrow_element.set_class_name("page-item__icon expanded");
 
             let page_item_arrow_img_element = document.create_element("img").unwrap();
-            let page_item_arrow_img_element: HtmlElement = page_item_arrow_img_element.dyn_into().unwrap();
+            let page_item_arrow_img_element: HtmlElement =
+                page_item_arrow_img_element.dyn_into().unwrap();
             page_item_arrow_img_element.set_attribute(&"src", &"./img/icons/arrow_down.svg");
 
             let page_item_name_element = document.create_element("div").unwrap();
             let page_item_name_element: HtmlElement = page_item_name_element.dyn_into().unwrap();
+            page_item_name_element.set_class_name("page-item__name");
             page_item_name_element.set_inner_text(&page.name());
 
             // Add html elements of page in hierarchy
@@ -128,91 +132,24 @@ impl Hierarchy {
 
             // Add all layouts in page
             for layout in page.layouts().iter() {
-                // Create html elements of layout
-                let layout_item_element = document.create_element("div").unwrap();
-                let layout_item_element: HtmlElement = layout_item_element.dyn_into().unwrap();
-                layout_item_element.set_class_name("page-item__children");
-
-                let layout_item_header_element = document.create_element("header").unwrap();
-                let layout_item_header_element: HtmlElement = layout_item_header_element.dyn_into().unwrap();
-
-                let layout_item_children_element = document.create_element("div").unwrap();
-                let layout_item_children_element: HtmlElement = layout_item_children_element.dyn_into().unwrap();
-                layout_item_children_element.set_class_name("page-item__layout__children");
-
-                let layout_item_arrow_element = document.create_element("div").unwrap();
-                let layout_item_arrow_element: HtmlElement = layout_item_arrow_element.dyn_into().unwrap();
-                layout_item_arrow_element.set_class_name("page-item__icon expanded");
-
-                let layout_item_arrow_img_element = document.create_element("img").unwrap();
-                let layout_item_arrow_img_element: HtmlElement = layout_item_arrow_img_element.dyn_into().unwrap();
-                layout_item_arrow_img_element.set_attribute(&"src", &"./img/icons/arrow_down.svg");
-
-                let layout_item_name_element = document.create_element("div").unwrap();
-                let layout_item_name_element: HtmlElement = layout_item_name_element.dyn_into().unwrap();
-                layout_item_name_element.set_inner_text(&layout.name());
-
-                // Add html elements of layout in page
-                layout_item_arrow_element.append_child(&layout_item_arrow_img_element);
-                layout_item_header_element.append_child(&layout_item_arrow_element);
-                layout_item_header_element.append_child(&layout_item_name_element);
-                layout_item_element.append_child(&layout_item_header_element);
-                layout_item_element.append_child(&layout_item_children_element);
-
-                // Add all components in layout
-                for component in layout.components().iter() {
-                    let component_item_element = document.create_element("div").unwrap();
-                    let component_item_element: HtmlElement = component_item_element.dyn_into().unwrap();
-                    component_item_element.class_list().add_1("page-item__component");
-
-                    let component_item_arrow_element = document.create_element("div").unwrap();
-                    let component_item_arrow_element: HtmlElement = component_item_arrow_element.dyn_into().unwrap();
-                    component_item_arrow_element.set_class_name("page-item__component_icon");
-
-                    let component_item_arrow_img_element = document.create_element("img").unwrap();
-                    let component_item_arrow_img_element: HtmlElement = component_item_arrow_img_element.dyn_into().unwrap();
-                    component_item_arrow_img_element.set_attribute(&"src", &"./img/icons/check.svg");
-
-                    let component_item_name_element = document.create_element("div").unwrap();
-                    let component_item_name_element: HtmlElement = component_item_name_element.dyn_into().unwrap();
-                    component_item_name_element.set_inner_text(&component.name());
-
-                    // Add html elements of component in layout children container
-                    component_item_arrow_element.append_child(&component_item_arrow_img_element);
-                    component_item_element.append_child(&component_item_arrow_element);
-                    component_item_element.append_child(&component_item_name_element);
-
-                    // Add component in layout
-                    layout_item_children_element.append_child(&component_item_element);
-
-                    // Process Hierarchy Item Data for Component
-                    let mut component_hierarchy_item_data = component.hierarchy_data_mut();
-                    component_hierarchy_item_data.item_html_element = Some(component_item_element);
-                    component_hierarchy_item_data.arrow_html_element = Some(component_item_arrow_element);
-                    component_hierarchy_item_data.element_type = ElementType::ComponentElement;
-                }
+                let layout_item_element = Self::layout_item(&document, layout);
+                page_item_children_element.append_child(&layout_item_element);
 
-                // Process Hierarchy Item Data for Layout (using previous states if needed)
-                let mut layout_hierarchy_item_data = layout.hierarchy_data_mut();
-                if(layout_hierarchy_item_data.collapse_status) {
-                    layout_item_arrow_element.set_class_name("page-item__icon");
-                    layout_item_children_element.set_class_name("hide-children");
+                // Layouts of a page can be dragged in front of other layouts
+                if let Some(header) = &layout.hierarchy_data().item_html_element {
+                    header.set_draggable(true);
                 }
-                layout_hierarchy_item_data.item_html_element = Some(layout_item_header_element);
-                layout_hierarchy_item_data.arrow_html_element = Some(layout_item_arrow_element);
-                layout_hierarchy_item_data.children_html_element = Some(layout_item_children_element);
-                layout_hierarchy_item_data.element_type = ElementType::LayoutElement;
-
-                // Add layout in page
-                page_item_children_element.append_child(&layout_item_element);
             }
 
             // Process Hierarchy Item Data for Page (using previous states if needed)
             let mut page_hierarchy_item_data = page.hierarchy_data_mut();
-            if(page_hierarchy_item_data.collapse_status) {
+            if (page_hierarchy_item_data.collapse_status) {
                 page_item_arrow_element.set_class_name("page-item__icon");
-                page_item_children_element.set_class_name("page-item__layout__children hide-children");
+                page_item_children_element
+                    .set_class_name("page-item__layout__children hide-children");
             }
+            // Pages can be dragged in front of other pages
+            page_item_header_element.set_draggable(true);
             page_hierarchy_item_data.item_html_element = Some(page_item_header_element);
             page_hierarchy_item_data.arrow_html_element = Some(page_item_arrow_element);
             page_hierarchy_item_data.element_type = ElementType::PageElement;
@@ -223,7 +160,178 @@ impl Hierarchy {
         }
 
         // Replace the html element for hierarchy
-        self.html_element.replace_child(&new_inspector_tree, &inspector_tree);
+        self.html_element
+            .replace_child(&new_inspector_tree, &inspector_tree);
+    }
+
+    /// Create the hierarchy item of a layout, together with its components and nested layouts
+    fn layout_item(document: &web_sys::Document, layout: &Layout) -> HtmlElement {
+        // Create html elements of layout
+        let layout_item_element = document.create_element("div").unwrap();
+        let layout_item_element: HtmlElement = layout_item_element.dyn_into().unwrap();
+        layout_item_element.set_class_name("page-item__children");
+
+        let layout_item_header_element = document.create_element("header").unwrap();
+        let layout_item_header_element: HtmlElement =
+            layout_item_header_element.dyn_into().unwrap();
+
+        let layout_item_children_element = document.create_element("div").unwrap();
+        let layout_item_children_element: HtmlElement =
+            layout_item_children_element.dyn_into().unwrap();
+        layout_item_children_element.set_class_name("page-item__layout__children");
+
+        let layout_item_arrow_element = document.create_element("div").unwrap();
+        let layout_item_arrow_element: HtmlElement = layout_item_arrow_element.dyn_into().unwrap();
+        layout_item_arrow_element.set_class_name("page-item__icon expanded");
+
+        let layout_item_arrow_img_element = document.create_element("img").unwrap();
+        let layout_item_arrow_img_element: HtmlElement =
+            layout_item_arrow_img_element.dyn_into().unwrap();
+        layout_item_arrow_img_element.set_attribute(&"src", &"./img/icons/arrow_down.svg");
+
+        let layout_item_name_element = document.create_element("div").unwrap();
+        let layout_item_name_element: HtmlElement = layout_item_name_element.dyn_into().unwrap();
+        layout_item_name_element.set_class_name("page-item__name");
+        layout_item_name_element.set_inner_text(&layout.name());
+        layout_item_name_element.set_title(&layout.uid());
+
+        // Add html elements of layout in page
+        layout_item_arrow_element.append_child(&layout_item_arrow_img_element);
+        layout_item_header_element.append_child(&layout_item_arrow_element);
+        layout_item_header_element.append_child(&layout_item_name_element);
+        layout_item_element.append_child(&layout_item_header_element);
+        layout_item_element.append_child(&layout_item_children_element);
+
+        // Add all components in layout
+        for component in layout.components().iter() {
+            let component_item_element = document.create_element("div").unwrap();
+            let component_item_element: HtmlElement = component_item_element.dyn_into().unwrap();
+            component_item_element
+                .class_list()
+                .add_1("page-item__component");
+
+            let component_item_arrow_element = document.create_element("div").unwrap();
+            let component_item_arrow_element: HtmlElement =
+                component_item_arrow_element.dyn_into().unwrap();
+            component_item_arrow_element.set_class_name("page-item__component_icon");
+
+            let component_item_arrow_img_element = document.create_element("img").unwrap();
+            let component_item_arrow_img_element: HtmlElement =
+                component_item_arrow_img_element.dyn_into().unwrap();
+            component_item_arrow_img_element.set_attribute(&"src", &"./img/icons/check.svg");
+
+            let component_item_name_element = document.create_element("div").unwrap();
+            let component_item_name_element: HtmlElement =
+                component_item_name_element.dyn_into().unwrap();
+            component_item_name_element.set_class_name("page-item__name");
+            component_item_name_element.set_inner_text(&component.name());
+            component_item_name_element.set_title(&component.uid());
+
+            // Add html elements of component in layout children container
+            component_item_arrow_element.append_child(&component_item_arrow_img_element);
+            component_item_element.append_child(&component_item_arrow_element);
+            component_item_element.append_child(&component_item_name_element);
+
+            // Add component in layout
+            layout_item_children_element.append_child(&component_item_element);
+
+            // Components can be dragged onto other layouts, or in front of other components
+            component_item_element.set_draggable(true);
+
+            // Process Hierarchy Item Data for Component
+            let mut component_hierarchy_item_data = component.hierarchy_data_mut();
+            component_hierarchy_item_data.item_html_element = Some(component_item_element);
+            component_hierarchy_item_data.arrow_html_element = Some(component_item_arrow_element);
+            component_hierarchy_item_data.element_type = ElementType::ComponentElement;
+        }
+
+        // Add all nested layouts in layout
+        for child in layout.children().iter() {
+            let child_item_element = Self::layout_item(document, child);
+            layout_item_children_element.append_child(&child_item_element);
+        }
+
+        // Process Hierarchy Item Data for Layout (using previous states if needed)
+        let mut layout_hierarchy_item_data = layout.hierarchy_data_mut();
+        if (layout_hierarchy_item_data.collapse_status) {
+            layout_item_arrow_element.set_class_name("page-item__icon");
+            layout_item_children_element.set_class_name("hide-children");
+        }
+        layout_hierarchy_item_data.item_html_element = Some(layout_item_header_element);
+        layout_hierarchy_item_data.arrow_html_element = Some(layout_item_arrow_element);
+        layout_hierarchy_item_data.children_html_element = Some(layout_item_children_element);
+        layout_hierarchy_item_data.element_type = ElementType::LayoutElement;
+
+        layout_item_element
+    }
+
+    /// Find the page whose hierarchy item (not including its layouts) contains the element
+    pub fn find_page(&self, workspace: &Workspace, elm: &HtmlElement) -> Option<Page> {
+        workspace
+            .pages()
+            .iter()
+            .find(|page| {
+                let data = page.hierarchy_data();
+                let item = data.item_html_element.as_ref();
+                item.is_some_and(|item| item.contains(Some(elm)))
+            })
+            .cloned()
+    }
+
+    /// Find the layout (or a nested layout) whose hierarchy item contains the element
+    pub fn find_layout(&self, workspace: &Workspace, elm: &HtmlElement) -> Option<Layout> {
+        workspace
+            .pages()
+            .iter()
+            .flat_map(|page| page.all_layouts())
+            .find(|layout| {
+                let data = layout.hierarchy_data();
+                let item = data.item_html_element.as_ref();
+                item.is_some_and(|item| item.contains(Some(elm)))
+            })
+    }
+
+    /// Find the component whose hierarchy item contains the element
+    pub fn find_component(&self, workspace: &Workspace, elm: &HtmlElement) -> Option<Component> {
+        workspace
+            .components()
+            .iter()
+            .map(|(_, component)| component)
+            .find(|component| {
+                let data = component.hierarchy_data();
+                let item = data.item_html_element.as_ref();
+                item.is_some_and(|item| item.contains(Some(elm)))
+            })
+            .cloned()
+    }
+
+    /// Where a layout dropped onto a hierarchy item is moved to
+    ///
+    /// Returns the page together with the index of the layout that it is dropped in front of,
+    /// dropping onto a page moves it to the end of the page
+    pub fn layout_drop(
+        &self,
+        workspace: &Workspace,
+        target: &HtmlElement,
+    ) -> Option<(Page, usize)> {
+        let contains = |data: &HierarchyItemData| {
+            let item = data.item_html_element.as_ref();
+            item.is_some_and(|item| item.contains(Some(target)))
+        };
+
+        workspace.pages().iter().find_map(|page| {
+            if contains(&page.hierarchy_data()) {
+                return Some((page.clone(), page.layouts().len()));
+            }
+
+            // Dropping onto a nested layout means dropping onto the layout it is nested in
+            let index = page.layouts().iter().position(|root| {
+                root.tree()
+                    .iter()
+                    .any(|layout| contains(&layout.hierarchy_data()))
+            })?;
+            Some((page.clone(), index))
+        })
     }
 
     /// Determines whether the tree contains a given html element
@@ -238,58 +346,77 @@ impl Hierarchy {
             let mut page_hierarchy_item_data = page.hierarchy_data_mut();
             let page_item_html_element = page_hierarchy_item_data.item_html_element.as_ref();
             let page_arrow_html_element = page_hierarchy_item_data.arrow_html_element.as_ref();
-            let page_children_html_element = page_hierarchy_item_data.children_html_element.as_ref();
+            let page_children_html_element =
+                page_hierarchy_item_data.children_html_element.as_ref();
             if page_item_html_element.unwrap().contains(Some(target)) {
                 // If arrow icon is clicked, then expand/collapse the page and update its collapse status state
                 if page_arrow_html_element.unwrap().contains(Some(target)) {
                     if page_hierarchy_item_data.collapse_status {
-                        page_arrow_html_element.unwrap().set_class_name("page-item__icon expanded");
+                        page_arrow_html_element
+                            .unwrap()
+                            .set_class_name("page-item__icon expanded");
                         page_children_html_element.unwrap().set_class_name("");
                     } else {
-                        page_arrow_html_element.unwrap().set_class_name("page-item__icon");
-                        page_children_html_element.unwrap().set_class_name("hide-children");
+                        page_arrow_html_element
+                            .unwrap()
+                            .set_class_name("page-item__icon");
+                        page_children_html_element
+                            .unwrap()
+                            .set_class_name("hide-children");
                     }
-                    page_hierarchy_item_data.collapse_status = !page_hierarchy_item_data.collapse_status;
-                    return ClickResult::None
-                }
-                else {
-                    return ClickResult::Page(page.clone())
+                    page_hierarchy_item_data.collapse_status =
+                        !page_hierarchy_item_data.collapse_status;
+                    return ClickResult::None;
+                } else {
+                    return ClickResult::Page(page.clone());
                 }
             }
-            // Check if a layout is clicked
-            for layout in page.layouts().iter() {
+            // Check if a layout (or a nested layout) is clicked
+            for layout in page.all_layouts().iter() {
                 let mut layout_hierarchy_item_data = layout.hierarchy_data_mut();
-                let layout_item_html_element = layout_hierarchy_item_data.item_html_element.as_ref();
-                let layout_arrow_html_element = layout_hierarchy_item_data.arrow_html_element.as_ref();
-                let layout_children_html_element = layout_hierarchy_item_data.children_html_element.as_ref();
+                let layout_item_html_element =
+                    layout_hierarchy_item_data.item_html_element.as_ref();
+                let layout_arrow_html_element =
+                    layout_hierarchy_item_data.arrow_html_element.as_ref();
+                let layout_children_html_element =
+                    layout_hierarchy_item_data.children_html_element.as_ref();
                 if layout_item_html_element.unwrap().contains(Some(target)) {
                     // If arrow icon is clicked, then expand/collapse the layout and update its collapse status state
                     if layout_arrow_html_element.unwrap().contains(Some(target)) {
                         if layout_hierarchy_item_data.collapse_status {
-                            layout_arrow_html_element.unwrap().set_class_name("page-item__icon expanded");
-                            layout_children_html_element.unwrap().set_class_name("page-item__layout__children");
+                            layout_arrow_html_element
+                                .unwrap()
+                                .set_class_name("page-item__icon expanded");
+                            layout_children_html_element
+                                .unwrap()
+                                .set_class_name("page-item__layout__children");
                         } else {
-                            layout_arrow_html_element.unwrap().set_class_name("page-item__icon");
-                            layout_children_html_element.unwrap().set_class_name("page-item__layout__children hide-children");
+                            layout_arrow_html_element
+                                .unwrap()
+                                .set_class_name("page-item__icon");
+                            layout_children_html_element
+                                .unwrap()
+                                .set_class_name("page-item__layout__children hide-children");
                         }
-                        layout_hierarchy_item_data.collapse_status = !layout_hierarchy_item_data.collapse_status;
-                        return ClickResult::None
-                    }
-                    else {
-                        return ClickResult::Layout(layout.clone())
+                        layout_hierarchy_item_data.collapse_status =
+                            !layout_hierarchy_item_data.collapse_status;
+                        return ClickResult::None;
+                    } else {
+                        return ClickResult::Layout(layout.clone());
                     }
                 }
                 // Check if a component is clicked
                 for component in layout.components().iter() {
                     let mut component_hierarchy_item_data = component.hierarchy_data_mut();
-                    let component_item_html_element = component_hierarchy_item_data.item_html_element.as_ref();
-                    let component_arrow_html_element = component_hierarchy_item_data.arrow_html_element.as_ref();
+                    let component_item_html_element =
+                        component_hierarchy_item_data.item_html_element.as_ref();
+                    let component_arrow_html_element =
+                        component_hierarchy_item_data.arrow_html_element.as_ref();
                     if component_item_html_element.unwrap().contains(Some(target)) {
                         if component_arrow_html_element.unwrap().contains(Some(target)) {
-                            return ClickResult::None
-                        }
-                        else {
-                            return ClickResult::Component(component.clone())
+                            return ClickResult::None;
+                        } else {
+                            return ClickResult::Component(component.clone());
                         }
                     }
                 }
@@ -0,0 +1,722 @@
+use std::{cell::Cell, collections::VecDeque};
+
+use crate::{
+    component::Component,
+    page::{
+        breakpoint::Breakpoint,
+        layout::{ChildPlacement, KindSnapshot, Layout, LayoutKind},
+        metadata::PageMetadata,
+        Page, RemovedBreakpoint,
+    },
+};
+
+use super::EditorState;
+
+/// Maximum number of commands remembered by the history
+pub const HISTORY_LIMIT: usize = 100;
+
+thread_local! {
+    /// Id of the next input session
+    static NEXT_SESSION: Cell<u32> = const { Cell::new(0) };
+}
+
+/// Start a new input session, the changes made by an input until it is committed
+///
+/// Returns the id of the session, see [`History::push_in_session`]
+pub fn new_input_session() -> u32 {
+    NEXT_SESSION.with(|next| {
+        let session = next.get();
+        next.set(session.wrapping_add(1));
+        session
+    })
+}
+
+/// State of a component inside of a layout
+///
+/// Captured before and after a mutation, so that it can be restored later
+#[derive(Clone, PartialEq)]
+pub struct ComponentPlacement {
+    /// Layout that the component is placed in
+    pub layout: Layout,
+
+    grid_pos: Option<(usize, usize)>,
+    grid_size: Option<(usize, usize)>,
+    position: Option<(i32, i32)>,
+    size: Option<(f64, f64)>,
+    /// Position in the order of the components, only captured in flex layouts
+    order: Option<usize>,
+}
+
+impl ComponentPlacement {
+    /// Capture the current placement of a component
+    pub fn capture(layout: Layout, component: &Component) -> Self {
+        let order = match &*layout.kind() {
+            LayoutKind::Flex { .. } => layout.component_order(component),
+            _ => None,
+        };
+
+        Self {
+            layout,
+
+            grid_pos: component.grid_pos(),
+            grid_size: component.grid_size(),
+            position: component.position(),
+            size: component.stored_size(),
+            order,
+        }
+    }
+
+    /// Captured top left cell of the component inside of a grid layout
+    pub fn grid_pos(&self) -> Option<(usize, usize)> {
+        self.grid_pos
+    }
+
+    /// Captured position of the component inside of a free layout
+    pub fn position(&self) -> Option<(i32, i32)> {
+        self.position
+    }
+
+    /// Put the component back into the captured state, and into the captured layout
+    pub fn restore(&self, component: &mut Component) {
+        self.apply(component);
+        self.layout.clone().insert_component(component.clone());
+
+        if let Some(order) = self.order {
+            self.layout.move_component(component, order);
+        }
+    }
+
+    /// Put the component back into the captured state
+    fn apply(&self, component: &mut Component) {
+        if let Some(pos) = self.grid_pos {
+            component.set_grid_pos(pos);
+        }
+        if let Some(size) = self.grid_size {
+            component.set_grid_size(size);
+        }
+
+        match self.position {
+            Some(position) => component.set_position(position),
+            None => component.unset_absolute_pos(),
+        }
+
+        match self.size {
+            Some((w, h)) => component.set_size(w, h),
+            None => component.unset_size(),
+        }
+    }
+}
+
+/// A single reversible mutation of the workspace
+pub enum Command {
+    /// Component was added, moved, resized or removed
+    ///
+    /// `None` placement means that the component is not a part of the workspace
+    PlaceComponent {
+        component: Component,
+        from: Option<ComponentPlacement>,
+        to: Option<ComponentPlacement>,
+    },
+    /// Layout was inserted into a page
+    InsertLayout {
+        page: Page,
+        layout: Layout,
+        index: usize,
+    },
+    /// Layout was removed from a page, together with all of its components
+    RemoveLayout {
+        page: Page,
+        layout: Layout,
+        index: usize,
+    },
+    /// Layout was moved to a different position in its page, or to a different page
+    ///
+    /// Positions are the page together with the index of the layout in it
+    MoveLayout {
+        layout: Layout,
+        from: (Page, usize),
+        to: (Page, usize),
+    },
+    /// Nested layout was added to, moved inside of, or removed from its parent layout
+    ///
+    /// `None` placement means that the layout is not a part of the parent
+    PlaceLayout {
+        parent: Layout,
+        layout: Layout,
+        from: Option<ChildPlacement>,
+        to: Option<ChildPlacement>,
+    },
+    /// Page was added to the workspace
+    InsertPage { page: Page, index: usize },
+    /// Page was removed from the workspace, together with all of its layouts
+    RemovePage { page: Page, index: usize },
+    /// Page was moved to a different position in the workspace
+    MovePage { from: usize, to: usize },
+    /// Page was renamed
+    RenamePage {
+        page: Page,
+        from: String,
+        to: String,
+    },
+    /// Layout was renamed
+    RenameLayout {
+        layout: Layout,
+        from: String,
+        to: String,
+    },
+    /// Component was renamed
+    RenameComponent {
+        component: Component,
+        from: String,
+        to: String,
+    },
+    /// Route, title or description of a page was changed
+    SetPageMetadata {
+        page: Page,
+        from: PageMetadata,
+        to: PageMetadata,
+    },
+    /// Page width was changed
+    ResizePage { page: Page, from: u32, to: u32 },
+    /// Layout height was changed
+    ResizeLayout { layout: Layout, from: u32, to: u32 },
+    /// Breakpoint was added to a page, or the width of an existing one was changed
+    ///
+    /// `from` is the breakpoint with the same name that the page had before, if any
+    AddBreakpoint {
+        page: Page,
+        from: Option<Breakpoint>,
+        to: Breakpoint,
+    },
+    /// Breakpoint was removed from a page, together with the arrangements stored for it
+    RemoveBreakpoint {
+        page: Page,
+        removed: Box<RemovedBreakpoint>,
+    },
+    /// Page was switched to a different breakpoint
+    SetBreakpoint {
+        page: Page,
+        from: String,
+        to: String,
+    },
+    /// Component was hidden or shown at the current breakpoint
+    SetComponentHidden {
+        layout: Layout,
+        component: Component,
+        hidden: bool,
+    },
+    /// Layout was converted to a different kind, together with the placements of its items
+    ConvertLayout {
+        layout: Layout,
+        from: Box<KindSnapshot>,
+        to: Box<KindSnapshot>,
+    },
+    /// Several commands that are undone and redone as a single step
+    Group(Vec<Command>),
+}
+
+impl Command {
+    /// Combine commands into a single step, a lone command is returned as is
+    pub fn group(mut commands: Vec<Command>) -> Self {
+        if commands.len() == 1 {
+            commands.pop().unwrap()
+        } else {
+            Self::Group(commands)
+        }
+    }
+
+    /// Apply the command once again
+    pub fn redo(&self, editor: &mut EditorState) {
+        match self {
+            Self::PlaceComponent {
+                component,
+                from,
+                to,
+            } => place_component(editor, component, from, to),
+            Self::InsertLayout {
+                page,
+                layout,
+                index,
+            } => attach_layout(editor, page, layout, *index),
+            Self::RemoveLayout { page, index, .. } => detach_layout(editor, page, *index),
+            Self::MoveLayout { layout, from, to } => move_layout(layout, from, to),
+            Self::PlaceLayout {
+                parent,
+                layout,
+                from,
+                to,
+            } => place_layout(editor, parent, layout, from, to),
+            Self::InsertPage { page, index } => attach_page(editor, page, *index),
+            Self::RemovePage { page, .. } => detach_page(editor, page),
+            Self::MovePage { from, to } => {
+                editor.workspace.move_page(*from, *to);
+            }
+            Self::RenamePage { page, to, .. } => page.clone().set_name(to),
+            Self::RenameLayout { layout, to, .. } => layout.clone().set_name(to),
+            Self::RenameComponent { component, to, .. } => component.clone().set_name(to),
+            Self::SetPageMetadata { page, to, .. } => page.clone().set_metadata(to.clone()),
+            Self::ResizePage { page, to, .. } => editor.resize_page(&page.html_element, *to),
+            Self::ResizeLayout { layout, to, .. } => {
+                editor.resize_layout(&layout.html_element, *to)
+            }
+            Self::AddBreakpoint { page, to, .. } => page.clone().add_breakpoint(to.clone()),
+            Self::RemoveBreakpoint { page, removed } => {
+                page.clone().remove_breakpoint(&removed.breakpoint.name);
+            }
+            Self::SetBreakpoint { page, to, .. } => {
+                page.clone().set_breakpoint(to);
+            }
+            Self::SetComponentHidden {
+                layout,
+                component,
+                hidden,
+            } => layout.clone().set_component_hidden(component, *hidden),
+            Self::ConvertLayout { layout, to, .. } => layout.clone().apply_kind_snapshot(to),
+            Self::Group(commands) => {
+                for command in commands.iter() {
+                    command.redo(editor);
+                }
+            }
+        }
+    }
+
+    /// Revert the changes made by the command
+    pub fn undo(&self, editor: &mut EditorState) {
+        match self {
+            Self::PlaceComponent {
+                component,
+                from,
+                to,
+            } => place_component(editor, component, to, from),
+            Self::InsertLayout { page, index, .. } => detach_layout(editor, page, *index),
+            Self::RemoveLayout {
+                page,
+                layout,
+                index,
+            } => attach_layout(editor, page, layout, *index),
+            Self::MoveLayout { layout, from, to } => move_layout(layout, to, from),
+            Self::PlaceLayout {
+                parent,
+                layout,
+                from,
+                to,
+            } => place_layout(editor, parent, layout, to, from),
+            Self::InsertPage { page, .. } => detach_page(editor, page),
+            Self::RemovePage { page, index } => attach_page(editor, page, *index),
+            Self::MovePage { from, to } => {
+                editor.workspace.move_page(*to, *from);
+            }
+            Self::RenamePage { page, from, .. } => page.clone().set_name(from),
+            Self::RenameLayout { layout, from, .. } => layout.clone().set_name(from),
+            Self::RenameComponent {
+                component, from, ..
+            } => component.clone().set_name(from),
+            Self::SetPageMetadata { page, from, .. } => page.clone().set_metadata(from.clone()),
+            Self::ResizePage { page, from, .. } => editor.resize_page(&page.html_element, *from),
+            Self::ResizeLayout { layout, from, .. } => {
+                editor.resize_layout(&layout.html_element, *from)
+            }
+            Self::AddBreakpoint { page, from, to } => match from {
+                Some(from) => page.clone().add_breakpoint(from.clone()),
+                None => {
+                    page.clone().remove_breakpoint(&to.name);
+                }
+            },
+            Self::RemoveBreakpoint { page, removed } => page.clone().restore_breakpoint(removed),
+            Self::SetBreakpoint { page, from, .. } => {
+                page.clone().set_breakpoint(from);
+            }
+            Self::SetComponentHidden {
+                layout,
+                component,
+                hidden,
+            } => layout.clone().set_component_hidden(component, !*hidden),
+            Self::ConvertLayout { layout, from, .. } => layout.clone().apply_kind_snapshot(from),
+            Self::Group(commands) => {
+                for command in commands.iter().rev() {
+                    command.undo(editor);
+                }
+            }
+        }
+    }
+}
+
+impl Merge for Command {
+    /// Fold the next resize of the same page or layout into this one
+    ///
+    /// The history only merges commands of a single input session, like one slider drag
+    fn merge(&mut self, next: &Command) -> bool {
+        match (self, next) {
+            (
+                Self::ResizePage { page, to, .. },
+                Self::ResizePage {
+                    page: next_page,
+                    to: next_to,
+                    ..
+                },
+            ) if page == next_page => {
+                *to = *next_to;
+                true
+            }
+            (
+                Self::ResizeLayout { layout, to, .. },
+                Self::ResizeLayout {
+                    layout: next_layout,
+                    to: next_to,
+                    ..
+                },
+            ) if layout == next_layout => {
+                *to = *next_to;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Move the component between two placements
+fn place_component(
+    editor: &mut EditorState,
+    component: &Component,
+    from: &Option<ComponentPlacement>,
+    to: &Option<ComponentPlacement>,
+) {
+    let mut component = component.clone();
+
+    match from {
+        Some(from) => from.layout.clone().remove_component(&mut component),
+        None => {
+            // The component is not known to the workspace, so it has to be registered again
+            let id = editor.workspace.insert_component(component.clone());
+            component.set_id(id);
+            component.restore();
+        }
+    }
+
+    match to {
+        Some(to) => {
+            to.restore(&mut component);
+        }
+        None => {
+            component.remove();
+            editor.workspace.components_mut().remove(component.index());
+        }
+    }
+}
+
+/// Move the nested layout between two placements inside of its parent
+fn place_layout(
+    editor: &mut EditorState,
+    parent: &Layout,
+    layout: &Layout,
+    from: &Option<ChildPlacement>,
+    to: &Option<ChildPlacement>,
+) {
+    let mut parent = parent.clone();
+
+    match (from, to) {
+        (_, Some(to)) => {
+            parent.insert_child(layout.clone(), *to);
+            if from.is_none() {
+                register_components(editor, layout);
+            }
+        }
+        (Some(_), None) => {
+            parent.remove_child(layout);
+            unregister_components(editor, layout);
+            layout.clone().remove();
+        }
+        (None, None) => {}
+    }
+}
+
+/// Insert the layout back into the page, together with all of its components
+fn attach_layout(editor: &mut EditorState, page: &Page, layout: &Layout, index: usize) {
+    page.clone().insert_layout(layout.clone(), Some(index));
+    register_components(editor, layout);
+}
+
+/// Remove the layout at a given index from the page, together with all of its components
+fn detach_layout(editor: &mut EditorState, page: &Page, index: usize) {
+    if let Some(layout) = page.clone().remove_layout(index) {
+        unregister_components(editor, &layout);
+        layout.remove();
+    }
+}
+
+/// Move the layout between two positions, possibly in different pages
+///
+/// Layouts moved to a different page take its width and are arranged for the breakpoint it is shown at
+fn move_layout(layout: &Layout, (from_page, from): &(Page, usize), (to_page, to): &(Page, usize)) {
+    let mut layout = layout.clone();
+
+    if from_page == to_page {
+        from_page.clone().move_layout(*from, *to);
+        return;
+    }
+
+    if from_page.clone().remove_layout(*from).is_none() {
+        return;
+    }
+
+    layout.store_breakpoint(&from_page.active_breakpoint().name);
+    layout.resize(Some(to_page.width()), None);
+
+    to_page.clone().insert_layout(layout.clone(), Some(*to));
+    layout.apply_breakpoint(&to_page.active_breakpoint().name);
+}
+
+/// Insert the page back into the workspace, together with all of its components
+fn attach_page(editor: &mut EditorState, page: &Page, index: usize) {
+    editor.workspace.insert_page_at(page.clone(), index);
+
+    for layout in page.layouts().iter() {
+        register_components(editor, layout);
+    }
+}
+
+/// Remove the page from the workspace, together with all of its components
+fn detach_page(editor: &mut EditorState, page: &Page) {
+    editor.workspace.remove_page(page);
+}
+
+/// Register the components of the layout (and of the layouts nested inside of it) in the workspace again
+fn register_components(editor: &mut EditorState, layout: &Layout) {
+    for mut layout in layout.tree() {
+        let components: Vec<Component> = layout.components().to_vec();
+
+        for mut component in components {
+            // Components got new ids when they were removed from the arena,
+            // so they have to be reinserted under the new id
+            layout.remove_component(&mut component);
+
+            let id = editor.workspace.insert_component(component.clone());
+            component.set_id(id);
+            component.restore();
+
+            layout.insert_component(component);
+        }
+    }
+}
+
+/// Remove the components of the layout (and of the layouts nested inside of it) from the workspace
+fn unregister_components(editor: &mut EditorState, layout: &Layout) {
+    for layout in layout.tree() {
+        for component in layout.components().iter() {
+            component.remove();
+            editor.workspace.components_mut().remove(component.index());
+        }
+    }
+}
+
+/// Command that can be folded together with the command recorded after it
+pub trait Merge {
+    /// Try to fold the next command into this one
+    ///
+    /// Returns `false` if the commands have to stay separate steps
+    fn merge(&mut self, next: &Self) -> bool;
+}
+
+/// Bounded undo/redo history of the editor
+pub struct History<C = Command> {
+    undo: VecDeque<C>,
+    redo: Vec<C>,
+
+    limit: usize,
+    /// Input session that the last recorded command belongs to
+    session: Option<u32>,
+}
+
+impl<C: Merge> History<C> {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+
+            limit,
+            session: None,
+        }
+    }
+
+    /// Record a command that was just applied
+    ///
+    /// Any undone commands are discarded, as they no longer apply to the current state
+    pub fn push(&mut self, command: C) {
+        self.redo.clear();
+        self.session = None;
+
+        self.undo.push_back(command);
+
+        if self.undo.len() > self.limit {
+            self.undo.pop_front();
+        }
+    }
+
+    /// Record a command that was applied by an input session, like a slider drag
+    ///
+    /// Inputs fire an event for every change, so the commands of one session are merged
+    /// into a single step, instead of flooding the history with hundreds of tiny ones
+    ///
+    /// # Arguments
+    /// * `session` - id of the session, that changes whenever a new one starts
+    pub fn push_in_session(&mut self, command: C, session: u32) {
+        if self.session == Some(session) {
+            if let Some(last) = self.undo.back_mut() {
+                if last.merge(&command) {
+                    self.redo.clear();
+                    return;
+                }
+            }
+        }
+
+        self.push(command);
+        self.session = Some(session);
+    }
+
+    /// Take the last applied command, in order to undo it
+    pub fn take_undo(&mut self) -> Option<C> {
+        self.session = None;
+        self.undo.pop_back()
+    }
+
+    /// Take the last undone command, in order to redo it
+    pub fn take_redo(&mut self) -> Option<C> {
+        self.session = None;
+        self.redo.pop()
+    }
+
+    /// Store a command that was just undone
+    pub fn push_undone(&mut self, command: C) {
+        self.redo.push(command);
+    }
+
+    /// Store a command that was just redone
+    pub fn push_redone(&mut self, command: C) {
+        self.undo.push_back(command);
+    }
+
+    /// Forget all of the recorded commands
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.session = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Command that sets a value, consecutive ones merge if they set the same value
+    #[derive(Debug, PartialEq)]
+    struct Set {
+        value: &'static str,
+        to: u32,
+    }
+
+    impl Merge for Set {
+        fn merge(&mut self, next: &Self) -> bool {
+            let merged = self.value == next.value;
+            if merged {
+                self.to = next.to;
+            }
+            merged
+        }
+    }
+
+    fn set(value: &'static str, to: u32) -> Set {
+        Set { value, to }
+    }
+
+    fn undo_all(history: &mut History<Set>) -> Vec<Set> {
+        std::iter::from_fn(|| history.take_undo()).collect()
+    }
+
+    #[test]
+    fn oldest_commands_are_dropped_over_the_limit() {
+        let mut history = History::new(3);
+        for to in 0..5 {
+            history.push(set("width", to));
+        }
+
+        assert_eq!(
+            undo_all(&mut history),
+            vec![set("width", 4), set("width", 3), set("width", 2)]
+        );
+    }
+
+    #[test]
+    fn new_commands_discard_the_undone_ones() {
+        let mut history = History::new(HISTORY_LIMIT);
+        history.push(set("width", 1));
+        history.push(set("height", 2));
+
+        let undone = history.take_undo().unwrap();
+        history.push_undone(undone);
+
+        history.push(set("width", 3));
+        assert_eq!(history.take_redo(), None);
+        assert_eq!(
+            undo_all(&mut history),
+            vec![set("width", 3), set("width", 1)]
+        );
+
+        history.push(set("width", 4));
+        let undone = history.take_undo().unwrap();
+        history.push_undone(undone);
+
+        history.push_in_session(set("width", 5), 1);
+        assert_eq!(history.take_redo(), None);
+    }
+
+    #[test]
+    fn only_commands_of_the_same_session_are_merged() {
+        let mut history = History::new(HISTORY_LIMIT);
+        history.push(set("width", 1));
+        history.push(set("width", 2));
+
+        history.push_in_session(set("width", 3), 1);
+        history.push_in_session(set("width", 4), 1);
+        history.push_in_session(set("width", 5), 1);
+        history.push_in_session(set("height", 6), 1);
+        history.push_in_session(set("height", 7), 1);
+
+        // Same value as the last command, but changed by a different session
+        history.push_in_session(set("height", 8), 2);
+        history.push_in_session(set("height", 9), 2);
+
+        assert_eq!(
+            undo_all(&mut history),
+            vec![
+                set("height", 9),
+                set("height", 7),
+                set("width", 5),
+                set("width", 2),
+                set("width", 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn sessions_are_interrupted_by_other_commands_and_undo() {
+        let mut history = History::new(HISTORY_LIMIT);
+        history.push_in_session(set("width", 1), 1);
+        history.push(set("height", 2));
+        history.push_in_session(set("height", 3), 1);
+
+        history.push_in_session(set("width", 4), 2);
+        let undone = history.take_undo().unwrap();
+        history.push_undone(undone);
+        history.push_in_session(set("height", 5), 2);
+
+        assert_eq!(
+            undo_all(&mut history),
+            vec![
+                set("height", 5),
+                set("height", 3),
+                set("height", 2),
+                set("width", 1)
+            ]
+        );
+    }
+}
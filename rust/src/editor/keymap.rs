@@ -0,0 +1,282 @@
+use std::fmt;
+
+/// Action of the editor that can be triggered from the keyboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Remove the current selection
+    Delete,
+    /// Cancel the drag, resize or marquee selection in progress
+    Cancel,
+    Undo,
+    Redo,
+    Copy,
+    Paste,
+    Duplicate,
+    /// Move the selection by a single px (or a single cell in grid layouts)
+    NudgeLeft,
+    NudgeRight,
+    NudgeUp,
+    NudgeDown,
+    /// Move the selection by `FAR_NUDGE` px (or still a single cell in grid layouts)
+    NudgeLeftFar,
+    NudgeRightFar,
+    NudgeUpFar,
+    NudgeDownFar,
+}
+
+/// Distance in px of a far nudge in free layouts
+pub const FAR_NUDGE: i32 = 10;
+
+impl Action {
+    /// Every action that can be bound to a key
+    pub const ALL: [Action; 15] = [
+        Action::Delete,
+        Action::Cancel,
+        Action::Undo,
+        Action::Redo,
+        Action::Copy,
+        Action::Paste,
+        Action::Duplicate,
+        Action::NudgeLeft,
+        Action::NudgeRight,
+        Action::NudgeUp,
+        Action::NudgeDown,
+        Action::NudgeLeftFar,
+        Action::NudgeRightFar,
+        Action::NudgeUpFar,
+        Action::NudgeDownFar,
+    ];
+
+    /// Name used by the JS side of the editor
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Delete => "delete",
+            Self::Cancel => "cancel",
+            Self::Undo => "undo",
+            Self::Redo => "redo",
+            Self::Copy => "copy",
+            Self::Paste => "paste",
+            Self::Duplicate => "duplicate",
+            Self::NudgeLeft => "nudge_left",
+            Self::NudgeRight => "nudge_right",
+            Self::NudgeUp => "nudge_up",
+            Self::NudgeDown => "nudge_down",
+            Self::NudgeLeftFar => "nudge_left_far",
+            Self::NudgeRightFar => "nudge_right_far",
+            Self::NudgeUpFar => "nudge_up_far",
+            Self::NudgeDownFar => "nudge_down_far",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|action| action.name() == name)
+    }
+
+    /// Direction of a nudge action, and whether it is a far one
+    pub fn nudge(&self) -> Option<((i32, i32), bool)> {
+        match self {
+            Self::NudgeLeft => Some(((-1, 0), false)),
+            Self::NudgeRight => Some(((1, 0), false)),
+            Self::NudgeUp => Some(((0, -1), false)),
+            Self::NudgeDown => Some(((0, 1), false)),
+            Self::NudgeLeftFar => Some(((-1, 0), true)),
+            Self::NudgeRightFar => Some(((1, 0), true)),
+            Self::NudgeUpFar => Some(((0, -1), true)),
+            Self::NudgeDownFar => Some(((0, 1), true)),
+            _ => None,
+        }
+    }
+}
+
+/// Key together with its modifiers, for example `Ctrl+Shift+Z`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shortcut {
+    /// Value of `KeyboardEvent.key` in lower case
+    key: String,
+    /// Control key on Windows and Linux, Command key on macOS
+    ctrl: bool,
+    shift: bool,
+    alt: bool,
+}
+
+impl Shortcut {
+    /// Parse a shortcut like `Ctrl+Shift+Z`, `Delete` or `Shift+ArrowLeft`
+    ///
+    /// Modifiers (`Ctrl`, `Cmd`, `Meta`, `Shift`, `Alt`, `Option`) are case insensitive,
+    /// the key is the value of `KeyboardEvent.key`
+    pub fn parse(shortcut: &str) -> Option<Self> {
+        let mut parts: Vec<&str> = shortcut.split('+').map(str::trim).collect();
+        let key = parts.pop().filter(|key| !key.is_empty())?;
+
+        let mut result = Self {
+            key: key.to_lowercase(),
+            ctrl: false,
+            shift: false,
+            alt: false,
+        };
+
+        for modifier in parts {
+            match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" | "cmd" | "meta" => result.ctrl = true,
+                "shift" => result.shift = true,
+                "alt" | "option" => result.alt = true,
+                _ => return None,
+            }
+        }
+
+        Some(result)
+    }
+
+    /// Shortcut pressed in a keyboard event
+    pub fn from_event(event: &web_sys::KeyboardEvent) -> Self {
+        Self {
+            key: event.key().to_lowercase(),
+            ctrl: event.ctrl_key() || event.meta_key(),
+            shift: event.shift_key(),
+            alt: event.alt_key(),
+        }
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            write!(f, "ctrl+")?;
+        }
+        if self.shift {
+            write!(f, "shift+")?;
+        }
+        if self.alt {
+            write!(f, "alt+")?;
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+/// Mapping of keyboard shortcuts to editor actions
+pub struct Keymap {
+    bindings: Vec<(Shortcut, Action)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let mut keymap = Self {
+            bindings: Vec::new(),
+        };
+
+        let defaults = [
+            ("Delete", Action::Delete),
+            ("Backspace", Action::Delete),
+            ("Escape", Action::Cancel),
+            ("Ctrl+Z", Action::Undo),
+            ("Ctrl+Shift+Z", Action::Redo),
+            ("Ctrl+Y", Action::Redo),
+            ("Ctrl+C", Action::Copy),
+            ("Ctrl+V", Action::Paste),
+            ("Ctrl+D", Action::Duplicate),
+            ("ArrowLeft", Action::NudgeLeft),
+            ("ArrowRight", Action::NudgeRight),
+            ("ArrowUp", Action::NudgeUp),
+            ("ArrowDown", Action::NudgeDown),
+            ("Shift+ArrowLeft", Action::NudgeLeftFar),
+            ("Shift+ArrowRight", Action::NudgeRightFar),
+            ("Shift+ArrowUp", Action::NudgeUpFar),
+            ("Shift+ArrowDown", Action::NudgeDownFar),
+        ];
+
+        for (shortcut, action) in defaults.iter() {
+            // Default shortcuts are always valid
+            keymap.bind(Shortcut::parse(shortcut).unwrap(), *action);
+        }
+
+        keymap
+    }
+}
+
+impl Keymap {
+    /// Bind a shortcut to an action, replacing the previous action of the shortcut
+    ///
+    /// An action can be bound to several shortcuts
+    pub fn bind(&mut self, shortcut: Shortcut, action: Action) {
+        self.unbind(&shortcut);
+        self.bindings.push((shortcut, action));
+    }
+
+    /// Remove the binding of a shortcut, returns `false` if it was not bound
+    pub fn unbind(&mut self, shortcut: &Shortcut) -> bool {
+        let len = self.bindings.len();
+        self.bindings.retain(|(s, _)| s != shortcut);
+        len != self.bindings.len()
+    }
+
+    /// Action bound to a shortcut
+    pub fn action(&self, shortcut: &Shortcut) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(s, _)| s == shortcut)
+            .map(|(_, action)| *action)
+    }
+
+    /// All of the bindings, in the order they were added
+    pub fn bindings(&self) -> &[(Shortcut, Action)] {
+        &self.bindings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shortcut(s: &str) -> Shortcut {
+        Shortcut::parse(s).unwrap()
+    }
+
+    #[test]
+    fn shortcuts_are_case_insensitive() {
+        assert_eq!(shortcut("ctrl+shift+z"), shortcut("Shift+Cmd+Z"));
+        assert_ne!(shortcut("Ctrl+Z"), shortcut("Z"));
+        assert_eq!(shortcut("Alt+ArrowLeft").to_string(), "alt+arrowleft");
+
+        assert_eq!(Shortcut::parse("Hyper+Z"), None);
+        assert_eq!(Shortcut::parse("Ctrl+"), None);
+    }
+
+    #[test]
+    fn default_keymap() {
+        let keymap = Keymap::default();
+
+        assert_eq!(keymap.action(&shortcut("Delete")), Some(Action::Delete));
+        assert_eq!(keymap.action(&shortcut("Ctrl+Shift+Z")), Some(Action::Redo));
+        assert_eq!(
+            keymap.action(&shortcut("Shift+ArrowUp")),
+            Some(Action::NudgeUpFar)
+        );
+        assert_eq!(keymap.action(&shortcut("Ctrl+Q")), None);
+    }
+
+    #[test]
+    fn shortcuts_can_be_rebound() {
+        let mut keymap = Keymap::default();
+
+        keymap.bind(shortcut("Ctrl+Z"), Action::Redo);
+        assert_eq!(keymap.action(&shortcut("Ctrl+Z")), Some(Action::Redo));
+
+        assert!(keymap.unbind(&shortcut("Backspace")));
+        assert!(!keymap.unbind(&shortcut("Backspace")));
+        assert_eq!(keymap.action(&shortcut("Backspace")), None);
+        // Other shortcuts of the action are kept
+        assert_eq!(keymap.action(&shortcut("Delete")), Some(Action::Delete));
+    }
+
+    #[test]
+    fn actions_are_named() {
+        for action in Action::ALL.iter() {
+            assert_eq!(Action::from_name(action.name()), Some(*action));
+        }
+        assert_eq!(Action::NudgeUpFar.nudge(), Some(((0, -1), true)));
+        assert_eq!(Action::Copy.nudge(), None);
+    }
+}
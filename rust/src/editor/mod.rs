@@ -17,36 +17,45 @@ use component_picker::ComponentPicker;
 pub mod hierarchy;
 use hierarchy::Hierarchy;
 
+mod history;
+use history::{Command, ComponentPlacement, History, HISTORY_LIMIT};
+
+mod selection;
+pub use selection::Selection;
+use selection::{align_spans, Align};
+
+mod keymap;
+use keymap::{Action, Keymap, Shortcut, FAR_NUDGE};
+
+mod clipboard;
+use clipboard::{Clipboard, PASTE_OFFSET};
+
 use crate::{
     component::{Component, ComponentSource},
+    document::{ComponentDocument, Document, DocumentError, LayoutDocument, PlacementDocument},
+    drag_controller::marquee_controller::MarqueeController,
     drag_controller::move_controller::{DragMoveResult, MoveController},
-    drag_controller::resize_controller::{DragResizeResult, ResizeController},
+    drag_controller::resize_controller::{DragResizeResult, ResizeController, ResizeHandle},
+    export::{self, Export, Exporter, HtmlExporter},
     html_elements::component::{ComponentDescriptor, EditorComponentSource},
-    page::layout::Layout,
-    page::Page,
+    import::{self, Import, ImportComponent},
+    page::layout::{
+        convert::ConvertTarget,
+        grid::{Block, GridItem},
+        model::MovePlacement,
+        ChildPlacement, Layout, LayoutKind,
+    },
+    page::{
+        breakpoint::Breakpoint,
+        drop_index,
+        metadata::{self, PageMetadata},
+        Page, DEFAULT_PAGE_WIDTH,
+    },
+    utils,
 };
 
 use self::hierarchy::ClickResult;
 
-#[derive(Clone, PartialEq)]
-pub enum Selection {
-    Page(Page),
-    Layout(Layout),
-    Component(Component),
-    None,
-}
-
-impl Selection {
-    pub fn set_is_selected(&mut self, is: bool) {
-        match self {
-            Self::Page(page) => page.set_is_selected(is),
-            Self::Layout(layout) => layout.set_is_selected(is),
-            Self::Component(component) => component.set_is_selected(is),
-            Self::None => {}
-        }
-    }
-}
-
 /// The main state of the whole editor
 pub struct EditorState {
     component_picker: ComponentPicker,
@@ -58,8 +67,19 @@ pub struct EditorState {
 
     drag_state: DragState,
 
-    /// Currently selected item
+    /// Currently selected items
     selection: Selection,
+    /// Ignore the next click, it ends a marquee selection
+    ignore_click: bool,
+
+    /// Undo/redo history of the workspace mutations
+    history: History,
+
+    /// Keyboard shortcuts of the editor
+    keymap: Keymap,
+
+    /// Copied components or layout
+    clipboard: Clipboard,
 }
 
 impl EditorState {
@@ -69,12 +89,14 @@ impl EditorState {
         let mut workspace = Workspace::new();
         // Add a debug page
         {
-            let mut page = Page::new("Home", 908);
+            let mut page = Page::new("Home", DEFAULT_PAGE_WIDTH);
+            page.add_breakpoint(Breakpoint::new("mobile", 375));
+            page.add_breakpoint(Breakpoint::new("tablet", 768));
 
             // Add some debug layouts
-            page.insert_layout(Layout::new_flex(908, 76), None);
-            page.insert_layout(Layout::new_grid(908), None);
-            page.insert_layout(Layout::new_free(908, 255), None);
+            page.insert_layout(Layout::new_flex(DEFAULT_PAGE_WIDTH, 76), None);
+            page.insert_layout(Layout::new_grid(DEFAULT_PAGE_WIDTH), None);
+            page.insert_layout(Layout::new_free(DEFAULT_PAGE_WIDTH, 255), None);
 
             workspace.insert_page(page);
         }
@@ -91,53 +113,1579 @@ impl EditorState {
 
             drag_state: DragState::None,
             selection: Selection::None,
+            ignore_click: false,
+
+            history: History::new(HISTORY_LIMIT),
+
+            keymap: Keymap::default(),
+
+            clipboard: Clipboard::Empty,
         }
     }
 
     /// Resize one of pages in workspace
     fn resize_page(&mut self, page: &HtmlElement, width: u32) {
-        let document = web_sys::window().unwrap().document().unwrap();
+        if let Some(page) = self.workspace.get_page_mut(page) {
+            page.resize(width);
+        }
+    }
 
-        let gap = 4;
+    /// Resize one of layouts in workspace
+    fn resize_layout(&mut self, layout: &HtmlElement, height: u32) {
+        if let Some(mut layout) = self.workspace.get_layout(layout) {
+            layout.resize(None, Some(height));
+        }
+    }
 
-        let cell_size = width / 10;
+    /// Serialize the whole workspace into a json document
+    fn save_document(&self) -> String {
+        self.workspace.to_document().to_json()
+    }
 
-        {
-            let cell_size = format!("{}", cell_size);
+    /// Export the whole workspace with a given exporter
+    fn export(&self, exporter: &dyn Exporter) -> Export {
+        exporter.export(&self.workspace.to_document())
+    }
+
+    /// Replace the workspace with the one described by a json document
+    fn load_document(&mut self, json: &str) -> Result<(), DocumentError> {
+        let document = Document::from_json(json)?;
+
+        self.set_selection(Selection::None);
+        self.drag_state = DragState::None;
+
+        self.workspace
+            .load_document(&document, self.component_picker.sources())?;
 
-            let pattern = document.get_element_by_id("grid-pattern").unwrap();
-            pattern.set_attribute("width", &cell_size).unwrap();
-            pattern.set_attribute("height", &cell_size).unwrap();
+        // Recorded commands refer to the old pages, so they can no longer be applied
+        self.history.clear();
+
+        self.update_tree();
+
+        Ok(())
+    }
+
+    /// Apply a command and record it in the history
+    fn execute(&mut self, command: Command) {
+        command.redo(self);
+        self.history.push(command);
+        self.update_tree();
+    }
+
+    /// Apply a command made by an input session, merging it with the previous commands of the session
+    fn execute_in_session(&mut self, command: Command, session: u32) {
+        command.redo(self);
+        self.history.push_in_session(command, session);
+        self.update_tree();
+    }
+
+    /// Revert the last recorded command
+    fn undo(&mut self) -> bool {
+        if let Some(command) = self.history.take_undo() {
+            self.set_selection(Selection::None);
+
+            command.undo(self);
+            self.history.push_undone(command);
+            self.update_tree();
+            true
+        } else {
+            false
         }
+    }
 
-        {
-            let cell_size = format!("{}", cell_size - gap * 2);
+    /// Apply the last reverted command once again
+    fn redo(&mut self) -> bool {
+        if let Some(command) = self.history.take_redo() {
+            self.set_selection(Selection::None);
+
+            command.redo(self);
+            self.history.push_redone(command);
+            self.update_tree();
+            true
+        } else {
+            false
+        }
+    }
 
-            let rect = document.get_element_by_id("grid-pattern__rect").unwrap();
-            rect.set_attribute("width", &cell_size).unwrap();
-            rect.set_attribute("height", &cell_size).unwrap();
+    /// Resize one of layouts in workspace, recording the change in the history
+    ///
+    /// # Arguments
+    /// * `session` - input session that made the change, resizes of one session are a single step
+    fn resize_layout_with_history(&mut self, layout: &HtmlElement, height: u32, session: u32) {
+        if let Some(layout) = self.workspace.get_layout(layout) {
+            let from = layout.size().1;
+
+            self.resize_layout(&layout.html_element, height);
+
+            // Grid layouts refuse to shrink over occupied cells
+            let to = layout.size().1;
+            if from != to {
+                self.history
+                    .push_in_session(Command::ResizeLayout { layout, from, to }, session);
+            }
         }
+    }
 
-        if let Some(page) = self.workspace.get_page_mut(page) {
-            page.resize(width);
+    /// Convert a layout to a different kind, recording the change in the history
+    ///
+    /// Returns the components that could not be placed in the converted layout,
+    /// they stay in the layout, but are hidden
+    fn convert_layout(&mut self, layout: &HtmlElement, target: ConvertTarget) -> Vec<Component> {
+        let layout = match self.workspace.get_layout(layout) {
+            Some(layout) if !target.is_kind_of(&layout.kind()) => layout,
+            _ => return Vec::new(),
+        };
+
+        // The settings of the old kind are dropped together with the selection
+        let is_selected = self.selection == Selection::Layout(layout.clone());
+        self.set_selection(Selection::None);
+
+        let from = layout.kind_snapshot();
+        let (to, unplaced) = layout.convert_snapshot(target);
+
+        self.execute(Command::ConvertLayout {
+            layout: layout.clone(),
+            from: Box::new(from),
+            to: Box::new(to),
+        });
+
+        if is_selected {
+            self.set_selection(Selection::Layout(layout));
         }
+
+        unplaced
     }
 
-    /// Resize one of layouts in workspace
-    fn resize_layout(&mut self, layout: &HtmlElement, height: u32) {
-        // Finda a page that it belongs to
+    /// Name for a new page, numbered if it or its route is taken already
+    ///
+    /// # Arguments
+    /// * `name` - requested name, `None` or an empty one for `Page`
+    fn new_page_name(&self, name: Option<&str>) -> String {
+        metadata::unique_page_name(
+            name.map(str::trim)
+                .filter(|name| !name.is_empty())
+                .unwrap_or("Page"),
+            &self.workspace.page_names(),
+            &self.workspace.page_routes(),
+        )
+    }
+
+    /// Whether a page other than `page` is served at the route
+    fn is_route_taken(&self, route: &str, page: &Page) -> bool {
+        self.workspace
+            .pages()
+            .iter()
+            .any(|other| other != page && other.route() == route)
+    }
+
+    /// Add a new empty page at the end of the workspace and show it
+    ///
+    /// # Arguments
+    /// * `name` - name of the page, `None` for a numbered `Page`.
+    ///   It is numbered as well, if a different page has it or is served at its route
+    /// * `width` - width of the page, `None` for the width of the visible page
+    fn add_page(&mut self, name: Option<&str>, width: Option<u32>) -> Page {
+        let name = self.new_page_name(name);
+        let width = width
+            .or_else(|| self.workspace.current_page().map(|page| page.width()))
+            .unwrap_or(DEFAULT_PAGE_WIDTH);
+
+        let page = Page::new(&name, width);
+        let index = self.workspace.pages().len();
+
+        self.execute(Command::InsertPage {
+            page: page.clone(),
+            index,
+        });
+        self.set_selection(Selection::Page(page.clone()));
+
+        page
+    }
+
+    /// Remove a page together with all of its layouts and components
+    ///
+    /// Returns `false` if the page is not in the workspace
+    fn remove_page(&mut self, page: &Page) -> bool {
+        let index = match self.workspace.pages().iter().position(|p| p == page) {
+            Some(index) => index,
+            None => return false,
+        };
+
+        // The selection may point into the removed page
+        self.set_selection(Selection::None);
+
+        self.execute(Command::RemovePage {
+            page: page.clone(),
+            index,
+        });
+        true
+    }
+
+    /// Move a page to a different position in the workspace
+    ///
+    /// Returns `false` if the page would stay where it is
+    ///
+    /// # Arguments
+    /// * `index` - index of the page after the move, out of bounds index moves it to the end
+    fn move_page(&mut self, page: &Page, index: usize) -> bool {
+        let pages = self.workspace.pages();
+        let from = match pages.iter().position(|p| p == page) {
+            Some(from) => from,
+            None => return false,
+        };
+        let to = index.min(pages.len() - 1);
+
+        if from == to {
+            return false;
+        }
+
+        self.execute(Command::MovePage { from, to });
+        true
+    }
+
+    /// Duplicate a page together with its layouts and components, right after itself
+    ///
+    /// The duplicate gets a unique name, its route follows that name
+    fn duplicate_page(&mut self, page: &Page) -> Option<Page> {
+        let index = self.workspace.pages().iter().position(|p| p == page)?;
+
+        let mut document = page.to_document();
+        document.name = self.new_page_name(Some(&format!("{} copy", document.name)));
+        document.metadata.route = None;
+        document.clear_ids();
+
+        let duplicate = self
+            .workspace
+            .instantiate_page(&document, self.component_picker.sources())
+            .ok()?;
+        self.name_copies(&duplicate.layouts(), &[]);
+        self.workspace.insert_page_at(duplicate.clone(), index + 1);
+
+        // Components of the duplicate are registered already, so the command is not applied again
+        self.history.push(Command::InsertPage {
+            page: duplicate.clone(),
+            index: index + 1,
+        });
+
+        self.set_selection(Selection::Page(duplicate.clone()));
+        self.update_tree();
+
+        Some(duplicate)
+    }
+
+    /// Import a fragment of html with Tailwind classes as a new page at the end of the workspace
+    ///
+    /// Elements whose tag matches a registered component become components, see `import::import_html`.
+    /// Returns everything that could not be imported
+    ///
+    /// # Arguments
+    /// * `name` - name of the page, `None` for a numbered `Page`
+    fn import_html(&mut self, html: &str, name: Option<&str>) -> Vec<String> {
+        let name = self.new_page_name(name);
+        let width = self
+            .workspace
+            .current_page()
+            .map(|page| page.width())
+            .unwrap_or(DEFAULT_PAGE_WIDTH);
+
+        let components: Vec<ImportComponent> = self
+            .component_picker
+            .sources()
+            .iter()
+            .map(|source| {
+                let descriptor = source.source.descriptor();
+                ImportComponent {
+                    tag_name: descriptor.tag_name(),
+                    name: descriptor.display_name(),
+                    parameters: descriptor.parameter_schemas(),
+                }
+            })
+            .collect();
+
+        let Import { page, warnings } =
+            import::import_html(html, &name, width, &components, self.workspace.uids());
+
+        // Only the registered components are imported, so instantiating the page can not fail
         let page = self
             .workspace
-            .pages_mut()
-            .iter_mut()
-            .find(|page| page.contains(layout));
+            .instantiate_page(&page, self.component_picker.sources())
+            .unwrap();
+        self.name_copies(&page.layouts(), &[]);
+
+        let index = self.workspace.pages().len();
+        self.workspace.insert_page_at(page.clone(), index);
+
+        // Components of the imported page are registered already, so the command is not applied again
+        self.history.push(Command::InsertPage {
+            page: page.clone(),
+            index,
+        });
+
+        self.set_selection(Selection::Page(page));
+        self.update_tree();
+
+        warnings
+    }
+
+    /// Rename a page, recording the change in the history
+    ///
+    /// Returns `false` if the name is empty, the page already has it,
+    /// or the page follows its name and a different page is served at the route of the new one
+    fn rename_page(&mut self, page: &Page, name: &str) -> bool {
+        let name = name.trim();
+        let from = page.name().to_owned();
+
+        if name.is_empty() || name == from {
+            return false;
+        }
+
+        let route = page.metadata().route(name);
+        if self.is_route_taken(&route, page) {
+            return false;
+        }
+
+        self.execute(Command::RenamePage {
+            page: page.clone(),
+            from,
+            to: name.to_owned(),
+        });
+        true
+    }
+
+    /// Rename a layout, recording the change in the history
+    ///
+    /// Returns `false` if the name is empty, or the layout already has it
+    fn rename_layout(&mut self, layout: &Layout, name: &str) -> bool {
+        let name = name.trim();
+        let from = layout.name().to_owned();
+
+        if name.is_empty() || name == from {
+            return false;
+        }
+
+        self.execute(Command::RenameLayout {
+            layout: layout.clone(),
+            from,
+            to: name.to_owned(),
+        });
+        true
+    }
+
+    /// Rename a component, recording the change in the history
+    ///
+    /// Returns `false` if the name is empty, or the component already has it
+    fn rename_component(&mut self, component: &Component, name: &str) -> bool {
+        let name = name.trim();
+        let from = component.name().to_owned();
+
+        if name.is_empty() || name == from {
+            return false;
+        }
+
+        self.execute(Command::RenameComponent {
+            component: component.clone(),
+            from,
+            to: name.to_owned(),
+        });
+        true
+    }
+
+    /// Number the name of a new layout if it is taken, `Grid` becomes `Grid 2`
+    ///
+    /// The layout must not be placed in a page yet
+    fn name_new_layout(&self, layout: &mut Layout) {
+        let name = utils::unique_name(&layout.name(), &self.workspace.layout_names());
+        layout.set_name(&name);
+    }
+
+    /// Give copied layouts (with their nested layouts and components) and copied components
+    /// unique names, a copy of `Button 2` becomes `Button 3`
+    ///
+    /// The layouts must not be placed in a page yet, the components are registered already
+    fn name_copies(&self, layouts: &[Layout], components: &[Component]) {
+        let layouts: Vec<Layout> = layouts.iter().flat_map(|layout| layout.tree()).collect();
+        let mut components = components.to_vec();
+        for layout in layouts.iter() {
+            components.extend(layout.components().iter().cloned());
+        }
+
+        let mut layout_names = self.workspace.layout_names();
+        for mut layout in layouts {
+            let name = utils::unique_name(&layout.name(), &layout_names);
+            layout.set_name(&name);
+            layout_names.push(name);
+        }
+
+        // The copies are registered already, they must not count as taken
+        let mut component_names: Vec<String> = self
+            .workspace
+            .components()
+            .iter()
+            .filter(|(index, _)| components.iter().all(|c| c.index() != *index))
+            .map(|(_, component)| component.name().to_owned())
+            .collect();
+        for mut component in components {
+            let name = utils::unique_name(&component.name(), &component_names);
+            component.set_name(&name);
+            component_names.push(name);
+        }
+    }
+
+    /// Change the route, title and description of a page, recording the change in the history
+    ///
+    /// Routes are normalized, an empty route or title follows the page name again
+    ///
+    /// Returns `false` if the route is taken by a different page, or nothing changed
+    fn set_page_metadata(
+        &mut self,
+        page: &Page,
+        route: &str,
+        title: &str,
+        description: &str,
+    ) -> bool {
+        let non_empty = |value: &str| Some(value.trim().to_owned()).filter(|v| !v.is_empty());
+
+        let to = PageMetadata {
+            route: non_empty(route).map(|route| metadata::normalize_route(&route)),
+            title: non_empty(title),
+            description: description.trim().to_owned(),
+        };
+        let from = page.metadata().clone();
+
+        if self.is_route_taken(&to.route(&page.name()), page) || from == to {
+            return false;
+        }
+
+        self.execute(Command::SetPageMetadata {
+            page: page.clone(),
+            from,
+            to,
+        });
+        true
+    }
+
+    /// Move the components (and nested layouts) of a grid layout into new blocks,
+    /// for example when they were displaced by a drop
+    ///
+    /// Returns the commands that describe the moves, so that they can be recorded in the history
+    fn move_displaced(
+        &mut self,
+        layout: &Layout,
+        displaced: Vec<(GridItem, Block)>,
+    ) -> Vec<Command> {
+        let mut commands = Vec::with_capacity(displaced.len());
+
+        for (item, block) in displaced {
+            let child = layout
+                .children()
+                .iter()
+                .find(|child| child.grid_item() == item)
+                .cloned();
+            let component = match item {
+                GridItem::Component(index) => self.workspace.components().get(index).cloned(),
+                GridItem::Layout(_) => None,
+            };
+
+            if let Some(child) = child {
+                let to = ChildPlacement::Grid {
+                    pos: (block.x, block.y),
+                    size: (block.width, block.height),
+                };
+                let from = child.placement();
+
+                layout.clone().insert_child(child.clone(), to);
+
+                commands.push(Command::PlaceLayout {
+                    parent: layout.clone(),
+                    layout: child,
+                    from,
+                    to: Some(to),
+                });
+            } else if let Some(mut component) = component {
+                let from = ComponentPlacement::capture(layout.clone(), &component);
+
+                component.set_grid_pos((block.x, block.y));
+                component.set_grid_size((block.width, block.height));
+                layout.clone().insert_component(component.clone());
+
+                let to = ComponentPlacement::capture(layout.clone(), &component);
+                commands.push(Command::PlaceComponent {
+                    component,
+                    from: Some(from),
+                    to: Some(to),
+                });
+            }
+        }
+
+        commands
+    }
+
+    /// Remove the trailing empty rows of a grid layout, if it shrinks automatically
+    ///
+    /// Returns the command that describes the change, so that it can be recorded in the history
+    fn shrink_grid(&mut self, layout: &Layout) -> Option<Command> {
+        let rows = match &*layout.kind() {
+            LayoutKind::Grid { grid_data, .. } if grid_data.auto_shrink() => {
+                Some(grid_data.used_rows().max(1)).filter(|rows| *rows < grid_data.height())
+            }
+            _ => None,
+        }?;
+
+        let from = layout.size().1;
+        layout.clone().set_grid_rows(rows);
+
+        Some(Command::ResizeLayout {
+            layout: layout.clone(),
+            from,
+            to: layout.size().1,
+        })
+    }
+
+    /// Put a dragged component into the layout that it was dropped onto
+    ///
+    /// The component has to be placed already (grid cells, position or flex properties),
+    /// it is taken out of its old layout and inserted into the new one
+    ///
+    /// # Arguments
+    /// * `displaced` - other components of the layout that are moved to make room for this one
+    /// * `previous_height` - height of the layout before it grew to fit the component
+    /// * `order` - position among the components of a flex layout, `None` keeps the current one
+    /// * `origin` - placement before the drag, `None` for newly spawned components
+    ///
+    /// Returns the command that describes the change, it is not recorded in the history
+    fn drop_component(
+        &mut self,
+        mut component: Component,
+        mut layout: Layout,
+        displaced: Vec<(GridItem, Block)>,
+        previous_height: u32,
+        order: Option<usize>,
+        origin: Option<ComponentPlacement>,
+    ) -> Command {
+        // The old layout can be nested, so it is looked up in the whole workspace
+        let old_layout = component
+            .layout()
+            .and_then(|layout| self.workspace.get_layout(&layout));
+
+        if let Some(mut old_layout) = old_layout {
+            old_layout.remove_component(&mut component);
+        }
+
+        layout.insert_component(component.clone());
+
+        if let Some(order) = order {
+            layout.move_component(&component, order);
+        }
+
+        let mut commands = self.move_displaced(&layout, displaced);
+
+        if let Some(origin) = origin.as_ref() {
+            commands.extend(self.move_followers(&component, origin, &layout));
+        }
+
+        let to = ComponentPlacement::capture(layout.clone(), &component);
+        commands.push(Command::PlaceComponent {
+            component,
+            from: origin.clone(),
+            to: Some(to),
+        });
+
+        record_resize(&mut commands, &layout, previous_height);
+
+        // The component left its old grid, so it might have some empty rows now
+        if let Some(origin) = origin.filter(|origin| origin.layout != layout) {
+            if let Some(command) = self.shrink_grid(&origin.layout) {
+                commands.push(command);
+            }
+        }
+
+        Command::group(commands)
+    }
+
+    /// Move a component into a layout without a mouse position to drop it at,
+    /// used by drops in the hierarchy tree
+    ///
+    /// Grid layouts place it into the first free cells (growing if there are none left),
+    /// free layouts keep its position if it comes from a free layout (the left-top corner otherwise),
+    /// flex layouts put it in front of `before`, or at the end
+    ///
+    /// Returns `false` if the component would stay where it is
+    fn move_component_to_layout(
+        &mut self,
+        component: &Component,
+        layout: &Layout,
+        before: Option<&Component>,
+    ) -> bool {
+        let old_layout = component
+            .layout()
+            .and_then(|layout| self.workspace.get_layout(&layout));
+
+        let old_layout = match old_layout {
+            Some(old_layout) => old_layout,
+            None => return false,
+        };
+
+        let placement = match layout.move_placement(component, before) {
+            Some(placement) => placement,
+            None => return false,
+        };
+
+        let origin = ComponentPlacement::capture(old_layout.clone(), component);
+        let previous_height = layout.size().1;
+
+        let mut component = component.clone();
+        let mut layout = layout.clone();
+        let mut order = None;
+
+        let rows = match placement {
+            MovePlacement::Grid { block, rows } => {
+                component.unset_absolute_pos();
+                component.unset_size();
+                component.set_grid_pos((block.x, block.y));
+                component.set_grid_size((block.width, block.height));
+
+                Some(rows)
+            }
+            MovePlacement::Free => {
+                let position = match &*old_layout.kind() {
+                    LayoutKind::Free { .. } => component.position(),
+                    _ => None,
+                };
+
+                // Components of grid and flex layouts are sized by the layout, so they keep the size they had there
+                if component.stored_size().is_none() {
+                    let (_, (width, height)) = component.bounding_client_rect();
+                    component.set_size(width, height);
+                }
+                component.set_position(position.unwrap_or((0, 0)));
+
+                None
+            }
+            MovePlacement::Flex { order: to } => {
+                component.unset_absolute_pos();
+                order = Some(to);
+
+                None
+            }
+        };
+
+        // Grow the grid to fit the component
+        if let Some(rows) = rows {
+            layout.set_grid_rows(rows);
+        }
+
+        let command = self.drop_component(
+            component,
+            layout,
+            Vec::new(),
+            previous_height,
+            order,
+            Some(origin),
+        );
+        self.history.push(command);
+        self.update_tree();
+
+        true
+    }
+
+    /// Move several components of a grid layout at once, growing the grid if needed
+    ///
+    /// Returns the commands that describe the change, `None` if the grid refused the move
+    fn move_blocks(
+        &mut self,
+        layout: &Layout,
+        moves: Vec<(GridItem, Block)>,
+    ) -> Option<Vec<Command>> {
+        let rows = match &*layout.kind() {
+            LayoutKind::Grid { grid_data, .. } => grid_data.resolve_group_move(&moves),
+            _ => None,
+        }?;
+
+        let from = layout.size().1;
+        layout.clone().set_grid_rows(rows);
+
+        let mut commands = self.move_displaced(layout, moves);
+        record_resize(&mut commands, layout, from);
+
+        Some(commands)
+    }
+
+    /// Move components of a free layout to new positions
+    ///
+    /// Returns the commands that describe the moves, so that they can be recorded in the history
+    fn move_positions(
+        &mut self,
+        layout: &Layout,
+        moves: Vec<(Component, (i32, i32))>,
+    ) -> Vec<Command> {
+        moves
+            .into_iter()
+            .map(|(component, position)| {
+                let from = ComponentPlacement::capture(layout.clone(), &component);
+                component.set_position(position);
+                let to = ComponentPlacement::capture(layout.clone(), &component);
+
+                Command::PlaceComponent {
+                    component,
+                    from: Some(from),
+                    to: Some(to),
+                }
+            })
+            .collect()
+    }
+
+    /// Move components of a layout by an offset, in cells for grid layouts and in px for free layouts
+    ///
+    /// Returns the commands that describe the moves, `None` if the layout refused them
+    fn offset_components(
+        &mut self,
+        layout: &Layout,
+        components: &[Component],
+        (dx, dy): (i32, i32),
+    ) -> Option<Vec<Command>> {
+        let is_grid = match &*layout.kind() {
+            LayoutKind::Grid { .. } => true,
+            LayoutKind::Free { .. } => false,
+            LayoutKind::Flex { .. } => return None,
+        };
+
+        if is_grid {
+            let moves = components
+                .iter()
+                .map(|component| {
+                    let (pos, size) = (component.grid_pos()?, component.grid_size()?);
+                    let (x, y) = (pos.0 as i32 + dx, pos.1 as i32 + dy);
+
+                    if x < 1 || y < 1 {
+                        return None;
+                    }
+
+                    let block = Block {
+                        x: x as usize,
+                        y: y as usize,
+                        width: size.0,
+                        height: size.1,
+                    };
+                    Some((component.grid_item(), block))
+                })
+                .collect::<Option<Vec<_>>>()?;
+
+            self.move_blocks(layout, moves)
+        } else {
+            let moves = components
+                .iter()
+                .filter_map(|component| {
+                    let (x, y) = component.position()?;
+                    Some((component.clone(), (x + dx, y + dy)))
+                })
+                .collect();
+
+            Some(self.move_positions(layout, moves))
+        }
+    }
+
+    /// Move the other selected components of a layout together with the dragged one
+    ///
+    /// Returns the commands that describe the moves, so that they can be recorded in the history
+    fn move_followers(
+        &mut self,
+        component: &Component,
+        origin: &ComponentPlacement,
+        layout: &Layout,
+    ) -> Vec<Command> {
+        let selected = self.selection.components();
+        if origin.layout != *layout || !selected.contains(component) {
+            return Vec::new();
+        }
+
+        let followers: Vec<Component> = selected
+            .iter()
+            .filter(|c| *c != component && layout.components().contains(c))
+            .cloned()
+            .collect();
 
-        if let Some(page) = page {
-            let mut layouts = page.layouts_mut();
-            let layout = layouts.iter_mut().find(|l| *l == layout);
+        let offset = match (origin.grid_pos(), component.grid_pos()) {
+            (Some(from), Some(to)) => {
+                Some((to.0 as i32 - from.0 as i32, to.1 as i32 - from.1 as i32))
+            }
+            _ => match (origin.position(), component.position()) {
+                (Some(from), Some(to)) => Some((to.0 - from.0, to.1 - from.1)),
+                _ => None,
+            },
+        };
+
+        match offset {
+            Some(offset) if !followers.is_empty() && offset != (0, 0) => self
+                .offset_components(layout, &followers, offset)
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Selected components grouped by the layout they are placed in
+    fn selection_by_layout(&self) -> Vec<(Layout, Vec<Component>)> {
+        let mut groups: Vec<(Layout, Vec<Component>)> = Vec::new();
+
+        for component in self.selection.components() {
+            let layout = component
+                .layout()
+                .and_then(|layout| self.workspace.get_layout(&layout));
 
             if let Some(layout) = layout {
-                layout.resize(None, Some(height));
+                match groups.iter_mut().find(|(l, _)| *l == layout) {
+                    Some((_, components)) => components.push(component.clone()),
+                    None => groups.push((layout, vec![component.clone()])),
+                }
+            }
+        }
+
+        groups
+    }
+
+    /// Move the selected components by an offset, in cells for grid layouts and in px for free layouts
+    ///
+    /// Returns `false` if nothing was moved
+    fn move_selection(&mut self, offset: (i32, i32)) -> bool {
+        self.move_selection_with(|_| offset)
+    }
+
+    /// Move the selected components a little, by a single cell in grid layouts,
+    /// or the selected layout by a single position in its page
+    ///
+    /// # Arguments
+    /// * `direction` - direction of the move, `(-1, 0)` moves the components to the left
+    /// * `far` - move components of free layouts by `FAR_NUDGE` px instead of a single one
+    fn nudge_selection(&mut self, (dx, dy): (i32, i32), far: bool) -> bool {
+        // Layouts placed in a page are moved up and down between the other layouts
+        if let Selection::Layout(layout) = &self.selection {
+            let layout = layout.clone();
+            return dx == 0 && self.shift_layout(&layout, dy);
+        }
+
+        let step = if far { FAR_NUDGE } else { 1 };
+
+        self.move_selection_with(|is_grid| {
+            if is_grid {
+                (dx, dy)
+            } else {
+                (dx * step, dy * step)
+            }
+        })
+    }
+
+    /// Move the selected components of every layout by an offset computed for the layout
+    ///
+    /// # Arguments
+    /// * `offset` - returns the offset, given whether the layout is a grid
+    fn move_selection_with(&mut self, offset: impl Fn(bool) -> (i32, i32)) -> bool {
+        let mut commands = Vec::new();
+
+        for (layout, components) in self.selection_by_layout() {
+            let is_grid = matches!(&*layout.kind(), LayoutKind::Grid { .. });
+
+            if let Some(moves) = self.offset_components(&layout, &components, offset(is_grid)) {
+                commands.extend(moves);
+            }
+        }
+
+        self.record_group(commands)
+    }
+
+    /// Align the selected components of every layout to each other
+    ///
+    /// Grid layouts refuse the alignment if the components would overlap
+    ///
+    /// Returns `false` if nothing was moved
+    fn align_selection(&mut self, align: Align) -> bool {
+        let mut commands = Vec::new();
+
+        for (layout, components) in self.selection_by_layout() {
+            if components.len() < 2 {
+                continue;
+            }
+
+            let is_grid = matches!(&*layout.kind(), LayoutKind::Grid { .. });
+
+            if is_grid {
+                let blocks: Vec<(GridItem, Block)> = components
+                    .iter()
+                    .filter_map(|component| {
+                        let (pos, size) = (component.grid_pos()?, component.grid_size()?);
+                        let block = Block {
+                            x: pos.0,
+                            y: pos.1,
+                            width: size.0,
+                            height: size.1,
+                        };
+                        Some((component.grid_item(), block))
+                    })
+                    .collect();
+
+                let spans: Vec<(i32, i32)> = blocks
+                    .iter()
+                    .map(|(_, b)| match align.is_horizontal() {
+                        true => (b.x as i32, b.width as i32),
+                        false => (b.y as i32, b.height as i32),
+                    })
+                    .collect();
+
+                let moves = blocks
+                    .into_iter()
+                    .zip(align_spans(&spans, align))
+                    .map(|((index, block), start)| match align.is_horizontal() {
+                        true => (
+                            index,
+                            Block {
+                                x: start as usize,
+                                ..block
+                            },
+                        ),
+                        false => (
+                            index,
+                            Block {
+                                y: start as usize,
+                                ..block
+                            },
+                        ),
+                    })
+                    .collect();
+
+                if let Some(moves) = self.move_blocks(&layout, moves) {
+                    commands.extend(moves);
+                }
+            } else {
+                let positioned: Vec<(Component, (i32, i32))> = components
+                    .iter()
+                    .filter_map(|c| c.position().map(|position| (c.clone(), position)))
+                    .collect();
+
+                let spans: Vec<(i32, i32)> = positioned
+                    .iter()
+                    .map(|(component, (x, y))| {
+                        let (w, h) = component.stored_size().unwrap_or_else(|| component.size());
+                        match align.is_horizontal() {
+                            true => (*x, w as i32),
+                            false => (*y, h as i32),
+                        }
+                    })
+                    .collect();
+
+                let moves = positioned
+                    .into_iter()
+                    .zip(align_spans(&spans, align))
+                    .map(|((component, (x, y)), start)| match align.is_horizontal() {
+                        true => (component, (start, y)),
+                        false => (component, (x, start)),
+                    })
+                    .collect();
+
+                commands.extend(self.move_positions(&layout, moves));
+            }
+        }
+
+        self.record_group(commands)
+    }
+
+    /// Remove the selected components, or the selected layout
+    ///
+    /// Returns `false` if there was nothing to remove
+    fn delete_selection(&mut self) -> bool {
+        let groups = self.selection_by_layout();
+        let layout = match &self.selection {
+            Selection::Layout(layout) => Some(layout.clone()),
+            _ => None,
+        };
+
+        self.set_selection(Selection::None);
+
+        if let Some(layout) = layout {
+            return match self.remove_layout_command(&layout) {
+                Some(command) => {
+                    self.execute(command);
+                    true
+                }
+                None => false,
+            };
+        }
+
+        let mut commands = Vec::new();
+
+        for (layout, components) in groups.iter() {
+            for component in components {
+                let command = Command::PlaceComponent {
+                    component: component.clone(),
+                    from: Some(ComponentPlacement::capture(layout.clone(), component)),
+                    to: None,
+                };
+                command.redo(self);
+                commands.push(command);
+            }
+        }
+
+        // Grids can shrink once the components are gone
+        for (layout, _) in groups.iter() {
+            if let Some(command) = self.shrink_grid(layout) {
+                commands.push(command);
+            }
+        }
+
+        self.record_group(commands)
+    }
+
+    /// The selected layout, or the layout of the first selected component
+    fn selected_layout(&self) -> Option<Layout> {
+        match &self.selection {
+            Selection::Layout(layout) => Some(layout.clone()),
+            Selection::Components(components) => components
+                .first()
+                .and_then(|component| component.layout())
+                .and_then(|layout| self.workspace.get_layout(&layout)),
+            _ => None,
+        }
+    }
+
+    /// The selected page, or the currently visible one if no page is selected
+    fn selected_page(&self) -> Option<Page> {
+        match &self.selection {
+            Selection::Page(page) => Some(page.clone()),
+            _ => self.workspace.current_page().cloned(),
+        }
+    }
+
+    /// Page that the layout belongs to, together with the index of the layout in the page
+    ///
+    /// Nested layouts give the index of the layout they are nested in
+    fn layout_page(&self, layout: &Layout) -> Option<(Page, usize)> {
+        self.workspace.pages().iter().find_map(|page| {
+            let index = page
+                .layouts()
+                .iter()
+                .position(|l| l.tree().contains(layout))?;
+            Some((page.clone(), index))
+        })
+    }
+
+    /// Command that removes the layout from its page, or from its parent layout if it is nested
+    fn remove_layout_command(&self, layout: &Layout) -> Option<Command> {
+        self.workspace.pages().iter().find_map(|page| {
+            if let Some(parent) = page.find_parent_layout(layout) {
+                return Some(Command::PlaceLayout {
+                    parent,
+                    layout: layout.clone(),
+                    from: layout.placement(),
+                    to: None,
+                });
+            }
+
+            let index = page.layouts().iter().position(|l| l == layout)?;
+            Some(Command::RemoveLayout {
+                page: page.clone(),
+                layout: layout.clone(),
+                index,
+            })
+        })
+    }
+
+    /// Move a layout placed directly in a page in front of the layout at a drop position,
+    /// in the same page or in a different one
+    ///
+    /// Returns `false` if the layout is nested, or it would stay where it is
+    ///
+    /// # Arguments
+    /// * `drop` - index of the layout that the moved one is dropped in front of,
+    ///   out of bounds index moves it to the end of the page
+    fn move_layout(&mut self, layout: &Layout, page: &Page, drop: usize) -> bool {
+        let (from_page, from) = match self.layout_page(layout) {
+            Some((from_page, from)) if from_page.layouts()[from] == *layout => (from_page, from),
+            // Nested layouts are moved inside of their parent
+            _ => return false,
+        };
+
+        let index = if from_page == *page {
+            let last = page.layouts().len() - 1;
+            drop_index(Some(from), drop).min(last)
+        } else {
+            drop_index(None, drop).min(page.layouts().len())
+        };
+
+        if from_page == *page && index == from {
+            return false;
+        }
+
+        self.execute(Command::MoveLayout {
+            layout: layout.clone(),
+            from: (from_page, from),
+            to: (page.clone(), index),
+        });
+        true
+    }
+
+    /// Move a layout placed directly in a page up (negative offset) or down inside of the page
+    ///
+    /// Returns `false` if the layout is nested, or it can not move any further
+    fn shift_layout(&mut self, layout: &Layout, offset: i32) -> bool {
+        let (page, from) = match self.layout_page(layout) {
+            Some(found) => found,
+            None => return false,
+        };
+
+        let to = (from as i32 + offset).max(0) as usize;
+        // Moving down means dropping in front of the layout after the target position
+        let drop = if to > from { to + 1 } else { to };
+
+        self.move_layout(layout, &page, drop)
+    }
+
+    /// Snapshot of the layout, with its current arrangement stored for the active breakpoint
+    fn layout_document(&self, layout: &Layout) -> Option<LayoutDocument> {
+        let (page, _) = self.layout_page(layout)?;
+        // Placements at other breakpoints are copied as well, so the active one has to be up to date
+        layout.store_breakpoint(&page.active_breakpoint().name);
+
+        Some(layout.to_document())
+    }
+
+    /// Copy the selected components, or the selected layout
+    ///
+    /// Returns `false` if there was nothing to copy
+    fn copy_selection(&mut self) -> bool {
+        let clipboard = match &self.selection {
+            Selection::Layout(layout) => self
+                .layout_document(layout)
+                .map(|document| Clipboard::Layout(Box::new(document))),
+            Selection::Components(_) => {
+                let documents = self
+                    .selection_by_layout()
+                    .iter()
+                    .flat_map(|(layout, components)| component_documents(layout, components))
+                    .collect();
+
+                Some(Clipboard::Components(documents))
+            }
+            _ => None,
+        };
+
+        match clipboard {
+            Some(clipboard) => {
+                self.clipboard = clipboard;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Paste the copied components or layout
+    ///
+    /// Components are pasted into the selected layout, or into the first layout of the page.
+    /// A layout is pasted right after the selected layout, or at the end of the page
+    ///
+    /// Returns `false` if there was nothing to paste, or nowhere to paste it
+    ///
+    /// # Arguments
+    /// * `page` - page to paste into regardless of the selection, `None` to follow the selection
+    fn paste(&mut self, page: Option<Page>) -> bool {
+        let selected_layout = match page {
+            Some(_) => None,
+            None => self.selected_layout(),
+        };
+        let page = page.or_else(|| self.selected_page());
+
+        match self.clipboard.clone() {
+            Clipboard::Components(documents) => {
+                let layout = selected_layout
+                    .or_else(|| page.and_then(|page| page.layouts().first().cloned()));
+
+                match layout {
+                    Some(layout) => {
+                        let (commands, pasted) = self.paste_components(&layout, &documents);
+                        self.set_selection(Selection::with_components(pasted));
+                        self.record_group(commands)
+                    }
+                    None => false,
+                }
+            }
+            Clipboard::Layout(document) => {
+                let target = selected_layout
+                    .and_then(|layout| self.layout_page(&layout))
+                    .map(|(page, index)| (page, index + 1))
+                    .or_else(|| page.map(|page| (page.clone(), page.layouts().len())));
+
+                match target {
+                    Some((page, index)) => self.paste_layout(&page, index, &document),
+                    None => false,
+                }
+            }
+            Clipboard::Empty => false,
+        }
+    }
+
+    /// Duplicate the selected components inside of their layouts,
+    /// or the selected layout right after itself
+    ///
+    /// The clipboard is left untouched
+    ///
+    /// Returns `false` if there was nothing to duplicate
+    fn duplicate_selection(&mut self) -> bool {
+        if let Selection::Layout(layout) = &self.selection {
+            return self.duplicate_layout(&layout.clone());
+        }
+
+        let mut commands = Vec::new();
+        let mut duplicates = Vec::new();
+
+        for (layout, components) in self.selection_by_layout() {
+            let documents = component_documents(&layout, &components);
+            let (pasted_commands, pasted) = self.paste_components(&layout, &documents);
+
+            commands.extend(pasted_commands);
+            duplicates.extend(pasted);
+        }
+
+        if duplicates.is_empty() {
+            return false;
+        }
+
+        self.set_selection(Selection::with_components(duplicates));
+        self.record_group(commands)
+    }
+
+    /// Duplicate a layout together with its components, the duplicate is selected
+    ///
+    /// Layouts placed in a page are duplicated right after themselves,
+    /// nested layouts inside of their parent
+    ///
+    /// Returns `false` if the layout could not be duplicated
+    fn duplicate_layout(&mut self, layout: &Layout) -> bool {
+        let parent = self
+            .layout_page(layout)
+            .and_then(|(page, _)| page.find_parent_layout(layout));
+
+        match (
+            self.layout_page(layout),
+            self.layout_document(layout),
+            parent,
+        ) {
+            // Nested layouts are duplicated inside of their parent
+            (_, Some(mut document), Some(parent)) => {
+                document.clear_ids();
+
+                let duplicate = self
+                    .workspace
+                    .instantiate_layout(&document, self.component_picker.sources());
+
+                match duplicate {
+                    Ok(duplicate) => {
+                        self.name_copies(std::slice::from_ref(&duplicate), &[]);
+
+                        let requested = match layout.placement() {
+                            Some(ChildPlacement::Free { position }) => ChildPlacement::Free {
+                                position: (position.0 + PASTE_OFFSET, position.1 + PASTE_OFFSET),
+                            },
+                            placement => placement.unwrap_or(ChildPlacement::Flex),
+                        };
+
+                        self.nest_layout(&parent, duplicate, Some(requested));
+                        true
+                    }
+                    Err(_) => false,
+                }
+            }
+            (Some((page, index)), Some(document), None) => {
+                self.paste_layout(&page, index + 1, &document)
+            }
+            _ => false,
+        }
+    }
+
+    /// Create components from snapshots and insert them into a layout
+    ///
+    /// Components keep their attributes. In grid layouts they take the free cells
+    /// nearest to their copied cells (the grid grows if there are none left),
+    /// in free layouts they are moved by `PASTE_OFFSET`
+    ///
+    /// Returns the applied commands, together with the new components
+    fn paste_components(
+        &mut self,
+        layout: &Layout,
+        documents: &[ComponentDocument],
+    ) -> (Vec<Command>, Vec<Component>) {
+        let mut layout = layout.clone();
+        let from = layout.size().1;
+
+        let mut commands = Vec::new();
+        let mut pasted = Vec::new();
+
+        for document in documents {
+            let mut document = document.clone();
+            // Placements at other breakpoints and the id belong to the copied component
+            document.breakpoints.clear();
+            document.id = None;
+
+            let (placement, rows) = match &*layout.kind() {
+                LayoutKind::Grid { grid_data, .. } => {
+                    let (pos, size) = match document.placement {
+                        PlacementDocument::Grid { pos, size } => (pos, size),
+                        _ => ((1, 1), (1, 1)),
+                    };
+
+                    let block = grid_data.nearest_free_block(Block {
+                        x: pos.0,
+                        y: pos.1,
+                        width: size.0,
+                        height: size.1,
+                    });
+                    let rows = (block.y + block.height - 1).max(grid_data.height());
+
+                    let placement = PlacementDocument::Grid {
+                        pos: (block.x, block.y),
+                        size: (block.width, block.height),
+                    };
+                    (placement, Some(rows))
+                }
+                LayoutKind::Free { .. } => {
+                    let ((x, y), size) = match document.placement {
+                        PlacementDocument::Free { position, size } => (position, size),
+                        _ => ((0, 0), None),
+                    };
+
+                    let placement = PlacementDocument::Free {
+                        position: (x + PASTE_OFFSET, y + PASTE_OFFSET),
+                        size,
+                    };
+                    (placement, None)
+                }
+                LayoutKind::Flex { .. } => {
+                    let item = match document.placement {
+                        PlacementDocument::Flex { item } => item,
+                        _ => Default::default(),
+                    };
+
+                    (PlacementDocument::Flex { item }, None)
+                }
+            };
+            document.placement = placement;
+
+            if let Some(rows) = rows {
+                layout.set_grid_rows(rows);
+            }
+
+            let component = self
+                .workspace
+                .instantiate_component(&document, self.component_picker.sources());
+
+            // Copied components were registered, so this does not fail
+            if let Ok(component) = component {
+                layout.insert_component(component.clone());
+
+                commands.push(Command::PlaceComponent {
+                    component: component.clone(),
+                    from: None,
+                    to: Some(ComponentPlacement::capture(layout.clone(), &component)),
+                });
+                pasted.push(component);
+            }
+        }
+
+        record_resize(&mut commands, &layout, from);
+        self.name_copies(&[], &pasted);
+
+        (commands, pasted)
+    }
+
+    /// Create a layout from a snapshot and insert it into a page
+    ///
+    /// Returns `false` if the layout could not be created
+    ///
+    /// # Arguments
+    /// * `index` - position of the layout in the page, out of bounds index appends it at the end
+    fn paste_layout(&mut self, page: &Page, index: usize, document: &LayoutDocument) -> bool {
+        let mut document = document.clone();
+        document.clear_ids();
+
+        let layout = self
+            .workspace
+            .instantiate_layout(&document, self.component_picker.sources());
+
+        let mut layout = match layout {
+            Ok(layout) => layout,
+            Err(_) => return false,
+        };
+        self.name_copies(std::slice::from_ref(&layout), &[]);
+
+        // The layout may come from a page of a different width
+        layout.resize(Some(page.width()), None);
+
+        let index = index.min(page.layouts().len());
+        page.clone().insert_layout(layout.clone(), Some(index));
+
+        // Hide and arrange the components as they should be at the breakpoint the page is shown at
+        layout.apply_breakpoint(&page.active_breakpoint().name);
+
+        self.history.push(Command::InsertLayout {
+            page: page.clone(),
+            layout: layout.clone(),
+            index,
+        });
+
+        self.set_selection(Selection::Layout(layout));
+        self.update_tree();
+        true
+    }
+
+    /// Insert a layout into a parent layout, as close to the requested placement as possible
+    ///
+    /// Grid layouts place it into the nearest free cells (growing if there are none left),
+    /// free layouts at the requested position and flex layouts at the end.
+    /// Components of the layout have to be registered in the workspace already
+    fn nest_layout(&mut self, parent: &Layout, layout: Layout, requested: Option<ChildPlacement>) {
+        let mut parent = parent.clone();
+        let from = parent.size().1;
+
+        let (placement, rows) = match &*parent.kind() {
+            LayoutKind::Grid { grid_data, metrics } => {
+                let (pos, size) = match requested {
+                    Some(ChildPlacement::Grid { pos, size }) => (pos, size),
+                    _ => {
+                        let height = layout.size().1 as f64;
+                        let rows = metrics.cells_for_size((0.0, height)).1;
+                        ((1, 1), ((grid_data.width() / 2).max(1), rows))
+                    }
+                };
+
+                let block = grid_data.nearest_free_block(Block {
+                    x: pos.0,
+                    y: pos.1,
+                    width: size.0,
+                    height: size.1,
+                });
+                let rows = (block.y + block.height - 1).max(grid_data.height());
+
+                let placement = ChildPlacement::Grid {
+                    pos: (block.x, block.y),
+                    size: (block.width, block.height),
+                };
+                (placement, Some(rows))
+            }
+            LayoutKind::Free { .. } => {
+                let position = match requested {
+                    Some(ChildPlacement::Free { position }) => position,
+                    _ => (0, 0),
+                };
+                (ChildPlacement::Free { position }, None)
+            }
+            LayoutKind::Flex { .. } => (ChildPlacement::Flex, None),
+        };
+
+        if let Some(rows) = rows {
+            parent.set_grid_rows(rows);
+        }
+        parent.insert_child(layout.clone(), placement);
+
+        let mut commands = vec![Command::PlaceLayout {
+            parent: parent.clone(),
+            layout: layout.clone(),
+            from: None,
+            to: Some(placement),
+        }];
+        record_resize(&mut commands, &parent, from);
+
+        self.set_selection(Selection::Layout(layout));
+        self.record_group(commands);
+    }
+
+    /// Record commands that were already applied as a single step of the history
+    ///
+    /// Returns `false` if there were no commands
+    fn record_group(&mut self, commands: Vec<Command>) -> bool {
+        if commands.is_empty() {
+            return false;
+        }
+
+        self.history.push(Command::group(commands));
+        self.update_tree();
+        true
+    }
+
+    /// Abort the drag, resize or marquee selection in progress
+    ///
+    /// Returns `false` if nothing was in progress
+    fn cancel_drag(&mut self) -> bool {
+        match self.drag_state.take() {
+            DragState::Move { controller, origin } => {
+                let mut component = controller.cancel();
+
+                match origin {
+                    Some(origin) => origin.restore(&mut component),
+                    // Newly spawned component, that never made it into a layout
+                    None => {
+                        component.remove();
+                        self.workspace.remove_component(&mut component);
+                    }
+                }
+            }
+            DragState::Resize { controller, origin } => {
+                let mut component = controller.cancel();
+
+                if let Some(origin) = origin {
+                    origin.restore(&mut component);
+                }
+            }
+            DragState::Marquee { controller } => controller.cancel(),
+            DragState::None => return false,
+        }
+
+        true
+    }
+
+    /// Apply an action triggered from the keyboard
+    ///
+    /// Returns `false` if the action had nothing to do
+    fn run_action(&mut self, action: Action) -> bool {
+        // Only cancelling makes sense in the middle of a drag
+        if !self.drag_state.is_none() {
+            return action == Action::Cancel && self.cancel_drag();
+        }
+
+        if let Some((direction, far)) = action.nudge() {
+            return self.nudge_selection(direction, far);
+        }
+
+        match action {
+            Action::Delete => self.delete_selection(),
+            Action::Cancel => {
+                let selected = self.selection != Selection::None;
+                self.set_selection(Selection::None);
+                selected
+            }
+            Action::Undo => self.undo(),
+            Action::Redo => self.redo(),
+            Action::Copy => self.copy_selection(),
+            Action::Paste => self.paste(None),
+            Action::Duplicate => self.duplicate_selection(),
+            // Nudging is handled above
+            _ => false,
+        }
+    }
+
+    fn on_key_event(&mut self, event: &web_sys::KeyboardEvent) {
+        // Typing into the parameters panel should not move or remove anything
+        let is_typing = event
+            .target()
+            .and_then(|target| target.dyn_into::<HtmlElement>().ok())
+            .is_some_and(|target| {
+                target.is_content_editable()
+                    || matches!(target.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+            });
+
+        if is_typing {
+            return;
+        }
+
+        let action = self.keymap.action(&Shortcut::from_event(event));
+
+        if let Some(action) = action {
+            if self.run_action(action) {
+                event.prevent_default();
             }
         }
     }
@@ -154,7 +1702,8 @@ impl EditorState {
 
         selection.set_is_selected(true);
 
-        self.parameters_panel.set_selected(&selection);
+        self.parameters_panel
+            .set_selected(&selection, &self.workspace);
         self.selection = selection;
     }
 
@@ -174,6 +1723,9 @@ impl EditorState {
         target: &HtmlElement,
     ) {
         match kind {
+            MouseEventKind::Click if self.ignore_click => {
+                self.ignore_click = false;
+            }
             MouseEventKind::Click => {
                 let add_btn = web_sys::window()
                     .unwrap()
@@ -183,34 +1735,27 @@ impl EditorState {
                     .unwrap();
 
                 if add_btn.contains(Some(target)) {
-                    // Add a debug page
-                    {
-                        let page = Page::new("Home", 908);
-
-                        self.workspace.insert_page(page);
-                        self.update_tree();
-                    }
+                    self.add_page(None, None);
                 }
                 // Hierarchy
                 else if self.hierarchy.contains(&target) {
                     let res = self.hierarchy.on_click(&self.workspace, &target);
 
                     match res {
-                        ClickResult::Page(mut page) => {
-                            if let Some(page) = self.workspace.current_page() {
-                                page.clone().set_visible(false);
-                            }
-
-                            page.set_visible(true);
-
-                            self.workspace.set_current_page(page.clone());
+                        ClickResult::Page(page) => {
+                            self.workspace.show_page(page.clone());
                             self.set_selection(Selection::Page(page));
                         }
                         ClickResult::Layout(layout) => {
                             self.set_selection(Selection::Layout(layout));
                         }
                         ClickResult::Component(component) => {
-                            self.set_selection(Selection::Component(component))
+                            let selection = if is_multi_select(event) {
+                                self.selection.toggle_component(component)
+                            } else {
+                                Selection::with_components(vec![component])
+                            };
+                            self.set_selection(selection)
                         }
                         _ => {}
                     }
@@ -225,31 +1770,28 @@ impl EditorState {
                         .find(|page| page.contains(target));
 
                     if let Some(page) = page {
-                        let layout = page.layouts().iter().find(|l| l == &target).cloned();
+                        let layout = page.find_layout_by_element(target).filter(|l| l == target);
 
-                        if let Some(layout) = layout {
+                        if page.find_component_by_element(target).is_some() {
+                            // Components are selected on mouse up, as they can also be dragged
+                        } else if let Some(layout) = layout {
                             if event.button() == 0 {
                                 let selection = Selection::Layout(layout);
                                 self.set_selection(selection)
                             }
                         } else {
                             let layout = page
-                                .layouts()
-                                .iter()
-                                .position(|l| l.close_icon_element().contains(Some(target)));
-                            if let Some(id) = layout {
-                                if let Some(layout) = page.remove_layout(id) {
-                                    for component in layout.components().iter() {
-                                        component.remove();
-                                        self.workspace.components_mut().remove(component.index());
-                                    }
-
-                                    layout.remove();
-                                    self.update_tree();
-                                }
-                            }
+                                .find_layout_by_element(target)
+                                .filter(|l| l.close_icon_element().contains(Some(target)));
 
                             self.set_selection(Selection::None);
+
+                            let command =
+                                layout.and_then(|layout| self.remove_layout_command(&layout));
+
+                            if let Some(command) = command {
+                                self.execute(command);
+                            }
                         }
                     } else {
                         self.set_selection(Selection::None);
@@ -269,12 +1811,45 @@ impl EditorState {
 
                         if let Some(page) = page {
                             if let Some(component) = page.find_component_by_element(target) {
-                                if event.button() == 0 {
-                                    self.drag_state =
-                                        DragState::Move(MoveController::new(component));
+                                let origin = page
+                                    .find_layout_by_element(component.element())
+                                    .map(|layout| ComponentPlacement::capture(layout, &component));
+
+                                let handle = ResizeHandle::from_element(target);
+
+                                if let (0, Some(handle)) = (event.button(), handle) {
+                                    self.drag_state = DragState::Resize {
+                                        controller: ResizeController::new(component, handle),
+                                        origin,
+                                    };
+                                } else if event.button() == 0 {
+                                    self.drag_state = DragState::Move {
+                                        controller: MoveController::new(component),
+                                        origin,
+                                    };
                                 } else if event.button() == 2 {
-                                    self.drag_state =
-                                        DragState::Resize(ResizeController::new(component));
+                                    self.drag_state = DragState::Resize {
+                                        controller: ResizeController::new(
+                                            component,
+                                            ResizeHandle::SE,
+                                        ),
+                                        origin,
+                                    };
+                                }
+                            } else if let Some(layout) = page.find_layout_by_element(target) {
+                                let on_close_icon =
+                                    layout.close_icon_element().contains(Some(target));
+                                // Layouts are dragged by their handle with the html drag and drop
+                                let on_drag_handle = target
+                                    .closest(".container__drag-handle")
+                                    .ok()
+                                    .flatten()
+                                    .is_some();
+
+                                if event.button() == 0 && !on_close_icon && !on_drag_handle {
+                                    self.drag_state = DragState::Marquee {
+                                        controller: MarqueeController::new(layout, event),
+                                    };
                                 }
                             }
                         }
@@ -289,74 +1864,130 @@ impl EditorState {
                             let component = source.new_instance();
                             let id = self.workspace.insert_component(component);
 
+                            let names = self.workspace.component_names();
                             let component = self.workspace.components_mut().get_mut(id).unwrap();
                             component.set_id(id);
 
-                            self.drag_state =
-                                DragState::Move(MoveController::new(component.clone()));
+                            let name = utils::unique_name(&component.name(), &names);
+                            component.set_name(&name);
+
+                            self.drag_state = DragState::Move {
+                                controller: MoveController::new(component.clone()),
+                                origin: None,
+                            };
                         }
                     }
                 }
             }
             MouseEventKind::MouseMove => {
                 match &mut self.drag_state {
-                    DragState::Move(s) => s.mouse_move(&mut self.workspace, event),
-                    DragState::Resize(s) => s.mouse_move(&mut self.workspace, event),
+                    DragState::Move { controller, .. } => {
+                        controller.mouse_move(&mut self.workspace, event)
+                    }
+                    DragState::Resize { controller, .. } => {
+                        controller.mouse_move(&mut self.workspace, event)
+                    }
+                    DragState::Marquee { controller } => controller.mouse_move(event),
                     _ => {}
                 };
             }
             MouseEventKind::MouseUp => {
                 match self.drag_state.take() {
-                    DragState::Move(drag) => {
-                        let res = drag.mouse_up(&mut self.workspace, event);
+                    DragState::Move { controller, origin } => {
+                        let res = controller.mouse_up(&mut self.workspace, event);
 
                         match res {
                             DragMoveResult::MovedToLayout {
-                                mut component,
-                                mut layout,
-                                ..
+                                component,
+                                layout,
+                                displaced,
+                                previous_height,
+                                order,
                             } => {
-                                if let Some(layout) = component.layout() {
-                                    // Finda a page that it belongs to
-                                    let page = self
-                                        .workspace
-                                        .pages_mut()
-                                        .iter_mut()
-                                        .find(|page| page.contains(&layout));
-
-                                    if let Some(page) = page {
-                                        let mut layouts = page.layouts_mut();
-                                        let layout = layouts.iter_mut().find(|l| **l == layout);
-
-                                        if let Some(layout) = layout {
-                                            layout.remove_component(&mut component);
-                                        }
-                                    }
-                                }
-
-                                layout.insert_component(component);
+                                let command = self.drop_component(
+                                    component,
+                                    layout,
+                                    displaced,
+                                    previous_height,
+                                    order,
+                                    origin,
+                                );
+                                self.history.push(command);
                             }
                             DragMoveResult::Removed { mut component } => {
                                 component.remove();
                                 self.workspace.remove_component(&mut component);
+
+                                // Newly spawned components that never made it into a layout
+                                // are not worth recording
+                                if let Some(origin) = origin {
+                                    let mut commands = vec![Command::PlaceComponent {
+                                        component,
+                                        from: Some(origin.clone()),
+                                        to: None,
+                                    }];
+
+                                    if let Some(command) = self.shrink_grid(&origin.layout) {
+                                        commands.push(command);
+                                    }
+
+                                    self.history.push(Command::group(commands));
+                                }
                             }
                             DragMoveResult::NotStarted { component } => {
                                 if !self.workspace.contains(component.element()) {
                                     component.remove();
+                                } else if is_multi_select(event) {
+                                    let selection = self.selection.toggle_component(component);
+                                    self.set_selection(selection);
+                                } else {
+                                    self.set_selection(Selection::with_components(vec![component]));
                                 }
                             }
                         }
 
                         self.update_tree();
                     }
-                    DragState::Resize(drag) => {
+                    DragState::Resize { controller, origin } => {
                         if let DragResizeResult::Resized {
                             mut layout,
                             component,
-                        } = drag.mouse_up(event)
+                            displaced,
+                            previous_height,
+                        } = controller.mouse_up(event)
                         {
                             // Reinsert component with new size
-                            layout.insert_component(component);
+                            layout.insert_component(component.clone());
+
+                            let mut commands = self.move_displaced(&layout, displaced);
+
+                            let to = ComponentPlacement::capture(layout.clone(), &component);
+                            if origin.as_ref() != Some(&to) {
+                                commands.push(Command::PlaceComponent {
+                                    component,
+                                    from: origin,
+                                    to: Some(to),
+                                });
+                            }
+
+                            record_resize(&mut commands, &layout, previous_height);
+
+                            if !commands.is_empty() {
+                                self.history.push(Command::group(commands));
+                            }
+                        }
+                    }
+                    DragState::Marquee { controller } => {
+                        if let Some(components) = controller.mouse_up(event) {
+                            let selection = if is_multi_select(event) {
+                                self.selection.extend_components(components)
+                            } else {
+                                Selection::with_components(components)
+                            };
+                            self.set_selection(selection);
+
+                            // The mouse was dragged, so the click that follows should not select the layout
+                            self.ignore_click = true;
                         }
                     }
                     _ => {}
@@ -366,6 +1997,40 @@ impl EditorState {
     }
 }
 
+/// Whether the clicked item should be added to the selection, instead of replacing it
+fn is_multi_select(event: &web_sys::MouseEvent) -> bool {
+    event.shift_key() || event.ctrl_key() || event.meta_key()
+}
+
+/// Snapshots of components placed in a layout
+fn component_documents(layout: &Layout, components: &[Component]) -> Vec<ComponentDocument> {
+    let kind = layout.kind();
+
+    components
+        .iter()
+        .map(|component| component.to_document(&kind))
+        .collect()
+}
+
+/// Record the change of the layout height made by a drag
+///
+/// Grown layouts have to be resized before the components are moved in,
+/// and shrunk layouts after the components are moved out
+fn record_resize(commands: &mut Vec<Command>, layout: &Layout, from: u32) {
+    let to = layout.size().1;
+    let command = Command::ResizeLayout {
+        layout: layout.clone(),
+        from,
+        to,
+    };
+
+    if to > from {
+        commands.insert(0, command);
+    } else if to < from {
+        commands.push(command);
+    }
+}
+
 thread_local! {
     /// A global variable that stores the state of the editor
     ///
@@ -419,38 +2084,684 @@ impl Editor {
         });
     }
 
+    /// Start a new input session, and return its id
+    ///
+    /// Resizes made with the same session id are undone as a single step,
+    /// inputs start a new session whenever their value is committed
+    pub fn new_input_session(&self) -> u32 {
+        history::new_input_session()
+    }
+
     /// Resize one of pages in workspace
-    pub fn resize_page(&mut self, page: &HtmlElement, width: u32) {
+    pub fn resize_page(&mut self, page: &HtmlElement, width: u32, session: u32) {
         with_editor_state(|editor| {
-            editor.resize_page(page, width);
+            if let Some(page) = editor.workspace.get_page(page).cloned() {
+                let from = page.width();
+                if from != width {
+                    editor.execute_in_session(
+                        Command::ResizePage {
+                            page,
+                            from,
+                            to: width,
+                        },
+                        session,
+                    );
+                }
+            }
         })
     }
 
     /// Resize one of layouts in workspace
-    pub fn resize_layout(&mut self, layout: &HtmlElement, height: u32) {
+    pub fn resize_layout(&mut self, layout: &HtmlElement, height: u32, session: u32) {
+        with_editor_state(|editor| editor.resize_layout_with_history(layout, height, session))
+    }
+
+    /// Revert the last change made in the workspace
+    ///
+    /// Returns `false` if there was nothing to undo
+    pub fn undo(&mut self) -> bool {
+        with_editor_state(|editor| editor.undo())
+    }
+
+    /// Apply the last reverted change once again
+    ///
+    /// Returns `false` if there was nothing to redo
+    pub fn redo(&mut self) -> bool {
+        with_editor_state(|editor| editor.redo())
+    }
+
+    /// Serialize the whole design into a versioned json document
+    pub fn save_document(&self) -> String {
+        with_editor_state(|editor| editor.save_document())
+    }
+
+    /// Export every page as a static html file with Tailwind classes,
+    /// together with the `tailwind.config.js` that the classes need
+    ///
+    /// Returns json of the generated files, `{ "files": [{ "path": "index.html", "contents": "..." }] }`
+    pub fn export_html(&self) -> String {
+        with_editor_state(|editor| editor.export(&HtmlExporter).to_json())
+    }
+
+    /// Export every page for a target: `html`, `css`, `react`, `vue` or `solid`
+    ///
+    /// The `css` target writes plain html with a generated stylesheet, without depending on Tailwind.
+    /// Framework targets write each page as a component,
+    /// that imports the components of the design from `library` (`./components` by default)
+    ///
+    /// Returns json of the generated files, like `export_html`
+    pub fn export(&self, target: &str, library: Option<String>) -> Result<String, JsValue> {
+        let library = library.unwrap_or_else(|| export::DEFAULT_LIBRARY.into());
+        let exporter = export::exporter(target, &library)
+            .ok_or_else(|| JsValue::from_str(&format!("Unknown export target `{}`", target)))?;
+
+        Ok(with_editor_state(|editor| {
+            editor.export(exporter.as_ref()).to_json()
+        }))
+    }
+
+    /// Load a design previously saved with `save_document`, replacing the current one
+    pub fn load_document(&mut self, json: &str) -> Result<(), JsValue> {
+        with_editor_state(|editor| editor.load_document(json))
+            .map_err(|err| JsValue::from_str(&err.to_string()))
+    }
+
+    /// Add a new empty page at the end of the workspace and show it
+    ///
+    /// # Arguments
+    /// * `name` - name of the page, `None` for a numbered `Page`
+    /// * `width` - width of the page in px, `None` for the width of the visible page
+    pub fn add_page(&mut self, name: Option<String>, width: Option<u32>) -> HtmlElement {
+        with_editor_state(|editor| editor.add_page(name.as_deref(), width).html_element)
+    }
+
+    /// Remove a page together with all of its layouts and components
+    pub fn remove_page(&mut self, page: &HtmlElement) -> bool {
+        with_editor_state(|editor| match editor.workspace.get_page(page).cloned() {
+            Some(page) => editor.remove_page(&page),
+            None => false,
+        })
+    }
+
+    /// Move a page to a different position in the workspace
+    ///
+    /// # Arguments
+    /// * `index` - index of the page after the move, out of bounds index moves it to the end
+    pub fn move_page(&mut self, page: &HtmlElement, index: usize) -> bool {
+        with_editor_state(|editor| match editor.workspace.get_page(page).cloned() {
+            Some(page) => editor.move_page(&page, index),
+            None => false,
+        })
+    }
+
+    /// Duplicate a page together with its layouts and components, right after itself
+    ///
+    /// Returns the html element of the duplicate
+    pub fn duplicate_page(&mut self, page: &HtmlElement) -> Option<HtmlElement> {
+        with_editor_state(|editor| {
+            let page = editor.workspace.get_page(page).cloned()?;
+            editor
+                .duplicate_page(&page)
+                .map(|duplicate| duplicate.html_element)
+        })
+    }
+
+    /// Import a fragment of html with Tailwind classes as a new page, and show it
+    ///
+    /// Grid, flex and absolutely positioned containers become layouts,
+    /// elements named like registered components become components.
+    /// Returns the messages about everything that could not be imported
+    ///
+    /// # Arguments
+    /// * `name` - name of the page, `None` for a numbered `Page`
+    pub fn import_html(&mut self, html: &str, name: Option<String>) -> js_sys::Array {
+        with_editor_state(|editor| editor.import_html(html, name.as_deref()))
+            .into_iter()
+            .map(|warning| JsValue::from_str(&warning))
+            .collect()
+    }
+
+    /// Rename a page, returns `false` if the name is empty
+    pub fn rename_page(&mut self, page: &HtmlElement, name: &str) -> bool {
+        with_editor_state(|editor| match editor.workspace.get_page(page).cloned() {
+            Some(page) => editor.rename_page(&page, name),
+            None => false,
+        })
+    }
+
+    /// Rename a page, a layout or a component by its html element
+    ///
+    /// Returns `false` if the name is empty, or the element is not a part of the workspace
+    pub fn rename(&mut self, element: &HtmlElement, name: &str) -> bool {
+        with_editor_state(|editor| {
+            if let Some(page) = editor.workspace.get_page(element).cloned() {
+                return editor.rename_page(&page, name);
+            }
+
+            if let Some(layout) = editor.workspace.get_layout(element) {
+                return editor.rename_layout(&layout, name);
+            }
+
+            let component = editor
+                .workspace
+                .pages()
+                .iter()
+                .find_map(|page| page.find_component_by_element(element));
+
+            match component {
+                Some(component) => editor.rename_component(&component, name),
+                None => false,
+            }
+        })
+    }
+
+    /// Rename the page, layout or component of a hierarchy item
+    ///
+    /// # Arguments
+    /// * `item` - html element inside of the hierarchy item, usually its name
+    ///
+    /// Returns `false` if the name is empty, or the item is not found
+    pub fn rename_in_hierarchy(&mut self, item: &HtmlElement, name: &str) -> bool {
+        with_editor_state(|editor| {
+            let hierarchy = &editor.hierarchy;
+            let workspace = &editor.workspace;
+
+            if let Some(page) = hierarchy.find_page(workspace, item) {
+                return editor.rename_page(&page, name);
+            }
+
+            if let Some(component) = hierarchy.find_component(workspace, item) {
+                return editor.rename_component(&component, name);
+            }
+
+            match hierarchy.find_layout(workspace, item) {
+                Some(layout) => editor.rename_layout(&layout, name),
+                None => false,
+            }
+        })
+    }
+
+    /// Stable id of a layout or a component, used to refer to it in exported code
+    pub fn uid(&self, element: &HtmlElement) -> Option<String> {
+        with_editor_state(|editor| {
+            if let Some(layout) = editor.workspace.get_layout(element) {
+                return Some(layout.uid().to_owned());
+            }
+
+            editor
+                .workspace
+                .pages()
+                .iter()
+                .find_map(|page| page.find_component_by_element(element))
+                .map(|component| component.uid().to_owned())
+        })
+    }
+
+    /// Name of a page
+    pub fn page_name(&self, page: &HtmlElement) -> Option<String> {
+        with_editor_state(|editor| {
+            editor
+                .workspace
+                .get_page(page)
+                .map(|page| page.name().to_owned())
+        })
+    }
+
+    /// Route, title and description of a page as json
+    ///
+    /// Route and title are `null` while they follow the page name,
+    /// the values derived from the name are given next to them
+    ///
+    /// ```json
+    /// {
+    ///   "route": null,
+    ///   "title": "About",
+    ///   "description": "",
+    ///   "default_route": "/about-us",
+    ///   "default_title": "About Us"
+    /// }
+    /// ```
+    pub fn page_metadata(&self, page: &HtmlElement) -> String {
+        with_editor_state(|editor| {
+            let metadata = editor.workspace.get_page(page).map(|page| {
+                let name = page.name();
+                let metadata = page.metadata();
+
+                serde_json::json!({
+                    "route": metadata.route,
+                    "title": metadata.title,
+                    "description": metadata.description,
+                    "default_route": metadata::route_for_name(&name),
+                    "default_title": &*name,
+                })
+            });
+
+            serde_json::to_string(&metadata.unwrap_or_default()).unwrap()
+        })
+    }
+
+    /// Change the route, title and description of a page
+    ///
+    /// An empty route or title follows the page name
+    ///
+    /// Returns `false` if the route is taken by a different page
+    pub fn set_page_metadata(
+        &mut self,
+        page: &HtmlElement,
+        route: &str,
+        title: &str,
+        description: &str,
+    ) -> bool {
+        with_editor_state(|editor| match editor.workspace.get_page(page).cloned() {
+            Some(page) => editor.set_page_metadata(&page, route, title, description),
+            None => false,
+        })
+    }
+
+    /// Add a breakpoint to a page, or change the width of an existing one
+    ///
+    /// The width of the active breakpoint follows the page width instead
+    pub fn add_breakpoint(&mut self, page: &HtmlElement, name: &str, width: u32) {
+        with_editor_state(|editor| {
+            if let Some(page) = editor.workspace.get_page(page).cloned() {
+                let to = Breakpoint::new(name, width);
+                let from = page.breakpoints().iter().find(|b| b.name == name).cloned();
+
+                if page.active_breakpoint().name != name && from.as_ref() != Some(&to) {
+                    editor.execute(Command::AddBreakpoint { page, from, to });
+                }
+            }
+        })
+    }
+
+    /// Remove a breakpoint from a page, together with everything that was arranged for it
+    ///
+    /// Returns `false` if the breakpoint does not exist or is currently active
+    pub fn remove_breakpoint(&mut self, page: &HtmlElement, name: &str) -> bool {
+        with_editor_state(|editor| {
+            let page = match editor.workspace.get_page(page).cloned() {
+                Some(page) => page,
+                None => return false,
+            };
+
+            match page.clone().remove_breakpoint(name) {
+                Some(removed) => {
+                    // The breakpoint is removed already, so the command is not applied again
+                    editor.history.push(Command::RemoveBreakpoint {
+                        page,
+                        removed: Box::new(removed),
+                    });
+                    editor.update_tree();
+                    true
+                }
+                None => false,
+            }
+        })
+    }
+
+    /// Breakpoints of a page as a json array of `{ name, width }` objects, sorted by width
+    pub fn breakpoints(&self, page: &HtmlElement) -> String {
+        with_editor_state(|editor| {
+            let breakpoints = editor
+                .workspace
+                .get_page(page)
+                .map(|page| page.breakpoints().to_vec())
+                .unwrap_or_default();
+
+            serde_json::to_string(&breakpoints).unwrap()
+        })
+    }
+
+    /// Name of the breakpoint that a page is currently shown at
+    pub fn active_breakpoint(&self, page: &HtmlElement) -> Option<String> {
+        with_editor_state(|editor| {
+            editor
+                .workspace
+                .get_page(page)
+                .map(|page| page.active_breakpoint().name.clone())
+        })
+    }
+
+    /// Show a page at a different breakpoint
+    ///
+    /// Returns `false` if the breakpoint does not exist
+    pub fn set_breakpoint(&mut self, page: &HtmlElement, name: &str) -> bool {
+        with_editor_state(|editor| {
+            let page = match editor.workspace.get_page(page).cloned() {
+                Some(page) => page,
+                None => return false,
+            };
+
+            if !page.breakpoints().iter().any(|b| b.name == name) {
+                return false;
+            }
+
+            let from = page.active_breakpoint().name.clone();
+            if from != name {
+                editor.set_selection(Selection::None);
+                editor.execute(Command::SetBreakpoint {
+                    page,
+                    from,
+                    to: name.into(),
+                });
+            }
+
+            true
+        })
+    }
+
+    /// Hide or show a component at the current breakpoint of its page
+    pub fn set_component_visible(&mut self, component: &HtmlElement, visible: bool) {
+        with_editor_state(|editor| {
+            let found = editor.workspace.pages().iter().find_map(|page| {
+                let layout = page.find_layout_by_element(component)?;
+                let component = page.find_component_by_element(component)?;
+                Some((layout, component))
+            });
+
+            if let Some((layout, component)) = found {
+                if component.is_hidden() == visible {
+                    editor.execute(Command::SetComponentHidden {
+                        layout,
+                        component,
+                        hidden: !visible,
+                    });
+                }
+            }
+        })
+    }
+
+    /// Remove the selected components, or the selected layout
+    ///
+    /// Returns `false` if there was nothing to remove
+    pub fn delete_selection(&mut self) -> bool {
+        with_editor_state(|editor| editor.delete_selection())
+    }
+
+    /// Move the selected components, by cells in grid layouts and by px in free layouts
+    ///
+    /// Grid components are moved only if all of them fit into their new cells
+    ///
+    /// Returns `false` if nothing was moved
+    pub fn move_selection(&mut self, dx: i32, dy: i32) -> bool {
+        with_editor_state(|editor| editor.move_selection((dx, dy)))
+    }
+
+    /// Align the selected components to each other
+    ///
+    /// # Arguments
+    /// * `align` - one of `left`, `horizontal_center`, `right`, `top`, `vertical_center` or `bottom`
+    ///
+    /// Returns `false` if nothing was moved
+    pub fn align_selection(&mut self, align: &str) -> bool {
+        match Align::from_name(align) {
+            Some(align) => with_editor_state(|editor| editor.align_selection(align)),
+            None => false,
+        }
+    }
+
+    /// Copy the selected components, or the selected layout
+    ///
+    /// Returns `false` if there was nothing to copy
+    pub fn copy_selection(&mut self) -> bool {
+        with_editor_state(|editor| editor.copy_selection())
+    }
+
+    /// Paste the copied items next to the selection
+    ///
+    /// Components go into the selected layout (or the first layout of the current page),
+    /// a layout goes right after the selected layout (or at the end of the current page)
+    ///
+    /// Returns `false` if there was nothing to paste, or nowhere to paste it
+    pub fn paste(&mut self) -> bool {
+        with_editor_state(|editor| editor.paste(None))
+    }
+
+    /// Paste the copied items into a page, regardless of the selection
+    ///
+    /// Components go into the first layout of the page, a layout goes at the end of the page
+    ///
+    /// Returns `false` if there was nothing to paste, or nowhere to paste it
+    pub fn paste_into_page(&mut self, page: &HtmlElement) -> bool {
+        with_editor_state(|editor| {
+            let page = editor.workspace.get_page(page).cloned();
+            page.is_some() && editor.paste(page)
+        })
+    }
+
+    /// Duplicate the selected components, or the selected layout
+    ///
+    /// Returns `false` if there was nothing to duplicate
+    pub fn duplicate_selection(&mut self) -> bool {
+        with_editor_state(|editor| editor.duplicate_selection())
+    }
+
+    /// Bind a keyboard shortcut to an editor action, replacing the previous action of the shortcut
+    ///
+    /// # Arguments
+    /// * `shortcut` - key with modifiers, for example `Ctrl+Shift+Z` or `ArrowLeft`
+    /// * `action` - name of the action, for example `undo` or `nudge_left`
+    ///
+    /// Returns `false` if the shortcut or the action is not valid
+    pub fn bind_key(&mut self, shortcut: &str, action: &str) -> bool {
+        match (Shortcut::parse(shortcut), Action::from_name(action)) {
+            (Some(shortcut), Some(action)) => {
+                with_editor_state(|editor| editor.keymap.bind(shortcut, action));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Remove the binding of a keyboard shortcut
+    ///
+    /// Returns `false` if the shortcut was not bound
+    pub fn unbind_key(&mut self, shortcut: &str) -> bool {
+        match Shortcut::parse(shortcut) {
+            Some(shortcut) => with_editor_state(|editor| editor.keymap.unbind(&shortcut)),
+            None => false,
+        }
+    }
+
+    /// Go back to the default keyboard shortcuts
+    pub fn reset_keymap(&mut self) {
+        with_editor_state(|editor| editor.keymap = Keymap::default())
+    }
+
+    /// Keyboard shortcuts as a json object of `{ shortcut: action }`
+    pub fn keymap(&self) -> String {
+        with_editor_state(|editor| {
+            let keymap: serde_json::Map<String, serde_json::Value> = editor
+                .keymap
+                .bindings()
+                .iter()
+                .map(|(shortcut, action)| (shortcut.to_string(), action.name().into()))
+                .collect();
+
+            serde_json::Value::Object(keymap).to_string()
+        })
+    }
+
+    /// Nest a new layout inside of another layout
+    ///
+    /// The layout takes the first free cells of grid layouts (half of the columns wide),
+    /// the left-top point of free layouts, or the end of flex layouts
+    ///
+    /// # Arguments
+    /// * `parent` - html element of the parent layout
+    /// * `layout_kind` - one of `grid`, `flex` or `free`
+    ///
+    /// Returns `false` if the parent or the kind is not valid
+    pub fn add_layout_to_layout(&mut self, parent: &HtmlElement, layout_kind: &str) -> bool {
+        with_editor_state(|editor| {
+            let parent = match editor.workspace.get_layout(parent) {
+                Some(parent) => parent,
+                None => return false,
+            };
+
+            let width = match &*parent.kind() {
+                LayoutKind::Grid { grid_data, metrics } => {
+                    metrics.span_width((grid_data.width() / 2).max(1))
+                }
+                _ => parent.size().0 / 2,
+            };
+
+            let mut layout = match layout_kind {
+                "grid" => Layout::new_grid(width),
+                "flex" => Layout::new_flex(width, 76),
+                "free" => Layout::new_free(width, 76),
+                _ => return false,
+            };
+            editor.name_new_layout(&mut layout);
+
+            editor.nest_layout(&parent, layout, None);
+            true
+        })
+    }
+
+    /// Convert a layout to a different kind ("free", "flex" or "grid"), keeping its components
+    ///
+    /// Returns the elements of the components that could not be placed in the converted layout,
+    /// they are hidden until they are moved somewhere else
+    pub fn convert_layout(&mut self, layout: &HtmlElement, layout_kind: &str) -> js_sys::Array {
+        let target = match ConvertTarget::from_name(layout_kind) {
+            Some(target) => target,
+            None => return js_sys::Array::new(),
+        };
+
+        with_editor_state(|editor| {
+            editor
+                .convert_layout(layout, target)
+                .iter()
+                .map(|component| JsValue::from(component.element()))
+                .collect()
+        })
+    }
+
+    /// Move a layout placed directly in a page in front of another layout,
+    /// in the same page or in a different one
+    ///
+    /// # Arguments
+    /// * `page` - html element of the page that the layout is moved to
+    /// * `index` - index of the layout that it is dropped in front of (as in `add_layout_to_page`),
+    ///   out of bounds index moves it to the end of the page
+    ///
+    /// Returns `false` if the layout is nested, or it would stay where it is
+    pub fn move_layout(&mut self, layout: &HtmlElement, page: &HtmlElement, index: usize) -> bool {
+        with_editor_state(|editor| {
+            let layout = editor.workspace.get_layout(layout);
+            let page = editor.workspace.get_page(page).cloned();
+
+            match (layout, page) {
+                (Some(layout), Some(page)) => editor.move_layout(&layout, &page, index),
+                _ => false,
+            }
+        })
+    }
+
+    /// Move a layout placed directly in a page up (negative offset) or down between the other layouts
+    ///
+    /// Returns `false` if the layout is nested, or it can not move any further
+    pub fn shift_layout(&mut self, layout: &HtmlElement, offset: i32) -> bool {
+        with_editor_state(|editor| match editor.workspace.get_layout(layout) {
+            Some(layout) => editor.shift_layout(&layout, offset),
+            None => false,
+        })
+    }
+
+    /// Duplicate a layout together with its components, the duplicate is selected
+    ///
+    /// Layouts placed in a page are duplicated right after themselves,
+    /// nested layouts inside of their parent
+    pub fn duplicate_layout(&mut self, layout: &HtmlElement) -> bool {
+        with_editor_state(|editor| match editor.workspace.get_layout(layout) {
+            Some(layout) => editor.duplicate_layout(&layout),
+            None => false,
+        })
+    }
+
+    /// Move a component, a layout or a page dragged in the hierarchy tree to the item it was dropped onto
+    ///
+    /// Dropping a component onto a layout moves it into the layout (into free cells of grids),
+    /// dropping it onto a component of a flex layout moves it in front of that component,
+    /// dropping it onto a component of a different layout kind moves it into that layout.
+    /// Dropping a layout onto a layout moves the dragged one in front of it,
+    /// dropping it onto a page moves it to the end of the page.
+    /// Pages can only be dropped onto pages, they are moved in front of them
+    ///
+    /// # Arguments
+    /// * `dragged` - html element of the hierarchy item that was dragged
+    /// * `target` - html element of the hierarchy item that it was dropped onto
+    pub fn drop_in_hierarchy(&mut self, dragged: &HtmlElement, target: &HtmlElement) -> bool {
         with_editor_state(|editor| {
-            editor.resize_layout(layout, height);
+            if let Some(page) = editor.hierarchy.find_page(&editor.workspace, dragged) {
+                let pages = editor.workspace.pages();
+                let from = pages.iter().position(|p| *p == page);
+                let drop = editor
+                    .hierarchy
+                    .find_page(&editor.workspace, target)
+                    .and_then(|target| pages.iter().position(|p| *p == target));
+
+                return match drop {
+                    Some(drop) => editor.move_page(&page, drop_index(from, drop)),
+                    None => false,
+                };
+            }
+
+            if let Some(component) = editor.hierarchy.find_component(&editor.workspace, dragged) {
+                let before = editor.hierarchy.find_component(&editor.workspace, target);
+                if before.as_ref() == Some(&component) {
+                    return false;
+                }
+
+                let layout = match &before {
+                    Some(before) => before
+                        .layout()
+                        .and_then(|layout| editor.workspace.get_layout(&layout)),
+                    None => editor.hierarchy.find_layout(&editor.workspace, target),
+                };
+
+                return match layout {
+                    Some(layout) => {
+                        editor.move_component_to_layout(&component, &layout, before.as_ref())
+                    }
+                    None => false,
+                };
+            }
+
+            let layout = editor.hierarchy.find_layout(&editor.workspace, dragged);
+            let drop = editor.hierarchy.layout_drop(&editor.workspace, target);
+
+            match (layout, drop) {
+                (Some(layout), Some((page, index))) => editor.move_layout(&layout, &page, index),
+                _ => false,
+            }
         })
     }
 
     pub fn add_layout_to_page(&mut self, page: &HtmlElement, id: usize, layout_kind: &str) {
         with_editor_state(|editor| {
-            let page = editor.workspace.get_page_mut(page);
+            let page = editor.workspace.get_page(page).cloned();
             if let Some(page) = page {
-                match layout_kind {
-                    "grid" => {
-                        page.insert_layout(Layout::new_grid(page.width()), Some(id));
-                    }
-                    "flex" => {
-                        page.insert_layout(Layout::new_flex(page.width(), 76), Some(id));
-                    }
-                    "free" => {
-                        page.insert_layout(Layout::new_free(page.width(), 76), Some(id));
-                    }
-                    _ => {}
-                }
+                let layout = match layout_kind {
+                    "grid" => Some(Layout::new_grid(page.width())),
+                    "flex" => Some(Layout::new_flex(page.width(), 76)),
+                    "free" => Some(Layout::new_free(page.width(), 76)),
+                    _ => None,
+                };
+
+                if let Some(mut layout) = layout {
+                    editor.name_new_layout(&mut layout);
+
+                    // Out of bounds index means that the layout is appended at the end
+                    let index = id.min(page.layouts().len());
 
-                editor.update_tree();
+                    editor.execute(Command::InsertLayout {
+                        page,
+                        layout,
+                        index,
+                    });
+                }
             }
         });
     }
@@ -492,11 +2803,29 @@ fn register_editor_listeners() {
         with_editor_state(|editor| editor.on_mouse_event(MouseEventKind::MouseUp, event))
     });
     mouse_up.forget();
+
+    let key_down = EventListener::new(&document, "keydown", |event| {
+        let event = event.dyn_ref().unwrap();
+        with_editor_state(|editor| editor.on_key_event(event))
+    });
+    key_down.forget();
 }
 
 enum DragState {
-    Move(MoveController),
-    Resize(ResizeController),
+    Move {
+        controller: MoveController,
+        /// Placement of the component before the drag, `None` for newly spawned components
+        origin: Option<ComponentPlacement>,
+    },
+    Resize {
+        controller: ResizeController,
+        /// Placement of the component before the resize
+        origin: Option<ComponentPlacement>,
+    },
+    /// Rubber-band selection inside of a layout
+    Marquee {
+        controller: MarqueeController,
+    },
     None,
 }
 
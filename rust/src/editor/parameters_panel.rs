@@ -1,13 +1,14 @@
 use wasm_bindgen::JsCast;
-use web_sys::{HtmlElement, HtmlInputElement, HtmlTextAreaElement};
+use web_sys::{EventTarget, HtmlElement, HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
 
 use crate::{
+    component::{Component, ParameterKind, ParameterSchema},
     editor::{Selection, Workspace},
-    html_elements::component::ComponentPropertie,
+    page::layout::LayoutKind,
     utils,
 };
 
-use self::layout_settings::LayoutSettings;
+use self::layout_settings::{FlexItemSettings, LayoutSettings};
 
 mod layout_settings;
 
@@ -17,6 +18,8 @@ pub struct ParametersPanel {
     component_list: HtmlElement,
 
     selected_settings: Option<LayoutSettings>,
+    /// Settings of a single selected component of a flex layout
+    item_settings: Option<FlexItemSettings>,
 }
 
 impl ParametersPanel {
@@ -32,10 +35,13 @@ impl ParametersPanel {
             root,
             component_list,
             selected_settings: None,
+            item_settings: None,
         }
     }
 
-    pub fn set_selected(&mut self, selection: &Selection) {
+    pub fn set_selected(&mut self, selection: &Selection, workspace: &Workspace) {
+        self.set_selected_item(selection, workspace);
+
         match selection {
             Selection::Layout(layout) => {
                 if Some(layout) != self.selected_settings.as_ref().map(|s| &s.layout) {
@@ -58,6 +64,35 @@ impl ParametersPanel {
         }
     }
 
+    /// Show the flex settings of a component, if a single component of a flex layout is selected
+    fn set_selected_item(&mut self, selection: &Selection, workspace: &Workspace) {
+        let component = match selection.components() {
+            [component] => Some(component),
+            _ => None,
+        };
+
+        let is_flex = component
+            .and_then(|component| component.layout())
+            .and_then(|layout| workspace.get_layout(&layout))
+            .is_some_and(|layout| matches!(&*layout.kind(), LayoutKind::Flex { .. }));
+
+        let component = component.filter(|_| is_flex);
+
+        if component == self.item_settings.as_ref().map(|s| &s.component) {
+            return;
+        }
+
+        if let Some(old) = self.item_settings.take() {
+            old.root.remove();
+        }
+
+        if let Some(component) = component {
+            let settings = FlexItemSettings::new(component.clone());
+            self.root.append_child(&settings.root).unwrap();
+            self.item_settings = Some(settings);
+        }
+    }
+
     /// Update the list of components
     pub fn update_debug_components_tree(&self, workspace: &Workspace) {
         self.component_list.set_inner_html("");
@@ -74,230 +109,10 @@ impl ParametersPanel {
 
             let properties_div = document.create_element("div").unwrap();
 
-            for param in comp.element().descriptor().parameters().iter() {
-                let key = param.name();
-
-                // TODO(poly): Replace this huge messy match with something cleaner
-                match param.data_type() {
-                    ComponentPropertie::Color => {
-                        let div = document.create_element("div").unwrap();
-                        let div: HtmlElement = div.dyn_into().unwrap();
-                        div.style().set_property("display", "flex").unwrap();
-                        div.style().set_property("align-items", "center").unwrap();
-
-                        {
-                            let label = document.create_element("div").unwrap();
-                            let label: HtmlElement = label.dyn_into().unwrap();
-
-                            label.set_inner_text("Color: ");
-
-                            div.append_child(&label).unwrap();
-                        }
-
-                        {
-                            let input = document.create_element("input").unwrap();
-                            let input: HtmlInputElement = input.dyn_into().unwrap();
-
-                            input.set_class_name("color-picker");
-                            input.set_type("color");
-
-                            let value = comp.element().get_attribute(&key);
-
-                            input.set_value(&value.unwrap_or_else(|| "#2ecc71".into()));
-
-                            let cb = utils::new_listener(
-                                (comp.element().clone(), key.clone()),
-                                |(element, key), e: web_sys::InputEvent| {
-                                    let input: HtmlInputElement =
-                                        e.target().unwrap().dyn_into().unwrap();
-                                    element.set_attribute(key, &input.value()).unwrap();
-                                },
-                            );
-
-                            input.set_oninput(Some(&cb));
-
-                            div.append_child(&input).unwrap();
-                        }
-
-                        properties_div.append_child(&div).unwrap();
-                    }
-                    ComponentPropertie::Text => {
-                        let div = document.create_element("div").unwrap();
-                        let div: HtmlElement = div.dyn_into().unwrap();
-                        div.style().set_property("display", "flex").unwrap();
-                        div.style().set_property("align-items", "center").unwrap();
-
-                        {
-                            let label = document.create_element("div").unwrap();
-                            let label: HtmlElement = label.dyn_into().unwrap();
-
-                            label.set_inner_text(&key);
-
-                            div.append_child(&label).unwrap();
-                        }
-
-                        {
-                            let input = document.create_element("input").unwrap();
-                            let input: HtmlInputElement = input.dyn_into().unwrap();
-
-                            input.set_class_name("text-picker");
-                            input.set_type("text");
-
-                            let value = comp.element().get_attribute(&key);
-
-                            input.set_value(&value.unwrap_or_else(|| "".into()));
-
-                            let cb = utils::new_listener(
-                                (comp.element().clone(), key.clone()),
-                                |(element, key), e: web_sys::InputEvent| {
-                                    let input: HtmlInputElement =
-                                        e.target().unwrap().dyn_into().unwrap();
-                                    element.set_attribute(key, &input.value()).unwrap();
-                                },
-                            );
-
-                            input.set_oninput(Some(&cb));
-
-                            div.append_child(&input).unwrap();
-                        }
-
-                        properties_div.append_child(&div).unwrap();
-                    }
-                    ComponentPropertie::Number => {
-                        let div = document.create_element("div").unwrap();
-                        let div: HtmlElement = div.dyn_into().unwrap();
-                        div.style().set_property("display", "flex").unwrap();
-                        div.style().set_property("align-items", "center").unwrap();
-
-                        {
-                            let label = document.create_element("div").unwrap();
-                            let label: HtmlElement = label.dyn_into().unwrap();
-                            label.style().set_property("margin-right", "2px").unwrap();
-
-                            label.set_inner_text(&key);
-
-                            div.append_child(&label).unwrap();
-                        }
-
-                        {
-                            let input = document.create_element("input").unwrap();
-                            let input: HtmlInputElement = input.dyn_into().unwrap();
-
-                            input.set_class_name("text-picker");
-                            input.set_type("number");
-
-                            let value = comp.element().get_attribute(&key);
-
-                            input.set_value(&value.unwrap_or_else(|| "0".into()));
-
-                            let cb = utils::new_listener(
-                                (comp.element().clone(), key.clone()),
-                                |(element, key), e: web_sys::InputEvent| {
-                                    let input: HtmlInputElement =
-                                        e.target().unwrap().dyn_into().unwrap();
-                                    element.set_attribute(key, &input.value()).unwrap();
-                                },
-                            );
-
-                            input.set_oninput(Some(&cb));
-
-                            div.append_child(&input).unwrap();
-                        }
-
-                        properties_div.append_child(&div).unwrap();
-                    }
-                    ComponentPropertie::Boolean => {
-                        let div = document.create_element("div").unwrap();
-                        let div: HtmlElement = div.dyn_into().unwrap();
-                        div.style().set_property("display", "flex").unwrap();
-                        div.style().set_property("align-items", "center").unwrap();
-
-                        {
-                            let label = document.create_element("div").unwrap();
-                            let label: HtmlElement = label.dyn_into().unwrap();
-                            label.style().set_property("margin-right", "2px").unwrap();
-
-                            label.set_inner_text(&key);
-
-                            div.append_child(&label).unwrap();
-                        }
-
-                        {
-                            let input = document.create_element("input").unwrap();
-                            let input: HtmlInputElement = input.dyn_into().unwrap();
-
-                            input.set_class_name("text-picker");
-                            input.set_type("checkbox");
-
-                            let value = comp.element().get_attribute(&key);
-
-                            input.set_checked(value.unwrap_or_else(|| "false".into()) == "true");
-
-                            let cb = utils::new_listener(
-                                (comp.element().clone(), key.clone()),
-                                |(element, key), e: web_sys::InputEvent| {
-                                    let input: HtmlInputElement =
-                                        e.target().unwrap().dyn_into().unwrap();
-                                    element
-                                        .set_attribute(
-                                            key,
-                                            if input.checked() { "true" } else { "false" },
-                                        )
-                                        .unwrap();
-                                },
-                            );
-
-                            input.set_oninput(Some(&cb));
-
-                            div.append_child(&input).unwrap();
-                        }
-
-                        properties_div.append_child(&div).unwrap();
-                    }
-                    ComponentPropertie::LayoutStyle => {
-                        let div = document.create_element("div").unwrap();
-                        let div: HtmlElement = div.dyn_into().unwrap();
-                        div.style().set_property("display", "flex").unwrap();
-                        div.style().set_property("align-items", "center").unwrap();
-
-                        {
-                            let label = document.create_element("div").unwrap();
-                            let label: HtmlElement = label.dyn_into().unwrap();
-                            label.style().set_property("margin-right", "2px").unwrap();
-
-                            label.set_inner_text(&key);
-
-                            div.append_child(&label).unwrap();
-                        }
-
-                        {
-                            let input = document.create_element("textarea").unwrap();
-                            let input: HtmlTextAreaElement = input.dyn_into().unwrap();
-
-                            // input.set_class_name("text-picker");
-
-                            let value = comp.element().get_attribute(&key);
-
-                            input.set_value(&value.unwrap_or_else(|| "".into()));
-
-                            let cb = utils::new_listener(
-                                (comp.element().clone(), key.clone()),
-                                |(element, key), e: web_sys::InputEvent| {
-                                    let input: HtmlTextAreaElement =
-                                        e.target().unwrap().dyn_into().unwrap();
-                                    element.set_attribute(key, &input.value()).unwrap();
-                                },
-                            );
-
-                            input.set_oninput(Some(&cb));
-
-                            div.append_child(&input).unwrap();
-                        }
-
-                        properties_div.append_child(&div).unwrap();
-                    }
-                    _ => {}
-                }
+            for schema in comp.element().descriptor().parameter_schemas() {
+                properties_div
+                    .append_child(&parameter_input(comp, schema))
+                    .unwrap();
             }
 
             li.append_child(&properties_div).unwrap();
@@ -328,3 +143,130 @@ impl ParametersPanel {
         }
     }
 }
+
+/// Label and input of a descriptor parameter, for the list of components
+///
+/// Values are validated before they are written as attributes,
+/// invalid ones mark the input and show the reason in its title instead
+fn parameter_input(component: &Component, schema: ParameterSchema) -> HtmlElement {
+    let document = web_sys::window().unwrap().document().unwrap();
+
+    let div = document.create_element("div").unwrap();
+    let div: HtmlElement = div.dyn_into().unwrap();
+    div.style().set_property("display", "flex").unwrap();
+    div.style().set_property("align-items", "center").unwrap();
+
+    {
+        let label = document.create_element("div").unwrap();
+        let label: HtmlElement = label.dyn_into().unwrap();
+        label.style().set_property("margin-right", "2px").unwrap();
+
+        label.set_inner_text(&schema.name);
+
+        div.append_child(&label).unwrap();
+    }
+
+    let value = component
+        .element()
+        .get_attribute(&schema.name)
+        .unwrap_or_else(|| schema.initial_value());
+
+    let input: HtmlElement = match &schema.kind {
+        ParameterKind::Text { multiline: true } | ParameterKind::LayoutStyle => {
+            let input = document.create_element("textarea").unwrap();
+            let input: HtmlTextAreaElement = input.dyn_into().unwrap();
+            input.set_value(&value);
+            input.into()
+        }
+        ParameterKind::Select { options } => {
+            let input = document.create_element("select").unwrap();
+            let input: HtmlSelectElement = input.dyn_into().unwrap();
+
+            for option in options.iter() {
+                let element = document.create_element("option").unwrap();
+                element.set_attribute("value", option).unwrap();
+                element.set_text_content(Some(option));
+                input.append_child(&element).unwrap();
+            }
+
+            input.set_value(&value);
+            input.into()
+        }
+        kind => {
+            let input = document.create_element("input").unwrap();
+            let input: HtmlInputElement = input.dyn_into().unwrap();
+
+            if let ParameterKind::Number { min, max, step } = kind {
+                let limits = [("min", min), ("max", max), ("step", step)];
+                for (attribute, limit) in limits.iter() {
+                    if let Some(limit) = limit {
+                        input.set_attribute(attribute, &limit.to_string()).unwrap();
+                    }
+                }
+            }
+
+            let (class, input_type) = match kind {
+                ParameterKind::Color => ("color-picker", "color"),
+                ParameterKind::Number { .. } => ("text-picker", "number"),
+                ParameterKind::Boolean => ("text-picker", "checkbox"),
+                ParameterKind::Url | ParameterKind::Image => ("text-picker", "url"),
+                _ => ("text-picker", "text"),
+            };
+            input.set_class_name(class);
+            input.set_type(input_type);
+
+            if *kind == ParameterKind::Boolean {
+                input.set_checked(value == "true");
+            } else {
+                input.set_value(&value);
+            }
+
+            input.into()
+        }
+    };
+
+    if schema.required {
+        input.set_attribute("required", "").unwrap();
+    }
+
+    let cb = utils::new_listener(
+        (component.clone(), schema),
+        |(component, schema), e: web_sys::InputEvent| {
+            let target = e.target().unwrap();
+            let input: &HtmlElement = target.dyn_ref().unwrap();
+
+            match component.set_parameter(schema, &input_value(&target)) {
+                Ok(()) => {
+                    input.class_list().remove_1("invalid").unwrap();
+                    input.remove_attribute("title").unwrap();
+                }
+                Err(err) => {
+                    input.class_list().add_1("invalid").unwrap();
+                    input.set_title(&err.to_string());
+                }
+            }
+        },
+    );
+
+    input.set_oninput(Some(&cb));
+
+    div.append_child(&input).unwrap();
+    div
+}
+
+/// Value of an input created by `parameter_input`
+fn input_value(target: &EventTarget) -> String {
+    if let Some(input) = target.dyn_ref::<HtmlInputElement>() {
+        if input.type_() == "checkbox" {
+            input.checked().to_string()
+        } else {
+            input.value()
+        }
+    } else if let Some(input) = target.dyn_ref::<HtmlTextAreaElement>() {
+        input.value()
+    } else if let Some(input) = target.dyn_ref::<HtmlSelectElement>() {
+        input.value()
+    } else {
+        String::new()
+    }
+}
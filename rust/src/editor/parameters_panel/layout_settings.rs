@@ -1,19 +1,24 @@
 use wasm_bindgen::JsCast;
 use web_sys::HtmlElement;
 
-use crate::page::layout::{Layout, LayoutKind};
+use crate::{
+    editor::with_editor_state,
+    page::layout::{Layout, LayoutKind},
+};
 
 mod flex;
 mod free;
 mod grid;
 
+pub use flex::FlexItemSettings;
 use flex::FlexSettings;
+use free::FreeSettings;
 use grid::GridSettings;
 
 trait SettingsData {}
 impl SettingsData for FlexSettings {}
+impl SettingsData for FreeSettings {}
 impl SettingsData for GridSettings {}
-impl SettingsData for () {}
 
 fn title(title: &str) -> HtmlElement {
     let document = web_sys::window().unwrap().document().unwrap();
@@ -35,8 +40,23 @@ impl LayoutSettings {
     pub fn new(layout: Layout) -> Self {
         let (root, _data): (HtmlElement, Box<dyn SettingsData>) = match &*layout.kind() {
             LayoutKind::Free { .. } => {
-                let root = free::settings();
-                (root, Box::new(()))
+                let mut data = FreeSettings::new(&layout);
+
+                {
+                    let layout = layout.clone();
+                    data.connect_snap_grid(move |size| {
+                        layout.set_free_snap_grid(size);
+                    });
+                }
+
+                {
+                    let layout = layout.clone();
+                    data.connect_snap_to_items(move |is| {
+                        layout.set_free_snap_to_items(is);
+                    });
+                }
+
+                (data.root.clone(), Box::new(data))
             }
             LayoutKind::Flex { .. } => {
                 let mut data = FlexSettings::new(&layout);
@@ -55,21 +75,92 @@ impl LayoutSettings {
                     });
                 }
 
+                {
+                    let layout = layout.clone();
+                    data.connect_direction(move |direction| {
+                        layout.set_flex_direction(direction);
+                    });
+                }
+
+                {
+                    let layout = layout.clone();
+                    data.connect_wrap(move |is| {
+                        layout.set_flex_wrap(is);
+                    });
+                }
+
+                {
+                    let layout = layout.clone();
+                    data.connect_gap(move |gap| {
+                        layout.set_flex_gap(gap);
+                    });
+                }
+
                 (data.root.clone(), Box::new(data))
             }
             LayoutKind::Grid { .. } => {
                 let mut data = GridSettings::new(&layout);
 
-                let mut layout = layout.clone();
-                data.connect_height(move |value| {
-                    let cell_size = if let LayoutKind::Grid { cell_size, .. } = &*layout.kind() {
-                        Some(*cell_size)
+                {
+                    let mut layout = layout.clone();
+                    data.connect_columns(move |columns| {
+                        if let Some((_, row_height, gap)) = layout.grid_geometry() {
+                            layout.set_grid_geometry(columns as usize, row_height, gap);
+                        }
+                    });
+                }
+
+                {
+                    let mut layout = layout.clone();
+                    data.connect_row_height(move |row_height| {
+                        if let Some((columns, _, gap)) = layout.grid_geometry() {
+                            layout.set_grid_geometry(columns, row_height, gap);
+                        }
+                    });
+                }
+
+                {
+                    let mut layout = layout.clone();
+                    data.connect_gap(move |gap| {
+                        if let Some((columns, row_height, _)) = layout.grid_geometry() {
+                            layout.set_grid_geometry(columns, row_height, gap);
+                        }
+                    });
+                }
+
+                {
+                    let layout = layout.clone();
+                    data.connect_collision(move |strategy| {
+                        layout.set_grid_collision(strategy);
+                    });
+                }
+
+                {
+                    let layout = layout.clone();
+                    data.connect_auto_grow(move |is| {
+                        layout.set_grid_auto_grow(is);
+                    });
+                }
+
+                {
+                    let layout = layout.clone();
+                    data.connect_auto_shrink(move |is| {
+                        layout.set_grid_auto_shrink(is);
+                    });
+                }
+
+                let layout = layout.clone();
+                data.connect_height(move |value, session| {
+                    let height = if let LayoutKind::Grid { metrics, .. } = &*layout.kind() {
+                        Some(metrics.height_for_rows(value as usize))
                     } else {
                         None
                     };
 
-                    if let Some(cell_size) = cell_size {
-                        layout.resize(None, Some(value * cell_size));
+                    if let Some(height) = height {
+                        with_editor_state(|editor| {
+                            editor.resize_layout_with_history(&layout.html_element, height, session)
+                        });
                     }
                 });
 
@@ -2,9 +2,17 @@ use std::{cell::RefCell, rc::Rc};
 
 use gloo_events::EventListener;
 use wasm_bindgen::JsCast;
-use web_sys::HtmlElement;
+use web_sys::{HtmlElement, HtmlSelectElement};
 
-use crate::page::layout::Layout;
+use crate::{
+    component::Component,
+    page::layout::{
+        flex::{FlexDirection, FlexItem},
+        Layout,
+    },
+};
+
+use super::grid::{NumberInput, ToggleInput};
 
 pub struct Icon {
     name: &'static str,
@@ -168,9 +176,65 @@ impl Icons {
     }
 }
 
+struct DirectionInput {
+    root: HtmlElement,
+    select: HtmlSelectElement,
+    change_listener: Option<EventListener>,
+}
+
+impl DirectionInput {
+    fn new(direction: FlexDirection) -> Self {
+        let document = web_sys::window().unwrap().document().unwrap();
+
+        let root = document.create_element("div").unwrap();
+        let root: HtmlElement = root.dyn_into().unwrap();
+        root.class_list().add_1("select-input").unwrap();
+
+        let span = document.create_element("span").unwrap();
+        let span: HtmlElement = span.dyn_into().unwrap();
+
+        span.set_inner_text("Direction");
+        root.append_child(&span).unwrap();
+
+        let select = document.create_element("select").unwrap();
+        let select: HtmlSelectElement = select.dyn_into().unwrap();
+
+        for direction in FlexDirection::ALL.iter() {
+            let option = document.create_element("option").unwrap();
+            option.set_attribute("value", direction.class()).unwrap();
+            option.set_text_content(Some(direction.class()));
+            select.append_child(&option).unwrap();
+        }
+
+        select.set_value(direction.class());
+
+        root.append_child(&select).unwrap();
+
+        Self {
+            root,
+            select,
+            change_listener: None,
+        }
+    }
+
+    fn connect<F: FnMut(FlexDirection) + 'static>(&mut self, mut cb: F) {
+        let select = self.select.clone();
+        let listener = EventListener::new(&self.select, "change", move |_| {
+            if let Some(direction) = FlexDirection::from_class(&select.value()) {
+                cb(direction);
+            }
+        });
+
+        self.change_listener = Some(listener);
+    }
+}
+
 pub struct FlexSettings {
     pub root: HtmlElement,
     pub icons: Icons,
+    direction_input: DirectionInput,
+    wrap_input: ToggleInput,
+    gap_input: NumberInput,
 }
 
 impl FlexSettings {
@@ -186,6 +250,105 @@ impl FlexSettings {
 
         root.append_child(&icons.root).unwrap();
 
-        Self { root, icons }
+        let (direction, wrap, gap) = layout.flex_options().unwrap_or_default();
+
+        let direction_input = DirectionInput::new(direction);
+        root.append_child(&direction_input.root).unwrap();
+
+        let wrap_input = ToggleInput::new("Wrap items", wrap);
+        root.append_child(&wrap_input.root).unwrap();
+
+        let gap_input = NumberInput::new("Gap", gap, 0);
+        root.append_child(&gap_input.root).unwrap();
+
+        Self {
+            root,
+            icons,
+            direction_input,
+            wrap_input,
+            gap_input,
+        }
+    }
+
+    pub fn connect_direction<F: FnMut(FlexDirection) + 'static>(&mut self, cb: F) {
+        self.direction_input.connect(cb);
+    }
+
+    pub fn connect_wrap<F: FnMut(bool) + 'static>(&mut self, cb: F) {
+        self.wrap_input.connect(cb);
+    }
+
+    pub fn connect_gap<F: FnMut(u32) + 'static>(&mut self, cb: F) {
+        self.gap_input.connect(cb);
+    }
+}
+
+/// Settings of a component placed in a flex layout
+pub struct FlexItemSettings {
+    pub component: Component,
+    pub root: HtmlElement,
+    _grow_input: NumberInput,
+    _shrink_input: NumberInput,
+    _basis_input: NumberInput,
+    _order_input: NumberInput,
+}
+
+impl FlexItemSettings {
+    pub fn new(component: Component) -> Self {
+        let document = web_sys::window().unwrap().document().unwrap();
+
+        let root = document.create_element("section").unwrap();
+        let root: HtmlElement = root.dyn_into().unwrap();
+
+        root.append_child(&super::title("Flex item")).unwrap();
+
+        let item = component.flex_item();
+
+        let mut grow_input = NumberInput::new("Grow", item.grow, 0);
+        root.append_child(&grow_input.root).unwrap();
+
+        let mut shrink_input = NumberInput::new("Shrink", item.shrink, 0);
+        root.append_child(&shrink_input.root).unwrap();
+
+        // Basis of 0 px stands for the size of the content
+        let mut basis_input = NumberInput::new("Basis", item.basis.unwrap_or(0), 0);
+        root.append_child(&basis_input.root).unwrap();
+
+        let mut order_input = NumberInput::new("Order", item.order.max(0) as u32, 0);
+        root.append_child(&order_input.root).unwrap();
+
+        connect_item(&mut grow_input, &component, |item, value| item.grow = value);
+        connect_item(&mut shrink_input, &component, |item, value| {
+            item.shrink = value
+        });
+        connect_item(&mut basis_input, &component, |item, value| {
+            item.basis = Some(value).filter(|basis| *basis > 0)
+        });
+        connect_item(&mut order_input, &component, |item, value| {
+            item.order = value as i32
+        });
+
+        Self {
+            component,
+            root,
+            _grow_input: grow_input,
+            _shrink_input: shrink_input,
+            _basis_input: basis_input,
+            _order_input: order_input,
+        }
     }
 }
+
+/// Update a single flex property of the component whenever the input changes
+fn connect_item<F: Fn(&mut FlexItem, u32) + 'static>(
+    input: &mut NumberInput,
+    component: &Component,
+    update: F,
+) {
+    let component = component.clone();
+    input.connect(move |value| {
+        let mut item = component.flex_item();
+        update(&mut item, value);
+        component.set_flex_item(item);
+    });
+}
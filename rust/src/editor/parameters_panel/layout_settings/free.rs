@@ -1,11 +1,45 @@
 use wasm_bindgen::JsCast;
 use web_sys::HtmlElement;
 
-pub fn settings() -> HtmlElement {
-    let document = web_sys::window().unwrap().document().unwrap();
+use crate::page::layout::Layout;
 
-    let root = document.create_element("section").unwrap();
-    let root: HtmlElement = root.dyn_into().unwrap();
-    root.append_child(&super::title("Free")).unwrap();
-    root
+use super::grid::{NumberInput, ToggleInput};
+
+pub struct FreeSettings {
+    pub root: HtmlElement,
+    snap_grid_input: NumberInput,
+    snap_to_items_input: ToggleInput,
+}
+
+impl FreeSettings {
+    pub fn new(layout: &Layout) -> Self {
+        let document = web_sys::window().unwrap().document().unwrap();
+
+        let root = document.create_element("section").unwrap();
+        let root: HtmlElement = root.dyn_into().unwrap();
+        root.append_child(&super::title("Free")).unwrap();
+
+        let (snap_grid, snap_to_items) = layout.free_snap_options().unwrap_or((0, false));
+
+        // Grid of 0 px turns the pixel snapping off
+        let snap_grid_input = NumberInput::new("Snap", snap_grid, 0);
+        root.append_child(&snap_grid_input.root).unwrap();
+
+        let snap_to_items_input = ToggleInput::new("Snap to components", snap_to_items);
+        root.append_child(&snap_to_items_input.root).unwrap();
+
+        Self {
+            root,
+            snap_grid_input,
+            snap_to_items_input,
+        }
+    }
+
+    pub fn connect_snap_grid<F: FnMut(u32) + 'static>(&mut self, cb: F) {
+        self.snap_grid_input.connect(cb);
+    }
+
+    pub fn connect_snap_to_items<F: FnMut(bool) + 'static>(&mut self, cb: F) {
+        self.snap_to_items_input.connect(cb);
+    }
 }
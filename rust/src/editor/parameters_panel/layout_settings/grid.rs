@@ -1,13 +1,20 @@
+use std::{cell::Cell, rc::Rc};
+
 use gloo_events::EventListener;
 use wasm_bindgen::JsCast;
-use web_sys::{HtmlElement, HtmlInputElement};
+use web_sys::{HtmlElement, HtmlInputElement, HtmlSelectElement};
 
-use crate::page::layout::{Layout, LayoutKind};
+use crate::{
+    editor::history::new_input_session,
+    page::layout::{grid::CollisionStrategy, Layout, LayoutKind},
+};
 
 struct HeightInput {
     root: HtmlElement,
     input: HtmlInputElement,
     click_listener: Option<EventListener>,
+    /// Ends the input session when the slider is released
+    change_listener: Option<EventListener>,
 }
 
 impl HeightInput {
@@ -45,28 +52,191 @@ impl HeightInput {
             root,
             input,
             click_listener: None,
+            change_listener: None,
         }
     }
 
-    fn connect<F: FnMut(u32) + 'static>(&mut self, mut cb: F) {
+    /// Call the callback with the value and the input session, whenever the slider moves
+    fn connect<F: FnMut(u32, u32) + 'static>(&mut self, mut cb: F) {
+        let session = Rc::new(Cell::new(new_input_session()));
+
         let input = self.input.clone();
-        let listener = EventListener::new(&self.root, "input", move |_| {
-            let value = input.value();
+        let listener = {
+            let session = session.clone();
+            EventListener::new(&self.root, "input", move |_| {
+                let value = input.value();
+
+                let value: Option<u32> = value.parse().ok();
+                //
+                if let Some(value) = value {
+                    cb(value, session.get());
+                }
+            })
+        };
+
+        self.click_listener = Some(listener);
+        self.change_listener = Some(EventListener::new(&self.input, "change", move |_| {
+            session.set(new_input_session());
+        }));
+    }
+}
+
+pub(super) struct NumberInput {
+    pub(super) root: HtmlElement,
+    input: HtmlInputElement,
+    change_listener: Option<EventListener>,
+}
+
+impl NumberInput {
+    pub(super) fn new(label: &str, value: u32, min: u32) -> Self {
+        let document = web_sys::window().unwrap().document().unwrap();
+
+        let root = document.create_element("div").unwrap();
+        let root: HtmlElement = root.dyn_into().unwrap();
+        root.class_list().add_1("size-input").unwrap();
+
+        let span = document.create_element("span").unwrap();
+        let span: HtmlElement = span.dyn_into().unwrap();
+
+        span.set_inner_text(label);
+        root.append_child(&span).unwrap();
+
+        let input = document.create_element("input").unwrap();
+        let input: HtmlInputElement = input.dyn_into().unwrap();
+
+        input.set_attribute("type", "number").unwrap();
+        input.set_attribute("step", "1").unwrap();
+        input.set_attribute("min", &min.to_string()).unwrap();
+        input.set_value(&value.to_string());
+
+        root.append_child(&input).unwrap();
+
+        Self {
+            root,
+            input,
+            change_listener: None,
+        }
+    }
 
-            let value: Option<u32> = value.parse().ok();
-            //
-            if let Some(value) = value {
+    pub(super) fn connect<F: FnMut(u32) + 'static>(&mut self, mut cb: F) {
+        let input = self.input.clone();
+        let listener = EventListener::new(&self.input, "change", move |_| {
+            if let Ok(value) = input.value().parse() {
                 cb(value);
             }
         });
 
-        self.click_listener = Some(listener);
+        self.change_listener = Some(listener);
+    }
+}
+
+struct CollisionInput {
+    root: HtmlElement,
+    select: HtmlSelectElement,
+    change_listener: Option<EventListener>,
+}
+
+impl CollisionInput {
+    fn new(layout: &Layout) -> Self {
+        let document = web_sys::window().unwrap().document().unwrap();
+
+        let root = document.create_element("div").unwrap();
+        let root: HtmlElement = root.dyn_into().unwrap();
+        root.class_list().add_1("select-input").unwrap();
+
+        let span = document.create_element("span").unwrap();
+        let span: HtmlElement = span.dyn_into().unwrap();
+
+        span.set_inner_text("On collision");
+        root.append_child(&span).unwrap();
+
+        let select = document.create_element("select").unwrap();
+        let select: HtmlSelectElement = select.dyn_into().unwrap();
+
+        for strategy in CollisionStrategy::ALL.iter() {
+            let option = document.create_element("option").unwrap();
+            option.set_attribute("value", strategy.name()).unwrap();
+            option.set_text_content(Some(strategy.name()));
+            select.append_child(&option).unwrap();
+        }
+
+        if let LayoutKind::Grid { grid_data, .. } = &*layout.kind() {
+            select.set_value(grid_data.collision().name());
+        }
+
+        root.append_child(&select).unwrap();
+
+        Self {
+            root,
+            select,
+            change_listener: None,
+        }
+    }
+
+    fn connect<F: FnMut(CollisionStrategy) + 'static>(&mut self, mut cb: F) {
+        let select = self.select.clone();
+        let listener = EventListener::new(&self.select, "change", move |_| {
+            if let Some(strategy) = CollisionStrategy::from_name(&select.value()) {
+                cb(strategy);
+            }
+        });
+
+        self.change_listener = Some(listener);
+    }
+}
+
+pub(super) struct ToggleInput {
+    pub(super) root: HtmlElement,
+    input: HtmlInputElement,
+    change_listener: Option<EventListener>,
+}
+
+impl ToggleInput {
+    pub(super) fn new(label: &str, value: bool) -> Self {
+        let document = web_sys::window().unwrap().document().unwrap();
+
+        let root = document.create_element("label").unwrap();
+        let root: HtmlElement = root.dyn_into().unwrap();
+        root.class_list().add_1("select-input").unwrap();
+
+        let span = document.create_element("span").unwrap();
+        let span: HtmlElement = span.dyn_into().unwrap();
+
+        span.set_inner_text(label);
+        root.append_child(&span).unwrap();
+
+        let input = document.create_element("input").unwrap();
+        let input: HtmlInputElement = input.dyn_into().unwrap();
+
+        input.set_attribute("type", "checkbox").unwrap();
+        input.set_checked(value);
+
+        root.append_child(&input).unwrap();
+
+        Self {
+            root,
+            input,
+            change_listener: None,
+        }
+    }
+
+    pub(super) fn connect<F: FnMut(bool) + 'static>(&mut self, mut cb: F) {
+        let input = self.input.clone();
+        let listener = EventListener::new(&self.input, "change", move |_| cb(input.checked()));
+
+        self.change_listener = Some(listener);
     }
 }
 
 pub struct GridSettings {
     pub root: HtmlElement,
     height_input: HeightInput,
+    columns_input: NumberInput,
+    row_height_input: NumberInput,
+    gap_input: NumberInput,
+    collision_input: CollisionInput,
+    auto_grow_input: ToggleInput,
+    auto_shrink_input: ToggleInput,
 }
 
 impl GridSettings {
@@ -80,10 +250,72 @@ impl GridSettings {
         let height_input = HeightInput::new(layout);
         root.append_child(&height_input.root).unwrap();
 
-        Self { root, height_input }
+        let (columns, metrics) = match &*layout.kind() {
+            LayoutKind::Grid { grid_data, metrics } => (grid_data.width(), Some(*metrics)),
+            _ => (0, None),
+        };
+        let (row_height, gap) = metrics.map_or((0, 0), |m| (m.row_height, m.gap));
+
+        let columns_input = NumberInput::new("Col", columns as u32, 1);
+        root.append_child(&columns_input.root).unwrap();
+
+        let row_height_input = NumberInput::new("Row", row_height, 1);
+        root.append_child(&row_height_input.root).unwrap();
+
+        let gap_input = NumberInput::new("Gap", gap, 0);
+        root.append_child(&gap_input.root).unwrap();
+
+        let collision_input = CollisionInput::new(layout);
+        root.append_child(&collision_input.root).unwrap();
+
+        let (auto_grow, auto_shrink) = match &*layout.kind() {
+            LayoutKind::Grid { grid_data, .. } => (grid_data.auto_grow(), grid_data.auto_shrink()),
+            _ => (false, false),
+        };
+
+        let auto_grow_input = ToggleInput::new("Add rows automatically", auto_grow);
+        root.append_child(&auto_grow_input.root).unwrap();
+
+        let auto_shrink_input = ToggleInput::new("Remove empty rows", auto_shrink);
+        root.append_child(&auto_shrink_input.root).unwrap();
+
+        Self {
+            root,
+            height_input,
+            columns_input,
+            row_height_input,
+            gap_input,
+            collision_input,
+            auto_grow_input,
+            auto_shrink_input,
+        }
     }
 
-    pub fn connect_height<F: FnMut(u32) + 'static>(&mut self, cb: F) {
+    pub fn connect_height<F: FnMut(u32, u32) + 'static>(&mut self, cb: F) {
         self.height_input.connect(cb);
     }
+
+    pub fn connect_columns<F: FnMut(u32) + 'static>(&mut self, cb: F) {
+        self.columns_input.connect(cb);
+    }
+
+    pub fn connect_row_height<F: FnMut(u32) + 'static>(&mut self, cb: F) {
+        self.row_height_input.connect(cb);
+    }
+
+    pub fn connect_gap<F: FnMut(u32) + 'static>(&mut self, cb: F) {
+        self.gap_input.connect(cb);
+    }
+
+    pub fn connect_collision<F: FnMut(CollisionStrategy) + 'static>(&mut self, cb: F) {
+        self.collision_input.connect(cb);
+    }
+
+    pub fn connect_auto_grow<F: FnMut(bool) + 'static>(&mut self, cb: F) {
+        self.auto_grow_input.connect(cb);
+    }
+
+    pub fn connect_auto_shrink<F: FnMut(bool) + 'static>(&mut self, cb: F) {
+        self.auto_shrink_input.connect(cb);
+    }
 }
@@ -0,0 +1,166 @@
+use crate::{
+    component::Component,
+    page::{layout::Layout, Page},
+};
+
+/// Currently selected items of the workspace
+#[derive(Clone, PartialEq)]
+pub enum Selection {
+    Page(Page),
+    Layout(Layout),
+    /// One or more components, in the order they were selected
+    Components(Vec<Component>),
+    None,
+}
+
+impl Selection {
+    pub fn set_is_selected(&mut self, is: bool) {
+        match self {
+            Self::Page(page) => page.set_is_selected(is),
+            Self::Layout(layout) => layout.set_is_selected(is),
+            Self::Components(components) => {
+                for component in components.iter() {
+                    component.set_is_selected(is);
+                }
+            }
+            Self::None => {}
+        }
+    }
+
+    /// Selected components, empty if something else is selected
+    pub fn components(&self) -> &[Component] {
+        match self {
+            Self::Components(components) => components,
+            _ => &[],
+        }
+    }
+
+    /// Selection with the component added to the selected components,
+    /// or removed from them if it was already selected
+    pub fn toggle_component(&self, component: Component) -> Self {
+        let mut components = self.components().to_vec();
+
+        if let Some(id) = components.iter().position(|c| *c == component) {
+            components.remove(id);
+        } else {
+            components.push(component);
+        }
+
+        Self::with_components(components)
+    }
+
+    /// Selection with the components added to the selected components
+    pub fn extend_components(&self, new: Vec<Component>) -> Self {
+        let mut components = self.components().to_vec();
+
+        for component in new {
+            if !components.contains(&component) {
+                components.push(component);
+            }
+        }
+
+        Self::with_components(components)
+    }
+
+    /// Select the components, nothing is selected if the list is empty
+    pub fn with_components(components: Vec<Component>) -> Self {
+        if components.is_empty() {
+            Self::None
+        } else {
+            Self::Components(components)
+        }
+    }
+}
+
+/// Alignment of several selected components
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    HorizontalCenter,
+    Right,
+    Top,
+    VerticalCenter,
+    Bottom,
+}
+
+impl Align {
+    /// Every alignment, in the order they are shown to the user
+    pub const ALL: [Align; 6] = [
+        Align::Left,
+        Align::HorizontalCenter,
+        Align::Right,
+        Align::Top,
+        Align::VerticalCenter,
+        Align::Bottom,
+    ];
+
+    /// Name used by the JS side of the editor
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Left => "left",
+            Self::HorizontalCenter => "horizontal_center",
+            Self::Right => "right",
+            Self::Top => "top",
+            Self::VerticalCenter => "vertical_center",
+            Self::Bottom => "bottom",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|align| align.name() == name)
+    }
+
+    /// Whether the components are moved along the x axis
+    pub fn is_horizontal(&self) -> bool {
+        matches!(self, Self::Left | Self::HorizontalCenter | Self::Right)
+    }
+}
+
+/// Align spans along a single axis
+///
+/// Returns the new start of every span, in the same order
+///
+/// # Arguments
+/// * `spans` - start and length of every aligned item, along the axis of `align`
+/// * `align` - alignment of the items
+pub fn align_spans(spans: &[(i32, i32)], align: Align) -> Vec<i32> {
+    let start = spans.iter().map(|(start, _)| *start).min().unwrap_or(0);
+    let end = spans
+        .iter()
+        .map(|(start, len)| start + len)
+        .max()
+        .unwrap_or(0);
+
+    spans
+        .iter()
+        .map(|(_, len)| match align {
+            Align::Left | Align::Top => start,
+            Align::Right | Align::Bottom => end - len,
+            Align::HorizontalCenter | Align::VerticalCenter => start + (end - start - len) / 2,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spans_are_aligned_to_the_outer_edges() {
+        let spans = [(2, 3), (6, 4), (4, 1)];
+
+        assert_eq!(align_spans(&spans, Align::Left), vec![2, 2, 2]);
+        assert_eq!(align_spans(&spans, Align::Bottom), vec![7, 6, 9]);
+        assert_eq!(align_spans(&spans, Align::HorizontalCenter), vec![4, 4, 5]);
+        assert_eq!(align_spans(&[], Align::Top), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn alignments_are_named() {
+        for align in Align::ALL.iter() {
+            assert_eq!(Align::from_name(align.name()), Some(*align));
+        }
+        assert!(Align::Right.is_horizontal());
+        assert!(!Align::Top.is_horizontal());
+    }
+}
@@ -1,8 +1,17 @@
+use std::collections::HashSet;
+
 use generational_arena::Arena;
 use wasm_bindgen::JsCast;
 use web_sys::{Element, HtmlElement};
 
-use crate::{component::Component, page::Page};
+use crate::{
+    component::{Component, ComponentSource},
+    document::{ComponentDocument, Document, DocumentError, LayoutDocument, PageDocument},
+    page::{
+        layout::{ChildPlacement, Layout},
+        Page,
+    },
+};
 
 /// Workspace is an area in the middle of the editor.
 ///
@@ -36,15 +45,90 @@ impl Workspace {
         }
     }
 
+    /// Add a page at the end of the workspace, and show it
     pub fn insert_page(&mut self, page: Page) {
-        page.append_to(&self.html_element);
-        self.pages.push(page.clone());
+        self.insert_page_at(page, self.pages.len());
+    }
 
-        if let Some(mut current_page) = self.current_page.take() {
-            current_page.set_visible(false);
+    /// Insert a page at a given index, and show it
+    ///
+    /// Components of the page have to be registered in the workspace separately
+    ///
+    /// # Arguments
+    /// * `index` - position of the page, out of bounds index appends it at the end
+    pub fn insert_page_at(&mut self, page: Page, index: usize) {
+        match self.pages.get(index) {
+            Some(next) => {
+                next.html_element
+                    .before_with_node_1(&page.html_element)
+                    .unwrap();
+                self.pages.insert(index, page.clone());
+            }
+            None => {
+                page.append_to(&self.html_element);
+                self.pages.push(page.clone());
+            }
         }
 
-        self.current_page = Some(page);
+        self.show_page(page);
+    }
+
+    /// Remove a page, together with all of its components
+    ///
+    /// If the page was visible, the one that took its place (or the last one) is shown instead
+    ///
+    /// Returns the index that the page was at, `None` if it is not in the workspace
+    pub fn remove_page(&mut self, page: &Page) -> Option<usize> {
+        let index = self.pages.iter().position(|p| p == page)?;
+        let page = self.pages.remove(index);
+
+        for layout in page.all_layouts() {
+            for component in layout.components().iter() {
+                self.components.remove(component.index());
+            }
+        }
+
+        page.html_element.remove();
+
+        if self.current_page.as_ref() == Some(&page) {
+            self.current_page = None;
+
+            let next = self.pages.get(index).or_else(|| self.pages.last()).cloned();
+            if let Some(next) = next {
+                self.show_page(next);
+            }
+        }
+
+        Some(index)
+    }
+
+    /// Move a page to a different position, keeping the DOM in the same order
+    ///
+    /// Returns `false` if there is no page at `from`
+    ///
+    /// # Arguments
+    /// * `to` - index of the page after the move, out of bounds index moves it to the end
+    pub fn move_page(&mut self, from: usize, to: usize) -> bool {
+        if from >= self.pages.len() {
+            return false;
+        }
+
+        let page = self.pages.remove(from);
+
+        match self.pages.get(to) {
+            Some(next) => {
+                next.html_element
+                    .before_with_node_1(&page.html_element)
+                    .unwrap();
+                self.pages.insert(to, page);
+            }
+            None => {
+                page.append_to(&self.html_element);
+                self.pages.push(page);
+            }
+        }
+
+        true
     }
 
     /// Get currently visible page
@@ -52,11 +136,61 @@ impl Workspace {
         self.current_page.as_ref()
     }
 
-    /// Set currently visible page
-    pub fn set_current_page(&mut self, page: Page) {
+    /// Hide the currently visible page, and show a different one instead
+    pub fn show_page(&mut self, mut page: Page) {
+        if let Some(mut current_page) = self.current_page.take() {
+            current_page.set_visible(false);
+        }
+
+        page.set_visible(true);
         self.current_page = Some(page);
     }
 
+    /// Names of all pages, in their order
+    pub fn page_names(&self) -> Vec<String> {
+        self.pages
+            .iter()
+            .map(|page| page.name().to_owned())
+            .collect()
+    }
+
+    /// Routes that the pages are served at, in their order
+    pub fn page_routes(&self) -> Vec<String> {
+        self.pages.iter().map(Page::route).collect()
+    }
+
+    /// Names of all layouts, nested layouts included
+    pub fn layout_names(&self) -> Vec<String> {
+        self.pages
+            .iter()
+            .flat_map(|page| page.all_layouts())
+            .map(|layout| layout.name().to_owned())
+            .collect()
+    }
+
+    /// Names of all components known to the editor
+    pub fn component_names(&self) -> Vec<String> {
+        self.components
+            .iter()
+            .map(|(_, component)| component.name().to_owned())
+            .collect()
+    }
+
+    /// Stable ids of all layouts and components, nested layouts included
+    pub fn uids(&self) -> HashSet<String> {
+        let layouts = self
+            .pages
+            .iter()
+            .flat_map(|page| page.all_layouts())
+            .map(|layout| layout.uid().to_owned());
+        let components = self
+            .components
+            .iter()
+            .map(|(_, component)| component.uid().to_owned());
+
+        layouts.chain(components).collect()
+    }
+
     /// Determines whether the workspace contains a given html element
     pub fn contains(&self, elm: &Element) -> bool {
         self.html_element.contains(Some(elm))
@@ -74,6 +208,13 @@ impl Workspace {
         self.pages.iter_mut().find(|page| page == &elm)
     }
 
+    /// Get a layout by html element, nested layouts included
+    pub fn get_layout(&self, elm: &HtmlElement) -> Option<Layout> {
+        self.pages
+            .iter()
+            .find_map(|page| page.all_layouts().into_iter().find(|l| l == elm))
+    }
+
     pub fn pages(&self) -> &[Page] {
         &self.pages
     }
@@ -109,26 +250,157 @@ impl Workspace {
     ///
     /// Tracking it after this is done is not posible
     pub fn remove_component(&mut self, component: &mut Component) -> Option<Component> {
-        if let Some(layout) = component.layout() {
-            // Finda a page that it belongs to
-            let page = self
-                .pages_mut()
-                .iter_mut()
-                .find(|page| page.contains(&layout));
-
-            if let Some(page) = page {
-                let mut layouts = page.layouts_mut();
-                let layout = layouts.iter_mut().find(|l| **l == layout);
-
-                if let Some(layout) = layout {
-                    layout.remove_component(component);
-                }
-            }
+        if let Some(mut layout) = component.layout().and_then(|l| self.get_layout(&l)) {
+            layout.remove_component(component);
         }
 
         self.components.remove(component.index())
     }
 
+    /// Remove all pages and components from the workspace
+    pub fn clear(&mut self) {
+        for page in self.pages.drain(..) {
+            page.html_element.remove();
+        }
+
+        for (_, component) in self.components.drain() {
+            component.element().remove();
+        }
+
+        self.current_page = None;
+    }
+
+    /// Take a serializable snapshot of the whole workspace
+    pub fn to_document(&self) -> Document {
+        Document::new(self.pages.iter().map(|page| page.to_document()).collect())
+    }
+
+    /// Replace the content of the workspace with the one described by the document
+    ///
+    /// # Arguments
+    /// * `document` - document to load
+    /// * `sources` - registered component sources, used to instantiate the components
+    pub fn load_document(
+        &mut self,
+        document: &Document,
+        sources: &[ComponentSource],
+    ) -> Result<(), DocumentError> {
+        let find_source = |tag_name: &str| {
+            sources
+                .iter()
+                .find(|source| source.source.descriptor().tag_name() == tag_name)
+        };
+
+        // Validate the document before touching the workspace,
+        // so that a broken file does not leave the editor half loaded
+        if let Some(component) = document
+            .components()
+            .find(|component| find_source(&component.tag_name).is_none())
+        {
+            return Err(DocumentError::UnknownComponent(component.tag_name.clone()));
+        }
+        document.validate()?;
+
+        self.clear();
+
+        for page_document in document.pages.iter() {
+            // Components were checked above
+            let page = self.instantiate_page(page_document, sources)?;
+            self.insert_page(page);
+        }
+
+        // Show the first page, like right after the editor starts
+        if let Some(first_page) = self.pages.first().cloned() {
+            self.show_page(first_page);
+        }
+
+        Ok(())
+    }
+
+    /// Create a new page from a serialized snapshot, together with all of its layouts and components
+    ///
+    /// The page is not inserted into the workspace, but its components are registered
+    ///
+    /// # Arguments
+    /// * `document` - snapshot of the page
+    /// * `sources` - registered component sources, used to instantiate the components
+    pub fn instantiate_page(
+        &mut self,
+        document: &PageDocument,
+        sources: &[ComponentSource],
+    ) -> Result<Page, DocumentError> {
+        let mut page = Page::new(&document.name, document.width);
+        page.set_breakpoints(document.breakpoints.clone(), document.breakpoint.as_deref());
+        page.set_metadata(document.metadata.clone());
+
+        for layout_document in document.layouts.iter() {
+            let layout = self.instantiate_layout(layout_document, sources)?;
+            page.insert_layout(layout, None);
+        }
+
+        // Hide the components that are not shown at the breakpoint the page was saved at
+        page.apply_breakpoint();
+
+        Ok(page)
+    }
+
+    /// Create a new component from a serialized snapshot, and register it in the workspace
+    ///
+    /// The component is not inserted into any layout
+    ///
+    /// # Arguments
+    /// * `document` - snapshot of the component
+    /// * `sources` - registered component sources, used to instantiate the component
+    pub fn instantiate_component(
+        &mut self,
+        document: &ComponentDocument,
+        sources: &[ComponentSource],
+    ) -> Result<Component, DocumentError> {
+        let source = sources
+            .iter()
+            .find(|source| source.source.descriptor().tag_name() == document.tag_name)
+            .ok_or_else(|| DocumentError::UnknownComponent(document.tag_name.clone()))?;
+
+        let component = Component::new(source.source.instantiate_component());
+        let id = self.insert_component(component);
+
+        let component = self.components.get_mut(id).unwrap();
+        component.set_id(id);
+        component.apply_document(document);
+
+        Ok(component.clone())
+    }
+
+    /// Create a new layout from a serialized snapshot, together with all of its components and nested layouts
+    ///
+    /// The layout is not inserted into any page.
+    /// Components created before an unknown one is found stay registered,
+    /// so the documents should be validated first
+    ///
+    /// # Arguments
+    /// * `document` - snapshot of the layout
+    /// * `sources` - registered component sources, used to instantiate the components
+    pub fn instantiate_layout(
+        &mut self,
+        document: &LayoutDocument,
+        sources: &[ComponentSource],
+    ) -> Result<Layout, DocumentError> {
+        let mut layout = Layout::from_document(document);
+
+        for component_document in document.components.iter() {
+            let component = self.instantiate_component(component_document, sources)?;
+            layout.insert_component(component);
+        }
+
+        for child_document in document.children.iter() {
+            let child = self.instantiate_layout(child_document, sources)?;
+            let placement = child_document.placement.unwrap_or(ChildPlacement::Flex);
+            layout.insert_child(child, placement);
+        }
+
+        Ok(layout)
+    }
+
     /// Remove all components that are no longer in the DOM tree
     ///
     /// Curently not used anywhere, but it may be usefull
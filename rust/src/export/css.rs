@@ -0,0 +1,381 @@
+use crate::{
+    document::{Document, PageDocument},
+    page::breakpoint::Breakpoint,
+};
+
+use super::{
+    element::Element,
+    html::{html_document, STYLESHEET},
+    page::{page_element, page_screens},
+    page_paths,
+    style::{Display, Length, Position, Style},
+    Export, Exporter,
+};
+
+/// Rules that every stylesheet starts with
+///
+/// Sizes in the editor include the borders of the elements, as they do with Tailwind
+const RESET: &str = "*,\n::before,\n::after {\n  box-sizing: border-box;\n}\n";
+
+/// Exports every page as a static html file, styled by a generated stylesheet instead of Tailwind
+pub struct CssExporter;
+
+impl Exporter for CssExporter {
+    fn name(&self) -> &str {
+        "css"
+    }
+
+    fn extension(&self) -> &str {
+        "html"
+    }
+
+    fn page_file(&self, page: &PageDocument, body: &Element, path: &str) -> String {
+        html_document(page, body, path)
+    }
+
+    /// Export every page at its route, together with the stylesheet that all of them link to
+    fn export(&self, document: &Document) -> Export {
+        let mut stylesheet = Stylesheet::default();
+        let mut export = Export::default();
+
+        let paths = page_paths(document, self.extension());
+        for (page, path) in document.pages.iter().zip(paths) {
+            let mut body = page_element(page);
+            stylesheet.add_page(&page.name, &mut body, &page_screens(page));
+
+            export.add_file(&path, self.page_file(page, &body, &path));
+        }
+
+        export.add_file(STYLESHEET, stylesheet.to_css());
+        export
+    }
+}
+
+/// Css rule, a selector with its declarations
+type Rule = (String, Vec<(&'static str, String)>);
+
+/// Stylesheet with the rules of exported pages
+#[derive(Debug, Default)]
+pub struct Stylesheet {
+    /// Rules of the pages, in the order that they were added in
+    css: String,
+    /// Number of elements without an id, that got a generated class
+    classes: usize,
+}
+
+impl Stylesheet {
+    /// Replace the styles of an element tree with the rules of the stylesheet
+    ///
+    /// Elements are selected by their ids, the ones without an id get a generated class.
+    /// Styles of the breakpoints other than the smallest one are put into `min-width` media queries
+    ///
+    /// # Arguments
+    /// * `name` - name of the page, written as a comment above its rules
+    /// * `screens` - breakpoints of the page sorted by width, that the styles refer to
+    pub fn add_page(&mut self, name: &str, body: &mut Element, screens: &[Breakpoint]) {
+        let mut rules = vec![Vec::new(); screens.len()];
+        self.collect_rules(body, &mut rules);
+
+        self.css
+            .push_str(&format!("\n/* {} */\n", name.replace("*/", "* /")));
+
+        for (screen, rules) in rules.iter().enumerate() {
+            if rules.is_empty() {
+                continue;
+            }
+
+            if screen == 0 {
+                self.css.push_str(&write_rules(rules, 0));
+            } else {
+                self.css.push_str(&format!(
+                    "\n@media (min-width: {}px) {{\n{}}}\n",
+                    screens[screen].width,
+                    write_rules(rules, 2)
+                ));
+            }
+        }
+    }
+
+    /// Content of the stylesheet
+    pub fn to_css(&self) -> String {
+        format!("{}{}", RESET, self.css)
+    }
+
+    /// Move the styles of an element and its children into rules of the breakpoints
+    fn collect_rules(&mut self, element: &mut Element, rules: &mut [Vec<Rule>]) {
+        if !element.styles.is_empty() {
+            let selector = match &element.id {
+                Some(id) => format!("#{}", escape_identifier(id)),
+                None => {
+                    self.classes += 1;
+
+                    let class = format!("{}-{}", element.tag, self.classes);
+                    element.classes.push(class.clone());
+                    format!(".{}", class)
+                }
+            };
+
+            for responsive in element.styles.drain(..) {
+                let rules = &mut rules[responsive.screen];
+
+                // Styles of an element come one after the other, so they share the last rule
+                match rules.last_mut() {
+                    Some((last, declarations)) if last == &selector => {
+                        declarations.extend(self::declarations(&responsive.style));
+                    }
+                    _ => rules.push((selector.clone(), declarations(&responsive.style))),
+                }
+            }
+        }
+
+        for child in element.children.iter_mut() {
+            self.collect_rules(child, rules);
+        }
+    }
+}
+
+/// Escape an id so that it can be used in a selector, the way `CSS.escape` does
+///
+/// Ids are stable ids of the document that the user may have changed, so they can start
+/// with a digit or contain characters like `.` and `:` that would change the selector
+fn escape_identifier(id: &str) -> String {
+    let mut escaped = String::with_capacity(id.len());
+    let code_point = |c: char| format!("\\{:x} ", c as u32);
+
+    for (i, c) in id.chars().enumerate() {
+        let leading_digit = c.is_ascii_digit() && (i == 0 || (i == 1 && id.starts_with('-')));
+
+        match c {
+            '\0' => escaped.push('\u{fffd}'),
+            '\u{1}'..='\u{1f}' | '\u{7f}' => escaped.push_str(&code_point(c)),
+            _ if leading_digit => escaped.push_str(&code_point(c)),
+            '-' if id == "-" => escaped.push_str("\\-"),
+            '-' | '_' => escaped.push(c),
+            _ if c.is_ascii_alphanumeric() || !c.is_ascii() => escaped.push(c),
+            _ => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+        }
+    }
+
+    escaped
+}
+
+/// Write rules one after the other
+///
+/// # Arguments
+/// * `indent` - number of spaces that the rules are indented with
+fn write_rules(rules: &[Rule], indent: usize) -> String {
+    let indent = " ".repeat(indent);
+    let mut css = String::new();
+
+    for (selector, declarations) in rules.iter() {
+        css.push_str(&format!("{}{} {{\n", indent, selector));
+        for (property, value) in declarations.iter() {
+            css.push_str(&format!("{}  {}: {};\n", indent, property, value));
+        }
+        css.push_str(&format!("{}}}\n", indent));
+    }
+
+    css
+}
+
+/// Css declarations of a single style
+fn declarations(style: &Style) -> Vec<(&'static str, String)> {
+    let px = |px: i64| format!("{}px", px);
+    let length = |length: &Length| match length {
+        Length::Px(value) => px(*value as i64),
+        Length::Full => "100%".into(),
+    };
+
+    match style {
+        Style::Position(Position::Relative) => vec![("position", "relative".into())],
+        Style::Position(Position::Absolute) => vec![("position", "absolute".into())],
+        Style::Left(value) => vec![("left", px(*value as i64))],
+        Style::Top(value) => vec![("top", px(*value as i64))],
+        Style::Width(value) => vec![("width", length(value))],
+        Style::Height(value) => vec![("height", length(value))],
+        Style::MarginXAuto => vec![
+            ("margin-left", "auto".into()),
+            ("margin-right", "auto".into()),
+        ],
+        Style::Display(display) => {
+            let display = match display {
+                Display::Block => "block",
+                Display::Flex => "flex",
+                Display::Grid => "grid",
+                Display::None => "none",
+            };
+            vec![("display", display.into())]
+        }
+        Style::FlexDirection(direction) => vec![("flex-direction", direction.css().into())],
+        Style::FlexWrap(wrap) => {
+            vec![("flex-wrap", if *wrap { "wrap" } else { "nowrap" }.into())]
+        }
+        Style::Justify(class) => vec![("justify-content", justify_content(class))],
+        Style::Align(class) => vec![("align-items", align_items(class))],
+        Style::Gap(value) => vec![("gap", px(*value as i64))],
+        Style::GridColumns(columns) => vec![(
+            "grid-template-columns",
+            format!("repeat({}, minmax(0, 1fr))", columns),
+        )],
+        Style::GridAutoRows(value) => vec![("grid-auto-rows", px(*value as i64))],
+        Style::GridArea { pos, size } => vec![
+            ("grid-column", format!("{} / span {}", pos.0, size.0)),
+            ("grid-row", format!("{} / span {}", pos.1, size.1)),
+        ],
+        Style::FlexGrow(grow) => vec![("flex-grow", grow.to_string())],
+        Style::FlexShrink(shrink) => vec![("flex-shrink", shrink.to_string())],
+        Style::FlexBasis(value) => vec![("flex-basis", px(*value as i64))],
+        Style::Order(order) => vec![("order", order.to_string())],
+    }
+}
+
+/// Value of `justify-content` for a Tailwind `justify-*` class
+fn justify_content(class: &str) -> String {
+    match class.trim_start_matches("justify-") {
+        "start" => "flex-start".into(),
+        "end" => "flex-end".into(),
+        "between" => "space-between".into(),
+        "around" => "space-around".into(),
+        "evenly" => "space-evenly".into(),
+        value => value.into(),
+    }
+}
+
+/// Value of `align-items` for a Tailwind `items-*` class
+fn align_items(class: &str) -> String {
+    match class.trim_start_matches("items-") {
+        "start" => "flex-start".into(),
+        "end" => "flex-end".into(),
+        value => value.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{export::style::ResponsiveStyle, page::metadata::PageMetadata};
+
+    #[test]
+    fn styles_are_written_as_responsive_rules() {
+        let json = r#"{
+            "name": "Home",
+            "width": 1280,
+            "breakpoints": [
+                { "name": "mobile", "width": 375 },
+                { "name": "desktop", "width": 1280 }
+            ],
+            "breakpoint": "desktop",
+            "layouts": [{
+                "id": "layout-1",
+                "name": "Flex",
+                "width": 1280,
+                "height": 120,
+                "kind": {
+                    "type": "flex",
+                    "justify": "justify-between",
+                    "align": "items-start",
+                    "direction": "column",
+                    "wrap": false,
+                    "gap": 12
+                },
+                "components": [{
+                    "id": "launch-button-1",
+                    "name": "Button",
+                    "tag_name": "launch-button",
+                    "attributes": {},
+                    "placement": { "type": "flex", "item": { "grow": 1, "shrink": 1, "basis": null, "order": 0 } },
+                    "breakpoints": { "mobile": { "type": "hidden" } }
+                }]
+            }]
+        }"#;
+        let page: PageDocument = serde_json::from_str(json).unwrap();
+
+        let mut stylesheet = Stylesheet::default();
+        let mut body = page_element(&page);
+        stylesheet.add_page(&page.name, &mut body, &page_screens(&page));
+
+        assert_eq!(body.classes, vec!["main-1"]);
+        assert!(body.children[0].classes.is_empty());
+        assert_eq!(
+            stylesheet.css,
+            "\n/* Home */\n\
+             .main-1 {\n  position: relative;\n  margin-left: auto;\n  margin-right: auto;\n  width: 375px;\n}\n\
+             #layout-1 {\n  width: 100%;\n  display: flex;\n  flex-direction: column;\n  flex-wrap: nowrap;\n  \
+             justify-content: space-between;\n  align-items: flex-start;\n  gap: 12px;\n  height: 120px;\n}\n\
+             #launch-button-1 {\n  flex-grow: 1;\n  display: none;\n}\n\
+             \n@media (min-width: 1280px) {\n  \
+               .main-1 {\n    width: 1280px;\n  }\n  \
+               #launch-button-1 {\n    display: block;\n  }\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn ids_are_escaped_in_selectors() {
+        let escaped = escape_identifier;
+
+        assert_eq!(escaped("launch-button-1"), "launch-button-1");
+        assert_eq!(escaped("1st"), "\\31 st");
+        assert_eq!(escaped("-2"), "-\\32 ");
+        assert_eq!(escaped("-"), "\\-");
+        assert_eq!(escaped("hero.main"), "hero\\.main");
+        assert_eq!(escaped("a:b"), "a\\:b");
+        assert_eq!(escaped("größe #1"), "größe\\ \\#1");
+        assert_eq!(escaped("tab\tend"), "tab\\9 end");
+
+        let mut body = Element::new("main");
+        for id in ["1st", "hero.main", "a:b"] {
+            let mut child = Element::new("div");
+            child.id = Some(id.into());
+            child.styles.push(ResponsiveStyle {
+                screen: 0,
+                style: Style::Order(1),
+            });
+            body.children.push(child);
+        }
+
+        let mut stylesheet = Stylesheet::default();
+        stylesheet.add_page("Home", &mut body, &[Breakpoint::new("mobile", 375)]);
+
+        assert_eq!(
+            stylesheet.css,
+            "\n/* Home */\n\
+             #\\31 st {\n  order: 1;\n}\n\
+             #hero\\.main {\n  order: 1;\n}\n\
+             #a\\:b {\n  order: 1;\n}\n"
+        );
+    }
+
+    #[test]
+    fn pages_link_the_generated_stylesheet() {
+        let page = PageDocument {
+            name: "Blog".into(),
+            width: 908,
+            breakpoints: Vec::new(),
+            breakpoint: None,
+            metadata: PageMetadata {
+                route: Some("/blog/first-post".into()),
+                ..PageMetadata::default()
+            },
+            layouts: Vec::new(),
+        };
+
+        let export = CssExporter.export(&Document::new(vec![page]));
+        let paths: Vec<_> = export.files.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(paths, vec!["blog/first-post.html", "styles.css"]);
+
+        assert!(export.files[0]
+            .contents
+            .contains("<main class=\"main-1\"></main>"));
+        assert!(export.files[0]
+            .contents
+            .contains("<link rel=\"stylesheet\" href=\"../styles.css\" />"));
+        assert!(export.files[1].contents.starts_with(RESET));
+        assert!(export.files[1]
+            .contents
+            .contains(".main-1 {\n  position: relative;"));
+    }
+}
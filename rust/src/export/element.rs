@@ -0,0 +1,120 @@
+use super::style::ResponsiveStyle;
+
+/// Html element of an exported page
+///
+/// Exports build a tree of these first, and then write it in the syntax of the target
+#[derive(Debug, Clone, PartialEq)]
+pub struct Element {
+    /// Tag name of the element
+    pub tag: String,
+    /// Stable id of the layout or component that the element was exported from
+    pub id: Option<String>,
+    /// Classes of the element
+    pub classes: Vec<String>,
+    /// Styles of the element, written as classes or as css rules by the export
+    pub styles: Vec<ResponsiveStyle>,
+    /// Other attributes, in the order they are written in
+    pub attributes: Vec<(String, String)>,
+    /// Text content, written before the children
+    pub text: Option<String>,
+    /// Child elements
+    pub children: Vec<Element>,
+}
+
+impl Element {
+    /// Create a new element without any attributes or children
+    pub fn new(tag: &str) -> Self {
+        Self {
+            tag: tag.into(),
+            id: None,
+            classes: Vec::new(),
+            styles: Vec::new(),
+            attributes: Vec::new(),
+            text: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Whether the element is the custom element of a component, rather than a plain html one
+    ///
+    /// Names of custom elements always contain a dash
+    pub fn is_custom(&self) -> bool {
+        self.tag.contains('-')
+    }
+
+    /// Write the element as html
+    ///
+    /// # Arguments
+    /// * `indent` - number of spaces that the element is indented with
+    pub fn to_html(&self, indent: usize) -> String {
+        let mut html = format!("{}<{}", " ".repeat(indent), self.tag);
+
+        if let Some(id) = &self.id {
+            html.push_str(&format!(" id=\"{}\"", escape(id)));
+        }
+        if !self.classes.is_empty() {
+            html.push_str(&format!(" class=\"{}\"", escape(&self.classes.join(" "))));
+        }
+        for (name, value) in self.attributes.iter() {
+            html.push_str(&format!(" {}=\"{}\"", name, escape(value)));
+        }
+        html.push('>');
+
+        if let Some(text) = &self.text {
+            html.push_str(&escape(text));
+        }
+
+        if !self.children.is_empty() {
+            html.push('\n');
+            for child in self.children.iter() {
+                html.push_str(&child.to_html(indent + 2));
+                html.push('\n');
+            }
+            html.push_str(&" ".repeat(indent));
+        }
+
+        html.push_str(&format!("</{}>", self.tag));
+        html
+    }
+}
+
+/// Escape the text of an html element or attribute
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elements_are_written_as_indented_html() {
+        let mut button = Element::new("launch-button");
+        button.id = Some("launch-button-1".into());
+        button.attributes.push(("variant".into(), "primary".into()));
+        button.text = Some("Save & <exit>".into());
+
+        let mut layout = Element::new("div");
+        layout.classes = vec!["flex".into(), "gap-4".into()];
+        layout.children.push(button);
+
+        assert_eq!(
+            layout.to_html(2),
+            "  <div class=\"flex gap-4\">\n    \
+             <launch-button id=\"launch-button-1\" variant=\"primary\">Save &amp; &lt;exit&gt;</launch-button>\n  \
+             </div>"
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::BTreeSet;
+
+use crate::document::PageDocument;
+
+use super::{element::Element, quote};
+
+/// Module that framework exports import the components from, unless a different one is given
+///
+/// Relative to the root of the export, it is expected to export a framework component
+/// for each of the registered custom elements (`LaunchButton` for `launch-button`)
+pub const DEFAULT_LIBRARY: &str = "./components";
+
+/// Value of a component parameter, as it is passed to a prop
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PropValue<'a> {
+    Bool(bool),
+    Text(&'a str),
+}
+
+impl<'a> PropValue<'a> {
+    /// Parameters are stored as attribute strings, `true` and `false` are passed as booleans
+    pub fn parse(value: &'a str) -> Self {
+        match value {
+            "true" => Self::Bool(true),
+            "false" => Self::Bool(false),
+            _ => Self::Text(value),
+        }
+    }
+}
+
+/// Name of a framework component in PascalCase
+///
+/// Used for both the pages (`About us` -> `AboutUs`) and the components (`launch-button` -> `LaunchButton`),
+/// names that do not start with a letter are prefixed with `Page`
+pub fn component_name(name: &str) -> String {
+    let name: String = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().map(|c| c.to_ascii_uppercase());
+
+            first.into_iter().chain(chars)
+        })
+        .collect();
+
+    match name.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() => name,
+        _ => format!("Page{}", name),
+    }
+}
+
+/// Collect the names of the components used in an element tree
+fn used_components(element: &Element, components: &mut BTreeSet<String>) {
+    if element.is_custom() {
+        components.insert(component_name(&element.tag));
+    }
+
+    for child in element.children.iter() {
+        used_components(child, components);
+    }
+}
+
+/// Statement that imports the components used by a page, `None` if it does not use any
+///
+/// # Arguments
+/// * `library` - module to import from, a relative one is resolved from the root of the export
+/// * `path` - path of the page file
+pub fn import_statement(body: &Element, library: &str, path: &str) -> Option<String> {
+    let mut components = BTreeSet::new();
+    used_components(body, &mut components);
+
+    if components.is_empty() {
+        return None;
+    }
+
+    let depth = path.matches('/').count();
+    let library = match library.strip_prefix("./") {
+        Some(module) if depth > 0 => format!("{}{}", "../".repeat(depth), module),
+        _ => library.to_owned(),
+    };
+
+    let components: Vec<String> = components.into_iter().collect();
+    Some(format!(
+        "import {{ {} }} from {};",
+        components.join(", "),
+        quote(&library)
+    ))
+}
+
+/// Export of the route, title and description of a page, for the router of the framework
+pub fn meta(page: &PageDocument) -> String {
+    format!(
+        "export const meta = {{\n  route: {},\n  title: {},\n  description: {},\n}};",
+        quote(&page.metadata.route(&page.name)),
+        quote(page.metadata.title(&page.name)),
+        quote(&page.metadata.description)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_converted_to_pascal_case() {
+        assert_eq!(component_name("launch-button"), "LaunchButton");
+        assert_eq!(component_name("About us"), "AboutUs");
+        assert_eq!(component_name("my_page 2"), "MyPage2");
+        assert_eq!(component_name("404"), "Page404");
+        assert_eq!(component_name(""), "Page");
+    }
+
+    #[test]
+    fn relative_libraries_are_resolved_from_the_page() {
+        let mut body = Element::new("main");
+        body.children.push(Element::new("launch-text"));
+        body.children.push(Element::new("launch-button"));
+        body.children.push(Element::new("launch-text"));
+
+        assert_eq!(
+            import_statement(&body, DEFAULT_LIBRARY, "index.jsx").unwrap(),
+            "import { LaunchButton, LaunchText } from \"./components\";"
+        );
+        assert_eq!(
+            import_statement(&body, DEFAULT_LIBRARY, "blog/first-post.jsx").unwrap(),
+            "import { LaunchButton, LaunchText } from \"../components\";"
+        );
+        assert_eq!(
+            import_statement(&body, "@launch/ui", "blog/first-post.jsx").unwrap(),
+            "import { LaunchButton, LaunchText } from \"@launch/ui\";"
+        );
+        assert!(import_statement(&Element::new("main"), DEFAULT_LIBRARY, "index.jsx").is_none());
+    }
+}
@@ -0,0 +1,104 @@
+use crate::document::PageDocument;
+
+use super::{element::escape, Element, Exporter};
+
+/// Stylesheet that the exported pages link to, built by Tailwind or generated by the css export
+pub const STYLESHEET: &str = "styles.css";
+
+/// Exports every page as a static html file with Tailwind classes
+///
+/// The stylesheet is built from the generated `tailwind.config.js` (`npx tailwindcss -o styles.css`)
+pub struct HtmlExporter;
+
+impl Exporter for HtmlExporter {
+    fn name(&self) -> &str {
+        "html"
+    }
+
+    fn extension(&self) -> &str {
+        "html"
+    }
+
+    fn page_file(&self, page: &PageDocument, body: &Element, path: &str) -> String {
+        html_document(page, body, path)
+    }
+}
+
+/// Write a whole html document of a page
+///
+/// # Arguments
+/// * `path` - path of the page file, the stylesheet is linked relative to it
+pub fn html_document(page: &PageDocument, body: &Element, path: &str) -> String {
+    let root = "../".repeat(path.matches('/').count());
+
+    let mut head = vec![
+        "<meta charset=\"utf-8\" />".to_owned(),
+        "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\" />".to_owned(),
+        format!("<title>{}</title>", escape(page.metadata.title(&page.name))),
+    ];
+    if !page.metadata.description.is_empty() {
+        head.push(format!(
+            "<meta name=\"description\" content=\"{}\" />",
+            escape(&page.metadata.description)
+        ));
+    }
+    head.push(format!(
+        "<link rel=\"stylesheet\" href=\"{}{}\" />",
+        root, STYLESHEET
+    ));
+
+    let head: String = head.iter().map(|line| format!("    {}\n", line)).collect();
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n  <head>\n{}  </head>\n  <body>\n{}\n  </body>\n</html>\n",
+        head,
+        body.to_html(4)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{document::Document, page::metadata::PageMetadata};
+
+    fn page(name: &str, metadata: PageMetadata) -> PageDocument {
+        PageDocument {
+            name: name.into(),
+            width: 908,
+            breakpoints: Vec::new(),
+            breakpoint: None,
+            metadata,
+            layouts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn pages_are_exported_at_their_routes() {
+        let about = PageMetadata {
+            route: Some("/company/about".into()),
+            title: Some("About <us>".into()),
+            description: "Who we are".into(),
+        };
+        let document = Document::new(vec![
+            page("Home", PageMetadata::default()),
+            page("About", about),
+        ]);
+
+        let export = HtmlExporter.export(&document);
+        let paths: Vec<_> = export.files.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["index.html", "company/about.html", "tailwind.config.js"]
+        );
+
+        let about = &export.files[1].contents;
+        assert!(about.contains("<title>About &lt;us&gt;</title>"));
+        assert!(about.contains("<meta name=\"description\" content=\"Who we are\" />"));
+        assert!(about.contains("<link rel=\"stylesheet\" href=\"../styles.css\" />"));
+        assert!(about.contains("    <main class=\"relative mx-auto w-227\"></main>\n"));
+
+        let home = &export.files[0].contents;
+        assert!(home.contains("<title>Home</title>"));
+        assert!(!home.contains("description"));
+    }
+}
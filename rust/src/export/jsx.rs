@@ -0,0 +1,236 @@
+use crate::document::PageDocument;
+
+use super::{
+    element::Element,
+    framework::{component_name, import_statement, meta, PropValue},
+    quote, Exporter,
+};
+
+/// Exports every page as a React function component
+pub struct ReactExporter {
+    /// Module that the components are imported from
+    library: String,
+}
+
+impl ReactExporter {
+    pub fn new(library: &str) -> Self {
+        Self {
+            library: library.into(),
+        }
+    }
+}
+
+impl Exporter for ReactExporter {
+    fn name(&self) -> &str {
+        "react"
+    }
+
+    fn extension(&self) -> &str {
+        "jsx"
+    }
+
+    fn page_file(&self, page: &PageDocument, body: &Element, path: &str) -> String {
+        jsx_module(page, body, path, &self.library, "className")
+    }
+}
+
+/// Exports every page as a Solid function component
+///
+/// Unlike React, Solid passes the classes in the plain `class` attribute
+pub struct SolidExporter {
+    /// Module that the components are imported from
+    library: String,
+}
+
+impl SolidExporter {
+    pub fn new(library: &str) -> Self {
+        Self {
+            library: library.into(),
+        }
+    }
+}
+
+impl Exporter for SolidExporter {
+    fn name(&self) -> &str {
+        "solid"
+    }
+
+    fn extension(&self) -> &str {
+        "jsx"
+    }
+
+    fn page_file(&self, page: &PageDocument, body: &Element, path: &str) -> String {
+        jsx_module(page, body, path, &self.library, "class")
+    }
+}
+
+/// Write a module with the page as its default export
+///
+/// # Arguments
+/// * `class` - name of the attribute that the classes are passed in
+fn jsx_module(
+    page: &PageDocument,
+    body: &Element,
+    path: &str,
+    library: &str,
+    class: &str,
+) -> String {
+    let mut module = String::new();
+
+    if let Some(import) = import_statement(body, library, path) {
+        module.push_str(&import);
+        module.push_str("\n\n");
+    }
+
+    format!(
+        "{}{}\n\nexport default function {}() {{\n  return (\n{}\n  );\n}}\n",
+        module,
+        meta(page),
+        component_name(&page.name),
+        to_jsx(body, 4, class)
+    )
+}
+
+/// Write an element tree as JSX
+///
+/// Custom elements are written as the components of the library, with their parameters as props
+///
+/// # Arguments
+/// * `indent` - number of spaces that the element is indented with
+/// * `class` - name of the attribute that the classes are passed in
+fn to_jsx(element: &Element, indent: usize, class: &str) -> String {
+    let tag = if element.is_custom() {
+        component_name(&element.tag)
+    } else {
+        element.tag.clone()
+    };
+    let mut jsx = format!("{}<{}", " ".repeat(indent), tag);
+
+    if let Some(id) = &element.id {
+        jsx.push_str(&format!(" id={}", attribute(id)));
+    }
+    if !element.classes.is_empty() {
+        jsx.push_str(&format!(
+            " {}={}",
+            class,
+            attribute(&element.classes.join(" "))
+        ));
+    }
+    for (name, value) in element.attributes.iter() {
+        match PropValue::parse(value) {
+            PropValue::Bool(true) => jsx.push_str(&format!(" {}", name)),
+            PropValue::Bool(false) => jsx.push_str(&format!(" {}={{false}}", name)),
+            PropValue::Text(value) => jsx.push_str(&format!(" {}={}", name, attribute(value))),
+        }
+    }
+
+    if element.text.is_none() && element.children.is_empty() {
+        jsx.push_str(" />");
+        return jsx;
+    }
+    jsx.push('>');
+
+    if let Some(text) = &element.text {
+        jsx.push_str(&text_content(text));
+    }
+
+    if !element.children.is_empty() {
+        jsx.push('\n');
+        for child in element.children.iter() {
+            jsx.push_str(&to_jsx(child, indent + 2, class));
+            jsx.push('\n');
+        }
+        jsx.push_str(&" ".repeat(indent));
+    }
+
+    jsx.push_str(&format!("</{}>", tag));
+    jsx
+}
+
+/// Value of a JSX attribute
+///
+/// JSX strings can not escape quotes and decode html entities, such values are passed as js strings
+fn attribute(value: &str) -> String {
+    if value.contains(['"', '&']) {
+        format!("{{{}}}", quote(value))
+    } else {
+        format!("\"{}\"", value)
+    }
+}
+
+/// Text content of a JSX element
+///
+/// Text with characters that JSX would interpret, or with whitespace that it would trim, is written as a js string
+fn text_content(text: &str) -> String {
+    let special = text.contains(['{', '}', '<', '>', '&', '\n']);
+
+    if special || text.trim() != text {
+        format!("{{{}}}", quote(text))
+    } else {
+        text.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::page::metadata::PageMetadata;
+
+    fn body() -> Element {
+        let mut button = Element::new("launch-button");
+        button.id = Some("launch-button-1".into());
+        button.classes = vec!["grow".into()];
+        button.attributes = vec![
+            ("disabled".into(), "false".into()),
+            ("outlined".into(), "true".into()),
+            ("variant".into(), "say \"hi\"".into()),
+        ];
+        button.text = Some("Save".into());
+
+        let mut text = Element::new("launch-text");
+        text.text = Some("{ price } & more".into());
+
+        let mut layout = Element::new("section");
+        layout.classes = vec!["flex".into(), "gap-4".into()];
+        layout.children = vec![button, text, Element::new("launch-divider")];
+
+        let mut main = Element::new("main");
+        main.children.push(layout);
+        main
+    }
+
+    #[test]
+    fn components_are_written_with_props() {
+        assert_eq!(
+            to_jsx(&body(), 0, "className"),
+            "<main>\n  \
+               <section className=\"flex gap-4\">\n    \
+                 <LaunchButton id=\"launch-button-1\" className=\"grow\" disabled={false} outlined variant={\"say \\\"hi\\\"\"}>Save</LaunchButton>\n    \
+                 <LaunchText>{\"{ price } & more\"}</LaunchText>\n    \
+                 <LaunchDivider />\n  \
+               </section>\n\
+             </main>"
+        );
+    }
+
+    #[test]
+    fn pages_are_default_exports() {
+        let page = PageDocument {
+            name: "About us".into(),
+            width: 908,
+            breakpoints: Vec::new(),
+            breakpoint: None,
+            metadata: PageMetadata::default(),
+            layouts: Vec::new(),
+        };
+
+        let solid = SolidExporter::new("@launch/ui").page_file(&page, &body(), "about-us.jsx");
+        assert!(solid.starts_with(
+            "import { LaunchButton, LaunchDivider, LaunchText } from \"@launch/ui\";\n\n\
+             export const meta = {\n  route: \"/about-us\",\n  title: \"About us\",\n  description: \"\",\n};\n\n\
+             export default function AboutUs() {\n  return (\n    <main>\n"
+        ));
+        assert!(solid.contains("<section class=\"flex gap-4\">"));
+        assert!(solid.ends_with("    </main>\n  );\n}\n"));
+    }
+}
@@ -0,0 +1,214 @@
+use serde::Serialize;
+
+use crate::{
+    document::{Document, PageDocument},
+    page::metadata::normalize_route,
+};
+
+mod css;
+mod element;
+mod framework;
+mod html;
+mod jsx;
+mod page;
+pub mod style;
+mod tailwind;
+mod vue;
+
+pub use css::CssExporter;
+pub use element::Element;
+pub use framework::DEFAULT_LIBRARY;
+pub use html::HtmlExporter;
+pub use jsx::{ReactExporter, SolidExporter};
+pub use vue::VueExporter;
+
+use page::{page_element, page_screens};
+use tailwind::TailwindConfig;
+
+/// Target of an export, writes every page of a document into its own file
+///
+/// All targets share the element tree of a page, styled with Tailwind classes unless `export` is overridden,
+/// they only differ in the syntax that the tree is written in
+pub trait Exporter {
+    /// Name of the target, as it is selected in the editor (`html`, `react`, ...)
+    fn name(&self) -> &str;
+
+    /// Extension of the page files, without the dot
+    fn extension(&self) -> &str;
+
+    /// Write the file of a single page
+    ///
+    /// # Arguments
+    /// * `page` - snapshot of the page, for its name and metadata
+    /// * `body` - element tree of the page
+    /// * `path` - path of the page file, relative to the root of the export
+    fn page_file(&self, page: &PageDocument, body: &Element, path: &str) -> String;
+
+    /// Export every page of the document at its route,
+    /// together with the `tailwind.config.js` that the classes of the pages need
+    fn export(&self, document: &Document) -> Export {
+        let mut config = TailwindConfig::default();
+        let mut export = Export::default();
+
+        let paths = page_paths(document, self.extension());
+        for (page, path) in document.pages.iter().zip(paths) {
+            let mut body = page_element(page);
+            config.apply(&mut body, &page_screens(page));
+
+            export.add_file(&path, self.page_file(page, &body, &path));
+        }
+
+        let content = format!("./**/*.{}", self.extension());
+        export.add_file("tailwind.config.js", config.to_js(&content));
+        export
+    }
+}
+
+/// Find an exporter by the name of its target
+///
+/// # Arguments
+/// * `library` - module that framework targets import the components from, see `DEFAULT_LIBRARY`
+pub fn exporter(target: &str, library: &str) -> Option<Box<dyn Exporter>> {
+    let exporters: Vec<Box<dyn Exporter>> = vec![
+        Box::new(HtmlExporter),
+        Box::new(CssExporter),
+        Box::new(ReactExporter::new(library)),
+        Box::new(VueExporter::new(library)),
+        Box::new(SolidExporter::new(library)),
+    ];
+
+    exporters
+        .into_iter()
+        .find(|exporter| exporter.name() == target)
+}
+
+/// A single file generated by an export
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExportFile {
+    /// Path of the file, relative to the root of the exported project
+    pub path: String,
+    /// Content of the file
+    pub contents: String,
+}
+
+/// Files of an exported project
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Export {
+    pub files: Vec<ExportFile>,
+}
+
+impl Export {
+    /// Add a file to the export
+    pub fn add_file(&mut self, path: &str, contents: String) {
+        self.files.push(ExportFile {
+            path: path.into(),
+            contents,
+        });
+    }
+
+    /// Serialize the export into json, `{ "files": [{ "path": "...", "contents": "..." }] }`
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+/// Path of the file that a page served at a route is exported to
+///
+/// The root route is exported to `index`, other routes to their path,
+/// for example `/blog/first-post` to `blog/first-post.html`
+///
+/// # Arguments
+/// * `extension` - extension of the file, without the dot
+pub fn page_path(route: &str, extension: &str) -> String {
+    // Routes of loaded documents were never normalized, they could point outside of the export
+    let route = normalize_route(route);
+    let path = route.trim_matches('/');
+
+    match path {
+        "" => format!("index.{}", extension),
+        _ => format!("{}.{}", path, extension),
+    }
+}
+
+/// Paths of the files that the pages of a document are exported to, in the order of the pages
+///
+/// The editor does not let pages share a route, but older documents may still do,
+/// so the later ones get a numbered path instead of overwriting the first one
+pub fn page_paths(document: &Document, extension: &str) -> Vec<String> {
+    let mut paths: Vec<String> = Vec::with_capacity(document.pages.len());
+
+    for page in document.pages.iter() {
+        let path = page_path(&page.metadata.route(&page.name), extension);
+        let stem = path.trim_end_matches(&format!(".{}", extension)).to_owned();
+
+        let path = std::iter::once(path)
+            .chain((2..).map(|n| format!("{}-{}.{}", stem, n, extension)))
+            .find(|path| !paths.contains(path))
+            .unwrap();
+        paths.push(path);
+    }
+
+    paths
+}
+
+/// Quote a string as a js string literal
+fn quote(value: &str) -> String {
+    // A json string is a valid js string
+    serde_json::to_string(value).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_are_exported_to_files() {
+        assert_eq!(page_path("/", "html"), "index.html");
+        assert_eq!(page_path("/about-us", "html"), "about-us.html");
+        assert_eq!(page_path("/blog/first-post", "jsx"), "blog/first-post.jsx");
+        assert_eq!(page_path("/../../x", "html"), "x.html");
+        assert_eq!(page_path("/blog/./..", "html"), "blog.html");
+    }
+
+    #[test]
+    fn pages_sharing_a_route_get_separate_files() {
+        let page = |name: &str| PageDocument {
+            name: name.into(),
+            width: 908,
+            breakpoints: Vec::new(),
+            breakpoint: None,
+            metadata: Default::default(),
+            layouts: Vec::new(),
+        };
+        let document = Document::new(vec![
+            page("Home"),
+            page("home"),
+            page("About Us"),
+            page("about-us"),
+            page("HOME"),
+        ]);
+
+        assert_eq!(
+            page_paths(&document, "html"),
+            vec![
+                "index.html",
+                "index-2.html",
+                "about-us.html",
+                "about-us-2.html",
+                "index-3.html"
+            ]
+        );
+
+        let export = HtmlExporter.export(&document);
+        let paths: Vec<_> = export.files.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(&paths[..2], ["index.html", "index-2.html"]);
+    }
+
+    #[test]
+    fn exporters_are_found_by_their_target() {
+        for target in ["html", "css", "react", "vue", "solid"].iter() {
+            assert_eq!(exporter(target, DEFAULT_LIBRARY).unwrap().name(), *target);
+        }
+        assert!(exporter("angular", DEFAULT_LIBRARY).is_none());
+    }
+}
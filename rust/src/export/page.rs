@@ -0,0 +1,537 @@
+use crate::{
+    component::TEXT_PARAMETER,
+    document::{
+        ComponentDocument, LayoutDocument, LayoutKindDocument, PageDocument, PlacementDocument,
+    },
+    page::{
+        breakpoint::{Breakpoint, BreakpointPlacement, DEFAULT_BREAKPOINT},
+        layout::ChildPlacement,
+    },
+};
+
+use super::{
+    element::Element,
+    style::{Display, Length, Position, ResponsiveStyle, Style},
+};
+
+/// Breakpoints of a page sorted by width, the ones that styles of its elements refer to
+pub fn page_screens(page: &PageDocument) -> Vec<Breakpoint> {
+    let mut breakpoints = page.breakpoints.clone();
+    breakpoints.sort_by_key(|breakpoint| breakpoint.width);
+
+    // Documents written before breakpoints existed
+    if breakpoints.is_empty() {
+        breakpoints.push(Breakpoint::new(DEFAULT_BREAKPOINT, page.width));
+    }
+
+    breakpoints
+}
+
+/// Breakpoints of an exported page
+///
+/// Styles are mobile first, the ones of the smallest breakpoint apply at every width,
+/// larger breakpoints override them (`tablet:col-span-4` or a `min-width` media query)
+struct Screens {
+    /// Breakpoints sorted by width
+    breakpoints: Vec<Breakpoint>,
+    /// Index of the breakpoint that the page was saved at
+    active: usize,
+}
+
+impl Screens {
+    fn new(page: &PageDocument) -> Self {
+        let breakpoints = page_screens(page);
+
+        let active = page
+            .breakpoint
+            .as_ref()
+            .and_then(|name| breakpoints.iter().position(|b| &b.name == name))
+            .or_else(|| breakpoints.iter().position(|b| b.width == page.width))
+            .unwrap_or(breakpoints.len() - 1);
+
+        Self {
+            breakpoints,
+            active,
+        }
+    }
+
+    /// Value of a property at every breakpoint
+    ///
+    /// # Arguments
+    /// * `stored` - value stored for a breakpoint
+    /// * `current` - value the page was saved with, used at the active breakpoint if nothing was stored for it
+    fn values<T: Clone>(
+        &self,
+        stored: impl Fn(&str) -> Option<T>,
+        current: Option<T>,
+    ) -> Vec<Option<T>> {
+        self.breakpoints
+            .iter()
+            .enumerate()
+            .map(|(index, breakpoint)| {
+                stored(&breakpoint.name).or_else(|| {
+                    if index == self.active {
+                        current.clone()
+                    } else {
+                        None
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Styles of a property that can differ between breakpoints
+    ///
+    /// Breakpoints without a value keep the one of the smaller breakpoint,
+    /// so styles are only added when the value changes
+    ///
+    /// # Arguments
+    /// * `values` - value at every breakpoint, see `Screens::values`
+    /// * `initial` - value that does not need any styles
+    /// * `styles` - styles of a value
+    fn styles<T: PartialEq>(
+        &self,
+        values: Vec<Option<T>>,
+        initial: Option<T>,
+        styles: impl Fn(&T) -> Vec<Style>,
+    ) -> Vec<ResponsiveStyle> {
+        let mut current = initial;
+        let mut out = Vec::new();
+
+        for (screen, value) in values.into_iter().enumerate() {
+            let value = match value {
+                Some(value) if current.as_ref() != Some(&value) => value,
+                _ => continue,
+            };
+
+            out.extend(
+                styles(&value)
+                    .into_iter()
+                    .map(|style| ResponsiveStyle { screen, style }),
+            );
+            current = Some(value);
+        }
+
+        out
+    }
+}
+
+/// Style that applies at every breakpoint
+fn base(style: Style) -> ResponsiveStyle {
+    ResponsiveStyle { screen: 0, style }
+}
+
+/// Build the element tree of a page, styled for every breakpoint of `page_screens`
+pub fn page_element(page: &PageDocument) -> Element {
+    let screens = Screens::new(page);
+
+    let mut element = Element::new("main");
+    element.styles = vec![
+        base(Style::Position(Position::Relative)),
+        base(Style::MarginXAuto),
+    ];
+
+    // The page is as wide as the breakpoint it is shown at
+    let widths = screens.values(
+        |name| {
+            screens
+                .breakpoints
+                .iter()
+                .find(|breakpoint| breakpoint.name == name)
+                .map(|breakpoint| breakpoint.width)
+        },
+        None,
+    );
+    element.styles.extend(screens.styles(widths, None, |width| {
+        vec![Style::Width(Length::Px(*width as i32))]
+    }));
+
+    element.children = page
+        .layouts
+        .iter()
+        .map(|layout| layout_element(layout, None, &screens))
+        .collect();
+
+    element
+}
+
+/// Build the element of a layout, together with its components and nested layouts
+///
+/// # Arguments
+/// * `parent` - kind of the parent layout, `None` for layouts placed directly in the page
+fn layout_element(
+    layout: &LayoutDocument,
+    parent: Option<&LayoutKindDocument>,
+    screens: &Screens,
+) -> Element {
+    let mut element = Element::new(if parent.is_some() { "div" } else { "section" });
+    element.id = layout.id.clone();
+
+    let mut styles = Vec::new();
+
+    // Placement inside of the parent
+    match parent {
+        None => styles.push(base(Style::Width(Length::Full))),
+        Some(LayoutKindDocument::Grid { .. }) => {
+            let blocks = screens.values(
+                |name| match layout.breakpoints.get(name) {
+                    Some(ChildPlacement::Grid { pos, size }) => Some((*pos, *size)),
+                    _ => None,
+                },
+                match layout.placement {
+                    Some(ChildPlacement::Grid { pos, size }) => Some((pos, size)),
+                    _ => None,
+                },
+            );
+            styles.extend(screens.styles(blocks, None, |(pos, size)| {
+                vec![Style::GridArea {
+                    pos: *pos,
+                    size: *size,
+                }]
+            }));
+        }
+        Some(LayoutKindDocument::Free { .. }) => {
+            let position = match layout.placement {
+                Some(ChildPlacement::Free { position }) => position,
+                _ => (0, 0),
+            };
+
+            styles.push(base(Style::Position(Position::Absolute)));
+            styles.push(base(Style::Left(position.0)));
+            styles.push(base(Style::Top(position.1)));
+            styles.push(base(Style::Width(Length::Px(layout.width as i32))));
+        }
+        Some(LayoutKindDocument::Flex { .. }) => {
+            styles.push(base(Style::Width(Length::Px(layout.width as i32))));
+        }
+    }
+
+    // Grid layouts are as high as their rows, items of grids fill their cells
+    let fixed_height = !matches!(layout.kind, LayoutKindDocument::Grid { .. })
+        && !matches!(parent, Some(LayoutKindDocument::Grid { .. }));
+
+    match &layout.kind {
+        LayoutKindDocument::Free { .. } => {
+            // Absolutely positioned layouts are positioned already
+            if !matches!(parent, Some(LayoutKindDocument::Free { .. })) {
+                styles.push(base(Style::Position(Position::Relative)));
+            }
+        }
+        LayoutKindDocument::Flex {
+            justify,
+            align,
+            direction,
+            wrap,
+            gap,
+        } => {
+            styles.push(base(Style::Display(Display::Flex)));
+            styles.push(base(Style::FlexDirection(*direction)));
+            styles.push(base(Style::FlexWrap(*wrap)));
+            styles.push(base(Style::Justify(justify.clone())));
+            styles.push(base(Style::Align(align.clone())));
+
+            if *gap > 0 {
+                styles.push(base(Style::Gap(*gap)));
+            }
+        }
+        LayoutKindDocument::Grid {
+            column_width,
+            row_height,
+            gap,
+            columns,
+            breakpoint_columns,
+            ..
+        } => {
+            styles.push(base(Style::Display(Display::Grid)));
+
+            let columns =
+                screens.values(|name| breakpoint_columns.get(name).copied(), Some(*columns));
+            styles.extend(
+                screens.styles(columns, None, |columns| vec![Style::GridColumns(*columns)]),
+            );
+
+            // Documents written before rows had their own height use square cells
+            styles.push(base(Style::GridAutoRows(
+                row_height.unwrap_or(*column_width),
+            )));
+
+            if *gap > 0 {
+                styles.push(base(Style::Gap(*gap)));
+            }
+        }
+    }
+
+    if fixed_height {
+        styles.push(base(Style::Height(Length::Px(layout.height as i32))));
+    }
+
+    element.styles = styles;
+
+    for component in layout.components.iter() {
+        let child = component_element(component, &layout.kind, screens);
+        element.children.push(child);
+    }
+
+    for child in layout.children.iter() {
+        let child = layout_element(child, Some(&layout.kind), screens);
+        element.children.push(child);
+    }
+
+    element
+}
+
+/// Build the element of a component
+///
+/// Descriptor parameters are written as attributes, except for the text content
+///
+/// # Arguments
+/// * `kind` - kind of the layout that the component is placed in
+fn component_element(
+    component: &ComponentDocument,
+    kind: &LayoutKindDocument,
+    screens: &Screens,
+) -> Element {
+    let mut element = Element::new(&component.tag_name);
+    element.id = component.id.clone();
+
+    let mut styles = Vec::new();
+
+    match (&component.placement, kind) {
+        (PlacementDocument::Grid { pos, size }, LayoutKindDocument::Grid { .. }) => {
+            styles.push(base(Style::Width(Length::Full)));
+            styles.push(base(Style::Height(Length::Full)));
+
+            let blocks = screens.values(
+                |name| match component.breakpoints.get(name) {
+                    Some(BreakpointPlacement::Grid { pos, size }) => Some((*pos, *size)),
+                    _ => None,
+                },
+                Some((*pos, *size)),
+            );
+            styles.extend(screens.styles(blocks, None, |(pos, size)| {
+                vec![Style::GridArea {
+                    pos: *pos,
+                    size: *size,
+                }]
+            }));
+        }
+        (PlacementDocument::Free { position, size }, LayoutKindDocument::Free { .. }) => {
+            styles.push(base(Style::Position(Position::Absolute)));
+            styles.push(base(Style::Left(position.0)));
+            styles.push(base(Style::Top(position.1)));
+
+            if let Some((width, height)) = size {
+                styles.push(base(Style::Width(Length::Px(width.round() as i32))));
+                styles.push(base(Style::Height(Length::Px(height.round() as i32))));
+            }
+        }
+        (PlacementDocument::Flex { item }, LayoutKindDocument::Flex { .. }) => {
+            // Only the values that differ from the css defaults
+            if item.grow != 0 {
+                styles.push(base(Style::FlexGrow(item.grow)));
+            }
+            if item.shrink != 1 {
+                styles.push(base(Style::FlexShrink(item.shrink)));
+            }
+            if let Some(basis) = item.basis {
+                styles.push(base(Style::FlexBasis(basis)));
+            }
+            if item.order != 0 {
+                styles.push(base(Style::Order(item.order)));
+            }
+        }
+        // Documents written by the editor always match the kind of the layout
+        _ => {}
+    }
+
+    // Components are shown at the breakpoints they were never hidden at
+    let hidden = screens.values(
+        |name| Some(component.breakpoints.get(name) == Some(&BreakpointPlacement::Hidden)),
+        None,
+    );
+    styles.extend(screens.styles(hidden, Some(false), |hidden| {
+        vec![Style::Display(if *hidden {
+            Display::None
+        } else {
+            Display::Block
+        })]
+    }));
+
+    element.styles = styles;
+
+    for (name, value) in component.attributes.iter() {
+        if value.is_empty() {
+            continue;
+        }
+
+        if name == TEXT_PARAMETER {
+            element.text = Some(value.clone());
+        } else {
+            element.attributes.push((name.clone(), value.clone()));
+        }
+    }
+
+    element
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::{document::Document, export::tailwind::TailwindConfig};
+
+    /// Build the element tree of a page with Tailwind classes
+    fn tailwind_element(page: &PageDocument) -> Element {
+        let mut config = TailwindConfig::default();
+        let mut element = page_element(page);
+        config.apply(&mut element, &page_screens(page));
+
+        element
+    }
+
+    fn grid_page() -> PageDocument {
+        let json = r#"{
+            "version": 1,
+            "pages": [{
+                "name": "Home",
+                "width": 1280,
+                "breakpoints": [
+                    { "name": "desktop", "width": 1280 },
+                    { "name": "mobile", "width": 375 }
+                ],
+                "breakpoint": "desktop",
+                "layouts": [{
+                    "id": "layout-1",
+                    "name": "Grid",
+                    "width": 1280,
+                    "height": 160,
+                    "kind": {
+                        "type": "grid",
+                        "column_width": 100,
+                        "row_height": 76,
+                        "gap": 8,
+                        "columns": 12,
+                        "rows": 2,
+                        "breakpoint_columns": { "desktop": 12, "mobile": 4 }
+                    },
+                    "components": [{
+                        "id": "launch-button-1",
+                        "name": "Button",
+                        "tag_name": "launch-button",
+                        "attributes": { "innertext": "Save", "variant": "primary", "size": "" },
+                        "placement": { "type": "grid", "pos": [7, 1], "size": [6, 1] },
+                        "breakpoints": {
+                            "desktop": { "type": "grid", "pos": [7, 1], "size": [6, 1] },
+                            "mobile": { "type": "grid", "pos": [1, 2], "size": [4, 1] }
+                        }
+                    }, {
+                        "id": "launch-text-1",
+                        "name": "Text",
+                        "tag_name": "launch-text",
+                        "attributes": {},
+                        "placement": { "type": "grid", "pos": [1, 1], "size": [2, 1] },
+                        "breakpoints": { "mobile": { "type": "hidden" } }
+                    }]
+                }]
+            }]
+        }"#;
+
+        Document::from_json(json).unwrap().pages.remove(0)
+    }
+
+    #[test]
+    fn grid_pages_are_exported_mobile_first() {
+        let page = tailwind_element(&grid_page());
+
+        assert_eq!(
+            page.classes,
+            vec!["relative", "mx-auto", "w-93.75", "desktop:w-320"]
+        );
+
+        let layout = &page.children[0];
+        assert_eq!(layout.tag, "section");
+        assert_eq!(layout.id.as_deref(), Some("layout-1"));
+        assert_eq!(
+            layout.classes,
+            vec![
+                "w-full",
+                "grid",
+                "grid-cols-4",
+                "desktop:grid-cols-12",
+                "auto-rows-76px",
+                "gap-2"
+            ]
+        );
+
+        let button = &layout.children[0];
+        assert_eq!(
+            button.classes,
+            vec![
+                "w-full",
+                "h-full",
+                "col-start-1",
+                "col-span-4",
+                "row-start-2",
+                "row-span-1",
+                "desktop:col-start-7",
+                "desktop:col-span-6",
+                "desktop:row-start-1",
+                "desktop:row-span-1",
+            ]
+        );
+        assert_eq!(button.text.as_deref(), Some("Save"));
+        assert_eq!(
+            button.attributes,
+            vec![("variant".into(), "primary".into())]
+        );
+
+        let text = &layout.children[1];
+        assert!(text
+            .classes
+            .ends_with(&["hidden".into(), "desktop:block".into()]));
+    }
+
+    #[test]
+    fn free_and_flex_layouts_keep_their_positions() {
+        let free = LayoutDocument {
+            id: Some("layout-2".into()),
+            name: "Free".into(),
+            width: 908,
+            height: 120,
+            kind: LayoutKindDocument::Free {
+                snap_grid: 8,
+                snap_to_items: true,
+            },
+            components: vec![ComponentDocument {
+                id: Some("launch-table-1".into()),
+                name: "Launch Table".into(),
+                tag_name: "launch-table".into(),
+                attributes: BTreeMap::new(),
+                placement: PlacementDocument::Free {
+                    position: (16, -8),
+                    size: Some((320.0, 95.6)),
+                },
+                breakpoints: BTreeMap::new(),
+            }],
+            placement: None,
+            breakpoints: BTreeMap::new(),
+            children: Vec::new(),
+        };
+
+        let mut page = grid_page();
+        page.breakpoints.clear();
+        page.breakpoint = None;
+        page.width = 908;
+        page.layouts = vec![free];
+
+        let page = tailwind_element(&page);
+        let layout = &page.children[0];
+
+        assert_eq!(layout.classes, vec!["w-full", "relative", "h-30"]);
+        assert_eq!(
+            layout.children[0].classes,
+            vec!["absolute", "left-4", "-top-2", "w-80", "h-24"]
+        );
+    }
+}
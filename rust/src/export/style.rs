@@ -0,0 +1,79 @@
+use crate::page::layout::flex::FlexDirection;
+
+/// Length of a box
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Length {
+    /// Fixed length in px
+    Px(i32),
+    /// Whole length of the parent
+    Full,
+}
+
+/// Css `position` of a box
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Position {
+    Relative,
+    Absolute,
+}
+
+/// Css `display` of a box
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Display {
+    Block,
+    Flex,
+    Grid,
+    None,
+}
+
+/// A single style of an exported element, derived from the layout model
+///
+/// Styles do not depend on the way they are written,
+/// so the same element tree can be exported with Tailwind classes or with css rules
+#[derive(Debug, Clone, PartialEq)]
+pub enum Style {
+    Position(Position),
+    /// Offset from the left edge of the parent in px
+    Left(i32),
+    /// Offset from the top edge of the parent in px
+    Top(i32),
+    Width(Length),
+    Height(Length),
+    /// Center the box horizontally in its parent
+    MarginXAuto,
+    Display(Display),
+    FlexDirection(FlexDirection),
+    /// Whether flex items that do not fit are moved to the next line
+    FlexWrap(bool),
+    /// Tailwind `justify-*` class, as it is stored in the layout
+    Justify(String),
+    /// Tailwind `items-*` class, as it is stored in the layout
+    Align(String),
+    /// Space between flex items or grid cells in px
+    Gap(u32),
+    /// Number of equally wide grid columns
+    GridColumns(usize),
+    /// Height of the grid rows in px
+    GridAutoRows(u32),
+    /// Block of grid cells that the item is placed into
+    GridArea {
+        /// Top left cell of the block (starting from 1)
+        pos: (usize, usize),
+        /// Size of the block in cells
+        size: (usize, usize),
+    },
+    FlexGrow(u32),
+    FlexShrink(u32),
+    /// Initial main size of a flex item in px
+    FlexBasis(u32),
+    Order(i32),
+}
+
+/// Style that applies from a breakpoint of the page up
+///
+/// Styles are mobile first, the ones of the smallest breakpoint (index 0) apply at every width
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponsiveStyle {
+    /// Index of the breakpoint, in the breakpoints of the page sorted by width
+    pub screen: usize,
+    pub style: Style,
+}
@@ -0,0 +1,380 @@
+use std::collections::BTreeMap;
+
+use crate::page::breakpoint::Breakpoint;
+
+use super::{
+    element::Element,
+    quote,
+    style::{Display, Length, Position, Style},
+};
+
+/// Default Tailwind spacing scale [px -> name]
+const SPACING_SCALE: [(u32, &str); 35] = [
+    (0, "0"),
+    (1, "px"),
+    (2, "0.5"),
+    (4, "1"),
+    (6, "1.5"),
+    (8, "2"),
+    (10, "2.5"),
+    (12, "3"),
+    (14, "3.5"),
+    (16, "4"),
+    (20, "5"),
+    (24, "6"),
+    (28, "7"),
+    (32, "8"),
+    (36, "9"),
+    (40, "10"),
+    (44, "11"),
+    (48, "12"),
+    (56, "14"),
+    (64, "16"),
+    (80, "20"),
+    (96, "24"),
+    (112, "28"),
+    (128, "32"),
+    (144, "36"),
+    (160, "40"),
+    (176, "44"),
+    (192, "48"),
+    (208, "52"),
+    (224, "56"),
+    (240, "60"),
+    (256, "64"),
+    (288, "72"),
+    (320, "80"),
+    (384, "96"),
+];
+
+/// Highest values of the grid utilities in the default Tailwind theme
+const MAX_GRID_COLS: usize = 12;
+const MAX_COL_START: usize = 13;
+const MAX_COL_SPAN: usize = 12;
+const MAX_ROW_START: usize = 7;
+const MAX_ROW_SPAN: usize = 6;
+const MAX_ORDER: i32 = 12;
+
+/// Names of the Tailwind utilities used by an export
+///
+/// Values that are not a part of the default theme get a custom name,
+/// they are collected as extensions of the theme in `tailwind.config.js`
+#[derive(Debug, Default)]
+pub struct TailwindConfig {
+    /// Custom spacing values [name -> css value]
+    spacing: BTreeMap<String, String>,
+    grid_template_columns: BTreeMap<String, String>,
+    grid_auto_rows: BTreeMap<String, String>,
+    grid_column: BTreeMap<String, String>,
+    grid_column_start: BTreeMap<String, String>,
+    grid_row: BTreeMap<String, String>,
+    grid_row_start: BTreeMap<String, String>,
+    flex_grow: BTreeMap<String, String>,
+    flex_shrink: BTreeMap<String, String>,
+    order: BTreeMap<String, String>,
+    /// Breakpoints that classes can be prefixed with, smallest first [name -> min width]
+    ///
+    /// Tailwind emits the media queries in this order, so it is not sorted by name
+    screens: Vec<(String, String)>,
+}
+
+impl TailwindConfig {
+    /// Name of a spacing value, for example `4` for 16px
+    ///
+    /// Values outside of the default scale are named by a quarter of their px value,
+    /// as the default scale does it (`227` for 908px)
+    pub fn spacing(&mut self, px: u32) -> String {
+        if let Some((_, name)) = SPACING_SCALE.iter().find(|(value, _)| *value == px) {
+            return (*name).into();
+        }
+
+        let name = (px as f64 / 4.0).to_string();
+        self.spacing
+            .insert(name.clone(), format!("{}rem", px as f64 / 16.0));
+        name
+    }
+
+    /// Class of a spacing utility that can be negative, for example `-top-4`
+    pub fn offset(&mut self, utility: &str, px: i32) -> String {
+        let name = self.spacing(px.unsigned_abs());
+
+        if px < 0 {
+            format!("-{}-{}", utility, name)
+        } else {
+            format!("{}-{}", utility, name)
+        }
+    }
+
+    /// `grid-cols-*` class of a grid with equally wide columns
+    pub fn grid_cols(&mut self, columns: usize) -> String {
+        if columns > MAX_GRID_COLS {
+            self.grid_template_columns.insert(
+                columns.to_string(),
+                format!("repeat({}, minmax(0, 1fr))", columns),
+            );
+        }
+
+        format!("grid-cols-{}", columns)
+    }
+
+    /// `auto-rows-*` class of a grid with rows of a fixed height
+    pub fn auto_rows(&mut self, px: u32) -> String {
+        let name = format!("{}px", px);
+        self.grid_auto_rows.insert(name.clone(), name.clone());
+
+        format!("auto-rows-{}", name)
+    }
+
+    /// Classes that place an item into a block of grid cells
+    ///
+    /// # Arguments
+    /// * `pos` - top left cell of the block (starting from 1)
+    /// * `size` - size of the block in cells
+    pub fn grid_area(&mut self, pos: (usize, usize), size: (usize, usize)) -> Vec<String> {
+        if pos.0 > MAX_COL_START {
+            self.grid_column_start
+                .insert(pos.0.to_string(), pos.0.to_string());
+        }
+        if size.0 > MAX_COL_SPAN {
+            self.grid_column.insert(
+                format!("span-{}", size.0),
+                format!("span {0} / span {0}", size.0),
+            );
+        }
+        if pos.1 > MAX_ROW_START {
+            self.grid_row_start
+                .insert(pos.1.to_string(), pos.1.to_string());
+        }
+        if size.1 > MAX_ROW_SPAN {
+            self.grid_row.insert(
+                format!("span-{}", size.1),
+                format!("span {0} / span {0}", size.1),
+            );
+        }
+
+        vec![
+            format!("col-start-{}", pos.0),
+            format!("col-span-{}", size.0),
+            format!("row-start-{}", pos.1),
+            format!("row-span-{}", size.1),
+        ]
+    }
+
+    /// `grow` class of a flex item, `None` for the default of 0
+    pub fn grow(&mut self, grow: u32) -> Option<String> {
+        match grow {
+            0 => None,
+            1 => Some("grow".into()),
+            _ => {
+                self.flex_grow.insert(grow.to_string(), grow.to_string());
+                Some(format!("grow-{}", grow))
+            }
+        }
+    }
+
+    /// `shrink` class of a flex item, `None` for the default of 1
+    pub fn shrink(&mut self, shrink: u32) -> Option<String> {
+        match shrink {
+            1 => None,
+            0 => Some("shrink-0".into()),
+            _ => {
+                self.flex_shrink
+                    .insert(shrink.to_string(), shrink.to_string());
+                Some(format!("shrink-{}", shrink))
+            }
+        }
+    }
+
+    /// `order` class of a flex item, `None` for the default of 0
+    pub fn order(&mut self, order: i32) -> Option<String> {
+        if order == 0 {
+            return None;
+        }
+
+        let value = order.unsigned_abs();
+        if value > MAX_ORDER as u32 {
+            self.order.insert(value.to_string(), value.to_string());
+        }
+
+        if order < 0 {
+            Some(format!("-order-{}", value))
+        } else {
+            Some(format!("order-{}", value))
+        }
+    }
+
+    /// Add a breakpoint that classes can be prefixed with, as `tablet:`
+    ///
+    /// Screens have to be added from the smallest one,
+    /// a screen that was already added keeps its first width
+    pub fn add_screen(&mut self, name: &str, width: u32) {
+        if self.screens.iter().all(|(screen, _)| screen != name) {
+            self.screens.push((name.into(), format!("{}px", width)));
+        }
+    }
+
+    /// Classes of a single style, without the breakpoint prefix
+    pub fn classes(&mut self, style: &Style) -> Vec<String> {
+        let class = match style {
+            Style::Position(Position::Relative) => "relative".into(),
+            Style::Position(Position::Absolute) => "absolute".into(),
+            Style::Left(px) => self.offset("left", *px),
+            Style::Top(px) => self.offset("top", *px),
+            Style::Width(Length::Full) => "w-full".into(),
+            Style::Width(Length::Px(px)) => self.offset("w", *px),
+            Style::Height(Length::Full) => "h-full".into(),
+            Style::Height(Length::Px(px)) => self.offset("h", *px),
+            Style::MarginXAuto => "mx-auto".into(),
+            Style::Display(Display::Block) => "block".into(),
+            Style::Display(Display::Flex) => "flex".into(),
+            Style::Display(Display::Grid) => "grid".into(),
+            Style::Display(Display::None) => "hidden".into(),
+            Style::FlexDirection(direction) => direction.class().into(),
+            Style::FlexWrap(true) => "flex-wrap".into(),
+            Style::FlexWrap(false) => "flex-nowrap".into(),
+            Style::Justify(class) | Style::Align(class) => class.clone(),
+            Style::Gap(px) => self.offset("gap", *px as i32),
+            Style::GridColumns(columns) => self.grid_cols(*columns),
+            Style::GridAutoRows(px) => self.auto_rows(*px),
+            Style::GridArea { pos, size } => return self.grid_area(*pos, *size),
+            Style::FlexGrow(grow) => return self.grow(*grow).into_iter().collect(),
+            Style::FlexShrink(shrink) => return self.shrink(*shrink).into_iter().collect(),
+            Style::FlexBasis(px) => self.offset("basis", *px as i32),
+            Style::Order(order) => return self.order(*order).into_iter().collect(),
+        };
+
+        vec![class]
+    }
+
+    /// Replace the styles of an element tree with classes
+    ///
+    /// Breakpoints other than the smallest one are added as screens,
+    /// styles of a breakpoint are prefixed with its name (`tablet:col-span-4`)
+    ///
+    /// # Arguments
+    /// * `screens` - breakpoints of the page sorted by width, that the styles refer to
+    pub fn apply(&mut self, element: &mut Element, screens: &[Breakpoint]) {
+        for breakpoint in screens.iter().skip(1) {
+            self.add_screen(&breakpoint.name, breakpoint.width);
+        }
+
+        self.apply_classes(element, screens);
+    }
+
+    fn apply_classes(&mut self, element: &mut Element, screens: &[Breakpoint]) {
+        for responsive in element.styles.drain(..) {
+            let prefix = match responsive.screen {
+                0 => String::new(),
+                screen => format!("{}:", screens[screen].name),
+            };
+
+            for class in self.classes(&responsive.style) {
+                element.classes.push(format!("{}{}", prefix, class));
+            }
+        }
+
+        for child in element.children.iter_mut() {
+            self.apply_classes(child, screens);
+        }
+    }
+
+    /// Content of `tailwind.config.js` that extends the default theme with all of the custom values
+    ///
+    /// # Arguments
+    /// * `content` - glob of the files that use the classes
+    pub fn to_js(&self, content: &str) -> String {
+        let sections: [(&str, Vec<(&String, &String)>); 11] = [
+            (
+                "screens",
+                self.screens.iter().map(|(k, v)| (k, v)).collect(),
+            ),
+            ("spacing", self.spacing.iter().collect()),
+            (
+                "gridTemplateColumns",
+                self.grid_template_columns.iter().collect(),
+            ),
+            ("gridAutoRows", self.grid_auto_rows.iter().collect()),
+            ("gridColumn", self.grid_column.iter().collect()),
+            ("gridColumnStart", self.grid_column_start.iter().collect()),
+            ("gridRow", self.grid_row.iter().collect()),
+            ("gridRowStart", self.grid_row_start.iter().collect()),
+            ("flexGrow", self.flex_grow.iter().collect()),
+            ("flexShrink", self.flex_shrink.iter().collect()),
+            ("order", self.order.iter().collect()),
+        ];
+
+        let mut extend = String::new();
+        for (name, values) in sections.iter().filter(|(_, values)| !values.is_empty()) {
+            extend.push_str(&format!("      {}: {{\n", name));
+            for (key, value) in values {
+                extend.push_str(&format!("        {}: {},\n", quote(key), quote(value)));
+            }
+            extend.push_str("      },\n");
+        }
+
+        format!(
+            "/** @type {{import('tailwindcss').Config}} */\n\
+             module.exports = {{\n  \
+               content: [{}],\n  \
+               theme: {{\n    \
+                 extend: {{\n{}    }},\n  \
+               }},\n\
+             }};\n",
+            quote(content),
+            extend
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spacing_outside_of_the_scale_is_added_to_the_config() {
+        let mut config = TailwindConfig::default();
+
+        assert_eq!(config.spacing(16), "4");
+        assert_eq!(config.spacing(908), "227");
+        assert_eq!(config.spacing(42), "10.5");
+        assert_eq!(config.offset("top", -16), "-top-4");
+
+        let js = config.to_js("./**/*.html");
+        assert!(js.contains("\"227\": \"56.75rem\""));
+        assert!(js.contains("\"10.5\": \"2.625rem\""));
+        assert!(!js.contains("\"4\":"));
+    }
+
+    #[test]
+    fn grid_utilities_beyond_the_default_theme_are_extended() {
+        let mut config = TailwindConfig::default();
+
+        assert_eq!(config.grid_cols(12), "grid-cols-12");
+        assert_eq!(config.grid_cols(16), "grid-cols-16");
+        assert_eq!(
+            config.grid_area((14, 2), (3, 8)),
+            vec!["col-start-14", "col-span-3", "row-start-2", "row-span-8"]
+        );
+
+        let js = config.to_js("./**/*.html");
+        assert!(
+            js.contains("gridTemplateColumns: {\n        \"16\": \"repeat(16, minmax(0, 1fr))\"")
+        );
+        assert!(js.contains("gridColumnStart: {\n        \"14\": \"14\""));
+        assert!(js.contains("\"span-8\": \"span 8 / span 8\""));
+        assert!(!js.contains("gridColumn: {"));
+    }
+
+    #[test]
+    fn screens_keep_their_order() {
+        let mut config = TailwindConfig::default();
+        config.add_screen("tablet", 768);
+        config.add_screen("desktop", 1280);
+        config.add_screen("tablet", 820);
+
+        let js = config.to_js("./**/*.html");
+        assert!(js.contains(
+            "screens: {\n        \"tablet\": \"768px\",\n        \"desktop\": \"1280px\",\n      },"
+        ));
+    }
+}
@@ -0,0 +1,155 @@
+use crate::document::PageDocument;
+
+use super::{
+    element::{escape, Element},
+    framework::{component_name, import_statement, meta, PropValue},
+    quote, Exporter,
+};
+
+/// Exports every page as a Vue single file component
+pub struct VueExporter {
+    /// Module that the components are imported from
+    library: String,
+}
+
+impl VueExporter {
+    pub fn new(library: &str) -> Self {
+        Self {
+            library: library.into(),
+        }
+    }
+}
+
+impl Exporter for VueExporter {
+    fn name(&self) -> &str {
+        "vue"
+    }
+
+    fn extension(&self) -> &str {
+        "vue"
+    }
+
+    fn page_file(&self, page: &PageDocument, body: &Element, path: &str) -> String {
+        // The plain script names the component and exports its metadata,
+        // components imported in the setup script are available in the template
+        let mut sfc = format!(
+            "<script>\n{}\n\nexport default {{\n  name: {},\n}};\n</script>\n\n",
+            meta(page),
+            quote(&component_name(&page.name))
+        );
+
+        if let Some(import) = import_statement(body, &self.library, path) {
+            sfc.push_str(&format!("<script setup>\n{}\n</script>\n\n", import));
+        }
+
+        sfc.push_str(&format!(
+            "<template>\n{}\n</template>\n",
+            to_template(body, 2)
+        ));
+        sfc
+    }
+}
+
+/// Write an element tree as a Vue template
+///
+/// Custom elements are written as the components of the library, with their parameters as props
+///
+/// # Arguments
+/// * `indent` - number of spaces that the element is indented with
+fn to_template(element: &Element, indent: usize) -> String {
+    let tag = if element.is_custom() {
+        component_name(&element.tag)
+    } else {
+        element.tag.clone()
+    };
+    let mut template = format!("{}<{}", " ".repeat(indent), tag);
+
+    if let Some(id) = &element.id {
+        template.push_str(&format!(" id=\"{}\"", escape(id)));
+    }
+    if !element.classes.is_empty() {
+        template.push_str(&format!(
+            " class=\"{}\"",
+            escape(&element.classes.join(" "))
+        ));
+    }
+    for (name, value) in element.attributes.iter() {
+        match PropValue::parse(value) {
+            PropValue::Bool(value) => template.push_str(&format!(" :{}=\"{}\"", name, value)),
+            PropValue::Text(value) => {
+                template.push_str(&format!(" {}=\"{}\"", name, escape(value)))
+            }
+        }
+    }
+
+    if element.text.is_none() && element.children.is_empty() {
+        template.push_str(" />");
+        return template;
+    }
+    template.push('>');
+
+    if let Some(text) = &element.text {
+        // Escaped braces are not parsed as an interpolation
+        template.push_str(&escape(text).replace("{{", "&#123;&#123;"));
+    }
+
+    if !element.children.is_empty() {
+        template.push('\n');
+        for child in element.children.iter() {
+            template.push_str(&to_template(child, indent + 2));
+            template.push('\n');
+        }
+        template.push_str(&" ".repeat(indent));
+    }
+
+    template.push_str(&format!("</{}>", tag));
+    template
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::page::metadata::PageMetadata;
+
+    #[test]
+    fn pages_are_single_file_components() {
+        let page = PageDocument {
+            name: "Home".into(),
+            width: 908,
+            breakpoints: Vec::new(),
+            breakpoint: None,
+            metadata: PageMetadata::default(),
+            layouts: Vec::new(),
+        };
+
+        let mut button = Element::new("launch-button");
+        button.classes = vec!["grow".into()];
+        button.attributes = vec![
+            ("disabled".into(), "true".into()),
+            ("variant".into(), "primary".into()),
+        ];
+        button.text = Some("{{ total }} & more".into());
+
+        let mut main = Element::new("main");
+        main.classes = vec!["relative".into()];
+        main.children = vec![button, Element::new("launch-divider")];
+
+        let sfc = VueExporter::new("./components").page_file(&page, &main, "index.vue");
+        assert_eq!(
+            sfc,
+            "<script>\n\
+             export const meta = {\n  route: \"/\",\n  title: \"Home\",\n  description: \"\",\n};\n\n\
+             export default {\n  name: \"Home\",\n};\n\
+             </script>\n\n\
+             <script setup>\n\
+             import { LaunchButton, LaunchDivider } from \"./components\";\n\
+             </script>\n\n\
+             <template>\n  \
+               <main class=\"relative\">\n    \
+                 <LaunchButton class=\"grow\" :disabled=\"true\" variant=\"primary\">&#123;&#123; total }} &amp; more</LaunchButton>\n    \
+                 <LaunchDivider />\n  \
+               </main>\n\
+             </template>\n"
+        );
+    }
+}
@@ -1,15 +1,15 @@
 export class ComponentParameter {
-  constructor(name, type) {
+  constructor(name, schema) {
     this._name = name;
-    this._type = type;
+    this._schema = schema;
   }
 
   get name() {
     return this._name;
   }
 
-  get data_type() {
-    return this._type;
+  get schema() {
+    return JSON.stringify(this._schema);
   }
 }
 
@@ -19,6 +19,8 @@ export class ComponentDescriptor {
     this._parameters = Object.entries(desc.parameters).map(
       ([key, val]) => new ComponentParameter(key, val)
     );
+    this._constraints = desc.constraints || {};
+    this._label = desc.label;
   }
 
   get tag_name() {
@@ -28,6 +30,14 @@ export class ComponentDescriptor {
   get parameters() {
     return this._parameters;
   }
+
+  get constraints() {
+    return JSON.stringify(this._constraints);
+  }
+
+  get label() {
+    return this._label;
+  }
 }
 
 export class EditorComponent extends HTMLElement {
@@ -2,15 +2,10 @@ use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use web_sys::HtmlElement;
 
-#[wasm_bindgen]
-#[derive(Clone, Debug)]
-pub enum ComponentPropertie {
-    Color = "color",
-    Text = "text",
-    Number = "number",
-    LayoutStyle = "layout_style",
-    Boolean = "boolean",
-}
+use crate::{
+    component::{ParameterSchema, SizeConstraints},
+    utils,
+};
 
 #[wasm_bindgen(module = "/src/html_elements/component.js")]
 extern "C" {
@@ -25,8 +20,15 @@ extern "C" {
     #[wasm_bindgen(method, getter)]
     pub fn name(this: &ComponentParameter) -> String;
 
-    #[wasm_bindgen(method, getter)]
-    pub fn data_type(this: &ComponentParameter) -> ComponentPropertie;
+    #[wasm_bindgen(method, getter, js_name = "schema")]
+    fn schema_json(this: &ComponentParameter) -> String;
+}
+
+impl ComponentParameter {
+    /// Type, default value and limits declared for the parameter
+    pub fn schema(&self) -> ParameterSchema {
+        ParameterSchema::from_json(&self.name(), &self.schema_json())
+    }
 }
 
 #[wasm_bindgen(module = "/src/html_elements/component.js")]
@@ -43,6 +45,11 @@ extern "C" {
     #[wasm_bindgen(method, getter, js_name = "parameters")]
     fn parameters_array(this: &ComponentDescriptor) -> js_sys::Array;
 
+    #[wasm_bindgen(method, getter, js_name = "constraints")]
+    fn constraints_json(this: &ComponentDescriptor) -> String;
+
+    #[wasm_bindgen(method, getter)]
+    fn label(this: &ComponentDescriptor) -> Option<String>;
 }
 
 impl ComponentDescriptor {
@@ -52,6 +59,27 @@ impl ComponentDescriptor {
             .map(|val| val.dyn_into().unwrap())
             .collect()
     }
+
+    /// Schemas of all parameters declared in the descriptor
+    pub fn parameter_schemas(&self) -> Vec<ParameterSchema> {
+        self.parameters()
+            .iter()
+            .map(|param| param.schema())
+            .collect()
+    }
+
+    /// Size constraints declared in the descriptor
+    pub fn constraints(&self) -> SizeConstraints {
+        SizeConstraints::from_json(&self.constraints_json())
+    }
+
+    /// Name that components of this kind get by default,
+    /// the `label` of the descriptor, or one derived from the tag name
+    pub fn display_name(&self) -> String {
+        self.label()
+            .filter(|label| !label.trim().is_empty())
+            .unwrap_or_else(|| utils::name_from_tag(&self.tag_name()))
+    }
 }
 
 #[wasm_bindgen(module = "/src/html_elements/component.js")]
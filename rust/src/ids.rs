@@ -0,0 +1,95 @@
+use std::{cell::RefCell, collections::HashMap};
+
+/// Highest number of a reserved id that the generator continues from
+///
+/// Generated ids never get anywhere near it, so higher numbers can only come from ids typed by hand,
+/// they are ignored, as counting up from them would overflow
+const MAX_RESERVED: u64 = u32::MAX as u64;
+
+/// Generator of stable, user-facing ids of components and layouts, like `launch-table-3` or `layout-1`
+///
+/// Unlike the arena index of a component, an id does not change when the component
+/// is removed and restored, and it is saved together with the document,
+/// so that exported code can refer to the items by it
+#[derive(Default)]
+pub struct IdGenerator {
+    /// Highest number used so far [prefix -> number]
+    last: HashMap<String, u64>,
+}
+
+impl IdGenerator {
+    /// Generate a new id, the prefix followed by the next free number
+    pub fn next(&mut self, prefix: &str) -> String {
+        let last = self.last.entry(prefix.to_owned()).or_insert(0);
+        *last += 1;
+
+        format!("{}-{}", prefix, last)
+    }
+
+    /// Mark an id as used, so that it is never generated again
+    ///
+    /// Used for ids that come from a loaded document,
+    /// ids numbered above `MAX_RESERVED` can not collide with generated ones and are ignored
+    pub fn reserve(&mut self, id: &str) {
+        let parsed = id
+            .rsplit_once('-')
+            .and_then(|(prefix, number)| Some((prefix, number.parse::<u64>().ok()?)));
+
+        if let Some((prefix, number)) = parsed.filter(|(_, number)| *number <= MAX_RESERVED) {
+            let last = self.last.entry(prefix.to_owned()).or_insert(0);
+            *last = (*last).max(number);
+        }
+    }
+}
+
+thread_local! {
+    static IDS: RefCell<IdGenerator> = RefCell::new(IdGenerator::default());
+}
+
+/// Generate a new id, see `IdGenerator::next`
+pub fn new_id(prefix: &str) -> String {
+    IDS.with(|ids| ids.borrow_mut().next(prefix))
+}
+
+/// Mark an id as used, see `IdGenerator::reserve`
+pub fn reserve_id(id: &str) {
+    IDS.with(|ids| ids.borrow_mut().reserve(id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_are_numbered_per_prefix() {
+        let mut ids = IdGenerator::default();
+
+        assert_eq!(ids.next("layout"), "layout-1");
+        assert_eq!(ids.next("launch-table"), "launch-table-1");
+        assert_eq!(ids.next("layout"), "layout-2");
+    }
+
+    #[test]
+    fn reserved_ids_are_skipped() {
+        let mut ids = IdGenerator::default();
+
+        ids.reserve("launch-table-7");
+        ids.reserve("launch-table-3");
+        // Ids typed by hand do not have to be numbered
+        ids.reserve("hero");
+
+        assert_eq!(ids.next("launch-table"), "launch-table-8");
+        assert_eq!(ids.next("hero"), "hero-1");
+    }
+
+    #[test]
+    fn huge_reserved_numbers_do_not_overflow() {
+        let mut ids = IdGenerator::default();
+
+        ids.reserve("layout-18446744073709551615");
+        assert_eq!(ids.next("layout"), "layout-1");
+
+        ids.reserve(&format!("layout-{}", MAX_RESERVED));
+        assert_eq!(ids.next("layout"), format!("layout-{}", MAX_RESERVED + 1));
+    }
+}
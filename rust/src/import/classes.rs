@@ -0,0 +1,254 @@
+use crate::{
+    export::style::{Display, Length, Position},
+    page::layout::flex::FlexDirection,
+};
+
+/// Values of the `justify-*` classes that flex layouts can have
+const JUSTIFY: [&str; 8] = [
+    "start", "end", "center", "between", "around", "evenly", "normal", "stretch",
+];
+
+/// Values of the `items-*` classes that flex layouts can have
+const ALIGN: [&str; 5] = ["start", "end", "center", "baseline", "stretch"];
+
+/// Tailwind classes of an element that describe its layout, the inverse of the export
+///
+/// Only the classes without a breakpoint prefix are read,
+/// everything that does not map to the layout model ends up in `unknown`
+#[derive(Debug, Default, PartialEq)]
+pub struct Classes {
+    pub display: Option<Display>,
+    pub position: Option<Position>,
+    /// Offset from the left edge of the parent in px
+    pub left: Option<i32>,
+    /// Offset from the top edge of the parent in px
+    pub top: Option<i32>,
+    pub width: Option<Length>,
+    pub height: Option<Length>,
+    pub direction: Option<FlexDirection>,
+    pub wrap: Option<bool>,
+    /// `justify-*` class
+    pub justify: Option<String>,
+    /// `items-*` class
+    pub align: Option<String>,
+    /// Space between flex items or grid cells in px
+    pub gap: Option<u32>,
+    pub grid_columns: Option<usize>,
+    /// Height of the grid rows in px
+    pub auto_rows: Option<u32>,
+    pub col_start: Option<usize>,
+    pub col_span: Option<usize>,
+    pub row_start: Option<usize>,
+    pub row_span: Option<usize>,
+    pub grow: Option<u32>,
+    pub shrink: Option<u32>,
+    /// Initial main size of a flex item in px
+    pub basis: Option<u32>,
+    pub order: Option<i32>,
+    /// Classes that could not be mapped
+    pub unknown: Vec<String>,
+}
+
+impl Classes {
+    pub fn parse(classes: &[&str]) -> Self {
+        let mut parsed = Self::default();
+
+        for class in classes {
+            if !parsed.parse_class(class) {
+                parsed.unknown.push((*class).to_owned());
+            }
+        }
+
+        parsed
+    }
+
+    /// Read a single class, returns `false` if it does not map to the layout model
+    fn parse_class(&mut self, class: &str) -> bool {
+        match class {
+            "block" => self.display = Some(Display::Block),
+            "flex" => self.display = Some(Display::Flex),
+            "grid" => self.display = Some(Display::Grid),
+            "hidden" => self.display = Some(Display::None),
+            "relative" => self.position = Some(Position::Relative),
+            "absolute" => self.position = Some(Position::Absolute),
+            "mx-auto" => {}
+            "flex-wrap" => self.wrap = Some(true),
+            "flex-nowrap" => self.wrap = Some(false),
+            "grow" => self.grow = Some(1),
+            "shrink" => self.shrink = Some(1),
+            _ => return self.parse_value(class),
+        }
+
+        true
+    }
+
+    /// Read a class with a value, like `w-80` or `-top-2`
+    fn parse_value(&mut self, class: &str) -> bool {
+        if let Some(direction) = FlexDirection::from_class(class) {
+            self.direction = Some(direction);
+            return true;
+        }
+
+        let (negative, class) = match class.strip_prefix('-') {
+            Some(class) => (true, class),
+            None => (false, class),
+        };
+        let (utility, value) = match split_utility(class) {
+            Some(split) => split,
+            None => return false,
+        };
+        let sign = if negative { -1 } else { 1 };
+
+        match (negative, utility) {
+            (_, "left") => set(&mut self.left, spacing(value).map(|px| sign * px)),
+            (_, "top") => set(&mut self.top, spacing(value).map(|px| sign * px)),
+            (_, "order") => set(
+                &mut self.order,
+                value.parse::<i32>().ok().map(|order| sign * order),
+            ),
+            (true, _) => false,
+            (false, "w") => set(&mut self.width, length(value)),
+            (false, "h") => set(&mut self.height, length(value)),
+            (false, "gap") => set(&mut self.gap, spacing(value).map(|px| px as u32)),
+            (false, "basis") => set(&mut self.basis, spacing(value).map(|px| px as u32)),
+            (false, "grow") => set(&mut self.grow, value.parse().ok()),
+            (false, "shrink") => set(&mut self.shrink, value.parse().ok()),
+            (false, "grid-cols") => set(&mut self.grid_columns, value.parse().ok()),
+            (false, "auto-rows") => set(&mut self.auto_rows, px(value).map(|px| px as u32)),
+            (false, "col-start") => set(&mut self.col_start, value.parse().ok()),
+            (false, "col-span") => set(&mut self.col_span, value.parse().ok()),
+            (false, "row-start") => set(&mut self.row_start, value.parse().ok()),
+            (false, "row-span") => set(&mut self.row_span, value.parse().ok()),
+            (false, "justify") if JUSTIFY.contains(&value) => {
+                set(&mut self.justify, Some(format!("justify-{}", value)))
+            }
+            (false, "items") if ALIGN.contains(&value) => {
+                set(&mut self.align, Some(format!("items-{}", value)))
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Store a value that was read, returns `false` if it could not be read
+///
+/// Values that can not be read do not override the previous ones, `w-auto` keeps the width of `w-80`
+fn set<T>(field: &mut Option<T>, value: Option<T>) -> bool {
+    match value {
+        Some(value) => {
+            *field = Some(value);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Split a class into its utility and value, `grid-cols-4` into `grid-cols` and `4`
+fn split_utility(class: &str) -> Option<(&str, &str)> {
+    const UTILITIES: [&str; 17] = [
+        "grid-cols",
+        "auto-rows",
+        "col-start",
+        "col-span",
+        "row-start",
+        "row-span",
+        "justify",
+        "items",
+        "left",
+        "top",
+        "order",
+        "w",
+        "h",
+        "gap",
+        "basis",
+        "grow",
+        "shrink",
+    ];
+
+    UTILITIES.iter().find_map(|utility| {
+        class
+            .strip_prefix(utility)
+            .and_then(|rest| rest.strip_prefix('-'))
+            .map(|value| (*utility, value))
+    })
+}
+
+/// Px value of a Tailwind spacing, `4` is 16px as in the default theme
+///
+/// Arbitrary values in px are read too (`[908px]`)
+fn spacing(value: &str) -> Option<i32> {
+    if value == "px" {
+        return Some(1);
+    }
+
+    match value.parse::<f64>() {
+        Ok(scale) if scale >= 0.0 => Some((scale * 4.0).round() as i32),
+        _ => px(value),
+    }
+}
+
+/// Px value of a custom theme value or an arbitrary value, `76px` or `[76px]`
+fn px(value: &str) -> Option<i32> {
+    let value = value.trim_start_matches('[').trim_end_matches(']');
+    value.strip_suffix("px")?.parse().ok()
+}
+
+/// Length of a `w-*` or `h-*` class
+fn length(value: &str) -> Option<Length> {
+    match value {
+        "full" => Some(Length::Full),
+        _ => spacing(value).map(Length::Px),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exported_classes_are_read_back() {
+        let classes = Classes::parse(&[
+            "absolute",
+            "left-4",
+            "-top-2",
+            "w-80",
+            "h-[95px]",
+            "w-full",
+            "auto-rows-76px",
+            "grid-cols-12",
+            "col-span-6",
+            "-order-3",
+            "flex-col-reverse",
+            "justify-between",
+            "gap-2.5",
+        ]);
+
+        assert_eq!(classes.position, Some(Position::Absolute));
+        assert_eq!((classes.left, classes.top), (Some(16), Some(-8)));
+        assert_eq!(classes.width, Some(Length::Full));
+        assert_eq!(classes.height, Some(Length::Px(95)));
+        assert_eq!(classes.auto_rows, Some(76));
+        assert_eq!(classes.grid_columns, Some(12));
+        assert_eq!(classes.col_span, Some(6));
+        assert_eq!(classes.order, Some(-3));
+        assert_eq!(classes.direction, Some(FlexDirection::ColumnReverse));
+        assert_eq!(classes.justify.as_deref(), Some("justify-between"));
+        assert_eq!(classes.gap, Some(10));
+        assert!(classes.unknown.is_empty());
+    }
+
+    #[test]
+    fn other_classes_are_unknown() {
+        let classes = Classes::parse(&["bg-white", "w-auto", "-w-4", "tablet:grid-cols-4", "flex"]);
+
+        assert_eq!(
+            classes.unknown,
+            vec!["bg-white", "w-auto", "-w-4", "tablet:grid-cols-4"]
+        );
+        assert_eq!(classes.display, Some(Display::Flex));
+        assert_eq!(classes.width, None);
+
+        let classes = Classes::parse(&["w-80", "w-auto"]);
+        assert_eq!(classes.width, Some(Length::Px(320)));
+    }
+}
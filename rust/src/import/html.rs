@@ -0,0 +1,387 @@
+/// Elements that never have any content, and are not closed
+const VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// Elements whose content is not parsed as html
+const RAW_TEXT_ELEMENTS: [&str; 4] = ["script", "style", "textarea", "title"];
+
+/// Node of a parsed html fragment
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+    Element(HtmlElement),
+    Text(String),
+}
+
+/// Element of a parsed html fragment
+#[derive(Debug, Clone, PartialEq)]
+pub struct HtmlElement {
+    /// Tag name, in lower case
+    pub tag: String,
+    /// Attributes in the order they are written in, names in lower case
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Node>,
+}
+
+impl HtmlElement {
+    /// Value of an attribute, `None` if the element does not have it
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| attribute == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Classes of the element
+    pub fn classes(&self) -> Vec<&str> {
+        self.attribute("class")
+            .map(|class| class.split_whitespace().collect())
+            .unwrap_or_default()
+    }
+
+    /// Child elements, without the text nodes
+    pub fn child_elements(&self) -> impl Iterator<Item = &HtmlElement> {
+        self.children.iter().filter_map(|child| match child {
+            Node::Element(element) => Some(element),
+            Node::Text(_) => None,
+        })
+    }
+
+    /// Text of the element and its children, with whitespace collapsed
+    pub fn text(&self) -> String {
+        fn collect(nodes: &[Node], text: &mut String) {
+            for node in nodes {
+                match node {
+                    Node::Text(t) => {
+                        text.push_str(t);
+                        text.push(' ');
+                    }
+                    Node::Element(element) => collect(&element.children, text),
+                }
+            }
+        }
+
+        let mut text = String::new();
+        collect(&self.children, &mut text);
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    /// Short description of the element for the import report, `<div#hero>` or `<div>`
+    pub fn describe(&self) -> String {
+        match self.attribute("id") {
+            Some(id) => format!("<{}#{}>", self.tag, id),
+            None => format!("<{}>", self.tag),
+        }
+    }
+}
+
+/// Parse an html fragment into a tree of nodes
+///
+/// The parser is forgiving like the browsers are, it never fails:
+/// unclosed elements are closed at the end of their parent, stray closing tags are ignored.
+/// Comments and doctypes are skipped, the content of raw text elements (`script`, `style`, ...) is kept as text
+pub fn parse(html: &str) -> Vec<Node> {
+    let mut parser = Parser {
+        html,
+        pos: 0,
+        stack: vec![HtmlElement {
+            tag: String::new(),
+            attributes: Vec::new(),
+            children: Vec::new(),
+        }],
+    };
+    parser.parse();
+
+    while parser.stack.len() > 1 {
+        parser.close_top();
+    }
+    parser.stack.pop().unwrap().children
+}
+
+struct Parser<'a> {
+    html: &'a str,
+    /// Byte offset of the next character to parse
+    pos: usize,
+    /// Elements that are open, the root of the fragment is at the bottom
+    stack: Vec<HtmlElement>,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.html[self.pos..]
+    }
+
+    fn parse(&mut self) {
+        while self.pos < self.html.len() {
+            let rest = self.rest();
+
+            if rest.starts_with("<!--") {
+                self.pos += rest.find("-->").map_or(rest.len(), |end| end + 3);
+            } else if rest.starts_with("<!") || rest.starts_with("<?") {
+                self.pos += rest.find('>').map_or(rest.len(), |end| end + 1);
+            } else if rest.starts_with("</") {
+                self.closing_tag();
+            } else if rest.starts_with('<')
+                && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic())
+            {
+                self.opening_tag();
+            } else {
+                // A lone `<` is a part of the text, the search starts after it (or after any other first character)
+                let skip = rest.chars().next().map_or(0, char::len_utf8);
+                let end = rest[skip..].find('<').map_or(rest.len(), |end| end + skip);
+                self.push_text(&rest[..end]);
+                self.pos += end;
+            }
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        let text = decode_entities(text);
+        let children = &mut self.stack.last_mut().unwrap().children;
+
+        match children.last_mut() {
+            Some(Node::Text(previous)) => previous.push_str(&text),
+            _ => children.push(Node::Text(text)),
+        }
+    }
+
+    /// Close the innermost open element, and add it to its parent
+    fn close_top(&mut self) {
+        let element = self.stack.pop().unwrap();
+        self.stack
+            .last_mut()
+            .unwrap()
+            .children
+            .push(Node::Element(element));
+    }
+
+    fn closing_tag(&mut self) {
+        let rest = &self.rest()[2..];
+        let end = rest.find('>').map_or(rest.len(), |end| end + 1);
+        let tag = rest[..end]
+            .trim_end_matches('>')
+            .trim()
+            .to_ascii_lowercase();
+        self.pos += 2 + end;
+
+        // Elements left open inside of the closed one are closed with it
+        if let Some(index) = self.stack.iter().skip(1).rposition(|e| e.tag == tag) {
+            while self.stack.len() > index + 1 {
+                self.close_top();
+            }
+        }
+    }
+
+    fn opening_tag(&mut self) {
+        self.pos += 1;
+        let tag = self.take_while(|c| !c.is_whitespace() && c != '>' && c != '/');
+        let mut element = HtmlElement {
+            tag: tag.to_ascii_lowercase(),
+            attributes: Vec::new(),
+            children: Vec::new(),
+        };
+
+        let mut self_closing = false;
+        loop {
+            self.take_while(char::is_whitespace);
+
+            let rest = self.rest();
+            if rest.is_empty() {
+                break;
+            } else if rest.starts_with('>') {
+                self.pos += 1;
+                break;
+            } else if rest.starts_with("/>") {
+                self.pos += 2;
+                self_closing = true;
+                break;
+            } else if rest.starts_with('/') {
+                self.pos += 1;
+                continue;
+            }
+
+            let name = self
+                .take_while(|c| !c.is_whitespace() && c != '=' && c != '>' && c != '/')
+                .to_ascii_lowercase();
+            self.take_while(char::is_whitespace);
+
+            let value = if self.rest().starts_with('=') {
+                self.pos += 1;
+                self.take_while(char::is_whitespace);
+                self.attribute_value()
+            } else {
+                String::new()
+            };
+
+            element.attributes.push((name, value));
+        }
+
+        if self_closing || VOID_ELEMENTS.contains(&element.tag.as_str()) {
+            self.stack
+                .last_mut()
+                .unwrap()
+                .children
+                .push(Node::Element(element));
+        } else if RAW_TEXT_ELEMENTS.contains(&element.tag.as_str()) {
+            let closing = format!("</{}", element.tag);
+            let rest = self.rest();
+            let end = rest
+                .to_ascii_lowercase()
+                .find(&closing)
+                .unwrap_or(rest.len());
+
+            if !rest[..end].is_empty() {
+                element
+                    .children
+                    .push(Node::Text(decode_entities(&rest[..end])));
+            }
+            self.pos += end;
+            self.pos += self
+                .rest()
+                .find('>')
+                .map_or(self.rest().len(), |end| end + 1);
+
+            self.stack
+                .last_mut()
+                .unwrap()
+                .children
+                .push(Node::Element(element));
+        } else {
+            self.stack.push(element);
+        }
+    }
+
+    fn attribute_value(&mut self) -> String {
+        let rest = self.rest();
+
+        let value = match rest.chars().next() {
+            Some(quote) if quote == '"' || quote == '\'' => {
+                let end = rest[1..].find(quote).map_or(rest.len(), |end| end + 1);
+                self.pos += (end + 1).min(rest.len());
+                &rest[1..end]
+            }
+            _ => self.take_while(|c| !c.is_whitespace() && c != '>'),
+        };
+
+        decode_entities(value)
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let end = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        self.pos += end;
+        &rest[..end]
+    }
+}
+
+/// Replace the character references of html text with the characters
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let reference = rest[1..]
+            .find(';')
+            .filter(|end| *end <= 10)
+            .map(|end| &rest[1..end + 1]);
+        let character = reference.and_then(|reference| match reference {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{a0}'),
+            _ => {
+                let code = match reference
+                    .strip_prefix("#x")
+                    .or_else(|| reference.strip_prefix("#X"))
+                {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => reference.strip_prefix('#')?.parse().ok(),
+                };
+                code.and_then(char::from_u32)
+            }
+        });
+
+        match (reference, character) {
+            (Some(reference), Some(character)) => {
+                decoded.push(character);
+                rest = &rest[reference.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn element(node: &Node) -> &HtmlElement {
+        match node {
+            Node::Element(element) => element,
+            Node::Text(text) => panic!("expected an element, found text {:?}", text),
+        }
+    }
+
+    #[test]
+    fn fragments_are_parsed_into_trees() {
+        let nodes = parse(
+            "<!DOCTYPE html><!-- a comment -->\n\
+             <section class=\"grid  grid-cols-4\" data-empty>\n  \
+               <launch-button id=save variant='primary'>Save &amp; exit</launch-button>\n  \
+               <img src=\"a.png\">\n  \
+               <launch-text/>\n\
+             </section>",
+        );
+
+        let section = element(&nodes[1]);
+        assert_eq!(section.tag, "section");
+        assert_eq!(section.classes(), vec!["grid", "grid-cols-4"]);
+        assert_eq!(section.attribute("data-empty"), Some(""));
+
+        let children: Vec<_> = section.child_elements().collect();
+        assert_eq!(children.len(), 3);
+        assert_eq!(children[0].describe(), "<launch-button#save>");
+        assert_eq!(children[0].attribute("variant"), Some("primary"));
+        assert_eq!(children[0].text(), "Save & exit");
+        assert_eq!(children[1].tag, "img");
+        assert_eq!(children[2].tag, "launch-text");
+    }
+
+    #[test]
+    fn unclosed_elements_are_closed_with_their_parent() {
+        let nodes = parse("<div><p>One<p>Two</div></span>3 < 4 &copy; &#65;&#x42;");
+
+        let div = element(&nodes[0]);
+        assert_eq!(div.text(), "One Two");
+        assert_eq!(nodes[1], Node::Text("3 < 4 &copy; AB".into()));
+    }
+
+    #[test]
+    fn non_ascii_text_is_kept() {
+        let nodes = parse("<p>é</p>über <b>größer</b>");
+
+        assert_eq!(element(&nodes[0]).text(), "é");
+        assert_eq!(nodes[1], Node::Text("über ".into()));
+        assert_eq!(element(&nodes[2]).text(), "größer");
+    }
+
+    #[test]
+    fn raw_text_is_not_parsed() {
+        let nodes = parse("<style>.a > .b { color: red }</style><div></div>");
+
+        assert_eq!(element(&nodes[0]).text(), ".a > .b { color: red }");
+        assert_eq!(element(&nodes[1]).tag, "div");
+    }
+}
@@ -0,0 +1,958 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::{
+    component::{ParameterKind, ParameterSchema, TEXT_PARAMETER},
+    document::{
+        ComponentDocument, LayoutDocument, LayoutKindDocument, PageDocument, PlacementDocument,
+    },
+    export::style::{Display, Length, Position},
+    page::{
+        layout::{
+            flex::{FlexItem, FlexLayout},
+            free::SNAP_GRID,
+            grid::{Block, GridItem, GridLayout, GridMetrics},
+            ChildPlacement, LayoutKind,
+        },
+        metadata::PageMetadata,
+    },
+};
+
+mod classes;
+mod html;
+
+use classes::Classes;
+use html::{HtmlElement, Node};
+
+/// Height of imported flex and free layouts without an `h-*` class, as new layouts in the editor have
+const DEFAULT_HEIGHT: u32 = 76;
+
+/// Rows that imported grid items can be placed into,
+/// classes like `row-start-99999` would otherwise allocate a huge grid
+const MAX_ROWS: usize = 100;
+
+/// Elements that are skipped without a warning, as they do not show anything on a page
+const IGNORED_ELEMENTS: [&str; 8] = [
+    "head", "title", "meta", "link", "script", "style", "template", "br",
+];
+
+/// Component that elements can be imported as, built from a registered `ComponentDescriptor`
+#[derive(Debug, Clone)]
+pub struct ImportComponent {
+    /// Tag name of the descriptor
+    pub tag_name: String,
+    /// Name that imported components get
+    pub name: String,
+    /// Parameters of the descriptor, that attribute values are validated with
+    pub parameters: Vec<ParameterSchema>,
+}
+
+impl ImportComponent {
+    fn parameter(&self, name: &str) -> Option<&ParameterSchema> {
+        self.parameters.iter().find(|schema| schema.name == name)
+    }
+}
+
+/// Result of an html import
+#[derive(Debug)]
+pub struct Import {
+    /// Imported page, to be instantiated in the workspace
+    pub page: PageDocument,
+    /// Everything that could not be mapped to the layout model, one message per problem
+    pub warnings: Vec<String>,
+}
+
+/// Import a fragment of html with Tailwind classes as a page
+///
+/// Grid, flex and relative containers with absolutely positioned children become layouts,
+/// elements with the tag name of a registered component become components,
+/// other elements are looked through. Components outside of any layout are put into a new flex layout
+///
+/// # Arguments
+/// * `name` - name of the page
+/// * `width` - width of the page in px
+/// * `components` - components that elements can be imported as
+/// * `ids` - stable ids that are taken already, imported items with these ids get new ones
+pub fn import_html(
+    html: &str,
+    name: &str,
+    width: u32,
+    components: &[ImportComponent],
+    ids: HashSet<String>,
+) -> Import {
+    let nodes = html::parse(html);
+    let mut importer = Importer {
+        components,
+        ids,
+        warnings: Vec::new(),
+    };
+
+    let mut items = Vec::new();
+    importer.collect_items(&nodes, &mut items);
+
+    let mut layouts = Vec::new();
+    let mut loose = Vec::new();
+    for item in items {
+        match item {
+            Item::Layout(element, kind, classes) => {
+                importer.wrap_components(&mut loose, width, &mut layouts);
+                layouts.push(importer.layout(element, kind, &classes, item_width(&classes, width)));
+            }
+            Item::Component(..) => loose.push(item),
+        }
+    }
+    importer.wrap_components(&mut loose, width, &mut layouts);
+
+    Import {
+        page: PageDocument {
+            name: name.into(),
+            width,
+            breakpoints: Vec::new(),
+            breakpoint: None,
+            metadata: PageMetadata::default(),
+            layouts,
+        },
+        warnings: importer.warnings,
+    }
+}
+
+/// Kind of an imported layout
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Free,
+    Flex,
+    Grid,
+}
+
+impl Kind {
+    /// Kind of the layout that an element is imported as, `None` if it is not a layout
+    fn of(element: &HtmlElement, classes: &Classes) -> Option<Self> {
+        match classes.display {
+            Some(Display::Grid) => Some(Self::Grid),
+            Some(Display::Flex) => Some(Self::Flex),
+            _ if classes.position.is_some()
+                && element
+                    .child_elements()
+                    .any(|child| child.classes().contains(&"absolute")) =>
+            {
+                Some(Self::Free)
+            }
+            _ => None,
+        }
+    }
+
+    /// Name of the imported layouts, as new layouts in the editor are named
+    fn name(self) -> &'static str {
+        match self {
+            Self::Free => "Free",
+            Self::Flex => "Flex",
+            Self::Grid => "Grid",
+        }
+    }
+}
+
+/// Element that is imported as an item of a layout
+enum Item<'a> {
+    Component(&'a HtmlElement, &'a ImportComponent, Classes),
+    Layout(&'a HtmlElement, Kind, Classes),
+}
+
+impl<'a> Item<'a> {
+    fn classes(&self) -> &Classes {
+        match self {
+            Self::Component(_, _, classes) | Self::Layout(_, _, classes) => classes,
+        }
+    }
+}
+
+/// Width of an item in px, its `w-*` class or the width of the parent
+fn item_width(classes: &Classes, parent: u32) -> u32 {
+    match classes.width {
+        Some(Length::Px(px)) if px > 0 => px as u32,
+        _ => parent,
+    }
+}
+
+/// Beginning of a long text, for the warnings
+fn excerpt(text: &str) -> String {
+    const MAX_CHARS: usize = 32;
+
+    let mut excerpt: String = text.chars().take(MAX_CHARS).collect();
+    if text.chars().count() > MAX_CHARS {
+        excerpt.push('…');
+    }
+    excerpt
+}
+
+struct Importer<'a> {
+    components: &'a [ImportComponent],
+    /// Ids that are taken, by the workspace or by the items imported so far
+    ids: HashSet<String>,
+    warnings: Vec<String>,
+}
+
+impl<'a> Importer<'a> {
+    fn warn(&mut self, element: &HtmlElement, message: &str) {
+        self.warnings
+            .push(format!("{}: {}", element.describe(), message));
+    }
+
+    /// Report the classes and inline styles of an imported element, that do not map to the layout model
+    fn warn_styles(&mut self, element: &HtmlElement, classes: &Classes) {
+        if !classes.unknown.is_empty() {
+            let unknown: Vec<String> = classes
+                .unknown
+                .iter()
+                .map(|class| format!("`{}`", class))
+                .collect();
+            self.warn(
+                element,
+                &format!("classes {} are not imported", unknown.join(", ")),
+            );
+        }
+
+        if element
+            .attribute("style")
+            .is_some_and(|style| !style.trim().is_empty())
+        {
+            self.warn(element, "inline styles are not imported");
+        }
+    }
+
+    /// Stable id of an imported element, `None` to generate a new one
+    fn id(&mut self, element: &HtmlElement) -> Option<String> {
+        let id = element.attribute("id")?.trim();
+
+        if id.is_empty() {
+            None
+        } else if self.ids.insert(id.to_owned()) {
+            Some(id.to_owned())
+        } else {
+            self.warn(
+                element,
+                &format!("id `{}` is already used, a new one is generated", id),
+            );
+            None
+        }
+    }
+
+    /// Find the layouts and components among the nodes, looking through the other elements
+    fn collect_items(&mut self, nodes: &'a [Node], items: &mut Vec<Item<'a>>) {
+        for node in nodes {
+            let element = match node {
+                Node::Element(element) => element,
+                Node::Text(text) => {
+                    if !text.trim().is_empty() {
+                        self.warnings.push(format!(
+                            "text `{}` outside of a component is not imported",
+                            excerpt(text.trim())
+                        ));
+                    }
+                    continue;
+                }
+            };
+
+            if IGNORED_ELEMENTS.contains(&element.tag.as_str()) {
+                continue;
+            }
+
+            let classes = Classes::parse(&element.classes());
+            let component = self
+                .components
+                .iter()
+                .find(|component| component.tag_name == element.tag);
+
+            if let Some(component) = component {
+                items.push(Item::Component(element, component, classes));
+            } else if let Some(kind) = Kind::of(element, &classes) {
+                items.push(Item::Layout(element, kind, classes));
+            } else if element.child_elements().next().is_some() {
+                // Wrappers like `main` or `body`, their content is imported in their place
+                self.collect_items(&element.children, items);
+            } else if element.tag.contains('-') {
+                self.warn(element, "not a registered component");
+            } else {
+                self.warn(element, "not a layout or a registered component");
+            }
+        }
+    }
+
+    /// Put the components found outside of any layout into a new flex layout
+    fn wrap_components(
+        &mut self,
+        items: &mut Vec<Item<'a>>,
+        width: u32,
+        layouts: &mut Vec<LayoutDocument>,
+    ) {
+        if items.is_empty() {
+            return;
+        }
+
+        self.warnings.push(format!(
+            "{} component(s) outside of a layout are put into a new flex layout",
+            items.len()
+        ));
+
+        let kind = LayoutKind::Flex {
+            flex: FlexLayout::new(width, DEFAULT_HEIGHT),
+        };
+        let mut layout = LayoutDocument {
+            id: None,
+            name: Kind::Flex.name().into(),
+            width,
+            height: DEFAULT_HEIGHT,
+            kind: kind.to_document(),
+            components: Vec::new(),
+            placement: None,
+            breakpoints: BTreeMap::new(),
+            children: Vec::new(),
+        };
+
+        for item in items.drain(..) {
+            if let Item::Component(element, component, classes) = item {
+                let placement = PlacementDocument::Flex {
+                    item: FlexItem::default(),
+                };
+                let component = self.component(element, component, &classes, placement);
+                layout.components.push(component);
+            }
+        }
+
+        layouts.push(layout);
+    }
+
+    /// Import an element as a layout, together with its items
+    fn layout(
+        &mut self,
+        element: &'a HtmlElement,
+        kind: Kind,
+        classes: &Classes,
+        width: u32,
+    ) -> LayoutDocument {
+        self.warn_styles(element, classes);
+
+        let mut layout = LayoutDocument {
+            id: self.id(element),
+            name: kind.name().into(),
+            width,
+            height: match classes.height {
+                Some(Length::Px(px)) if px > 0 => px as u32,
+                _ => DEFAULT_HEIGHT,
+            },
+            kind: LayoutKindDocument::Free {
+                snap_grid: SNAP_GRID,
+                snap_to_items: true,
+            },
+            components: Vec::new(),
+            placement: None,
+            breakpoints: BTreeMap::new(),
+            children: Vec::new(),
+        };
+
+        let mut items = Vec::new();
+        self.collect_items(&element.children, &mut items);
+
+        match kind {
+            Kind::Free => self.free_items(element, classes, &mut layout, items),
+            Kind::Flex => self.flex_items(classes, &mut layout, items),
+            Kind::Grid => self.grid_items(element, classes, &mut layout, items),
+        }
+
+        layout
+    }
+
+    /// Place items at their `left-*` and `top-*` offsets
+    ///
+    /// Layouts without a height are as high as their items
+    fn free_items(
+        &mut self,
+        element: &HtmlElement,
+        classes: &Classes,
+        layout: &mut LayoutDocument,
+        items: Vec<Item<'a>>,
+    ) {
+        let mut bottom = 0;
+
+        for item in items {
+            let item_classes = item.classes();
+            let position = (
+                item_classes.left.unwrap_or(0),
+                item_classes.top.unwrap_or(0),
+            );
+
+            match item {
+                Item::Component(child, component, classes) => {
+                    if classes.position != Some(Position::Absolute) {
+                        self.warn(
+                            child,
+                            "not absolutely positioned, placed at the top left corner",
+                        );
+                    }
+
+                    let size = match (classes.width, classes.height) {
+                        (Some(Length::Px(width)), Some(Length::Px(height))) => {
+                            bottom = bottom.max(position.1 + height);
+                            Some((width as f64, height as f64))
+                        }
+                        _ => None,
+                    };
+
+                    let placement = PlacementDocument::Free { position, size };
+                    let component = self.component(child, component, &classes, placement);
+                    layout.components.push(component);
+                }
+                Item::Layout(child, kind, classes) => {
+                    let width = item_width(&classes, layout.width);
+                    let mut child = self.layout(child, kind, &classes, width);
+                    child.placement = Some(ChildPlacement::Free { position });
+
+                    bottom = bottom.max(position.1 + child.height as i32);
+                    layout.children.push(child);
+                }
+            }
+        }
+
+        if classes.height.is_none() && bottom > 0 {
+            layout.height = bottom as u32;
+        } else if classes.height.is_none() {
+            self.warn(element, "free layout without a height, imported 76px high");
+        }
+
+        layout.kind = LayoutKindDocument::Free {
+            snap_grid: SNAP_GRID,
+            snap_to_items: true,
+        };
+    }
+
+    /// Keep the items in their order, with their flex properties
+    fn flex_items(&mut self, classes: &Classes, layout: &mut LayoutDocument, items: Vec<Item<'a>>) {
+        for item in items {
+            match item {
+                Item::Component(child, component, classes) => {
+                    let placement = PlacementDocument::Flex {
+                        item: FlexItem {
+                            grow: classes.grow.unwrap_or(0),
+                            shrink: classes.shrink.unwrap_or(1),
+                            basis: classes.basis,
+                            order: classes.order.unwrap_or(0),
+                        },
+                    };
+                    let component = self.component(child, component, &classes, placement);
+                    layout.components.push(component);
+                }
+                Item::Layout(child, kind, classes) => {
+                    let width = item_width(&classes, layout.width);
+                    let mut child = self.layout(child, kind, &classes, width);
+                    child.placement = Some(ChildPlacement::Flex);
+
+                    layout.children.push(child);
+                }
+            }
+        }
+
+        // Without classes, the items are laid out as the browsers lay them out
+        layout.kind = LayoutKindDocument::Flex {
+            justify: classes
+                .justify
+                .clone()
+                .unwrap_or_else(|| "justify-start".into()),
+            align: classes
+                .align
+                .clone()
+                .unwrap_or_else(|| "items-stretch".into()),
+            direction: classes.direction.unwrap_or_default(),
+            wrap: classes.wrap.unwrap_or(false),
+            gap: classes.gap.unwrap_or(0),
+        };
+    }
+
+    /// Place items into the cells of the grid, marking the cells as taken
+    ///
+    /// Items without a position are placed into the first free cells, row by row,
+    /// items that overlap the ones placed before them are moved to the nearest free cells
+    fn grid_items(
+        &mut self,
+        element: &HtmlElement,
+        classes: &Classes,
+        layout: &mut LayoutDocument,
+        items: Vec<Item<'a>>,
+    ) {
+        let columns = match classes.grid_columns {
+            Some(columns) if columns > 0 => columns,
+            _ => {
+                self.warn(
+                    element,
+                    "grid without a `grid-cols-*` class, imported with a single column",
+                );
+                1
+            }
+        };
+        let gap = classes.gap.unwrap_or(0);
+
+        let mut metrics = GridMetrics::new(layout.width, columns, 0, gap);
+        // Square cells, like the ones of a new grid in the editor
+        metrics.row_height = classes.auto_rows.unwrap_or(metrics.column_width);
+
+        let mut grid = GridLayout::new(columns, 1);
+
+        for (n, item) in items.into_iter().enumerate() {
+            let block = self.grid_block(&mut grid, &item);
+            // Nothing is instantiated yet, the keys only tell the imported items apart
+            grid.insert_component(
+                GridItem::Layout(n),
+                (block.x, block.y),
+                (block.width, block.height),
+            );
+
+            let pos = (block.x, block.y);
+            let size = (block.width, block.height);
+
+            match item {
+                Item::Component(child, component, classes) => {
+                    let placement = PlacementDocument::Grid { pos, size };
+                    let component = self.component(child, component, &classes, placement);
+                    layout.components.push(component);
+                }
+                Item::Layout(child, kind, classes) => {
+                    let width = metrics.span_width(size.0);
+                    let mut child = self.layout(child, kind, &classes, width);
+                    child.placement = Some(ChildPlacement::Grid { pos, size });
+
+                    layout.children.push(child);
+                }
+            }
+        }
+
+        let rows = grid.used_rows().max(1);
+        layout.height = metrics.height_for_rows(rows);
+        layout.kind = LayoutKindDocument::Grid {
+            column_width: metrics.column_width,
+            row_height: Some(metrics.row_height),
+            gap,
+            columns,
+            rows,
+            collision: Default::default(),
+            auto_grow: false,
+            auto_shrink: false,
+            breakpoint_columns: BTreeMap::new(),
+        };
+    }
+
+    /// Cells that a grid item is placed into
+    fn grid_block(&mut self, grid: &mut GridLayout, item: &Item) -> Block {
+        let (element, classes) = match item {
+            Item::Component(element, _, classes) | Item::Layout(element, _, classes) => {
+                (*element, classes)
+            }
+        };
+        let columns = grid.width();
+
+        let mut width = classes.col_span.unwrap_or(1).max(1);
+        let mut height = classes.row_span.unwrap_or(1).max(1);
+        if width > columns {
+            self.warn(
+                element,
+                &format!("spans {} columns, but the grid has {}", width, columns),
+            );
+            width = columns;
+        }
+        if height > MAX_ROWS {
+            self.warn(
+                element,
+                &format!(
+                    "spans {} rows, but at most {} are imported",
+                    height, MAX_ROWS
+                ),
+            );
+            height = MAX_ROWS;
+        }
+
+        let (x, y) = match (classes.col_start, classes.row_start) {
+            (Some(x), Some(y)) if x >= 1 && y >= 1 => (x, y),
+            // Like the browsers do, items without a position flow into the first free cells
+            _ => return first_free_block(grid, width, height),
+        };
+
+        let max_x = columns - width + 1;
+        if x > max_x {
+            self.warn(
+                element,
+                &format!("does not fit into the grid, moved to column {}", max_x),
+            );
+        }
+        let max_y = MAX_ROWS - height + 1;
+        if y > max_y {
+            self.warn(
+                element,
+                &format!("does not fit into the grid, moved to row {}", max_y),
+            );
+        }
+
+        let block = Block {
+            x: x.min(max_x),
+            y: y.min(max_y),
+            width,
+            height,
+        };
+        grow_rows(grid, block.y + block.height - 1);
+
+        if grid.get_block_component_indices(block).is_empty() {
+            return block;
+        }
+
+        let free = grid.nearest_free_block(block);
+        grow_rows(grid, free.y + free.height - 1);
+        self.warn(
+            element,
+            &format!(
+                "overlaps another item, moved to column {}, row {}",
+                free.x, free.y
+            ),
+        );
+        free
+    }
+
+    /// Import an element as a component, its attributes become the values of the parameters
+    fn component(
+        &mut self,
+        element: &HtmlElement,
+        component: &ImportComponent,
+        classes: &Classes,
+        placement: PlacementDocument,
+    ) -> ComponentDocument {
+        self.warn_styles(element, classes);
+
+        if classes.display == Some(Display::None) {
+            self.warn(element, "hidden component is imported visible");
+        }
+
+        let mut attributes = BTreeMap::new();
+        for (name, value) in element.attributes.iter() {
+            if ["id", "class", "style"].contains(&name.as_str()) {
+                continue;
+            }
+
+            let schema = match component.parameter(name) {
+                Some(schema) => schema,
+                None => {
+                    self.warn(
+                        element,
+                        &format!("attribute `{}` is not a parameter of the component", name),
+                    );
+                    continue;
+                }
+            };
+
+            // Boolean attributes of html are true without a value, `<launch-button disabled>`
+            let value = match schema.kind {
+                ParameterKind::Boolean if value.is_empty() => "true",
+                _ => value.as_str(),
+            };
+
+            match schema.validate(value) {
+                Ok(value) if value.is_empty() => {}
+                Ok(value) => {
+                    attributes.insert(name.clone(), value);
+                }
+                Err(err) => self.warn(element, &format!("attribute `{}`: {}", name, err)),
+            }
+        }
+
+        let text = element.text();
+        if !text.is_empty() && !attributes.contains_key(TEXT_PARAMETER) {
+            match component
+                .parameter(TEXT_PARAMETER)
+                .map(|schema| schema.validate(&text))
+            {
+                Some(Ok(text)) => {
+                    attributes.insert(TEXT_PARAMETER.into(), text);
+                }
+                Some(Err(err)) => self.warn(element, &format!("text content: {}", err)),
+                None => self.warn(element, "the component does not have any text content"),
+            }
+        }
+
+        ComponentDocument {
+            id: self.id(element),
+            name: component.name.clone(),
+            tag_name: component.tag_name.clone(),
+            attributes,
+            placement,
+            breakpoints: BTreeMap::new(),
+        }
+    }
+}
+
+/// Add rows to the grid, so that it has at least the given number of them
+fn grow_rows(grid: &mut GridLayout, rows: usize) {
+    if rows > grid.height() {
+        grid.resize(grid.width(), rows);
+    }
+}
+
+/// First free block of cells, searched row by row
+fn first_free_block(grid: &mut GridLayout, width: usize, height: usize) -> Block {
+    let columns = grid.width();
+
+    for y in 1.. {
+        grow_rows(grid, y + height - 1);
+
+        let free = (1..=columns - width + 1)
+            .map(|x| Block {
+                x,
+                y,
+                width,
+                height,
+            })
+            .find(|block| grid.get_block_component_indices(*block).is_empty());
+
+        if let Some(block) = free {
+            return block;
+        }
+    }
+
+    unreachable!("the rows below the last item are always free")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn components() -> Vec<ImportComponent> {
+        vec![
+            ImportComponent {
+                tag_name: "launch-button".into(),
+                name: "Button".into(),
+                parameters: vec![
+                    ParameterSchema::from_json("innertext", r#""text""#),
+                    ParameterSchema::from_json(
+                        "variant",
+                        r#"{ "type": "select", "options": ["primary", "secondary"] }"#,
+                    ),
+                    ParameterSchema::from_json("disabled", r#""boolean""#),
+                ],
+            },
+            ImportComponent {
+                tag_name: "launch-table".into(),
+                name: "Table".into(),
+                parameters: Vec::new(),
+            },
+        ]
+    }
+
+    fn import(html: &str) -> Import {
+        let ids = vec!["layout-1".to_owned()].into_iter().collect();
+        import_html(html, "Imported", 908, &components(), ids)
+    }
+
+    #[test]
+    fn grids_are_imported_with_their_occupancy() {
+        let import = import(
+            r#"<main class="relative mx-auto w-227">
+                <section id="hero" class="w-full grid grid-cols-4 auto-rows-76px gap-2">
+                    <launch-button id="save" class="col-start-3 col-span-2 row-start-1 row-span-1" variant="primary">
+                        Save
+                    </launch-button>
+                    <launch-button class="col-start-4 row-start-1">Overlapping</launch-button>
+                    <launch-table class="col-span-3"></launch-table>
+                    <div class="grid grid-cols-2 col-start-1 col-span-2 row-start-3"></div>
+                </section>
+            </main>"#,
+        );
+
+        let layout = &import.page.layouts[0];
+        assert_eq!(layout.id.as_deref(), Some("hero"));
+        assert_eq!((layout.width, layout.height), (908, 76 * 3 + 2 * 8));
+        assert_eq!(
+            layout.kind,
+            LayoutKindDocument::Grid {
+                column_width: 221,
+                row_height: Some(76),
+                gap: 8,
+                columns: 4,
+                rows: 3,
+                collision: Default::default(),
+                auto_grow: false,
+                auto_shrink: false,
+                breakpoint_columns: BTreeMap::new(),
+            }
+        );
+
+        let placements: Vec<_> = layout
+            .components
+            .iter()
+            .map(|component| component.placement.clone())
+            .collect();
+        assert_eq!(
+            placements,
+            vec![
+                PlacementDocument::Grid {
+                    pos: (3, 1),
+                    size: (2, 1)
+                },
+                PlacementDocument::Grid {
+                    pos: (2, 1),
+                    size: (1, 1)
+                },
+                PlacementDocument::Grid {
+                    pos: (1, 2),
+                    size: (3, 1)
+                },
+            ]
+        );
+
+        let save = &layout.components[0];
+        assert_eq!(save.id.as_deref(), Some("save"));
+        assert_eq!(save.name, "Button");
+        assert_eq!(save.attributes["innertext"], "Save");
+        assert_eq!(save.attributes["variant"], "primary");
+
+        let nested = &layout.children[0];
+        assert_eq!(
+            nested.placement,
+            Some(ChildPlacement::Grid {
+                pos: (1, 3),
+                size: (2, 1)
+            })
+        );
+        assert_eq!(nested.width, 221 * 2 + 8);
+
+        assert_eq!(
+            import.warnings,
+            vec!["<launch-button>: overlaps another item, moved to column 2, row 1"]
+        );
+    }
+
+    #[test]
+    fn free_and_flex_layouts_keep_their_items() {
+        let import = import(
+            r#"<div id="layout-1" class="relative h-30">
+                <launch-table class="absolute left-4 -top-2 w-80 h-24"></launch-table>
+                <launch-button class="absolute left-[100px] top-10 bg-red-500" size="lg">Go</launch-button>
+            </div>
+            <div class="flex flex-col items-center gap-4">
+                <launch-button class="grow basis-20 -order-1"></launch-button>
+            </div>"#,
+        );
+
+        let free = &import.page.layouts[0];
+        assert_eq!(free.id, None);
+        assert_eq!(free.height, 120);
+        assert_eq!(
+            free.components[0].placement,
+            PlacementDocument::Free {
+                position: (16, -8),
+                size: Some((320.0, 96.0))
+            }
+        );
+        assert_eq!(
+            free.components[1].placement,
+            PlacementDocument::Free {
+                position: (100, 40),
+                size: None
+            }
+        );
+
+        let flex = &import.page.layouts[1];
+        assert_eq!(flex.height, DEFAULT_HEIGHT);
+        assert_eq!(
+            flex.kind,
+            LayoutKindDocument::Flex {
+                justify: "justify-start".into(),
+                align: "items-center".into(),
+                direction: crate::page::layout::flex::FlexDirection::Column,
+                wrap: false,
+                gap: 16,
+            }
+        );
+        assert_eq!(
+            flex.components[0].placement,
+            PlacementDocument::Flex {
+                item: FlexItem {
+                    grow: 1,
+                    shrink: 1,
+                    basis: Some(80),
+                    order: -1
+                }
+            }
+        );
+
+        assert_eq!(
+            import.warnings,
+            vec![
+                "<div#layout-1>: id `layout-1` is already used, a new one is generated",
+                "<launch-button>: classes `bg-red-500` are not imported",
+                "<launch-button>: attribute `size` is not a parameter of the component",
+            ]
+        );
+    }
+
+    #[test]
+    fn everything_else_is_reported() {
+        let import = import(
+            "<p>Some text</p>Loose text<launch-chart></launch-chart>\
+             <launch-table>Rows</launch-table><launch-button variant=tertiary disabled></launch-button>",
+        );
+
+        let layout = &import.page.layouts[0];
+        assert!(matches!(layout.kind, LayoutKindDocument::Flex { .. }));
+        assert_eq!(layout.components.len(), 2);
+
+        let button = &layout.components[1];
+        assert_eq!(button.attributes.get("variant"), None);
+        assert_eq!(button.attributes["disabled"], "true");
+
+        assert_eq!(
+            import.warnings,
+            vec![
+                "<p>: not a layout or a registered component",
+                "text `Loose text` outside of a component is not imported",
+                "<launch-chart>: not a registered component",
+                "2 component(s) outside of a layout are put into a new flex layout",
+                "<launch-table>: the component does not have any text content",
+                "<launch-button>: attribute `variant`: Expected one of `primary`, `secondary`",
+            ]
+        );
+    }
+
+    #[test]
+    fn huge_rows_are_capped() {
+        let import = import(
+            r#"<section class="grid grid-cols-2">
+                <launch-button class="col-start-1 row-start-99999999">Far</launch-button>
+                <launch-table class="col-start-2 row-start-1 row-span-99999999"></launch-table>
+            </section>"#,
+        );
+
+        let layout = &import.page.layouts[0];
+        let placements: Vec<_> = layout
+            .components
+            .iter()
+            .map(|component| component.placement.clone())
+            .collect();
+        assert_eq!(
+            placements,
+            vec![
+                PlacementDocument::Grid {
+                    pos: (1, MAX_ROWS),
+                    size: (1, 1)
+                },
+                PlacementDocument::Grid {
+                    pos: (2, 1),
+                    size: (1, MAX_ROWS)
+                },
+            ]
+        );
+        assert!(matches!(
+            layout.kind,
+            LayoutKindDocument::Grid { rows: MAX_ROWS, .. }
+        ));
+
+        assert_eq!(
+            import.warnings,
+            vec![
+                "<launch-button>: does not fit into the grid, moved to row 100",
+                "<launch-table>: spans 99999999 rows, but at most 100 are imported",
+            ]
+        );
+    }
+}
@@ -1,9 +1,14 @@
 use wasm_bindgen::prelude::*;
 
 pub mod component;
+pub mod document;
+pub mod export;
+pub mod import;
 
+mod backend;
 mod drag_controller;
 mod editor;
+mod ids;
 mod utils;
 
 mod html_elements;
@@ -0,0 +1,166 @@
+use serde::{Deserialize, Serialize};
+
+use super::layout::grid::{Block, GridItem, GridLayout};
+
+/// Name of the breakpoint that every new page starts with
+pub const DEFAULT_BREAKPOINT: &str = "desktop";
+
+/// Named page width, at which components can be arranged differently
+///
+/// For example `mobile` at 375px, `tablet` at 768px and `desktop` at 1280px
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Breakpoint {
+    /// Name of a breakpoint
+    pub name: String,
+    /// Width of the page at the breakpoint in px
+    pub width: u32,
+}
+
+impl Breakpoint {
+    pub fn new(name: &str, width: u32) -> Self {
+        Self {
+            name: name.into(),
+            width,
+        }
+    }
+}
+
+/// Placement of a component at a single breakpoint
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BreakpointPlacement {
+    /// Component placed in a grid layout
+    Grid {
+        /// Top left cell of a component (starting from 1)
+        pos: (usize, usize),
+        /// Size of a component in cells
+        size: (usize, usize),
+    },
+    /// Component is shown, and positioned by its layout
+    Visible,
+    /// Component is not shown at the breakpoint
+    Hidden,
+}
+
+/// Scale a block between two column counts, keeping its relative position and width
+///
+/// # Arguments
+/// * `block` - Block placed in a grid with `from` columns
+/// * `from` - Column count of the old grid
+/// * `to` - Column count of the new grid
+pub fn scale_block(block: Block, from: usize, to: usize) -> Block {
+    if from == to || from == 0 {
+        return block;
+    }
+
+    let x = (block.x - 1) * to / from + 1;
+    let width = ((block.width * to) as f64 / from as f64).round().max(1.0) as usize;
+
+    Block { x, width, ..block }
+}
+
+/// Place components into a grid, as close to their requested blocks as possible
+///
+/// Blocks wider than the grid are narrowed, colliding blocks are moved down,
+/// and the grid gets extra rows if needed
+///
+/// Returns the final blocks of the components, they are already inserted into the grid
+pub fn arrange(
+    grid: &mut GridLayout,
+    mut requested: Vec<(GridItem, Block)>,
+) -> Vec<(GridItem, Block)> {
+    requested.sort_by_key(|(_, b)| (b.y, b.x));
+
+    let columns = grid.width();
+
+    requested
+        .into_iter()
+        .map(|(index, block)| {
+            let width = block.width.clamp(1, columns);
+            let mut block = Block {
+                x: block.x.clamp(1, columns + 1 - width),
+                y: block.y.max(1),
+                width,
+                height: block.height.max(1),
+            };
+
+            loop {
+                let bottom = block.y + block.height - 1;
+                if bottom > grid.height() {
+                    grid.resize(columns, bottom);
+                }
+
+                if !grid.is_component_overlapping(index, block) {
+                    break;
+                }
+
+                block.y += 1;
+            }
+
+            grid.insert_component(index, (block.x, block.y), (block.width, block.height));
+            (index, block)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use generational_arena::Index;
+
+    use super::*;
+
+    fn index(n: usize) -> GridItem {
+        GridItem::Component(Index::from_raw_parts(n, 0))
+    }
+
+    fn block(x: usize, y: usize, width: usize, height: usize) -> Block {
+        Block {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn blocks_are_scaled_between_column_counts() {
+        assert_eq!(scale_block(block(7, 2, 6, 1), 12, 4), block(3, 2, 2, 1));
+        assert_eq!(scale_block(block(1, 1, 1, 1), 12, 4), block(1, 1, 1, 1));
+        assert_eq!(scale_block(block(3, 1, 2, 2), 4, 12), block(7, 1, 6, 2));
+    }
+
+    #[test]
+    fn arrange_keeps_requested_blocks_when_possible() {
+        let mut grid = GridLayout::new(4, 2);
+        let arranged = arrange(
+            &mut grid,
+            vec![(index(0), block(1, 1, 2, 1)), (index(1), block(3, 1, 2, 2))],
+        );
+
+        assert_eq!(
+            arranged,
+            vec![(index(0), block(1, 1, 2, 1)), (index(1), block(3, 1, 2, 2))]
+        );
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.get_cell_component_index(4, 2), Some(index(1)));
+    }
+
+    #[test]
+    fn arrange_stacks_colliding_blocks() {
+        let mut grid = GridLayout::new(4, 1);
+        let arranged = arrange(
+            &mut grid,
+            vec![
+                (index(0), block(1, 1, 4, 1)),
+                // Too wide and too far to the right
+                (index(1), block(9, 1, 6, 1)),
+            ],
+        );
+
+        assert_eq!(
+            arranged,
+            vec![(index(0), block(1, 1, 4, 1)), (index(1), block(1, 2, 4, 1))]
+        );
+        assert_eq!(grid.height(), 2);
+    }
+}
@@ -0,0 +1,257 @@
+use crate::component::SpanLimits;
+
+use super::{
+    grid::{Block, GridItem, GridLayout, GridMetrics},
+    LayoutKind, Rect,
+};
+
+/// Kind that a layout is converted to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConvertTarget {
+    Free,
+    Flex,
+    Grid,
+}
+
+impl ConvertTarget {
+    /// Parse the target from the name used by the editor api ("free", "flex" or "grid")
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "free" => Some(Self::Free),
+            "flex" => Some(Self::Flex),
+            "grid" => Some(Self::Grid),
+            _ => None,
+        }
+    }
+
+    /// Whether a layout of the kind is already of the target kind
+    pub fn is_kind_of(self, kind: &LayoutKind) -> bool {
+        matches!(
+            (self, kind),
+            (Self::Free, LayoutKind::Free { .. })
+                | (Self::Flex, LayoutKind::Flex { .. })
+                | (Self::Grid, LayoutKind::Grid { .. })
+        )
+    }
+}
+
+/// Item of a layout that is being converted to a different kind, a component or a nested layout
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConvertItem {
+    /// Key of the item in the cells of a grid layout
+    pub index: GridItem,
+    /// Rendered rect of the item, relative to the layout
+    pub rect: Rect,
+    /// Cells taken by the item, `None` if the layout is not a grid
+    pub block: Option<Block>,
+    /// Span limits of the item in grid layouts
+    pub limits: SpanLimits,
+}
+
+/// Placement of an item in the converted layout
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ItemPlacement {
+    Grid(Block),
+    Free {
+        /// Position in px, relative to the left-top point of the layout
+        position: (i32, i32),
+    },
+    Flex,
+}
+
+/// Result of a conversion
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Conversion {
+    /// Placements of the items, in the order of the converted layout
+    pub placed: Vec<(GridItem, ItemPlacement)>,
+    /// Items that could not be placed in the converted layout
+    pub unplaced: Vec<GridItem>,
+}
+
+/// Reading order of the items, grid items follow their cells, other items their rendered position
+fn reading_order(items: &[ConvertItem]) -> Vec<ConvertItem> {
+    let key = |item: &ConvertItem| match item.block {
+        Some(block) => (block.y as f64, block.x as f64),
+        None => (item.rect.0 .1, item.rect.0 .0),
+    };
+
+    let mut items = items.to_vec();
+    items.sort_by(|a, b| key(a).partial_cmp(&key(b)).unwrap());
+    items
+}
+
+/// Convert items to a free layout, keeping them where they were rendered
+pub fn to_free(items: &[ConvertItem]) -> Conversion {
+    let placed = items
+        .iter()
+        .map(|item| {
+            let ((x, y), _) = item.rect;
+            let position = (x.round().max(0.0) as i32, y.round().max(0.0) as i32);
+
+            (item.index, ItemPlacement::Free { position })
+        })
+        .collect();
+
+    Conversion {
+        placed,
+        unplaced: Vec::new(),
+    }
+}
+
+/// Convert items to a flex layout, ordering them the way they are read (row by row)
+pub fn to_flex(items: &[ConvertItem]) -> Conversion {
+    let placed = reading_order(items)
+        .iter()
+        .map(|item| (item.index, ItemPlacement::Flex))
+        .collect();
+
+    Conversion {
+        placed,
+        unplaced: Vec::new(),
+    }
+}
+
+/// Convert items to a grid layout, placing them into the nearest free cells
+///
+/// The grid gets extra rows if needed, items that can not be narrowed down
+/// to the width of the grid are left out
+///
+/// # Arguments
+/// * `grid` - empty grid of the converted layout, the items are inserted into it
+/// * `metrics` - size of the grid cells
+pub fn to_grid(items: &[ConvertItem], grid: &mut GridLayout, metrics: &GridMetrics) -> Conversion {
+    let mut conversion = Conversion::default();
+
+    for item in reading_order(items) {
+        let (pos, size) = item.rect;
+        let (x, y) = metrics.cell_at_offset(pos);
+        let (width, height) = item.limits.clamp(metrics.cells_for_size(size));
+
+        if item.limits.min.0 > grid.width() {
+            conversion.unplaced.push(item.index);
+            continue;
+        }
+
+        let block = grid.nearest_free_block(Block {
+            x,
+            y,
+            width,
+            height,
+        });
+
+        let bottom = block.y + block.height - 1;
+        if bottom > grid.height() {
+            grid.resize(grid.width(), bottom);
+        }
+
+        grid.insert_component(item.index, (block.x, block.y), (block.width, block.height));
+        conversion
+            .placed
+            .push((item.index, ItemPlacement::Grid(block)));
+    }
+
+    conversion
+}
+
+#[cfg(test)]
+mod tests {
+    use generational_arena::Index;
+
+    use super::*;
+
+    fn index(n: usize) -> GridItem {
+        GridItem::Component(Index::from_raw_parts(n, 0))
+    }
+
+    fn item(n: usize, rect: Rect) -> ConvertItem {
+        ConvertItem {
+            index: index(n),
+            rect,
+            block: None,
+            limits: SpanLimits::default(),
+        }
+    }
+
+    fn block(x: usize, y: usize, width: usize, height: usize) -> Block {
+        Block {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// 100px cells without a gap
+    fn metrics() -> GridMetrics {
+        GridMetrics::new(400, 4, 100, 0)
+    }
+
+    #[test]
+    fn free_items_snap_to_nearest_free_cells() {
+        let items = [
+            item(0, ((10.0, 10.0), (180.0, 90.0))),
+            // Overlaps the first item
+            item(1, ((120.0, 40.0), (80.0, 80.0))),
+        ];
+
+        let mut grid = GridLayout::new(4, 1);
+        let conversion = to_grid(&items, &mut grid, &metrics());
+
+        assert_eq!(
+            conversion.placed,
+            vec![
+                (index(0), ItemPlacement::Grid(block(1, 1, 2, 1))),
+                (index(1), ItemPlacement::Grid(block(3, 1, 1, 1))),
+            ]
+        );
+        assert!(conversion.unplaced.is_empty());
+    }
+
+    #[test]
+    fn grid_grows_and_reports_items_that_do_not_fit() {
+        let mut wide = item(2, ((0.0, 0.0), (100.0, 100.0)));
+        wide.limits = SpanLimits {
+            min: (5, 1),
+            max: None,
+        };
+
+        let items = [
+            item(0, ((0.0, 0.0), (400.0, 100.0))),
+            item(1, ((0.0, 50.0), (400.0, 100.0))),
+            wide,
+        ];
+
+        let mut grid = GridLayout::new(4, 1);
+        let conversion = to_grid(&items, &mut grid, &metrics());
+
+        assert_eq!(conversion.placed.len(), 2);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.get_component_block(index(1)), Some(block(1, 2, 4, 1)));
+        assert_eq!(conversion.unplaced, vec![index(2)]);
+    }
+
+    #[test]
+    fn grid_order_becomes_flex_order() {
+        let mut first = item(0, ((0.0, 0.0), (0.0, 0.0)));
+        first.block = Some(block(3, 2, 1, 1));
+        let mut second = item(1, ((0.0, 0.0), (0.0, 0.0)));
+        second.block = Some(block(1, 1, 1, 1));
+        let mut third = item(2, ((0.0, 0.0), (0.0, 0.0)));
+        third.block = Some(block(1, 2, 1, 1));
+
+        let conversion = to_flex(&[first, second, third]);
+        let order: Vec<GridItem> = conversion.placed.iter().map(|(index, _)| *index).collect();
+
+        assert_eq!(order, vec![index(1), index(2), index(0)]);
+    }
+
+    #[test]
+    fn rendered_rects_become_free_positions() {
+        let conversion = to_free(&[item(0, ((12.4, 7.6), (50.0, 20.0)))]);
+
+        assert_eq!(
+            conversion.placed,
+            vec![(index(0), ItemPlacement::Free { position: (12, 8) })]
+        );
+    }
+}
@@ -1,13 +1,308 @@
-use generational_arena::Index;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::RenderBackend;
+
+use super::Rect;
+
+/// Main axis of a flex layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlexDirection {
+    /// Items are laid out from left to right
+    #[default]
+    Row,
+    /// Items are laid out from right to left
+    RowReverse,
+    /// Items are laid out from top to bottom
+    Column,
+    /// Items are laid out from bottom to top
+    ColumnReverse,
+}
+
+impl FlexDirection {
+    pub const ALL: [FlexDirection; 4] = [
+        FlexDirection::Row,
+        FlexDirection::RowReverse,
+        FlexDirection::Column,
+        FlexDirection::ColumnReverse,
+    ];
+
+    /// Tailwind `flex-*` class of the direction
+    pub fn class(self) -> &'static str {
+        match self {
+            Self::Row => "flex-row",
+            Self::RowReverse => "flex-row-reverse",
+            Self::Column => "flex-col",
+            Self::ColumnReverse => "flex-col-reverse",
+        }
+    }
+
+    /// Value of the css `flex-direction` property
+    pub fn css(self) -> &'static str {
+        match self {
+            Self::Row => "row",
+            Self::RowReverse => "row-reverse",
+            Self::Column => "column",
+            Self::ColumnReverse => "column-reverse",
+        }
+    }
+
+    /// Parse the direction from its Tailwind class
+    pub fn from_class(class: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|direction| direction.class() == class)
+    }
+
+    /// Whether the main axis is vertical
+    pub fn is_column(self) -> bool {
+        matches!(self, Self::Column | Self::ColumnReverse)
+    }
+
+    /// Whether the items are laid out against the direction of the axis
+    pub fn is_reversed(self) -> bool {
+        matches!(self, Self::RowReverse | Self::ColumnReverse)
+    }
+}
+
+/// Flex properties of a single item of a flex layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FlexItem {
+    /// Share of the free space that the item takes
+    pub grow: u32,
+    /// Share of the missing space that the item gives up
+    pub shrink: u32,
+    /// Initial size along the main axis in px, `None` for the size of the content
+    pub basis: Option<u32>,
+    /// Visual order of the item, items with the same order keep the order of the layout
+    pub order: i32,
+}
+
+impl Default for FlexItem {
+    fn default() -> Self {
+        Self {
+            grow: 0,
+            shrink: 1,
+            basis: None,
+            order: 0,
+        }
+    }
+}
+
+impl FlexItem {
+    /// Render the flex properties of the item
+    pub fn render(&self, backend: &mut impl RenderBackend) {
+        backend.set_style("flex-grow", &self.grow.to_string());
+        backend.set_style("flex-shrink", &self.shrink.to_string());
+        backend.set_style(
+            "flex-basis",
+            &self
+                .basis
+                .map_or_else(|| "auto".into(), |basis| format!("{}px", basis)),
+        );
+        backend.set_style("order", &self.order.to_string());
+    }
+}
 
 pub struct FlexLayout {
-    components: Vec<Index>,
+    /// Tailwind `justify-*` class applied to the layout
+    justify: String,
+    /// Tailwind `items-*` class applied to the layout
+    align: String,
+
+    /// Main axis of the layout
+    direction: FlexDirection,
+    /// Whether items that do not fit are moved to the next line
+    wrap: bool,
+    /// Space between the items in px
+    gap: u32,
 }
 
 impl FlexLayout {
     pub fn new(_width: u32, _height: u32) -> Self {
         Self {
-            components: Vec::new(),
+            justify: "justify-center".into(),
+            align: "items-center".into(),
+
+            direction: FlexDirection::Row,
+            wrap: true,
+            gap: 0,
         }
     }
+
+    pub fn justify(&self) -> &str {
+        &self.justify
+    }
+
+    pub fn set_justify(&mut self, class: &str) {
+        self.justify = class.into();
+    }
+
+    pub fn align(&self) -> &str {
+        &self.align
+    }
+
+    pub fn set_align(&mut self, class: &str) {
+        self.align = class.into();
+    }
+
+    pub fn direction(&self) -> FlexDirection {
+        self.direction
+    }
+
+    pub fn set_direction(&mut self, direction: FlexDirection) {
+        self.direction = direction;
+    }
+
+    pub fn wrap(&self) -> bool {
+        self.wrap
+    }
+
+    pub fn set_wrap(&mut self, is: bool) {
+        self.wrap = is;
+    }
+
+    pub fn gap(&self) -> u32 {
+        self.gap
+    }
+
+    pub fn set_gap(&mut self, gap: u32) {
+        self.gap = gap;
+    }
+
+    /// Render the direction, wrapping and gap of the layout
+    pub fn render(&self, backend: &mut impl RenderBackend) {
+        for direction in FlexDirection::ALL.iter() {
+            backend.set_class(direction.class(), *direction == self.direction);
+        }
+
+        backend.set_class("flex-wrap", self.wrap);
+        backend.set_class("flex-nowrap", !self.wrap);
+
+        backend.set_style("gap", &format!("{}px", self.gap));
+    }
+
+    /// Index at which an item dropped at a point should be inserted
+    ///
+    /// An item comes before the point if the point is on a later line,
+    /// or on the same line past the middle of the item along the main axis
+    ///
+    /// # Arguments
+    /// * `items` - bounding rects of the items, in the order of the layout
+    /// * `point` - drop point, in the same coordinates as the rects
+    pub fn drop_index(&self, items: &[Rect], point: (f64, f64)) -> usize {
+        let is_column = self.direction.is_column();
+        let is_reversed = self.direction.is_reversed();
+
+        // Split the coordinates into (main axis, cross axis)
+        let axes = |(x, y): (f64, f64)| if is_column { (y, x) } else { (x, y) };
+        let (main, cross) = axes(point);
+
+        items
+            .iter()
+            .position(|(pos, size)| {
+                let (item_main, item_cross) = axes(*pos);
+                let (item_length, item_thickness) = axes(*size);
+
+                let is_before = if cross >= item_cross + item_thickness {
+                    true
+                } else if cross < item_cross {
+                    false
+                } else if is_reversed {
+                    main < item_main + item_length / 2.0
+                } else {
+                    main > item_main + item_length / 2.0
+                };
+
+                !is_before
+            })
+            .unwrap_or(items.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::MemoryBackend;
+
+    /// Three 100x50 items in a row, with the last one wrapped to the second line
+    const ITEMS: [Rect; 3] = [
+        ((0.0, 0.0), (100.0, 50.0)),
+        ((100.0, 0.0), (100.0, 50.0)),
+        ((0.0, 50.0), (100.0, 50.0)),
+    ];
+
+    #[test]
+    fn drop_index_follows_the_main_axis() {
+        let flex = FlexLayout::new(200, 100);
+
+        assert_eq!(flex.drop_index(&ITEMS, (10.0, 20.0)), 0);
+        assert_eq!(flex.drop_index(&ITEMS, (60.0, 20.0)), 1);
+        assert_eq!(flex.drop_index(&ITEMS, (190.0, 20.0)), 2);
+        // Past the last item of the second line
+        assert_eq!(flex.drop_index(&ITEMS, (90.0, 70.0)), 3);
+        assert_eq!(flex.drop_index(&[], (90.0, 70.0)), 0);
+    }
+
+    #[test]
+    fn drop_index_of_reversed_layouts() {
+        let mut flex = FlexLayout::new(200, 100);
+        flex.set_direction(FlexDirection::RowReverse);
+
+        let items = [((100.0, 0.0), (100.0, 50.0)), ((0.0, 0.0), (100.0, 50.0))];
+
+        assert_eq!(flex.drop_index(&items, (190.0, 20.0)), 0);
+        assert_eq!(flex.drop_index(&items, (110.0, 20.0)), 1);
+        assert_eq!(flex.drop_index(&items, (10.0, 20.0)), 2);
+
+        flex.set_direction(FlexDirection::Column);
+        let items = [((0.0, 0.0), (100.0, 50.0)), ((0.0, 50.0), (100.0, 50.0))];
+
+        assert_eq!(flex.drop_index(&items, (50.0, 60.0)), 1);
+        assert_eq!(flex.drop_index(&items, (50.0, 90.0)), 2);
+    }
+
+    #[test]
+    fn render_replaces_the_direction_class() {
+        let mut flex = FlexLayout::new(200, 100);
+        let mut backend = MemoryBackend::new();
+        flex.render(&mut backend);
+
+        assert!(backend.has_class("flex-row"));
+        assert!(backend.has_class("flex-wrap"));
+
+        flex.set_direction(FlexDirection::ColumnReverse);
+        flex.set_wrap(false);
+        flex.set_gap(8);
+        flex.render(&mut backend);
+
+        assert!(!backend.has_class("flex-row"));
+        assert!(backend.has_class("flex-col-reverse"));
+        assert!(backend.has_class("flex-nowrap"));
+        assert!(!backend.has_class("flex-wrap"));
+        assert_eq!(backend.style("gap"), Some("8px"));
+    }
+
+    #[test]
+    fn item_renders_auto_basis() {
+        let mut backend = MemoryBackend::new();
+        FlexItem::default().render(&mut backend);
+
+        assert_eq!(backend.style("flex-shrink"), Some("1"));
+        assert_eq!(backend.style("flex-basis"), Some("auto"));
+
+        let item = FlexItem {
+            grow: 2,
+            basis: Some(120),
+            order: -1,
+            ..Default::default()
+        };
+        item.render(&mut backend);
+
+        assert_eq!(backend.style("flex-grow"), Some("2"));
+        assert_eq!(backend.style("flex-basis"), Some("120px"));
+        assert_eq!(backend.style("order"), Some("-1"));
+    }
 }
@@ -1,14 +1,460 @@
-use generational_arena::Index;
+use super::Rect;
+
+pub mod guides;
+
+/// Distance in px under which a dragged item snaps to an edge or a center
+pub const SNAP_THRESHOLD: f64 = 6.0;
+
+/// Pixel grid of a newly created free layout
+pub const SNAP_GRID: u32 = 8;
+
+/// Orientation of an alignment guide
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuideAxis {
+    /// Line of a constant x
+    Vertical,
+    /// Line of a constant y
+    Horizontal,
+}
+
+/// Alignment line shown while an item is dragged
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Guide {
+    pub axis: GuideAxis,
+    /// x of a vertical line, y of a horizontal one
+    pub position: f64,
+    /// Where the line starts along its own axis
+    pub start: f64,
+    /// Where the line ends along its own axis
+    pub end: f64,
+}
+
+/// Space between the dragged item and its nearest neighbour
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Measurement {
+    pub from: (f64, f64),
+    pub to: (f64, f64),
+}
+
+impl Measurement {
+    /// Length of the measured space in px
+    pub fn length(&self) -> f64 {
+        (self.to.0 - self.from.0).abs() + (self.to.1 - self.from.1).abs()
+    }
+}
+
+/// Result of snapping a dragged item
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snap {
+    /// Snapped left-top point of the item, relative to the layout
+    pub position: (f64, f64),
+    /// Snapped size of the item, only resized items change it
+    pub size: (f64, f64),
+    /// Alignment lines that the item snapped to
+    pub guides: Vec<Guide>,
+    /// Spaces between the snapped item and its nearest neighbours
+    pub measurements: Vec<Measurement>,
+}
 
-#[derive(Default)]
 pub struct FreeLayout {
-    components: Vec<Index>,
+    /// Size of the pixel grid that items snap to, 0 turns it off
+    snap_grid: u32,
+    /// Whether items snap to the edges and centers of other items and of the layout
+    snap_to_items: bool,
+}
+
+impl Default for FreeLayout {
+    fn default() -> Self {
+        Self {
+            snap_grid: SNAP_GRID,
+            snap_to_items: true,
+        }
+    }
 }
 
 impl FreeLayout {
     pub fn new(_width: u32, _height: u32) -> Self {
-        Self {
-            components: Vec::new(),
+        Self::default()
+    }
+
+    pub fn snap_grid(&self) -> u32 {
+        self.snap_grid
+    }
+
+    pub fn set_snap_grid(&mut self, size: u32) {
+        self.snap_grid = size;
+    }
+
+    pub fn snap_to_items(&self) -> bool {
+        self.snap_to_items
+    }
+
+    pub fn set_snap_to_items(&mut self, is: bool) {
+        self.snap_to_items = is;
+    }
+
+    /// Snap a dragged item
+    ///
+    /// Each axis snaps to the closest edge or center of the other items and of the layout bounds,
+    /// if there is one within `SNAP_THRESHOLD`, otherwise to the pixel grid
+    ///
+    /// # Arguments
+    /// * `rect` - rect of the dragged item, relative to the layout
+    /// * `siblings` - rects of the other items of the layout
+    /// * `bounds` - size of the layout in px
+    pub fn snap(&self, rect: Rect, siblings: &[Rect], bounds: (f64, f64)) -> Snap {
+        let ((x, y), (width, height)) = rect;
+        let targets = self.targets(siblings, bounds);
+
+        let x = self.snap_axis(x, width, &target_lines(&targets, |r| (r.0 .0, r.1 .0)));
+        let y = self.snap_axis(y, height, &target_lines(&targets, |r| (r.0 .1, r.1 .1)));
+
+        snap_result(((x, y), (width, height)), &targets, siblings)
+    }
+
+    /// Snap the edges of a resized item that are being dragged
+    ///
+    /// Unlike `FreeLayout::snap`, the opposite edges stay in place, so the size changes instead
+    ///
+    /// # Arguments
+    /// * `rect` - rect of the resized item, relative to the layout
+    /// * `edges` - dragged (horizontal, vertical) edges, -1 for the left/top one, 1 for the right/bottom one, 0 for none
+    /// * `siblings` - rects of the other items of the layout
+    /// * `bounds` - size of the layout in px
+    pub fn snap_edges(
+        &self,
+        rect: Rect,
+        edges: (i32, i32),
+        siblings: &[Rect],
+        bounds: (f64, f64),
+    ) -> Snap {
+        let targets = self.targets(siblings, bounds);
+
+        let (x, width) = self.snap_edge(
+            rect.0 .0,
+            rect.1 .0,
+            edges.0,
+            &target_lines(&targets, |r| (r.0 .0, r.1 .0)),
+        );
+        let (y, height) = self.snap_edge(
+            rect.0 .1,
+            rect.1 .1,
+            edges.1,
+            &target_lines(&targets, |r| (r.0 .1, r.1 .1)),
+        );
+
+        snap_result(((x, y), (width, height)), &targets, siblings)
+    }
+
+    /// Rects that items snap to, the siblings and the layout itself
+    fn targets(&self, siblings: &[Rect], bounds: (f64, f64)) -> Vec<Rect> {
+        if self.snap_to_items {
+            siblings
+                .iter()
+                .copied()
+                .chain(Some(((0.0, 0.0), bounds)))
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Snap the start of an item along a single axis
+    fn snap_axis(&self, start: f64, length: f64, targets: &[f64]) -> f64 {
+        match closest_offset(&lines(start, length), targets) {
+            Some(offset) => start + offset,
+            None => self.snap_to_grid(start),
+        }
+    }
+
+    /// Snap the dragged edge of an item along a single axis
+    ///
+    /// Returns the new (start, length) of the item
+    fn snap_edge(&self, start: f64, length: f64, edge: i32, targets: &[f64]) -> (f64, f64) {
+        let end = start + length;
+
+        match edge {
+            1 => {
+                let end = match closest_offset(&[end], targets) {
+                    Some(offset) => end + offset,
+                    None => self.snap_to_grid(end),
+                };
+                (start, end - start)
+            }
+            -1 => {
+                let start = match closest_offset(&[start], targets) {
+                    Some(offset) => start + offset,
+                    None => self.snap_to_grid(start),
+                };
+                (start, end - start)
+            }
+            _ => (start, length),
+        }
+    }
+
+    fn snap_to_grid(&self, value: f64) -> f64 {
+        if self.snap_grid > 0 {
+            let grid = self.snap_grid as f64;
+            (value / grid).round() * grid
+        } else {
+            value.round()
+        }
+    }
+}
+
+/// Smallest offset (within `SNAP_THRESHOLD`) that moves one of the lines onto one of the targets
+fn closest_offset(lines: &[f64], targets: &[f64]) -> Option<f64> {
+    lines
+        .iter()
+        .flat_map(|line| targets.iter().map(move |target| target - line))
+        .filter(|offset| offset.abs() <= SNAP_THRESHOLD)
+        .min_by(|a, b| a.abs().partial_cmp(&b.abs()).unwrap())
+}
+
+/// Edges and centers of the targets along a single axis
+///
+/// # Arguments
+/// * `axis` - (start, length) of a rect along the axis
+fn target_lines<F: Fn(&Rect) -> (f64, f64)>(targets: &[Rect], axis: F) -> Vec<f64> {
+    targets
+        .iter()
+        .flat_map(|rect| {
+            let (start, length) = axis(rect);
+            lines(start, length)
+        })
+        .collect()
+}
+
+/// Collect the guides and measurements of a snapped rect
+fn snap_result(rect: Rect, targets: &[Rect], siblings: &[Rect]) -> Snap {
+    let mut guides = Vec::new();
+    for target in targets.iter() {
+        for guide in alignment_guides(rect, *target) {
+            if !guides.contains(&guide) {
+                guides.push(guide);
+            }
+        }
+    }
+
+    Snap {
+        position: rect.0,
+        size: rect.1,
+        guides,
+        measurements: measurements(rect, siblings),
+    }
+}
+
+/// Start, center and end of a span
+fn lines(start: f64, length: f64) -> [f64; 3] {
+    [start, start + length / 2.0, start + length]
+}
+
+/// Whether two lines are aligned, snapped positions may be off by a rounding error
+fn is_aligned(a: f64, b: f64) -> bool {
+    (a - b).abs() < 0.5
+}
+
+/// Lines shared by the two rects, spanning both of them
+fn alignment_guides(rect: Rect, target: Rect) -> Vec<Guide> {
+    let ((x, y), (width, height)) = rect;
+    let ((tx, ty), (t_width, t_height)) = target;
+
+    let mut guides = Vec::new();
+
+    for line in lines(x, width).iter() {
+        if lines(tx, t_width).iter().any(|t| is_aligned(*t, *line)) {
+            guides.push(Guide {
+                axis: GuideAxis::Vertical,
+                position: *line,
+                start: y.min(ty),
+                end: (y + height).max(ty + t_height),
+            });
+        }
+    }
+
+    for line in lines(y, height).iter() {
+        if lines(ty, t_height).iter().any(|t| is_aligned(*t, *line)) {
+            guides.push(Guide {
+                axis: GuideAxis::Horizontal,
+                position: *line,
+                start: x.min(tx),
+                end: (x + width).max(tx + t_width),
+            });
+        }
+    }
+
+    guides
+}
+
+/// Spaces to the nearest sibling on each side of the rect
+///
+/// Only siblings that overlap with the rect on the other axis are measured
+fn measurements(rect: Rect, siblings: &[Rect]) -> Vec<Measurement> {
+    let ((x, y), (width, height)) = rect;
+
+    // Middle of the overlap of two spans, `None` if they do not overlap
+    let overlap = |a: f64, a_len: f64, b: f64, b_len: f64| {
+        let (start, end) = (a.max(b), (a + a_len).min(b + b_len));
+        if start < end {
+            Some((start + end) / 2.0)
+        } else {
+            None
+        }
+    };
+
+    let mut left: Option<Measurement> = None;
+    let mut right: Option<Measurement> = None;
+    let mut top: Option<Measurement> = None;
+    let mut bottom: Option<Measurement> = None;
+
+    let keep_shorter = |slot: &mut Option<Measurement>, measurement: Measurement| {
+        if slot.is_none_or(|m| measurement.length() < m.length()) {
+            *slot = Some(measurement);
         }
+    };
+
+    for ((sx, sy), (s_width, s_height)) in siblings.iter().copied() {
+        if let Some(my) = overlap(y, height, sy, s_height) {
+            if sx + s_width <= x {
+                let measurement = Measurement {
+                    from: (sx + s_width, my),
+                    to: (x, my),
+                };
+                keep_shorter(&mut left, measurement);
+            } else if sx >= x + width {
+                let measurement = Measurement {
+                    from: (x + width, my),
+                    to: (sx, my),
+                };
+                keep_shorter(&mut right, measurement);
+            }
+        }
+
+        if let Some(mx) = overlap(x, width, sx, s_width) {
+            if sy + s_height <= y {
+                let measurement = Measurement {
+                    from: (mx, sy + s_height),
+                    to: (mx, y),
+                };
+                keep_shorter(&mut top, measurement);
+            } else if sy >= y + height {
+                let measurement = Measurement {
+                    from: (mx, y + height),
+                    to: (mx, sy),
+                };
+                keep_shorter(&mut bottom, measurement);
+            }
+        }
+    }
+
+    [left, right, top, bottom]
+        .iter()
+        .flatten()
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOUNDS: (f64, f64) = (400.0, 300.0);
+
+    #[test]
+    fn snaps_to_the_pixel_grid() {
+        let free = FreeLayout::default();
+
+        let snap = free.snap(((43.0, 61.0), (50.0, 50.0)), &[], BOUNDS);
+        assert_eq!(snap.position, (40.0, 64.0));
+        assert!(snap.guides.is_empty());
+
+        let mut free = FreeLayout::default();
+        free.set_snap_grid(0);
+
+        let snap = free.snap(((43.2, 61.0), (50.0, 50.0)), &[], BOUNDS);
+        assert_eq!(snap.position, (43.0, 61.0));
+    }
+
+    #[test]
+    fn snaps_to_sibling_edges_and_centers() {
+        let free = FreeLayout::default();
+        let sibling = ((100.0, 100.0), (80.0, 40.0));
+
+        // Left edges are 3px apart, while there is nothing to snap to vertically
+        let snap = free.snap(((103.0, 172.0), (40.0, 40.0)), &[sibling], BOUNDS);
+        assert_eq!(snap.position, (100.0, 176.0));
+
+        assert!(snap.guides.contains(&Guide {
+            axis: GuideAxis::Vertical,
+            position: 100.0,
+            start: 100.0,
+            end: 216.0,
+        }));
+
+        // Right under the sibling, 36px below it
+        assert_eq!(
+            snap.measurements,
+            vec![Measurement {
+                from: (120.0, 140.0),
+                to: (120.0, 176.0),
+            }]
+        );
+    }
+
+    #[test]
+    fn snaps_to_layout_bounds() {
+        let free = FreeLayout::default();
+
+        // Center of a 100px wide item is 4px away from the center of the layout
+        let snap = free.snap(((154.0, 10.0), (100.0, 20.0)), &[], BOUNDS);
+        assert_eq!(snap.position.0, 150.0);
+        assert!(snap.guides.contains(&Guide {
+            axis: GuideAxis::Vertical,
+            position: 200.0,
+            start: 0.0,
+            end: 300.0,
+        }));
+
+        let mut free = FreeLayout::default();
+        free.set_snap_to_items(false);
+
+        let snap = free.snap(((154.0, 10.0), (100.0, 20.0)), &[], BOUNDS);
+        assert_eq!(snap.position.0, 152.0);
+        assert!(snap.guides.is_empty());
+    }
+
+    #[test]
+    fn resized_edges_snap_while_the_opposite_ones_stay() {
+        let free = FreeLayout::default();
+        let sibling = ((200.0, 0.0), (50.0, 50.0));
+
+        // Right edge is 4px away from the left edge of the sibling
+        let snap = free.snap_edges(((100.0, 10.0), (96.0, 43.0)), (1, 0), &[sibling], BOUNDS);
+        assert_eq!(snap.position, (100.0, 10.0));
+        assert_eq!(snap.size, (100.0, 43.0));
+
+        // Top edge goes to the pixel grid, the bottom one stays at 53px
+        let snap = free.snap_edges(((100.0, 10.0), (96.0, 43.0)), (0, -1), &[sibling], BOUNDS);
+        assert_eq!(snap.position, (100.0, 8.0));
+        assert_eq!(snap.size, (96.0, 45.0));
+    }
+
+    #[test]
+    fn measures_the_nearest_neighbours() {
+        let rect = ((100.0, 100.0), (50.0, 50.0));
+        let siblings = [
+            ((0.0, 110.0), (40.0, 20.0)),
+            ((60.0, 120.0), (20.0, 20.0)),
+            ((200.0, 0.0), (20.0, 20.0)),
+        ];
+
+        // Only the closer sibling on the left is measured, the last one does not overlap
+        assert_eq!(
+            measurements(rect, &siblings),
+            vec![Measurement {
+                from: (80.0, 130.0),
+                to: (100.0, 130.0),
+            }]
+        );
     }
 }
@@ -0,0 +1,92 @@
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlElement};
+
+use super::{GuideAxis, Snap};
+
+/// Alignment guides and spacing measurements drawn over a free layout while an item is dragged
+pub struct FreeGuides {
+    root: HtmlElement,
+}
+
+impl FreeGuides {
+    pub fn new() -> Self {
+        let document = web_sys::window().unwrap().document().unwrap();
+
+        let root = document.create_element("div").unwrap();
+        let root: HtmlElement = root.dyn_into().unwrap();
+        root.class_list().add_1("free-guides").unwrap();
+
+        Self { root }
+    }
+
+    /// Append the guides to the element
+    pub fn append_to(&self, parent: &Element) {
+        parent.append_child(&self.root).unwrap();
+    }
+
+    /// Remove the guides from their parent
+    pub fn remove(&self) {
+        self.root.remove();
+    }
+
+    /// Draw the guides and measurements of a snap, replacing the previous ones
+    pub fn show(&self, snap: &Snap) {
+        self.root.set_inner_html("");
+
+        for guide in snap.guides.iter() {
+            let elm = self.create("free-guide");
+
+            let (left, top, width, height) = match guide.axis {
+                GuideAxis::Vertical => (guide.position, guide.start, 0.0, guide.end - guide.start),
+                GuideAxis::Horizontal => {
+                    (guide.start, guide.position, guide.end - guide.start, 0.0)
+                }
+            };
+            set_rect(&elm, left, top, width, height);
+        }
+
+        for measurement in snap.measurements.iter() {
+            let elm = self.create("free-measurement");
+
+            let (from, to) = (measurement.from, measurement.to);
+            set_rect(
+                &elm,
+                from.0.min(to.0),
+                from.1.min(to.1),
+                (to.0 - from.0).abs(),
+                (to.1 - from.1).abs(),
+            );
+
+            elm.set_inner_text(&format!("{}", measurement.length().round()));
+        }
+    }
+
+    /// Remove all of the guides
+    pub fn hide(&self) {
+        self.root.set_inner_html("");
+    }
+
+    fn create(&self, class: &str) -> HtmlElement {
+        let document = web_sys::window().unwrap().document().unwrap();
+
+        let elm = document.create_element("div").unwrap();
+        let elm: HtmlElement = elm.dyn_into().unwrap();
+        elm.class_list().add_1(class).unwrap();
+
+        self.root.append_child(&elm).unwrap();
+        elm
+    }
+}
+
+/// Position the element inside of the layout
+fn set_rect(elm: &HtmlElement, left: f64, top: f64, width: f64, height: f64) {
+    let style = elm.style();
+    style.set_property("left", &format!("{}px", left)).unwrap();
+    style.set_property("top", &format!("{}px", top)).unwrap();
+    style
+        .set_property("width", &format!("{}px", width))
+        .unwrap();
+    style
+        .set_property("height", &format!("{}px", height))
+        .unwrap();
+}
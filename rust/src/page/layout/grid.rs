@@ -1,13 +1,15 @@
 /// Include relevent crates and modules
-use crate::component::Component;
 use generational_arena::Index;
 use ndarray::Array2;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::component::SpanLimits;
 
 pub mod background;
 
 /// Block Struct to represent position and size of block on grid
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Block {
     /// Top left X starting cell position of block
     pub x: usize,
@@ -19,6 +21,87 @@ pub struct Block {
     pub height: usize,
 }
 
+impl Block {
+    /// Check if two blocks share at least one cell
+    pub fn overlaps(&self, other: &Block) -> bool {
+        self.x < other.x + other.width
+            && other.x < self.x + self.width
+            && self.y < other.y + other.height
+            && other.y < self.y + self.height
+    }
+}
+
+/// Strategy used when a component is dropped onto cells occupied by other components
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CollisionStrategy {
+    /// Refuse the drop
+    #[default]
+    Deny,
+    /// Push the overlapped components down
+    Push,
+    /// Swap positions with the single overlapped component
+    Swap,
+    /// Push the overlapped components down, and then move every component up as far as possible
+    Compact,
+}
+
+impl CollisionStrategy {
+    /// All of the strategies, in the order they are presented to the user
+    pub const ALL: [CollisionStrategy; 4] = [Self::Deny, Self::Push, Self::Swap, Self::Compact];
+
+    /// Name of the strategy, as used in the settings panel and in documents
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Deny => "deny",
+            Self::Push => "push",
+            Self::Swap => "swap",
+            Self::Compact => "compact",
+        }
+    }
+
+    /// Find the strategy by its name
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|strategy| strategy.name() == name)
+    }
+}
+
+/// Item that occupies cells of a grid layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GridItem {
+    /// Component, by its index in the component arena of the workspace
+    Component(Index),
+    /// Nested layout, by its key from `Layout::grid_item`
+    Layout(usize),
+}
+
+/// Result of dropping a component onto the grid
+#[derive(Debug, Clone, PartialEq)]
+pub struct Arrangement {
+    /// Final block of the dropped component
+    pub block: Block,
+    /// Other components that have to be moved, together with their new blocks
+    pub displaced: Vec<(GridItem, Block)>,
+    /// Number of rows the grid should have after the drop
+    pub rows: usize,
+}
+
+/// Placeholder of a component dragged or resized over the grid
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placeholder {
+    /// Block that the component ends up at
+    pub block: Block,
+    /// Other components that have to be moved, together with their new blocks
+    pub displaced: Vec<(GridItem, Block)>,
+    /// Number of rows the grid needs for the placeholder
+    pub rows: usize,
+    /// The component can not be placed at the block
+    pub denied: bool,
+}
+
 /// GridComponentData Struct to store component related data inside the grid
 pub struct GridComponentData {
     /// Reference ID which is stored on the 2D grid
@@ -31,12 +114,10 @@ pub struct GridComponentData {
     width: usize,
     /// Total height of component on grid in cells
     height: usize,
-    /// Reference to the component attached to it
-    component: Component,
 }
 
 /// GridComponentsDataMap Type for grid component hash mapping [unique ID -> GridComponentData]
-pub type GridComponentsDataMap = HashMap<Index, GridComponentData>;
+pub type GridComponentsDataMap = HashMap<GridItem, GridComponentData>;
 
 /// GridLayout Struct to store components information inside the grid
 pub struct GridLayout {
@@ -49,9 +130,17 @@ pub struct GridLayout {
     /// Component mapping [unique ID (converted to String) -> GridComponentData]
     mapping: GridComponentsDataMap,
     /// Component mapping [Reference ID -> unique ID (converted to String)]
-    mapping_ref_id: HashMap<i32, Index>,
+    mapping_ref_id: HashMap<i32, GridItem>,
     /// Reference id counter for next value to be used inside the grid cell for component representation
     ref_id_count: i32,
+    /// What happens when a component is dropped onto occupied cells
+    collision: CollisionStrategy,
+    /// Add rows when components are dropped or resized past the last row
+    auto_grow: bool,
+    /// Remove trailing empty rows after a drop
+    auto_shrink: bool,
+    /// Column count of the grid at each breakpoint of the page [breakpoint name -> columns]
+    breakpoint_columns: BTreeMap<String, usize>,
 }
 
 /// Methods for GridLayout Struct
@@ -73,9 +162,64 @@ impl GridLayout {
             mapping,
             mapping_ref_id,
             ref_id_count,
+            collision: CollisionStrategy::default(),
+            auto_grow: false,
+            auto_shrink: false,
+            breakpoint_columns: BTreeMap::new(),
         }
     }
 
+    /// Column counts of the grid at each breakpoint of the page
+    pub fn breakpoint_columns(&self) -> &BTreeMap<String, usize> {
+        &self.breakpoint_columns
+    }
+
+    /// Remember the column count of the grid at a breakpoint
+    pub fn set_breakpoint_columns(&mut self, breakpoint: &str, columns: usize) {
+        self.breakpoint_columns.insert(breakpoint.into(), columns);
+    }
+
+    /// Forget the column count of the grid at a breakpoint, returns the forgotten one
+    pub fn remove_breakpoint_columns(&mut self, breakpoint: &str) -> Option<usize> {
+        self.breakpoint_columns.remove(breakpoint)
+    }
+
+    /// Whether rows are added when components are dropped or resized past the last row
+    pub fn auto_grow(&self) -> bool {
+        self.auto_grow
+    }
+
+    pub fn set_auto_grow(&mut self, is: bool) {
+        self.auto_grow = is;
+    }
+
+    /// Whether trailing empty rows are removed after a drop
+    pub fn auto_shrink(&self) -> bool {
+        self.auto_shrink
+    }
+
+    pub fn set_auto_shrink(&mut self, is: bool) {
+        self.auto_shrink = is;
+    }
+
+    /// Returns the last row occupied by a component (0 if the grid is empty)
+    pub fn used_rows(&self) -> usize {
+        self.mapping
+            .values()
+            .map(|data| data.y + data.height - 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Strategy used when a component is dropped onto occupied cells
+    pub fn collision(&self) -> CollisionStrategy {
+        self.collision
+    }
+
+    pub fn set_collision(&mut self, collision: CollisionStrategy) {
+        self.collision = collision;
+    }
+
     /// Total width of the grid in cells
     pub fn width(&self) -> usize {
         self.width
@@ -136,14 +280,27 @@ impl GridLayout {
 
     /// Insert new or update (position or size) component into the grid
     ///
+    /// Blocks that reach past the grid are narrowed to its size and moved back inside of it,
+    /// rows are not added, so the grid should be resized first if it has to grow.
+    /// Returns the block that the component was inserted into
+    ///
     /// # Arguments
-    /// * `component` - Component to add or update
-    pub fn insert_component(&mut self, component: Component) {
+    /// * `index` - Index of component to add or update
+    /// * `pos` - Top left cell of the component
+    /// * `size` - Size of the component in cells
+    pub fn insert_component(
+        &mut self,
+        index: GridItem,
+        pos: (usize, usize),
+        size: (usize, usize),
+    ) -> Block {
+        let block = self.fit_block(pos, size);
+        let (pos, size) = ((block.x, block.y), (block.width, block.height));
+
         let ref_id: i32;
         // If component already exists then remove it to insert it again with updated data
-        if self.mapping.contains_key(&component.index()) {
-            let grid_component_data: GridComponentData =
-                self.mapping.remove(&component.index()).unwrap();
+        if self.mapping.contains_key(&index) {
+            let grid_component_data: GridComponentData = self.mapping.remove(&index).unwrap();
             self.mapping_ref_id
                 .remove(&grid_component_data.ref_id)
                 .unwrap();
@@ -153,7 +310,7 @@ impl GridLayout {
                 width: grid_component_data.width,
                 height: grid_component_data.height,
             };
-            self.set_data_block(grid_component_block, 0);
+            self.clear_data_block(grid_component_block, grid_component_data.ref_id);
             ref_id = grid_component_data.ref_id;
         }
         // If component is new then calculate its new reference id
@@ -164,11 +321,10 @@ impl GridLayout {
         // Insert new or updated component back to mapping and update it on grid
         let new_grid_component_data = GridComponentData {
             ref_id,
-            x: component.grid_pos().unwrap().0 as usize,
-            y: component.grid_pos().unwrap().1 as usize,
-            width: component.grid_size().unwrap().0 as usize,
-            height: component.grid_size().unwrap().1 as usize,
-            component: component.clone(),
+            x: pos.0,
+            y: pos.1,
+            width: size.0,
+            height: size.1,
         };
         let grid_component_block = Block {
             x: new_grid_component_data.x,
@@ -178,24 +334,56 @@ impl GridLayout {
         };
         self.set_data_block(grid_component_block, new_grid_component_data.ref_id);
         self.mapping_ref_id
-            .insert(new_grid_component_data.ref_id, component.index());
-        self.mapping
-            .insert(component.index(), new_grid_component_data);
+            .insert(new_grid_component_data.ref_id, index);
+        self.mapping.insert(index, new_grid_component_data);
+
+        block
     }
 
-    /// Remove existing component from the grid using component reference
+    /// Block at a position narrowed and moved so that it lies within the grid
+    ///
+    /// A grid without any cells gets a single one, so that the block always has a place
+    fn fit_block(&mut self, pos: (usize, usize), size: (usize, usize)) -> Block {
+        if self.width == 0 || self.height == 0 {
+            self.resize(self.width.max(1), self.height.max(1));
+        }
+
+        let width = size.0.clamp(1, self.width);
+        let height = size.1.clamp(1, self.height);
+
+        Block {
+            x: pos.0.clamp(1, self.width + 1 - width),
+            y: pos.1.clamp(1, self.height + 1 - height),
+            width,
+            height,
+        }
+    }
+
+    /// Add a component to the grid, or update it, with its span clamped to the limits
+    ///
+    /// Limits come before the grid, but a block that does not fit is still narrowed like in `insert_component`.
+    /// Returns the block that the component was inserted into
     ///
     /// # Arguments
-    /// * `component` - Component to remove
-    pub fn remove_component(&mut self, component: &mut Component) {
-        self.remove_component_by_index(component.index());
+    /// * `index` - Index of component to add or update
+    /// * `pos` - Top left cell of the component
+    /// * `size` - Requested size of the component in cells
+    /// * `limits` - Span limits declared by the component
+    pub fn insert_constrained(
+        &mut self,
+        index: GridItem,
+        pos: (usize, usize),
+        size: (usize, usize),
+        limits: SpanLimits,
+    ) -> Block {
+        self.insert_component(index, pos, limits.clamp(size))
     }
 
     /// Remove existing component from the grid using component index
     ///
     /// # Arguments
     /// * `index` - Index of component to remove
-    pub fn remove_component_by_index(&mut self, index: Index) {
+    pub fn remove_component(&mut self, index: GridItem) {
         if self.mapping.contains_key(&index) {
             let grid_component_data: GridComponentData = self.mapping.remove(&index).unwrap();
             self.mapping_ref_id
@@ -207,7 +395,7 @@ impl GridLayout {
                 width: grid_component_data.width,
                 height: grid_component_data.height,
             };
-            self.set_data_block(grid_component_block, 0);
+            self.clear_data_block(grid_component_block, grid_component_data.ref_id);
         }
     }
 
@@ -215,9 +403,9 @@ impl GridLayout {
     ///  
     /// # Arguments
     /// * `block` - Block representing position and size on grid
-    pub fn get_block_component_indices(&self, block: Block) -> Vec<Index> {
+    pub fn get_block_component_indices(&self, block: Block) -> Vec<GridItem> {
         let mut cell_value = 0;
-        let mut indices: Vec<Index> = Vec::new();
+        let mut indices: Vec<GridItem> = Vec::new();
         for i in block.x..(block.x + block.width) {
             for j in block.y..(block.y + block.height) {
                 cell_value = self.get_data_cell(i, j);
@@ -239,25 +427,33 @@ impl GridLayout {
     /// # Arguments
     /// * `x` - X position of cell
     /// * `y` - Y position of cell
-    pub fn get_cell_component_index(&self, x: usize, y: usize) -> Option<Index> {
+    pub fn get_cell_component_index(&self, x: usize, y: usize) -> Option<GridItem> {
         let cell_value = self.get_data_cell(x, y);
         self.mapping_ref_id.get(&cell_value).copied()
     }
 
-    /// Check if the component is currently overlapping on another component (returns true if overlapping)
+    /// Returns the block occupied by a component (None if component is not in the grid)
+    ///
+    /// # Arguments
+    /// * `index` - Index of component
+    pub fn get_component_block(&self, index: GridItem) -> Option<Block> {
+        self.mapping.get(&index).map(|data| Block {
+            x: data.x,
+            y: data.y,
+            width: data.width,
+            height: data.height,
+        })
+    }
+
+    /// Check if the component would overlap another component when placed at a block (returns true if overlapping)
     ///  
     /// # Arguments
-    /// * `component` - Component to check if its overlapping on another component on grid
-    pub fn is_component_overlapping(&mut self, component: &mut Component) -> bool {
-        let block = Block {
-            x: component.grid_pos().unwrap().0 as usize,
-            y: component.grid_pos().unwrap().1 as usize,
-            width: component.grid_size().unwrap().0 as usize,
-            height: component.grid_size().unwrap().1 as usize,
-        };
+    /// * `index` - Index of component to check if its overlapping on another component on grid
+    /// * `block` - Block representing position and size of the component on grid
+    pub fn is_component_overlapping(&self, index: GridItem, block: Block) -> bool {
         let mut ref_id = -1;
-        if self.mapping.contains_key(&component.index()) {
-            ref_id = self.mapping.get(&component.index()).unwrap().ref_id;
+        if self.mapping.contains_key(&index) {
+            ref_id = self.mapping.get(&index).unwrap().ref_id;
         }
         let mut overlapping = false;
         'outer: for i in block.x..(block.x + block.width) {
@@ -273,6 +469,225 @@ impl GridLayout {
         }
     }
 
+    /// Check if the block lies completely inside of the grid
+    ///
+    /// # Arguments
+    /// * `block` - Block representing position and size on grid
+    pub fn contains_block(&self, block: Block) -> bool {
+        block.x >= 1
+            && block.y >= 1
+            && block.x + block.width <= self.width + 1
+            && block.y + block.height <= self.height + 1
+    }
+
+    /// Check if a component can be placed at a block (returns true if the block is inside of the grid and not occupied by other components)
+    ///
+    /// # Arguments
+    /// * `index` - Index of the placed component, cells occupied by it are treated as empty
+    /// * `block` - Block representing position and size on grid
+    pub fn can_place_component(&self, index: Option<GridItem>, block: Block) -> bool {
+        self.contains_block(block)
+            && self
+                .get_block_component_indices(block)
+                .iter()
+                .all(|i| Some(*i) == index)
+    }
+
+    /// Compute the arrangement of the grid after dropping a component at a block
+    ///
+    /// Returns `None` if the drop is refused by the collision strategy,
+    /// or if the resulting arrangement does not fit into the grid
+    ///
+    /// # Arguments
+    /// * `index` - Index of the dropped component, it can be new to the grid
+    /// * `block` - Block that the component is dropped at
+    pub fn resolve_drop(&self, index: GridItem, block: Block) -> Option<Arrangement> {
+        if !self.fits_block(block) {
+            return None;
+        }
+
+        let mut others: Vec<(GridItem, Block)> = self
+            .mapping
+            .keys()
+            .filter(|i| **i != index)
+            .filter_map(|i| self.get_component_block(*i).map(|b| (*i, b)))
+            .collect();
+        // Hash map order is random, so sort the components top to bottom to get a stable result
+        others.sort_by_key(|(_, b)| (b.y, b.x));
+
+        let (block, others) = match self.collision {
+            CollisionStrategy::Deny => {
+                if others.iter().any(|(_, b)| b.overlaps(&block)) {
+                    return None;
+                }
+                (block, others)
+            }
+            CollisionStrategy::Swap => swap_blocks(self.get_component_block(index), block, others)?,
+            CollisionStrategy::Push => (block, push_down(block, others)),
+            CollisionStrategy::Compact => compact(index, block, push_down(block, others)),
+        };
+
+        if !self.fits_block(block) || !others.iter().all(|(_, b)| self.fits_block(*b)) {
+            return None;
+        }
+
+        let used_rows = others
+            .iter()
+            .map(|(_, b)| b)
+            .chain(std::iter::once(&block))
+            .map(|b| b.y + b.height - 1)
+            .max()
+            .unwrap_or(0);
+
+        let rows = if self.auto_shrink {
+            used_rows.max(1)
+        } else {
+            used_rows.max(self.height)
+        };
+
+        let displaced = others
+            .into_iter()
+            .filter(|(i, b)| self.get_component_block(*i) != Some(*b))
+            .collect();
+
+        Some(Arrangement {
+            block,
+            displaced,
+            rows,
+        })
+    }
+
+    /// Placeholder of a component dropped at a block
+    ///
+    /// The drop is resolved with the collision strategy of the grid,
+    /// a denied placeholder stays at the requested block and keeps the rows of the grid
+    ///
+    /// # Arguments
+    /// * `index` - Index of the dropped component, it can be new to the grid
+    /// * `block` - Block that the component is dropped at
+    pub fn placeholder(&self, index: GridItem, block: Block) -> Placeholder {
+        match self.resolve_drop(index, block) {
+            Some(arrangement) => Placeholder {
+                block: arrangement.block,
+                displaced: arrangement.displaced,
+                rows: arrangement.rows,
+                denied: false,
+            },
+            None => Placeholder {
+                block,
+                displaced: Vec::new(),
+                rows: self.height,
+                denied: true,
+            },
+        }
+    }
+
+    /// Check if several components can be moved into new blocks at once
+    ///
+    /// Components of the group are free to move into each others cells,
+    /// but they can not overlap each other or any other component of the grid
+    ///
+    /// Returns the number of rows the grid needs after the move, `None` if the move is refused
+    ///
+    /// # Arguments
+    /// * `moves` - Indices of the moved components, together with their new blocks
+    pub fn resolve_group_move(&self, moves: &[(GridItem, Block)]) -> Option<usize> {
+        let others: Vec<Block> = self
+            .mapping
+            .keys()
+            .filter(|i| !moves.iter().any(|(moved, _)| moved == *i))
+            .filter_map(|i| self.get_component_block(*i))
+            .collect();
+
+        let fits = moves.iter().enumerate().all(|(n, (_, block))| {
+            self.fits_block(*block)
+                && moves[..n].iter().all(|(_, other)| !other.overlaps(block))
+                && others.iter().all(|other| !other.overlaps(block))
+        });
+
+        if !fits {
+            return None;
+        }
+
+        let rows = moves
+            .iter()
+            .map(|(_, b)| b.y + b.height - 1)
+            .max()
+            .unwrap_or(0);
+
+        Some(rows.max(self.height))
+    }
+
+    /// Find the free block closest to the requested one, used to place pasted components
+    ///
+    /// The block keeps its size (clamped to the grid width). If there is no free space
+    /// left in the grid, the block is placed below the last used row,
+    /// so the grid has to be grown to fit it
+    ///
+    /// # Arguments
+    /// * `block` - Requested position and size of the block
+    pub fn nearest_free_block(&self, block: Block) -> Block {
+        let width = block.width.clamp(1, self.width.max(1));
+        let height = block.height.max(1);
+
+        let distance = |b: &Block| {
+            let dx = b.x as i64 - block.x as i64;
+            let dy = b.y as i64 - block.y as i64;
+            dx * dx + dy * dy
+        };
+
+        let free = (1..=(self.height + 1).saturating_sub(height))
+            .flat_map(|y| (1..=(self.width + 1).saturating_sub(width)).map(move |x| (x, y)))
+            .map(|(x, y)| Block {
+                x,
+                y,
+                width,
+                height,
+            })
+            .filter(|b| self.get_block_component_indices(*b).is_empty())
+            .min_by_key(|b| (distance(b), b.y, b.x));
+
+        free.unwrap_or_else(|| {
+            let (x, _) = self.clamp_block_pos((block.x, 1), (width, height));
+            Block {
+                x,
+                y: self.used_rows() + 1,
+                width,
+                height,
+            }
+        })
+    }
+
+    /// Check if the block can be placed in the grid, rows are not limited if the grid grows automatically
+    ///
+    /// # Arguments
+    /// * `block` - Block representing position and size on grid
+    fn fits_block(&self, block: Block) -> bool {
+        if self.auto_grow {
+            block.x >= 1 && block.y >= 1 && block.x + block.width <= self.width + 1
+        } else {
+            self.contains_block(block)
+        }
+    }
+
+    /// Clamp the top left cell of a block, so that the whole block fits into the grid
+    ///
+    /// Grids that grow automatically only clamp the block from the top
+    ///
+    /// # Arguments
+    /// * `pos` - Requested top left cell of the block
+    /// * `size` - Size of the block in cells
+    pub fn clamp_block_pos(&self, pos: (usize, usize), size: (usize, usize)) -> (usize, usize) {
+        let max_x = (self.width + 1).saturating_sub(size.0).max(1);
+        let max_y = if self.auto_grow {
+            usize::MAX
+        } else {
+            (self.height + 1).saturating_sub(size.1).max(1)
+        };
+
+        (pos.0.clamp(1, max_x), pos.1.clamp(1, max_y))
+    }
+
     /// Check if the block is empty or contain components (returns true if empty)
     ///  
     /// # Arguments
@@ -304,6 +719,24 @@ impl GridLayout {
         }
     }
 
+    /// Clear the cells of a block that are still occupied by a given reference id
+    ///
+    /// Cells that were already taken over by other components are left untouched,
+    /// so components can be moved one by one into each other's spots
+    ///
+    /// # Arguments
+    /// * `block` - Block representing position and size on grid
+    /// * `ref_id` - Reference id of the component that is leaving the block
+    pub fn clear_data_block(&mut self, block: Block, ref_id: i32) {
+        for i in block.x..(block.x + block.width) {
+            for j in block.y..(block.y + block.height) {
+                if self.get_data_cell(i, j) == ref_id {
+                    self.set_data_cell(0, i, j);
+                }
+            }
+        }
+    }
+
     /// Set the data cell on the grid with new value
     ///  
     /// # Arguments
@@ -323,3 +756,587 @@ impl GridLayout {
         self.data[[x - 1, y - 1]]
     }
 }
+
+/// Swap the dropped block with the single block it overlaps
+///
+/// The overlapped component is moved to the previous position of the dropped one,
+/// so components that are new to the grid can not swap
+fn swap_blocks(
+    old: Option<Block>,
+    block: Block,
+    mut others: Vec<(GridItem, Block)>,
+) -> Option<(Block, Vec<(GridItem, Block)>)> {
+    let hit: Vec<usize> = (0..others.len())
+        .filter(|i| others[*i].1.overlaps(&block))
+        .collect();
+
+    match hit.as_slice() {
+        [] => Some((block, others)),
+        [hit] => {
+            let old = old?;
+            let swapped = Block {
+                x: old.x,
+                y: old.y,
+                ..others[*hit].1
+            };
+
+            let collides = swapped.overlaps(&block)
+                || others
+                    .iter()
+                    .enumerate()
+                    .any(|(i, (_, b))| i != *hit && b.overlaps(&swapped));
+            if collides {
+                return None;
+            }
+
+            others[*hit].1 = swapped;
+            Some((block, others))
+        }
+        _ => None,
+    }
+}
+
+/// Push the blocks down, until none of them overlaps the dropped block or each other
+///
+/// Blocks have to be sorted top to bottom
+fn push_down(block: Block, others: Vec<(GridItem, Block)>) -> Vec<(GridItem, Block)> {
+    let mut placed = vec![block];
+    let mut result = Vec::with_capacity(others.len());
+
+    for (index, mut b) in others {
+        while let Some(bottom) = placed
+            .iter()
+            .filter(|p| p.overlaps(&b))
+            .map(|p| p.y + p.height)
+            .max()
+        {
+            b.y = bottom;
+        }
+
+        placed.push(b);
+        result.push((index, b));
+    }
+
+    result
+}
+
+/// Move every block (including the dropped one) up as far as possible
+///
+/// Blocks can not overlap each other
+fn compact(
+    index: GridItem,
+    block: Block,
+    others: Vec<(GridItem, Block)>,
+) -> (Block, Vec<(GridItem, Block)>) {
+    let mut blocks = others;
+    blocks.push((index, block));
+    blocks.sort_by_key(|(_, b)| (b.y, b.x));
+
+    let mut placed: Vec<(GridItem, Block)> = Vec::with_capacity(blocks.len());
+    for (i, mut b) in blocks {
+        while b.y > 1 {
+            let up = Block { y: b.y - 1, ..b };
+            if placed.iter().any(|(_, p)| p.overlaps(&up)) {
+                break;
+            }
+            b = up;
+        }
+
+        placed.push((i, b));
+    }
+
+    let position = placed.iter().position(|(i, _)| *i == index).unwrap();
+    let (_, block) = placed.remove(position);
+    (block, placed)
+}
+
+/// Size of the grid cells in px
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridMetrics {
+    /// Width of a column in px
+    pub column_width: u32,
+    /// Height of a row in px
+    pub row_height: u32,
+    /// Space between columns and rows in px
+    pub gap: u32,
+}
+
+impl GridMetrics {
+    /// Create metrics of a grid, where the columns fill the whole width
+    ///
+    /// # Arguments
+    /// * `width` - Width of the grid in px
+    /// * `columns` - Number of columns
+    /// * `row_height` - Height of a row in px
+    /// * `gap` - Space between columns and rows in px
+    pub fn new(width: u32, columns: usize, row_height: u32, gap: u32) -> Self {
+        let columns = columns.max(1) as u32;
+        let gaps = gap * (columns - 1);
+
+        Self {
+            column_width: width.saturating_sub(gaps) / columns,
+            row_height,
+            gap,
+        }
+    }
+
+    /// Height of a grid with a given number of rows in px
+    pub fn height_for_rows(&self, rows: usize) -> u32 {
+        let rows = rows as u32;
+        rows * self.row_height + rows.saturating_sub(1) * self.gap
+    }
+
+    /// Width of an item spanning a given number of columns in px
+    pub fn span_width(&self, columns: usize) -> u32 {
+        let columns = columns as u32;
+        columns * self.column_width + columns.saturating_sub(1) * self.gap
+    }
+
+    /// Number of whole rows that fit into a given height
+    pub fn rows_for_height(&self, height: u32) -> usize {
+        ((height + self.gap) / (self.row_height + self.gap).max(1)) as usize
+    }
+
+    /// Returns the cell at a given offset (in px) from the top left point of the grid
+    ///
+    /// # Arguments
+    /// * `offset` - Offset in px
+    pub fn cell_at_offset(&self, offset: (f64, f64)) -> (usize, usize) {
+        let x = (offset.0 / self.column_step()).floor().max(0.0) as usize + 1;
+        let y = (offset.1 / self.row_step()).floor().max(0.0) as usize + 1;
+        (x, y)
+    }
+
+    /// Returns the number of cells needed to cover a given size (in px)
+    ///
+    /// # Arguments
+    /// * `size` - Size in px
+    pub fn cells_for_size(&self, size: (f64, f64)) -> (usize, usize) {
+        let gap = self.gap as f64;
+        let w = ((size.0 + gap) / self.column_step()).ceil().max(1.0) as usize;
+        let h = ((size.1 + gap) / self.row_step()).ceil().max(1.0) as usize;
+        (w, h)
+    }
+
+    /// Distance between the left edges of two neighbouring columns in px
+    pub fn column_step(&self) -> f64 {
+        (self.column_width + self.gap).max(1) as f64
+    }
+
+    /// Distance between the top edges of two neighbouring rows in px
+    pub fn row_step(&self) -> f64 {
+        (self.row_height + self.gap).max(1) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index(n: usize) -> GridItem {
+        GridItem::Component(Index::from_raw_parts(n, 0))
+    }
+
+    fn block(x: usize, y: usize, width: usize, height: usize) -> Block {
+        Block {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn insert_marks_cells_as_occupied() {
+        let mut grid = GridLayout::new(10, 3);
+        grid.insert_component(index(0), (2, 1), (2, 2));
+
+        assert_eq!(grid.get_cell_component_index(2, 1), Some(index(0)));
+        assert_eq!(grid.get_cell_component_index(3, 2), Some(index(0)));
+        assert_eq!(grid.get_cell_component_index(4, 1), None);
+        assert_eq!(grid.get_component_block(index(0)), Some(block(2, 1, 2, 2)));
+    }
+
+    #[test]
+    fn components_and_layouts_have_separate_keys() {
+        let mut grid = GridLayout::new(10, 3);
+        grid.insert_component(index(0), (1, 1), (1, 1));
+        grid.insert_component(GridItem::Layout(0), (2, 1), (1, 1));
+
+        assert_eq!(grid.get_component_block(index(0)), Some(block(1, 1, 1, 1)));
+        assert_eq!(
+            grid.get_component_block(GridItem::Layout(0)),
+            Some(block(2, 1, 1, 1))
+        );
+
+        grid.remove_component(GridItem::Layout(0));
+        assert_eq!(grid.get_cell_component_index(1, 1), Some(index(0)));
+        assert_eq!(grid.get_cell_component_index(2, 1), None);
+    }
+
+    #[test]
+    fn insert_clamps_span_to_limits() {
+        let mut grid = GridLayout::new(10, 3);
+        let limits = SpanLimits {
+            min: (2, 1),
+            max: Some((3, 1)),
+        };
+
+        assert_eq!(
+            grid.insert_constrained(index(0), (1, 1), (1, 2), limits),
+            block(1, 1, 2, 1)
+        );
+        assert_eq!(grid.get_component_block(index(0)), Some(block(1, 1, 2, 1)));
+
+        assert_eq!(
+            grid.insert_constrained(index(0), (1, 1), (5, 1), limits),
+            block(1, 1, 3, 1)
+        );
+        assert_eq!(grid.get_cell_component_index(4, 1), None);
+    }
+
+    #[test]
+    fn blocks_past_the_edge_are_moved_inside() {
+        let mut grid = GridLayout::new(3, 2);
+        let limits = SpanLimits {
+            min: (2, 1),
+            ..SpanLimits::default()
+        };
+
+        // The min span grows the block past the right edge
+        assert_eq!(
+            grid.insert_constrained(index(0), (3, 1), (1, 1), limits),
+            block(2, 1, 2, 1)
+        );
+        assert_eq!(grid.get_cell_component_index(3, 1), Some(index(0)));
+
+        // The min span is wider than the grid
+        let limits = SpanLimits {
+            min: (5, 3),
+            ..SpanLimits::default()
+        };
+        assert_eq!(
+            grid.insert_constrained(index(1), (2, 2), (1, 1), limits),
+            block(1, 1, 3, 2)
+        );
+
+        assert_eq!(
+            grid.insert_component(index(2), (4, 5), (1, 1)),
+            block(3, 2, 1, 1)
+        );
+    }
+
+    #[test]
+    fn reinsert_moves_component() {
+        let mut grid = GridLayout::new(10, 3);
+        grid.insert_component(index(0), (1, 1), (1, 1));
+        grid.insert_component(index(0), (5, 2), (1, 1));
+
+        assert_eq!(grid.get_cell_component_index(1, 1), None);
+        assert_eq!(grid.get_cell_component_index(5, 2), Some(index(0)));
+    }
+
+    #[test]
+    fn components_can_be_moved_into_each_others_spots() {
+        let mut grid = GridLayout::new(10, 3);
+        grid.insert_component(index(0), (1, 1), (1, 1));
+        grid.insert_component(index(1), (2, 1), (1, 1));
+
+        grid.insert_component(index(0), (2, 1), (1, 1));
+        grid.insert_component(index(1), (1, 1), (1, 1));
+
+        assert_eq!(grid.get_cell_component_index(1, 1), Some(index(1)));
+        assert_eq!(grid.get_cell_component_index(2, 1), Some(index(0)));
+    }
+
+    #[test]
+    fn remove_frees_cells() {
+        let mut grid = GridLayout::new(10, 3);
+        grid.insert_component(index(0), (1, 1), (3, 3));
+        grid.remove_component(index(0));
+
+        assert!(grid.is_data_block_empty(block(1, 1, 10, 3)));
+        assert_eq!(grid.get_component_block(index(0)), None);
+    }
+
+    #[test]
+    fn collisions_ignore_the_placed_component() {
+        let mut grid = GridLayout::new(10, 3);
+        grid.insert_component(index(0), (1, 1), (2, 2));
+        grid.insert_component(index(1), (4, 1), (2, 2));
+
+        // Overlapping itself is fine
+        assert!(grid.can_place_component(Some(index(0)), block(2, 1, 2, 2)));
+        assert!(!grid.is_component_overlapping(index(0), block(2, 1, 2, 2)));
+
+        // Overlapping a different component is not
+        assert!(!grid.can_place_component(Some(index(0)), block(3, 1, 2, 2)));
+        assert!(grid.is_component_overlapping(index(0), block(3, 1, 2, 2)));
+        assert_eq!(
+            grid.get_block_component_indices(block(1, 1, 5, 1)),
+            vec![index(0), index(1)]
+        );
+
+        // New components collide with everything
+        assert!(!grid.can_place_component(None, block(1, 1, 1, 1)));
+        assert!(grid.can_place_component(None, block(7, 1, 4, 3)));
+    }
+
+    #[test]
+    fn blocks_outside_of_the_grid_can_not_be_placed() {
+        let grid = GridLayout::new(10, 3);
+
+        assert!(grid.contains_block(block(9, 2, 2, 2)));
+        assert!(!grid.contains_block(block(10, 1, 2, 1)));
+        assert!(!grid.contains_block(block(1, 3, 1, 2)));
+        assert!(!grid.can_place_component(None, block(0, 1, 1, 1)));
+    }
+
+    #[test]
+    fn clamp_keeps_block_inside_of_the_grid() {
+        let grid = GridLayout::new(10, 3);
+
+        assert_eq!(grid.clamp_block_pos((12, 5), (2, 2)), (9, 2));
+        assert_eq!(grid.clamp_block_pos((0, 0), (1, 1)), (1, 1));
+        // Blocks bigger than the grid stick to the top left corner
+        assert_eq!(grid.clamp_block_pos((4, 2), (12, 1)), (1, 2));
+    }
+
+    #[test]
+    fn resize_refuses_to_cut_components() {
+        let mut grid = GridLayout::new(10, 3);
+        grid.insert_component(index(0), (1, 3), (1, 1));
+
+        assert!(!grid.resize(10, 2));
+        assert_eq!(grid.height(), 3);
+
+        assert!(grid.resize(10, 5));
+        assert_eq!(grid.height(), 5);
+        assert_eq!(grid.get_cell_component_index(1, 3), Some(index(0)));
+
+        grid.remove_component(index(0));
+        assert!(grid.resize(10, 1));
+        assert_eq!(grid.height(), 1);
+    }
+
+    fn grid_with(collision: CollisionStrategy) -> GridLayout {
+        let mut grid = GridLayout::new(10, 6);
+        grid.set_collision(collision);
+        grid.insert_component(index(0), (1, 1), (2, 2));
+        grid.insert_component(index(1), (4, 1), (2, 2));
+        grid
+    }
+
+    #[test]
+    fn deny_refuses_occupied_cells() {
+        let grid = grid_with(CollisionStrategy::Deny);
+
+        assert_eq!(grid.resolve_drop(index(0), block(3, 1, 2, 2)), None);
+        assert_eq!(
+            grid.resolve_drop(index(0), block(7, 1, 2, 2)),
+            Some(Arrangement {
+                block: block(7, 1, 2, 2),
+                displaced: vec![],
+                rows: 6,
+            })
+        );
+    }
+
+    #[test]
+    fn push_moves_overlapped_components_down() {
+        let mut grid = grid_with(CollisionStrategy::Push);
+        grid.insert_component(index(2), (4, 3), (1, 1));
+
+        // Pushing the second component also pushes the one below it
+        let arrangement = grid.resolve_drop(index(0), block(4, 1, 1, 2)).unwrap();
+        assert_eq!(arrangement.block, block(4, 1, 1, 2));
+        assert_eq!(
+            arrangement.displaced,
+            vec![(index(1), block(4, 3, 2, 2)), (index(2), block(4, 5, 1, 1))]
+        );
+
+        // There is no room left at the bottom
+        assert_eq!(grid.resolve_drop(index(3), block(4, 1, 1, 4)), None);
+    }
+
+    #[test]
+    fn swap_exchanges_positions() {
+        let grid = grid_with(CollisionStrategy::Swap);
+
+        let arrangement = grid.resolve_drop(index(0), block(4, 1, 2, 2)).unwrap();
+        assert_eq!(arrangement.displaced, vec![(index(1), block(1, 1, 2, 2))]);
+
+        // New components have no position to swap with
+        assert_eq!(grid.resolve_drop(index(2), block(4, 1, 1, 1)), None);
+        // Only a single component can be swapped
+        assert_eq!(grid.resolve_drop(index(2), block(2, 1, 3, 1)), None);
+    }
+
+    #[test]
+    fn compact_moves_everything_up() {
+        let mut grid = grid_with(CollisionStrategy::Compact);
+        grid.insert_component(index(2), (7, 4), (1, 1));
+
+        let arrangement = grid.resolve_drop(index(0), block(4, 2, 2, 2)).unwrap();
+        // The dropped component floats up and takes the spot of the pushed one
+        assert_eq!(arrangement.block, block(4, 1, 2, 2));
+        assert_eq!(
+            arrangement.displaced,
+            vec![(index(1), block(4, 3, 2, 2)), (index(2), block(7, 1, 1, 1))]
+        );
+    }
+
+    #[test]
+    fn auto_grow_adds_rows_past_the_bottom() {
+        let mut grid = grid_with(CollisionStrategy::Push);
+        grid.set_auto_grow(true);
+
+        assert_eq!(grid.clamp_block_pos((1, 8), (2, 2)), (1, 8));
+
+        let arrangement = grid.resolve_drop(index(2), block(1, 6, 2, 3)).unwrap();
+        assert_eq!(arrangement.rows, 8);
+
+        // Pushed components can also grow the grid
+        let arrangement = grid.resolve_drop(index(2), block(4, 1, 2, 6)).unwrap();
+        assert_eq!(arrangement.displaced, vec![(index(1), block(4, 7, 2, 2))]);
+        assert_eq!(arrangement.rows, 8);
+
+        // Columns are still limited
+        assert_eq!(grid.resolve_drop(index(2), block(10, 1, 2, 1)), None);
+    }
+
+    #[test]
+    fn auto_shrink_drops_trailing_empty_rows() {
+        let mut grid = grid_with(CollisionStrategy::Deny);
+        assert_eq!(grid.used_rows(), 2);
+
+        grid.set_auto_shrink(true);
+        let arrangement = grid.resolve_drop(index(1), block(4, 1, 1, 1)).unwrap();
+        assert_eq!(arrangement.rows, 2);
+
+        grid.remove_component(index(0));
+        let arrangement = grid.resolve_drop(index(1), block(4, 1, 1, 1)).unwrap();
+        assert_eq!(arrangement.rows, 1);
+    }
+
+    #[test]
+    fn groups_move_into_each_others_cells() {
+        let mut grid = grid_with(CollisionStrategy::Deny);
+
+        // Both components move two columns to the right, the first one into the cells of the second
+        let moves = [(index(0), block(3, 1, 2, 2)), (index(1), block(6, 1, 2, 2))];
+        assert_eq!(grid.resolve_group_move(&moves), Some(6));
+
+        // Members of the group can not overlap each other
+        let moves = [(index(0), block(4, 1, 2, 2)), (index(1), block(5, 2, 2, 2))];
+        assert_eq!(grid.resolve_group_move(&moves), None);
+
+        // Nor components that are not moved
+        let moves = [(index(1), block(1, 1, 2, 2))];
+        assert_eq!(grid.resolve_group_move(&moves), None);
+
+        // And the whole group has to stay inside of the grid, unless it grows
+        let moves = [(index(0), block(1, 6, 2, 2))];
+        assert_eq!(grid.resolve_group_move(&moves), None);
+
+        grid.set_auto_grow(true);
+        assert_eq!(grid.resolve_group_move(&moves), Some(7));
+    }
+
+    #[test]
+    fn pasted_blocks_take_the_nearest_free_cells() {
+        let mut grid = GridLayout::new(6, 2);
+        grid.insert_component(index(0), (1, 1), (2, 2));
+        grid.insert_component(index(1), (5, 1), (1, 1));
+
+        // The requested cells are taken, the closest free ones are right next to them
+        assert_eq!(
+            grid.nearest_free_block(block(1, 1, 2, 1)),
+            block(3, 1, 2, 1)
+        );
+        assert_eq!(
+            grid.nearest_free_block(block(5, 1, 1, 1)),
+            block(4, 1, 1, 1)
+        );
+        // Free cells are used as they are
+        assert_eq!(
+            grid.nearest_free_block(block(6, 2, 1, 1)),
+            block(6, 2, 1, 1)
+        );
+
+        // No space left, the block goes below the last used row
+        assert_eq!(
+            grid.nearest_free_block(block(1, 1, 3, 2)),
+            block(1, 3, 3, 2)
+        );
+        // Blocks wider than the grid are shrunk to fit
+        assert_eq!(grid.nearest_free_block(block(2, 1, 9, 1)).width, 6);
+    }
+
+    #[test]
+    fn removed_breakpoint_columns_can_be_restored() {
+        let mut grid = GridLayout::new(12, 3);
+        grid.set_breakpoint_columns("mobile", 4);
+        grid.set_breakpoint_columns("tablet", 8);
+
+        let columns = grid.remove_breakpoint_columns("mobile");
+        assert_eq!(columns, Some(4));
+        assert_eq!(grid.remove_breakpoint_columns("mobile"), None);
+        assert_eq!(grid.breakpoint_columns().len(), 1);
+
+        // Undoing the removal of the breakpoint stores the columns again
+        grid.set_breakpoint_columns("mobile", columns.unwrap());
+        assert_eq!(grid.breakpoint_columns().get("mobile"), Some(&4));
+    }
+
+    #[test]
+    fn strategies_are_named() {
+        for strategy in CollisionStrategy::ALL.iter() {
+            assert_eq!(
+                CollisionStrategy::from_name(strategy.name()),
+                Some(*strategy)
+            );
+        }
+        assert_eq!(CollisionStrategy::from_name("float"), None);
+    }
+
+    #[test]
+    fn spans_include_the_gaps_between_cells() {
+        let metrics = GridMetrics::new(920, 10, 90, 10);
+
+        assert_eq!(metrics.column_width, 83);
+        assert_eq!(metrics.span_width(1), 83);
+        assert_eq!(metrics.span_width(3), 3 * 83 + 2 * 10);
+        assert_eq!(metrics.span_width(10), metrics.column_width * 10 + 90);
+    }
+
+    #[test]
+    fn offsets_are_mapped_to_cells() {
+        let metrics = GridMetrics::new(900, 10, 90, 0);
+
+        assert_eq!(metrics.cell_at_offset((0.0, 0.0)), (1, 1));
+        assert_eq!(metrics.cell_at_offset((95.0, 179.0)), (2, 2));
+        assert_eq!(metrics.cell_at_offset((-20.0, 10.0)), (1, 1));
+
+        assert_eq!(metrics.cells_for_size((90.0, 91.0)), (1, 2));
+        assert_eq!(metrics.cells_for_size((0.0, 0.0)), (1, 1));
+    }
+
+    #[test]
+    fn metrics_account_for_gaps() {
+        // 12 columns with 16px gutters, like most css frameworks
+        let metrics = GridMetrics::new(1136, 12, 40, 16);
+        assert_eq!(metrics.column_width, 80);
+
+        assert_eq!(metrics.height_for_rows(3), 152);
+        assert_eq!(metrics.rows_for_height(152), 3);
+        assert_eq!(metrics.rows_for_height(200), 3);
+
+        assert_eq!(metrics.cell_at_offset((95.0, 57.0)), (1, 2));
+        assert_eq!(metrics.cell_at_offset((96.0, 56.0)), (2, 2));
+
+        // A component spanning two columns covers the gutter between them
+        assert_eq!(metrics.cells_for_size((176.0, 40.0)), (2, 1));
+        assert_eq!(metrics.cells_for_size((177.0, 41.0)), (3, 2));
+    }
+}
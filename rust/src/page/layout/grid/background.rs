@@ -1,20 +1,33 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 use wasm_bindgen::JsCast;
 use web_sys::{Element, HtmlElement, SvgsvgElement};
 
-use crate::{component::Component, editor::workspace::Workspace, page::layout::grid::Block};
+use crate::page::layout::grid::{Block, GridItem};
+
+use super::{GridMetrics, Placeholder};
+
+/// Counter used to give every grid its own svg pattern id
+static PATTERN_COUNT: AtomicUsize = AtomicUsize::new(0);
 
-use super::GridLayout;
+/// Inset of the cell rectangles in px, used when the grid has no gap
+const PATTERN_INSET: u32 = 4;
 
 pub struct GridBackground {
     svg: SvgsvgElement,
 
+    /// Svg pattern of a single cell, every grid has its own as cell sizes differ
+    pattern: Element,
+    pattern_rect: Element,
+
     placeholder_elm: HtmlElement,
 
     placeholder_visible: bool,
-    placeholder_denied: bool,
+    /// Last shown placeholder
+    placeholder: Placeholder,
 
-    placeholder_pos: (usize, usize),
-    placeholder_size: (usize, usize),
+    /// Previews of the components that will be moved by the drop
+    displaced_elms: Vec<HtmlElement>,
 }
 
 impl GridBackground {
@@ -29,15 +42,29 @@ impl GridBackground {
         svg.set_attribute("width", "100%");
         svg.set_attribute("height", "100%");
 
-        svg.set_inner_html(
+        let pattern_id = format!(
+            "grid-pattern-{}",
+            PATTERN_COUNT.fetch_add(1, Ordering::Relaxed)
+        );
+
+        svg.set_inner_html(&format!(
             r#"
+              <defs>
+                <pattern id="{id}" patternUnits="userSpaceOnUse">
+                  <rect fill="rgba(0,0,0,.02)"></rect>
+                </pattern>
+              </defs>
               <rect
                 width="100%"
                 height="100%"
-                fill="url(#grid-pattern)"
+                fill="url(#{id})"
               ></rect>
           "#,
-        );
+            id = pattern_id
+        ));
+
+        let pattern = svg.query_selector("pattern").unwrap().unwrap();
+        let pattern_rect = pattern.query_selector("rect").unwrap().unwrap();
 
         let placeholder_elm = document.create_element("div").unwrap();
         let placeholder_elm: HtmlElement = placeholder_elm.dyn_into().unwrap();
@@ -49,44 +76,100 @@ impl GridBackground {
         Self {
             svg,
 
+            pattern,
+            pattern_rect,
+
             placeholder_elm,
 
             placeholder_visible: false,
-            placeholder_denied: false,
-
-            placeholder_pos: (1, 1),
-            placeholder_size: (1, 1),
+            placeholder: Placeholder {
+                block: Block {
+                    x: 1,
+                    y: 1,
+                    width: 1,
+                    height: 1,
+                },
+                displaced: Vec::new(),
+                rows: 0,
+                denied: false,
+            },
+
+            displaced_elms: Vec::new(),
         }
     }
 
+    /// Resize the cells of the background pattern
+    pub fn update_pattern(&self, metrics: &GridMetrics) {
+        let inset = if metrics.gap == 0 { PATTERN_INSET } else { 0 };
+
+        let width = metrics.column_width + metrics.gap;
+        let height = metrics.row_height + metrics.gap;
+
+        self.pattern
+            .set_attribute("width", &width.to_string())
+            .unwrap();
+        self.pattern
+            .set_attribute("height", &height.to_string())
+            .unwrap();
+
+        let rect = &self.pattern_rect;
+        rect.set_attribute("x", &inset.to_string()).unwrap();
+        rect.set_attribute("y", &inset.to_string()).unwrap();
+        rect.set_attribute(
+            "width",
+            &metrics.column_width.saturating_sub(inset * 2).to_string(),
+        )
+        .unwrap();
+        rect.set_attribute(
+            "height",
+            &metrics.row_height.saturating_sub(inset * 2).to_string(),
+        )
+        .unwrap();
+    }
+
     /// Append the background to the element
     pub fn append_to(&self, parent: &Element) {
         parent.append_child(&self.svg).unwrap();
         parent.append_child(&self.placeholder_elm).unwrap();
     }
 
-    pub fn is_placeholder_denied(&self) -> bool {
-        self.placeholder_denied
+    /// Remove the background from its parent
+    pub fn remove(&self) {
+        self.svg.remove();
+        self.placeholder_elm.remove();
     }
 
-    fn set_placeholder_deny(&mut self, is: bool) {
-        if is != self.placeholder_denied {
-            if is {
-                self.placeholder_elm.class_list().add_1("deny").unwrap();
-            } else {
-                self.placeholder_elm.class_list().remove_1("deny").unwrap();
-            }
+    /// Last shown placeholder, with the components that it displaces
+    pub fn placeholder(&self) -> &Placeholder {
+        &self.placeholder
+    }
+
+    /// Show the new blocks of the displaced components
+    fn show_displaced(&mut self, displaced: &[(GridItem, Block)]) {
+        let document = self.placeholder_elm.owner_document().unwrap();
+
+        for elm in self
+            .displaced_elms
+            .drain(displaced.len().min(self.displaced_elms.len())..)
+        {
+            elm.remove();
         }
 
-        self.placeholder_denied = is;
-    }
+        while self.displaced_elms.len() < displaced.len() {
+            let elm = document.create_element("div").unwrap();
+            let elm: HtmlElement = elm.dyn_into().unwrap();
+            elm.class_list().add_1("grid-displaced").unwrap();
 
-    pub fn placeholder_pos(&self) -> (usize, usize) {
-        self.placeholder_pos
-    }
+            if let Some(parent) = self.placeholder_elm.parent_node() {
+                parent.append_child(&elm).unwrap();
+            }
 
-    pub fn placeholder_size(&self) -> (usize, usize) {
-        self.placeholder_size
+            self.displaced_elms.push(elm);
+        }
+
+        for (elm, (_, block)) in self.displaced_elms.iter().zip(displaced.iter()) {
+            set_grid_area(elm, block.x, block.y, block.width, block.height);
+        }
     }
 
     pub fn set_placeholder_visible(&mut self, is: bool) {
@@ -109,44 +192,48 @@ impl GridBackground {
                     .style()
                     .set_property("opacity", "0")
                     .unwrap();
+
+                // The displaced components are still needed to finish the drop, so only hide the previews
+                for elm in self.displaced_elms.drain(..) {
+                    elm.remove();
+                }
             }
         }
 
         self.placeholder_visible = is;
     }
 
-    pub fn update_placeholder(
-        &mut self,
-        workspace: &Workspace,
-        grid_data: &GridLayout,
-        component: &Component,
-        (x, y): (usize, usize),
-        (width, height): (usize, usize),
-    ) {
-        let is = grid_data.get_block_component_indices(Block {
-            x,
-            y,
-            width,
-            height,
-        });
-
-        let is = is
-            .iter()
-            .any(|i| workspace.components().get(*i) != Some(component));
-
-        self.set_placeholder_deny(is);
+    /// Show a placeholder, together with the previews of the components that it displaces
+    ///
+    /// Denied placeholders are marked, see `GridLayout::placeholder`
+    pub fn show_placeholder(&mut self, placeholder: Placeholder) {
         self.set_placeholder_visible(true);
 
-        self.placeholder_pos = (x, y);
-        self.placeholder_size = (width, height);
-
         self.placeholder_elm
-            .style()
-            .set_property("grid-column", &format!("{}/span {}", x, width))
-            .unwrap();
-        self.placeholder_elm
-            .style()
-            .set_property("grid-row", &format!("{}/span {}", y, height))
+            .class_list()
+            .toggle_with_force("deny", placeholder.denied)
             .unwrap();
+        self.show_displaced(&placeholder.displaced);
+
+        let block = placeholder.block;
+        set_grid_area(
+            &self.placeholder_elm,
+            block.x,
+            block.y,
+            block.width,
+            block.height,
+        );
+
+        self.placeholder = placeholder;
     }
 }
+
+/// Place the element at a block of the css grid
+fn set_grid_area(elm: &HtmlElement, x: usize, y: usize, width: usize, height: usize) {
+    elm.style()
+        .set_property("grid-column", &format!("{}/span {}", x, width))
+        .unwrap();
+    elm.style()
+        .set_property("grid-row", &format!("{}/span {}", y, height))
+        .unwrap();
+}
@@ -2,27 +2,43 @@
 
 use std::{
     borrow::Borrow,
-    cell::{Ref, RefCell, RefMut},
+    cell::{Cell, Ref, RefCell, RefMut},
+    collections::BTreeMap,
     rc::Rc,
 };
 
-use generational_arena::Index;
+use serde::{Deserialize, Serialize};
 use wasm_bindgen::JsCast;
 use web_sys::{Element, HtmlElement};
 
 pub mod grid;
-use grid::GridLayout;
+use grid::{Block, CollisionStrategy, GridItem, GridLayout, GridMetrics};
 
-mod free;
-use free::FreeLayout;
+pub mod free;
+use free::{guides::FreeGuides, FreeLayout, Snap};
 
-mod flex;
-use flex::FlexLayout;
+pub mod flex;
+use flex::{FlexDirection, FlexLayout};
 
-use crate::{component::Component, editor::hierarchy::HierarchyItemData};
+pub mod model;
+use model::{DraggedItem, DropTarget, GridResizeStep, LayoutModel, MovePlacement, GRID_COLUMNS};
+
+pub mod convert;
+use convert::{ConvertItem, ConvertTarget, ItemPlacement};
+
+use crate::{
+    component::{Component, SpanLimits},
+    document::{LayoutDocument, LayoutKindDocument, PlacementDocument},
+    editor::hierarchy::HierarchyItemData,
+    ids,
+    page::breakpoint::{self, BreakpointPlacement},
+};
 
 use self::grid::background::GridBackground;
 
+/// Bounding rect `((x, y), (width, height))` of an item of a layout
+pub type Rect = ((f64, f64), (f64, f64));
+
 /// Type of a layout
 pub enum LayoutKind {
     /// Layout based on absolute position
@@ -40,28 +56,214 @@ pub enum LayoutKind {
 
     /// CSS Grid based layout
     Grid {
-        /// Size of a grid cell in px
-        cell_size: u32,
-        /// Grid background
-        grid_background: GridBackground,
+        /// Size of grid cells in px
+        metrics: GridMetrics,
         /// Data related to grid layout implementation
         grid_data: Box<GridLayout>,
     },
 }
 
+impl LayoutKind {
+    /// Create the layout kind from its serialized counterpart
+    ///
+    /// Grid layouts are created empty, their components are inserted later on
+    pub fn from_document(document: &LayoutKindDocument, width: u32, height: u32) -> Self {
+        match document {
+            LayoutKindDocument::Free {
+                snap_grid,
+                snap_to_items,
+            } => {
+                let mut free = FreeLayout::new(width, height);
+                free.set_snap_grid(*snap_grid);
+                free.set_snap_to_items(*snap_to_items);
+
+                LayoutKind::Free { free }
+            }
+            LayoutKindDocument::Flex {
+                justify,
+                align,
+                direction,
+                wrap,
+                gap,
+            } => {
+                let mut flex = FlexLayout::new(width, height);
+                flex.set_justify(justify);
+                flex.set_align(align);
+                flex.set_direction(*direction);
+                flex.set_wrap(*wrap);
+                flex.set_gap(*gap);
+
+                LayoutKind::Flex { flex }
+            }
+            LayoutKindDocument::Grid {
+                column_width,
+                row_height,
+                gap,
+                columns,
+                rows,
+                collision,
+                auto_grow,
+                auto_shrink,
+                breakpoint_columns,
+            } => {
+                let mut grid_data = GridLayout::new(*columns, *rows);
+                grid_data.set_collision(*collision);
+                grid_data.set_auto_grow(*auto_grow);
+                grid_data.set_auto_shrink(*auto_shrink);
+                for (breakpoint, columns) in breakpoint_columns.iter() {
+                    grid_data.set_breakpoint_columns(breakpoint, *columns);
+                }
+
+                LayoutKind::Grid {
+                    metrics: GridMetrics {
+                        column_width: *column_width,
+                        row_height: row_height.unwrap_or(*column_width),
+                        gap: *gap,
+                    },
+                    grid_data: Box::new(grid_data),
+                }
+            }
+        }
+    }
+
+    /// Take a serializable snapshot of the layout kind
+    pub fn to_document(&self) -> LayoutKindDocument {
+        match self {
+            LayoutKind::Free { free } => LayoutKindDocument::Free {
+                snap_grid: free.snap_grid(),
+                snap_to_items: free.snap_to_items(),
+            },
+            LayoutKind::Flex { flex } => LayoutKindDocument::Flex {
+                justify: flex.justify().into(),
+                align: flex.align().into(),
+                direction: flex.direction(),
+                wrap: flex.wrap(),
+                gap: flex.gap(),
+            },
+            LayoutKind::Grid {
+                metrics, grid_data, ..
+            } => LayoutKindDocument::Grid {
+                column_width: metrics.column_width,
+                row_height: Some(metrics.row_height),
+                gap: metrics.gap,
+                columns: grid_data.width(),
+                rows: grid_data.height(),
+                collision: grid_data.collision(),
+                auto_grow: grid_data.auto_grow(),
+                auto_shrink: grid_data.auto_shrink(),
+                breakpoint_columns: grid_data.breakpoint_columns().clone(),
+            },
+        }
+    }
+}
+
+/// Placement of a nested layout inside of its parent layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChildPlacement {
+    /// Item of a grid layout
+    Grid {
+        /// Top left cell of the layout (starting from 1)
+        pos: (usize, usize),
+        /// Size of the layout in cells
+        size: (usize, usize),
+    },
+    /// Absolutely positioned item of a free layout
+    Free {
+        /// Position in px, relative to the left-top point of the parent
+        position: (i32, i32),
+    },
+    /// Item of a flex layout, positioned by the flexbox itself
+    Flex,
+}
+
+/// Layout kind of a layout together with the placements of all of its items
+///
+/// Used to convert a layout to a different kind, and to undo the conversion
+#[derive(Clone)]
+pub struct KindSnapshot {
+    kind: LayoutKindDocument,
+    /// Height of the layout in px
+    height: u32,
+    /// Components in the order of the layout
+    components: Vec<ComponentSnapshot>,
+    /// Nested layouts and their placements
+    children: Vec<(Layout, ChildPlacement)>,
+}
+
+/// Everything that layouts stored for a breakpoint, taken when the breakpoint is removed
+///
+/// Used to undo the removal
+#[derive(Clone, Default)]
+pub struct StoredArrangement {
+    /// Column counts of grid layouts
+    columns: Vec<(Layout, usize)>,
+    /// Placements of components
+    components: Vec<(Component, BreakpointPlacement)>,
+    /// Placements of nested layouts inside of their parents
+    children: Vec<(Layout, ChildPlacement)>,
+}
+
+impl StoredArrangement {
+    /// Store everything for the breakpoint again
+    pub fn restore(&self, breakpoint: &str) {
+        for (layout, columns) in self.columns.iter() {
+            if let LayoutKind::Grid { grid_data, .. } = &mut *layout.kind_mut() {
+                grid_data.set_breakpoint_columns(breakpoint, *columns);
+            }
+        }
+
+        for (component, placement) in self.components.iter() {
+            component.set_breakpoint_placement(breakpoint, *placement);
+        }
+
+        for (child, placement) in self.children.iter() {
+            child
+                .data
+                .breakpoints
+                .borrow_mut()
+                .insert(breakpoint.into(), *placement);
+        }
+    }
+}
+
+#[derive(Clone)]
+struct ComponentSnapshot {
+    component: Component,
+    placement: PlacementDocument,
+    /// Size in px, `None` if the component takes its natural size
+    size: Option<(f64, f64)>,
+    hidden: bool,
+}
+
+thread_local! {
+    /// Key of the next layout in the cells of grid layouts
+    static NEXT_GRID_KEY: Cell<usize> = const { Cell::new(0) };
+}
+
 struct Data {
-    /// Name of a layout
-    name: RefCell<String>,
-
-    /// Height of a layout
-    height: RefCell<u32>,
-    /// Width of a layout
-    width: RefCell<u32>,
-    /// Layout kind specyfic data
-    kind: RefCell<LayoutKind>,
+    /// DOM independent state of a layout (name, size, layout kind specyfic data)
+    model: RefCell<LayoutModel>,
     /// Children of a layout
     components: RefCell<Vec<Component>>,
 
+    /// Layouts nested inside of this one, in the order they were inserted
+    children: RefCell<Vec<Layout>>,
+    /// Placement inside of the parent layout, `None` for layouts placed directly in a page
+    placement: RefCell<Option<ChildPlacement>>,
+    /// Placement inside of a parent grid at each breakpoint of the page [breakpoint name -> placement]
+    breakpoints: RefCell<BTreeMap<String, ChildPlacement>>,
+    /// Key of the layout in the cells of a parent grid layout
+    grid_item: GridItem,
+
+    /// Grid background, only present in grid layouts
+    grid_background: RefCell<Option<GridBackground>>,
+    /// Alignment guides, only present in free layouts
+    free_guides: RefCell<Option<FreeGuides>>,
+
+    /// Stable user-facing id, kept when the layout is saved and loaded
+    uid: RefCell<String>,
+
     /// Hierarchy related data
     hierarchy_data: RefCell<HierarchyItemData>,
 }
@@ -82,66 +284,55 @@ impl Layout {
     /// It will initialize the layout data,
     /// and also create a new html element that represents the layout
     pub fn new(width: u32, height: u32, kind: LayoutKind) -> Self {
+        Self::from_model(LayoutModel::new(width, height, kind))
+    }
+
+    /// Creates a new layout from its model
+    ///
+    /// It will create a new html element that represents the layout
+    pub fn from_model(model: LayoutModel) -> Self {
         let document = web_sys::window().unwrap().document().unwrap();
 
         let html_element = document.create_element("layout-container").unwrap();
-        let html_element: HtmlElement = html_element.dyn_into().unwrap();
-
-        let name = match &kind {
-            LayoutKind::Free { .. } => {
-                html_element
-                    .class_list()
-                    .add_3("free", "block", "relative")
-                    .unwrap();
-                "Free"
-            }
-            LayoutKind::Flex { .. } => {
-                html_element
-                    .class_list()
-                    .add_4("flex", "items-center", "justify-center", "flex-wrap")
-                    .unwrap();
-                "Flex"
-            }
-            LayoutKind::Grid { cell_size, .. } => {
-                html_element
-                    .class_list()
-                    .add_3("grid", "justify-items-center", "items-center")
-                    .unwrap();
+        let mut html_element: HtmlElement = html_element.dyn_into().unwrap();
 
-                html_element.style().set_property(
-                    "grid-template-columns",
-                    &format!("repeat({}, {}px)", 10, cell_size),
-                );
-                html_element.style().set_property(
-                    "grid-template-rows",
-                    &format!("repeat({}, {}px)", 3, cell_size),
-                );
+        model.render(&mut html_element);
 
-                "Grid"
+        let grid_background = match model.kind() {
+            LayoutKind::Grid { metrics, .. } => {
+                let background = GridBackground::new();
+                background.update_pattern(metrics);
+                Some(background)
             }
+            _ => None,
         };
 
-        // We don't handle width properly ATM
-        //
-        // html_element
-        //     .style()
-        //     .set_property("width", &format!("{}px", width))
-        //     .unwrap();
-        html_element
-            .style()
-            .set_property("height", &format!("{}px", height))
-            .unwrap();
+        let free_guides = match model.kind() {
+            LayoutKind::Free { .. } => Some(FreeGuides::new()),
+            _ => None,
+        };
 
         Self {
             html_element,
             data: Rc::new(Data {
-                name: RefCell::new(name.into()),
-                height: RefCell::new(height),
-                width: RefCell::new(width),
-                kind: RefCell::new(kind),
+                model: RefCell::new(model),
 
                 components: Default::default(),
 
+                children: Default::default(),
+                placement: Default::default(),
+                breakpoints: Default::default(),
+                grid_item: NEXT_GRID_KEY.with(|next| {
+                    let key = next.get();
+                    next.set(key + 1);
+                    GridItem::Layout(key)
+                }),
+
+                grid_background: RefCell::new(grid_background),
+                free_guides: RefCell::new(free_guides),
+
+                uid: RefCell::new(ids::new_id("layout")),
+
                 hierarchy_data: RefCell::new(HierarchyItemData::new()),
             }),
         }
@@ -149,62 +340,52 @@ impl Layout {
 
     /// Creates a new free layout
     pub fn new_free(width: u32, height: u32) -> Self {
-        Self::new(
-            width,
-            height,
-            LayoutKind::Free {
-                free: FreeLayout::new(width, height),
-            },
-        )
+        Self::from_model(LayoutModel::new_free(width, height))
     }
 
     /// Creates a new flex layout
     pub fn new_flex(width: u32, height: u32) -> Self {
-        Self::new(
-            width,
-            height,
-            LayoutKind::Flex {
-                flex: FlexLayout::new(width, height),
-            },
-        )
+        Self::from_model(LayoutModel::new_flex(width, height))
     }
 
     /// Creates a new grid layout
     ///
     /// # Arguments
     /// * `width` - width of a layout in px
-    /// * `height` - height of a layout in px
-    /// * `cell_size` - size of a grid cell in px
     pub fn new_grid(width: u32) -> Self {
-        let cell_size = width / 10;
-        let grid_w = (width as f64 / cell_size as f64).round();
+        Self::from_model(LayoutModel::new_grid(width))
+    }
 
-        let height = cell_size * 3;
-        let grid_h = 3;
+    /// Creates a new layout from a serialized snapshot
+    ///
+    /// Components are not restored here, as they have to be registered in the workspace first
+    pub fn from_document(document: &LayoutDocument) -> Self {
+        let (width, height) = (document.width, document.height);
 
-        Self::new(
-            width,
-            height,
-            LayoutKind::Grid {
-                cell_size,
-                grid_background: GridBackground::new(),
-                grid_data: Box::new(GridLayout::new(grid_w as usize, grid_h as usize)),
-            },
-        )
+        let kind = LayoutKind::from_document(&document.kind, width, height);
+
+        let mut layout = Self::new(width, height, kind);
+        layout.set_name(&document.name);
+        if let Some(uid) = &document.id {
+            layout.set_uid(uid);
+        }
+        layout
+            .data
+            .breakpoints
+            .replace(document.breakpoints.clone());
+        layout
     }
 
     /// Append the layout to the element
     ///
     /// Used to append layout to the page
     pub fn append_to(&self, parent: &Element) {
-        let kind = self.data.kind.borrow();
-
-        if let LayoutKind::Grid {
-            grid_background, ..
-        } = &*kind
-        {
+        if let Some(grid_background) = self.grid_background() {
             grid_background.append_to(&self.html_element);
         }
+        if let Some(free_guides) = self.free_guides() {
+            free_guides.append_to(&self.html_element);
+        }
 
         parent.append_child(&self.html_element).unwrap();
     }
@@ -238,15 +419,43 @@ impl Layout {
     }
 
     pub fn name(&self) -> Ref<str> {
-        Ref::map(self.data.name.borrow(), |name| name.as_ref())
+        Ref::map(self.data.model.borrow(), |model| model.name())
+    }
+
+    pub fn set_name(&mut self, name: &str) {
+        self.data.model.borrow_mut().set_name(name);
+    }
+
+    /// Stable user-facing id of the layout, for example `layout-2`
+    pub fn uid(&self) -> Ref<'_, str> {
+        Ref::map(self.data.uid.borrow(), |uid| uid.as_ref())
+    }
+
+    /// Replace the id, used when a layout is loaded from a document
+    pub fn set_uid(&mut self, uid: &str) {
+        ids::reserve_id(uid);
+        self.data.uid.replace(uid.into());
     }
 
     pub fn kind(&self) -> Ref<LayoutKind> {
-        self.data.kind.borrow()
+        Ref::map(self.data.model.borrow(), |model| model.kind())
     }
 
     pub fn kind_mut(&self) -> RefMut<LayoutKind> {
-        self.data.kind.borrow_mut()
+        RefMut::map(self.data.model.borrow_mut(), |model| model.kind_mut())
+    }
+
+    /// Get the grid background, `None` if this is not a grid layout
+    pub fn grid_background(&self) -> Option<RefMut<'_, GridBackground>> {
+        RefMut::filter_map(self.data.grid_background.borrow_mut(), |background| {
+            background.as_mut()
+        })
+        .ok()
+    }
+
+    /// Get the alignment guides, `None` if this is not a free layout
+    pub fn free_guides(&self) -> Option<Ref<'_, FreeGuides>> {
+        Ref::filter_map(self.data.free_guides.borrow(), |guides| guides.as_ref()).ok()
     }
 
     pub fn bounding_client_rect(&self) -> ((f64, f64), (f64, f64)) {
@@ -267,19 +476,42 @@ impl Layout {
     }
 
     pub fn insert_component(&mut self, mut component: Component) {
-        self.html_element.append_child(component.element());
-
-        self.data.components.borrow_mut().push(component.clone());
+        // Components are reinserted after every move or resize within the layout,
+        // they keep their place in the order of the components then
+        match self.component_order(&component) {
+            Some(order) => self.move_component(&component, order),
+            None => {
+                self.html_element.append_child(component.element());
+                self.data.components.borrow_mut().push(component.clone());
+            }
+        }
 
         component.set_layout(Some(self.html_element.clone()));
 
         // Disabling the "redundand single branch match" lint
         // because we will want to extend this match in future
         #[allow(clippy::single_match)]
-        match &mut *self.data.kind.borrow_mut() {
+        match &mut *self.kind_mut() {
+            // Components hidden at the current breakpoint do not take any cells
             LayoutKind::Grid {
                 grid_data: grid, ..
-            } => grid.insert_component(component),
+            } if !component.is_hidden() => {
+                let block = grid.insert_constrained(
+                    component.grid_item(),
+                    component.grid_pos().unwrap(),
+                    component.grid_size().unwrap(),
+                    component.constraints().span_limits(),
+                );
+
+                // The block is moved back inside of the grid if its span does not fit
+                let (pos, size) = ((block.x, block.y), (block.width, block.height));
+                if component.grid_pos() != Some(pos) {
+                    component.set_grid_pos(pos);
+                }
+                if component.grid_size() != Some(size) {
+                    component.set_grid_size(size);
+                }
+            }
             _ => {}
         };
     }
@@ -295,83 +527,389 @@ impl Layout {
             // Disabling the "redundand single branch match" lint
             // because we will want to extend this match in future
             #[allow(clippy::single_match)]
-            match &mut *self.data.kind.borrow_mut() {
+            match &mut *self.kind_mut() {
                 LayoutKind::Grid {
                     grid_data: grid, ..
-                } => grid.remove_component(component),
+                } => grid.remove_component(component.grid_item()),
                 _ => {}
             };
         }
     }
 
+    /// Move a component of the layout to a new position in the order of the components
+    ///
+    /// Flex layouts lay out their items in this order
+    ///
+    /// # Arguments
+    /// * `component` - component of this layout
+    /// * `index` - new position, clamped to the number of components
+    pub fn move_component(&self, component: &Component, index: usize) {
+        let next = {
+            let mut components = self.data.components.borrow_mut();
+
+            let from = match components.iter().position(|c| c == component) {
+                Some(from) => from,
+                None => return,
+            };
+
+            let component = components.remove(from);
+            let index = index.min(components.len());
+            components.insert(index, component);
+
+            components.get(index + 1).cloned()
+        };
+
+        // Keep the html elements in the same order, so that the flexbox follows it
+        let element: &HtmlElement = component.element();
+        match next {
+            Some(next) => {
+                let next: &HtmlElement = next.element();
+                self.html_element
+                    .insert_before(element, Some(next))
+                    .unwrap();
+            }
+            None => {
+                self.html_element.append_child(element).unwrap();
+            }
+        }
+    }
+
+    /// Position of a component in the order of the components of the layout
+    pub fn component_order(&self, component: &Component) -> Option<usize> {
+        self.components().iter().position(|c| c == component)
+    }
+
+    /// Find where a component moved into this layout without a point to drop it at ends up,
+    /// see [`LayoutModel::move_placement`]
+    ///
+    /// Returns `None` if the component would stay where it is
+    pub fn move_placement(
+        &self,
+        component: &Component,
+        before: Option<&Component>,
+    ) -> Option<MovePlacement> {
+        self.data
+            .model
+            .borrow()
+            .move_placement(component, &self.components(), before, |metrics| {
+                component.grid_size().unwrap_or_else(|| {
+                    let (_, size) = component.bounding_client_rect();
+                    component
+                        .constraints()
+                        .initial_span(metrics.cells_for_size(size))
+                })
+            })
+    }
+
     pub fn size(&self) -> (u32, u32) {
-        (*self.data.width.borrow(), *self.data.height.borrow())
+        self.data.model.borrow().size()
     }
 
     pub fn resize(&mut self, width: Option<u32>, height: Option<u32>) {
-        // Disabling the "redundand single branch match" lint
-        // because we will want to extend this match in future
-        #[allow(clippy::single_match)]
-        match &mut *self.data.kind.borrow_mut() {
-            LayoutKind::Grid {
-                grid_data,
-                cell_size,
-                ..
-            } => {
-                let new_cell_size = if let Some(width) = width {
-                    width / 10
-                } else {
-                    *cell_size
-                };
+        let mut html_element = self.html_element.clone();
+        self.data
+            .model
+            .borrow_mut()
+            .resize(width, height, &mut html_element);
+
+        self.update_grid_pattern();
+        self.update_children();
+    }
 
-                let grid_w = 10;
+    /// Change the number of rows of a grid layout, keeping its row height
+    pub fn set_grid_rows(&mut self, rows: usize) {
+        let height = match &*self.kind() {
+            LayoutKind::Grid { metrics, .. } => metrics.height_for_rows(rows),
+            _ => return,
+        };
 
-                let grid_h = if let Some(height) = height {
-                    let h = (height as f64) / new_cell_size as f64;
-                    h.floor() as usize
-                } else {
-                    grid_data.height()
-                };
+        self.resize(None, Some(height));
+    }
 
-                if grid_data.resize(grid_w, grid_h) {
-                    if let Some(width) = width {
-                        self.data.width.replace(width);
-                    }
-                    if let Some(height) = height {
-                        self.data.height.replace(height);
-                    }
+    /// Column count, row height and gap of a grid layout, `None` if this is not a grid layout
+    pub fn grid_geometry(&self) -> Option<(usize, u32, u32)> {
+        match &*self.kind() {
+            LayoutKind::Grid { grid_data, metrics } => {
+                Some((grid_data.width(), metrics.row_height, metrics.gap))
+            }
+            _ => None,
+        }
+    }
 
-                    *cell_size = new_cell_size;
-
-                    self.html_element
-                        .style()
-                        .set_property("height", &format!("{}px", *cell_size as usize * grid_h))
-                        .unwrap();
-
-                    self.html_element.style().set_property(
-                        "grid-template-columns",
-                        &format!("repeat({}, {}px)", grid_w, cell_size),
-                    );
-                    self.html_element.style().set_property(
-                        "grid-template-rows",
-                        &format!("repeat({}, {}px)", grid_h, cell_size),
-                    );
-                }
+    /// Change the column count, row height and gap of a grid layout
+    ///
+    /// Returns `false` if the grid refused to drop columns occupied by components
+    pub fn set_grid_geometry(&mut self, columns: usize, row_height: u32, gap: u32) -> bool {
+        let mut html_element = self.html_element.clone();
+        let is = self.data.model.borrow_mut().set_grid_geometry(
+            columns,
+            row_height,
+            gap,
+            &mut html_element,
+        );
+
+        self.update_grid_pattern();
+        self.update_children();
+        is
+    }
+
+    /// Sync the background pattern with the size of the grid cells
+    fn update_grid_pattern(&self) {
+        if let (LayoutKind::Grid { metrics, .. }, Some(grid_background)) =
+            (&*self.kind(), self.grid_background())
+        {
+            grid_background.update_pattern(metrics);
+        }
+    }
+
+    /// Remember the current arrangement of the layout as the one of a breakpoint
+    pub fn store_breakpoint(&self, breakpoint: &str) {
+        let mut kind = self.kind_mut();
+
+        if let LayoutKind::Grid { grid_data, .. } = &mut *kind {
+            let columns = grid_data.width();
+            grid_data.set_breakpoint_columns(breakpoint, columns);
+        }
+
+        for component in self.components().iter() {
+            component.set_breakpoint_placement(breakpoint, component.current_placement(&kind));
+        }
+
+        for child in self.children().iter() {
+            if let Some(placement) = child.placement() {
+                child
+                    .data
+                    .breakpoints
+                    .borrow_mut()
+                    .insert(breakpoint.into(), placement);
+            }
+
+            child.store_breakpoint(breakpoint);
+        }
+    }
+
+    /// Forget everything that was stored for a breakpoint
+    ///
+    /// Returns what was forgotten, so that it can be restored later
+    pub fn remove_breakpoint(&self, breakpoint: &str) -> StoredArrangement {
+        let mut stored = StoredArrangement::default();
+        self.take_breakpoint(breakpoint, &mut stored);
+        stored
+    }
+
+    /// Move everything that was stored for a breakpoint into `stored`, nested layouts included
+    fn take_breakpoint(&self, breakpoint: &str, stored: &mut StoredArrangement) {
+        if let LayoutKind::Grid { grid_data, .. } = &mut *self.kind_mut() {
+            if let Some(columns) = grid_data.remove_breakpoint_columns(breakpoint) {
+                stored.columns.push((self.clone(), columns));
+            }
+        }
+
+        for component in self.components().iter() {
+            if let Some(placement) = component.remove_breakpoint_placement(breakpoint) {
+                stored.components.push((component.clone(), placement));
+            }
+        }
+
+        for child in self.children().iter() {
+            if let Some(placement) = child.data.breakpoints.borrow_mut().remove(breakpoint) {
+                stored.children.push((child.clone(), placement));
             }
-            _ => {
-                if let Some(width) = width {
-                    self.data.width.replace(width);
+            child.take_breakpoint(breakpoint, stored);
+        }
+    }
+
+    /// Arrange the layout (and the layouts nested inside of it) as it was stored for a breakpoint
+    ///
+    /// Grid layouts switch to the column count of the breakpoint.
+    /// Items that were never placed at the breakpoint are scaled from their current blocks,
+    /// and every item is moved down if it collides with another one
+    pub fn apply_breakpoint(&mut self, breakpoint: &str) {
+        self.arrange_breakpoint(breakpoint);
+
+        let children = self.children().to_vec();
+        for mut child in children {
+            child.apply_breakpoint(breakpoint);
+        }
+    }
+
+    /// Arrange the direct children of the layout as they were stored for a breakpoint
+    fn arrange_breakpoint(&mut self, breakpoint: &str) {
+        let components = self.components().to_vec();
+        let children = self.children().to_vec();
+
+        for component in components.iter() {
+            let placement = component.breakpoint_placement(breakpoint);
+            component.set_hidden(placement == Some(BreakpointPlacement::Hidden));
+        }
+
+        let (from, columns, row_height, gap) = match &mut *self.kind_mut() {
+            LayoutKind::Grid { grid_data, metrics } => {
+                for component in components.iter() {
+                    grid_data.remove_component(component.grid_item());
+                }
+                for child in children.iter() {
+                    grid_data.remove_component(child.grid_item());
                 }
-                if let Some(height) = height {
-                    self.data.height.replace(height);
 
-                    self.html_element
-                        .style()
-                        .set_property("height", &format!("{}px", height))
-                        .unwrap();
+                let from = grid_data.width();
+                let columns = grid_data
+                    .breakpoint_columns()
+                    .get(breakpoint)
+                    .copied()
+                    .unwrap_or(from);
+
+                (from, columns, metrics.row_height, metrics.gap)
+            }
+            _ => return,
+        };
+
+        // The grid is empty at this point, so it can always change its columns
+        self.set_grid_geometry(columns, row_height, gap);
+
+        let mut requested: Vec<(GridItem, Block)> = components
+            .iter()
+            .filter(|component| !component.is_hidden())
+            .filter_map(|component| {
+                let block = match component.breakpoint_placement(breakpoint) {
+                    Some(BreakpointPlacement::Grid { pos, size }) => Block {
+                        x: pos.0,
+                        y: pos.1,
+                        width: size.0,
+                        height: size.1,
+                    },
+                    _ => {
+                        let (pos, size) = (component.grid_pos()?, component.grid_size()?);
+                        let block = Block {
+                            x: pos.0,
+                            y: pos.1,
+                            width: size.0,
+                            height: size.1,
+                        };
+                        breakpoint::scale_block(block, from, columns)
+                    }
+                };
+
+                Some((component.grid_item(), block))
+            })
+            .collect();
+
+        requested.extend(children.iter().filter_map(|child| {
+            let stored = child.data.breakpoints.borrow().get(breakpoint).copied();
+            let block = match (stored, child.placement()?) {
+                (Some(ChildPlacement::Grid { pos, size }), _) => Block {
+                    x: pos.0,
+                    y: pos.1,
+                    width: size.0,
+                    height: size.1,
+                },
+                (_, ChildPlacement::Grid { pos, size }) => {
+                    let block = Block {
+                        x: pos.0,
+                        y: pos.1,
+                        width: size.0,
+                        height: size.1,
+                    };
+                    breakpoint::scale_block(block, from, columns)
                 }
+                _ => return None,
+            };
+
+            Some((child.grid_item(), block))
+        }));
+
+        let (arranged, rows) = match &mut *self.kind_mut() {
+            LayoutKind::Grid { grid_data, .. } => (
+                breakpoint::arrange(grid_data, requested),
+                grid_data.height(),
+            ),
+            _ => return,
+        };
+
+        for (index, block) in arranged {
+            if let Some(component) = components.iter().find(|c| c.grid_item() == index) {
+                let mut component = component.clone();
+                component.set_grid_pos((block.x, block.y));
+                component.set_grid_size((block.width, block.height));
+            } else if let Some(child) = children.iter().find(|c| c.grid_item() == index) {
+                child.data.placement.replace(Some(ChildPlacement::Grid {
+                    pos: (block.x, block.y),
+                    size: (block.width, block.height),
+                }));
             }
         }
+
+        // Also fits the nested layouts to their new cells
+        self.set_grid_rows(rows);
+    }
+
+    /// Hide or show a component at the current breakpoint
+    ///
+    /// Hidden components give up their grid cells,
+    /// a shown component is moved down if its cells were taken in the meantime
+    pub fn set_component_hidden(&mut self, component: &Component, is: bool) {
+        if component.is_hidden() == is {
+            return;
+        }
+
+        component.set_hidden(is);
+
+        let arranged = match (
+            &mut *self.kind_mut(),
+            component.grid_pos(),
+            component.grid_size(),
+        ) {
+            (LayoutKind::Grid { grid_data, .. }, _, _) if is => {
+                grid_data.remove_component(component.grid_item());
+                return;
+            }
+            (LayoutKind::Grid { grid_data, .. }, Some(pos), Some(size)) => {
+                let block = Block {
+                    x: pos.0,
+                    y: pos.1,
+                    width: size.0,
+                    height: size.1,
+                };
+                let arranged = breakpoint::arrange(grid_data, vec![(component.grid_item(), block)]);
+                (arranged[0].1, grid_data.height())
+            }
+            _ => return,
+        };
+
+        let (block, rows) = arranged;
+        let mut component = component.clone();
+        component.set_grid_pos((block.x, block.y));
+        component.set_grid_size((block.width, block.height));
+
+        self.set_grid_rows(rows);
+    }
+
+    /// Take a serializable snapshot of the layout and all of its components
+    pub fn to_document(&self) -> LayoutDocument {
+        let (width, height) = self.size();
+        let kind = self.kind();
+
+        let kind_document = kind.to_document();
+
+        LayoutDocument {
+            id: Some(self.uid().to_owned()),
+            name: self.name().to_owned(),
+            width,
+            height,
+            kind: kind_document,
+            components: self
+                .components()
+                .iter()
+                .map(|component| component.to_document(&kind))
+                .collect(),
+            placement: self.placement(),
+            breakpoints: self.data.breakpoints.borrow().clone(),
+            children: self
+                .children()
+                .iter()
+                .map(|child| child.to_document())
+                .collect(),
+        }
     }
 
     pub fn remove(self) {
@@ -380,13 +918,466 @@ impl Layout {
         for component in self.data.components.borrow_mut().iter_mut() {
             component.remove();
         }
+
+        for child in self.data.children.borrow().iter() {
+            child.clone().remove();
+        }
     }
 }
 
+// Conversion related methods:
 impl Layout {
-    pub fn set_flex_justify(&self, class: &str) {
+    /// Capture the layout kind and the placements of all of the items of the layout
+    pub fn kind_snapshot(&self) -> KindSnapshot {
+        let kind = self.kind();
+
+        KindSnapshot {
+            kind: kind.to_document(),
+            height: self.size().1,
+            components: self
+                .components()
+                .iter()
+                .map(|component| ComponentSnapshot {
+                    component: component.clone(),
+                    placement: component.placement(&kind),
+                    size: component.stored_size(),
+                    hidden: component.is_hidden(),
+                })
+                .collect(),
+            children: self
+                .children()
+                .iter()
+                .filter_map(|child| Some((child.clone(), child.placement()?)))
+                .collect(),
+        }
+    }
+
+    /// Switch the layout to the kind of the snapshot, placing all of the items as they were captured
+    pub fn apply_kind_snapshot(&mut self, snapshot: &KindSnapshot) {
+        let width = self.size().0;
+        let kind = LayoutKind::from_document(&snapshot.kind, width, snapshot.height);
+
+        let mut html_element = self.html_element.clone();
+        self.data
+            .model
+            .borrow_mut()
+            .set_kind(kind, snapshot.height, &mut html_element);
+        self.replace_decorations();
+
+        self.data.components.replace(
+            snapshot
+                .components
+                .iter()
+                .map(|item| item.component.clone())
+                .collect(),
+        );
+
+        for item in snapshot.components.iter() {
+            let mut component = item.component.clone();
+
+            // Moving every item to the end keeps them in order, after the decorations of the layout
+            self.html_element.append_child(component.element()).unwrap();
+
+            if !matches!(item.placement, PlacementDocument::Free { .. }) {
+                component.unset_absolute_pos();
+            }
+            component.apply_placement(&item.placement);
+            match item.size {
+                Some((w, h)) => component.set_size(w, h),
+                None => component.unset_size(),
+            }
+            component.set_hidden(item.hidden);
+
+            if let (LayoutKind::Grid { grid_data, .. }, PlacementDocument::Grid { pos, size }) =
+                (&mut *self.kind_mut(), &item.placement)
+            {
+                if !item.hidden {
+                    grid_data.insert_component(component.grid_item(), *pos, *size);
+                }
+            }
+        }
+
+        for (child, placement) in snapshot.children.iter() {
+            self.html_element.append_child(&child.html_element).unwrap();
+            self.insert_child(child.clone(), *placement);
+        }
+    }
+
+    /// Replace the grid background or the alignment guides with the ones of the current kind
+    fn replace_decorations(&self) {
+        if let Some(grid_background) = self.data.grid_background.borrow_mut().take() {
+            grid_background.remove();
+        }
+        if let Some(free_guides) = self.data.free_guides.borrow_mut().take() {
+            free_guides.remove();
+        }
+
         match &*self.kind() {
-            LayoutKind::Flex { .. } => {
+            LayoutKind::Grid { metrics, .. } => {
+                let background = GridBackground::new();
+                background.update_pattern(metrics);
+                background.append_to(&self.html_element);
+                self.data.grid_background.replace(Some(background));
+            }
+            LayoutKind::Free { .. } => {
+                let guides = FreeGuides::new();
+                guides.append_to(&self.html_element);
+                self.data.free_guides.replace(Some(guides));
+            }
+            LayoutKind::Flex { .. } => {}
+        }
+    }
+
+    /// Compute how the layout would look like converted to a different kind
+    ///
+    /// Free positions are mapped to the nearest free grid cells, grid cells to the flex order,
+    /// and rendered positions to free positions.
+    ///
+    /// Returns the snapshot of the converted layout, to be applied with `Layout::apply_kind_snapshot`,
+    /// and the components that could not be placed, they are hidden in the converted layout
+    pub fn convert_snapshot(&self, target: ConvertTarget) -> (KindSnapshot, Vec<Component>) {
+        let ((left, top), _) = self.bounding_client_rect();
+        let relative = |((x, y), size): Rect| ((x - left, y - top), size);
+        let (width, height) = self.size();
+
+        let components = self.components().to_vec();
+        let children = self.children().to_vec();
+        let is_grid = matches!(&*self.kind(), LayoutKind::Grid { .. });
+
+        let items: Vec<ConvertItem> = components
+            .iter()
+            .filter(|component| !component.is_hidden())
+            .map(|component| ConvertItem {
+                index: component.grid_item(),
+                rect: relative(component.bounding_client_rect()),
+                block: match (component.grid_pos(), component.grid_size()) {
+                    (Some(pos), Some(size)) if is_grid => Some(Block {
+                        x: pos.0,
+                        y: pos.1,
+                        width: size.0,
+                        height: size.1,
+                    }),
+                    _ => None,
+                },
+                limits: component.constraints().span_limits(),
+            })
+            .chain(children.iter().map(|child| ConvertItem {
+                index: child.grid_item(),
+                rect: relative(child.bounding_client_rect()),
+                block: match child.placement() {
+                    Some(ChildPlacement::Grid { pos, size }) => Some(Block {
+                        x: pos.0,
+                        y: pos.1,
+                        width: size.0,
+                        height: size.1,
+                    }),
+                    _ => None,
+                },
+                limits: SpanLimits::default(),
+            }))
+            .collect();
+
+        let (kind, conversion, height) = match target {
+            ConvertTarget::Free => {
+                // The layout grows to keep all of the items inside of it
+                let bottom = items
+                    .iter()
+                    .map(|item| (item.rect.0 .1 + item.rect.1 .1).ceil() as u32)
+                    .max()
+                    .unwrap_or(0);
+
+                let kind = LayoutKind::Free {
+                    free: FreeLayout::new(width, height),
+                };
+                (kind, convert::to_free(&items), height.max(bottom))
+            }
+            ConvertTarget::Flex => {
+                let kind = LayoutKind::Flex {
+                    flex: FlexLayout::new(width, height),
+                };
+                (kind, convert::to_flex(&items), height)
+            }
+            ConvertTarget::Grid => {
+                // Same cells as in a newly created grid layout
+                let metrics = GridMetrics::new(width, GRID_COLUMNS, width / GRID_COLUMNS as u32, 0);
+                let rows = metrics.rows_for_height(height).max(1);
+
+                let mut grid_data = GridLayout::new(GRID_COLUMNS, rows);
+                let conversion = convert::to_grid(&items, &mut grid_data, &metrics);
+
+                let height = metrics.height_for_rows(grid_data.height());
+                let grid_data = Box::new(GridLayout::new(GRID_COLUMNS, grid_data.height()));
+                (LayoutKind::Grid { metrics, grid_data }, conversion, height)
+            }
+        };
+
+        let placement_of = |index: GridItem| {
+            conversion
+                .placed
+                .iter()
+                .find(|(placed, _)| *placed == index)
+                .map(|(_, placement)| *placement)
+        };
+
+        // Placed components come first, in the order of the converted layout
+        let mut ordered: Vec<Component> = conversion
+            .placed
+            .iter()
+            .filter_map(|(index, _)| components.iter().find(|c| c.grid_item() == *index))
+            .cloned()
+            .collect();
+        let rest: Vec<Component> = components
+            .iter()
+            .filter(|component| !ordered.contains(component))
+            .cloned()
+            .collect();
+        ordered.extend(rest);
+
+        let unplaced: Vec<Component> = components
+            .iter()
+            .filter(|component| conversion.unplaced.contains(&component.grid_item()))
+            .cloned()
+            .collect();
+
+        let component_snapshots = ordered
+            .into_iter()
+            .map(|component| {
+                let placed = placement_of(component.grid_item());
+
+                let placement = match (placed, target) {
+                    (Some(ItemPlacement::Grid(block)), _) => PlacementDocument::Grid {
+                        pos: (block.x, block.y),
+                        size: (block.width, block.height),
+                    },
+                    (Some(ItemPlacement::Free { position }), _) => PlacementDocument::Free {
+                        position,
+                        size: component.stored_size(),
+                    },
+                    // Hidden and unplaced components only need a valid placement
+                    (_, ConvertTarget::Grid) => PlacementDocument::Grid {
+                        pos: (1, 1),
+                        size: component.constraints().initial_span((1, 1)),
+                    },
+                    (_, ConvertTarget::Free) => PlacementDocument::Free {
+                        position: component.position().unwrap_or((0, 0)),
+                        size: component.stored_size(),
+                    },
+                    _ => PlacementDocument::Flex {
+                        item: component.flex_item(),
+                    },
+                };
+
+                let size = match target {
+                    ConvertTarget::Grid => None,
+                    _ => component.stored_size(),
+                };
+
+                ComponentSnapshot {
+                    hidden: component.is_hidden() || unplaced.contains(&component),
+                    component,
+                    placement,
+                    size,
+                }
+            })
+            .collect();
+
+        let child_snapshots = children
+            .iter()
+            .filter_map(|child| {
+                let placement = match placement_of(child.grid_item())? {
+                    ItemPlacement::Grid(block) => ChildPlacement::Grid {
+                        pos: (block.x, block.y),
+                        size: (block.width, block.height),
+                    },
+                    ItemPlacement::Free { position } => ChildPlacement::Free { position },
+                    ItemPlacement::Flex => ChildPlacement::Flex,
+                };
+
+                Some((child.clone(), placement))
+            })
+            .collect();
+
+        let snapshot = KindSnapshot {
+            kind: kind.to_document(),
+            height,
+            components: component_snapshots,
+            children: child_snapshots,
+        };
+
+        (snapshot, unplaced)
+    }
+}
+
+// Nesting related methods:
+impl Layout {
+    /// Layouts nested directly inside of this one
+    pub fn children(&self) -> Ref<'_, [Layout]> {
+        Ref::map(self.data.children.borrow(), |children| children.as_ref())
+    }
+
+    /// Placement inside of the parent layout, `None` if the layout is placed directly in a page
+    pub fn placement(&self) -> Option<ChildPlacement> {
+        *self.data.placement.borrow()
+    }
+
+    /// Key of the layout in the cells of a parent grid layout
+    pub fn grid_item(&self) -> GridItem {
+        self.data.grid_item
+    }
+
+    /// This layout followed by all of the layouts nested inside of it, depth first
+    pub fn tree(&self) -> Vec<Layout> {
+        let mut layouts = vec![self.clone()];
+
+        for child in self.children().iter() {
+            layouts.extend(child.tree());
+        }
+
+        layouts
+    }
+
+    /// Find the innermost layout (this one or a nested one) that contains the element
+    pub fn find_layout_by_element(&self, elm: &Element) -> Option<Layout> {
+        if !self.contains(elm) {
+            return None;
+        }
+
+        self.children()
+            .iter()
+            .find_map(|child| child.find_layout_by_element(elm))
+            .or_else(|| Some(self.clone()))
+    }
+
+    /// Find the parent of a layout nested (at any depth) inside of this one
+    pub fn find_parent(&self, layout: &Layout) -> Option<Layout> {
+        if self.children().contains(layout) {
+            return Some(self.clone());
+        }
+
+        self.children()
+            .iter()
+            .find_map(|child| child.find_parent(layout))
+    }
+
+    /// Insert a layout as a child of this one, or move an existing child to a new placement
+    ///
+    /// # Arguments
+    /// * `child` - nested layout, it can not be placed anywhere else at the same time
+    /// * `placement` - placement inside of this layout, it has to match the kind of this layout
+    pub fn insert_child(&mut self, child: Layout, placement: ChildPlacement) {
+        child.data.placement.replace(Some(placement));
+
+        let is_new = !self.data.children.borrow().contains(&child);
+        if is_new {
+            child.append_to(&self.html_element);
+            self.data.children.borrow_mut().push(child.clone());
+        }
+
+        if let (LayoutKind::Grid { grid_data, .. }, ChildPlacement::Grid { pos, size }) =
+            (&mut *self.kind_mut(), placement)
+        {
+            grid_data.insert_component(child.grid_item(), pos, size);
+        }
+
+        self.apply_child_placement(&child);
+    }
+
+    /// Remove a nested layout
+    ///
+    /// Returns the placement the layout had, `None` if it is not a child of this layout
+    pub fn remove_child(&mut self, child: &Layout) -> Option<ChildPlacement> {
+        let index = self.children().iter().position(|c| c == child)?;
+        self.data.children.borrow_mut().remove(index);
+
+        if let LayoutKind::Grid { grid_data, .. } = &mut *self.kind_mut() {
+            grid_data.remove_component(child.grid_item());
+        }
+
+        child.html_element.remove();
+
+        let style = child.html_element.style();
+        for property in [
+            "grid-column",
+            "grid-row",
+            "position",
+            "left",
+            "top",
+            "width",
+        ]
+        .iter()
+        {
+            style.remove_property(property).unwrap();
+        }
+
+        child.data.placement.take()
+    }
+
+    /// Apply the placement of a nested layout to its html element
+    ///
+    /// Layouts placed in a grid are stretched over their cells
+    fn apply_child_placement(&self, child: &Layout) {
+        let placement = match child.placement() {
+            Some(placement) => placement,
+            None => return,
+        };
+
+        let width = match (&*self.kind(), placement) {
+            (LayoutKind::Grid { metrics, .. }, ChildPlacement::Grid { size, .. }) => {
+                Some(metrics.span_width(size.0))
+            }
+            _ => None,
+        };
+
+        let style = child.html_element.style();
+        match placement {
+            ChildPlacement::Grid { pos, size } => {
+                style
+                    .set_property("grid-column", &format!("{}/span {}", pos.0, size.0))
+                    .unwrap();
+                style
+                    .set_property("grid-row", &format!("{}/span {}", pos.1, size.1))
+                    .unwrap();
+                style.remove_property("position").unwrap();
+            }
+            ChildPlacement::Free { position } => {
+                style.set_property("position", "absolute").unwrap();
+                style
+                    .set_property("left", &format!("{}px", position.0))
+                    .unwrap();
+                style
+                    .set_property("top", &format!("{}px", position.1))
+                    .unwrap();
+            }
+            ChildPlacement::Flex => {
+                style.remove_property("position").unwrap();
+            }
+        }
+
+        let mut child = child.clone();
+        if let Some(width) = width {
+            child.resize(Some(width), None);
+        }
+        style
+            .set_property("width", &format!("{}px", child.size().0))
+            .unwrap();
+    }
+
+    /// Fit the nested layouts to their placements, after the size of this layout has changed
+    fn update_children(&self) {
+        let children = self.children().to_vec();
+
+        for child in children.iter() {
+            self.apply_child_placement(child);
+        }
+    }
+}
+
+impl Layout {
+    pub fn set_flex_justify(&self, class: &str) {
+        match &mut *self.kind_mut() {
+            LayoutKind::Flex { flex } => {
+                flex.set_justify(class);
+
                 self.html_element.class_list().remove_3(
                     "justify-start",
                     "justify-center",
@@ -398,9 +1389,171 @@ impl Layout {
         }
     }
 
-    pub fn set_flex_align(&self, class: &str) {
+    /// Pixel grid and snapping to items of a free layout, `None` if this is not a free layout
+    pub fn free_snap_options(&self) -> Option<(u32, bool)> {
         match &*self.kind() {
-            LayoutKind::Flex { .. } => {
+            LayoutKind::Free { free } => Some((free.snap_grid(), free.snap_to_items())),
+            _ => None,
+        }
+    }
+
+    /// Change the size of the pixel grid of a free layout, 0 turns it off
+    pub fn set_free_snap_grid(&self, size: u32) {
+        if let LayoutKind::Free { free } = &mut *self.kind_mut() {
+            free.set_snap_grid(size);
+        }
+    }
+
+    /// Enable or disable snapping to the edges and centers of the items of a free layout
+    pub fn set_free_snap_to_items(&self, is: bool) {
+        if let LayoutKind::Free { free } = &mut *self.kind_mut() {
+            free.set_snap_to_items(is);
+        }
+    }
+
+    /// Find where a component dragged over the layout would be dropped, see `LayoutModel::drop_target`
+    ///
+    /// # Arguments
+    /// * `dragged` - component that is being dragged, it is skipped if it is a part of this layout
+    /// * `rect` - rect of the component in client coordinates
+    pub fn drop_target(&self, dragged: &Component, rect: Rect) -> DropTarget {
+        let ((left, top), _) = self.bounding_client_rect();
+        let relative = |((x, y), size): Rect| ((x - left, y - top), size);
+
+        // Flex layouts order only their components, free layouts snap to every visible item
+        let others = match &*self.kind() {
+            LayoutKind::Grid { .. } => Vec::new(),
+            LayoutKind::Free { .. } => self.free_siblings(dragged),
+            LayoutKind::Flex { .. } => self
+                .components()
+                .iter()
+                .filter(|component| *component != dragged)
+                .map(|component| relative(component.bounding_client_rect()))
+                .collect(),
+        };
+
+        let item = DraggedItem {
+            grid_item: dragged.grid_item(),
+            rect: relative(rect),
+            grid_size: dragged.grid_size(),
+            constraints: dragged.constraints(),
+        };
+
+        self.data.model.borrow().drop_target(&item, &others)
+    }
+
+    /// Resize a block of a grid layout cell by cell, see `LayoutModel::grid_resize_step`
+    pub fn grid_resize_step(
+        &self,
+        block: Block,
+        edges: (i32, i32),
+        delta: (i32, i32),
+        limits: SpanLimits,
+    ) -> Option<GridResizeStep> {
+        self.data
+            .model
+            .borrow()
+            .grid_resize_step(block, edges, delta, limits)
+    }
+
+    /// Snap the dragged edges of a component resized in a free layout
+    ///
+    /// Returns `None` if this is not a free layout
+    ///
+    /// # Arguments
+    /// * `resized` - component that is being resized
+    /// * `rect` - rect of the component, relative to the layout
+    /// * `edges` - dragged edges, see `FreeLayout::snap_edges`
+    pub fn free_resize_snap(
+        &self,
+        resized: &Component,
+        rect: Rect,
+        edges: (i32, i32),
+    ) -> Option<Snap> {
+        let (_, size) = self.bounding_client_rect();
+        let siblings = self.free_siblings(resized);
+
+        match &*self.kind() {
+            LayoutKind::Free { free } => Some(free.snap_edges(rect, edges, &siblings, size)),
+            _ => None,
+        }
+    }
+
+    /// Rects of the visible items of the layout other than the component, relative to the layout
+    fn free_siblings(&self, component: &Component) -> Vec<Rect> {
+        let ((left, top), _) = self.bounding_client_rect();
+        let relative = |((x, y), size): Rect| ((x - left, y - top), size);
+
+        self.components()
+            .iter()
+            .filter(|other| *other != component && !other.is_hidden())
+            .map(|other| relative(other.bounding_client_rect()))
+            .chain(
+                self.children()
+                    .iter()
+                    .map(|child| relative(child.bounding_client_rect())),
+            )
+            .collect()
+    }
+
+    /// Direction, wrapping and gap of a flex layout, `None` if this is not a flex layout
+    pub fn flex_options(&self) -> Option<(FlexDirection, bool, u32)> {
+        match &*self.kind() {
+            LayoutKind::Flex { flex } => Some((flex.direction(), flex.wrap(), flex.gap())),
+            _ => None,
+        }
+    }
+
+    /// Change the main axis of a flex layout
+    pub fn set_flex_direction(&self, direction: FlexDirection) {
+        if let LayoutKind::Flex { flex } = &mut *self.kind_mut() {
+            flex.set_direction(direction);
+            flex.render(&mut self.html_element.clone());
+        }
+    }
+
+    /// Enable or disable moving items that do not fit to the next line of a flex layout
+    pub fn set_flex_wrap(&self, is: bool) {
+        if let LayoutKind::Flex { flex } = &mut *self.kind_mut() {
+            flex.set_wrap(is);
+            flex.render(&mut self.html_element.clone());
+        }
+    }
+
+    /// Change the space between the items of a flex layout
+    pub fn set_flex_gap(&self, gap: u32) {
+        if let LayoutKind::Flex { flex } = &mut *self.kind_mut() {
+            flex.set_gap(gap);
+            flex.render(&mut self.html_element.clone());
+        }
+    }
+
+    /// Change the collision strategy of a grid layout
+    pub fn set_grid_collision(&self, collision: CollisionStrategy) {
+        if let LayoutKind::Grid { grid_data, .. } = &mut *self.kind_mut() {
+            grid_data.set_collision(collision);
+        }
+    }
+
+    /// Enable or disable adding rows when components are dropped past the last row of a grid layout
+    pub fn set_grid_auto_grow(&self, is: bool) {
+        if let LayoutKind::Grid { grid_data, .. } = &mut *self.kind_mut() {
+            grid_data.set_auto_grow(is);
+        }
+    }
+
+    /// Enable or disable removing trailing empty rows of a grid layout
+    pub fn set_grid_auto_shrink(&self, is: bool) {
+        if let LayoutKind::Grid { grid_data, .. } = &mut *self.kind_mut() {
+            grid_data.set_auto_shrink(is);
+        }
+    }
+
+    pub fn set_flex_align(&self, class: &str) {
+        match &mut *self.kind_mut() {
+            LayoutKind::Flex { flex } => {
+                flex.set_align(class);
+
                 self.html_element
                     .class_list()
                     .remove_3("items-start", "items-center", "items-end");
@@ -0,0 +1,1033 @@
+use crate::{
+    backend::RenderBackend,
+    component::{SizeConstraints, SpanLimits},
+};
+
+use super::{
+    flex::{FlexDirection, FlexLayout},
+    free::{FreeLayout, Snap},
+    grid::{Block, GridItem, GridLayout, GridMetrics, Placeholder},
+    LayoutKind, Rect,
+};
+
+/// Number of columns in a newly created grid layout
+pub const GRID_COLUMNS: usize = 10;
+
+/// Number of rows in a newly created grid layout
+pub const GRID_ROWS: usize = 3;
+
+/// Placement of an item moved into a layout without a point to drop it at
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MovePlacement {
+    /// Cells of a grid layout, with the number of rows that the grid needs to fit them
+    Grid { block: Block, rows: usize },
+    /// Free layouts keep the position of the item if it comes from a free layout
+    Free,
+    /// Position among the items of a flex layout
+    Flex { order: usize },
+}
+
+/// Item dragged over a layout
+#[derive(Debug, Clone)]
+pub struct DraggedItem {
+    /// Index of the item in grid layouts
+    pub grid_item: GridItem,
+    /// Rect of the item, relative to the layout
+    pub rect: Rect,
+    /// Cells the item spans, `None` if it is not placed in a grid layout
+    pub grid_size: Option<(usize, usize)>,
+    /// Size constraints of the item, they give its span when it is newly placed in a grid
+    pub constraints: SizeConstraints,
+}
+
+/// Where an item dragged over a layout would be dropped
+#[derive(Debug, Clone, PartialEq)]
+pub enum DropTarget {
+    /// Placeholder in a grid layout, it can be denied
+    Grid(Placeholder),
+    /// Snapped rect in a free layout, relative to the layout
+    Free(Snap),
+    /// Position among the other items of a flex layout
+    Flex { order: usize },
+}
+
+/// Block of a grid item resized by a step of whole cells
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridResizeStep {
+    pub block: Block,
+    /// Whether the block changed (horizontally, vertically)
+    pub changed: (bool, bool),
+}
+
+/// DOM independent state of a layout
+///
+/// It holds everything that is needed to compute the layout geometry,
+/// the visual changes are pushed to a `RenderBackend`
+pub struct LayoutModel {
+    /// Name of a layout
+    name: String,
+
+    /// Height of a layout
+    height: u32,
+    /// Width of a layout
+    width: u32,
+    /// Layout kind specyfic data
+    kind: LayoutKind,
+}
+
+impl LayoutModel {
+    /// Creates a new layout model
+    pub fn new(width: u32, height: u32, kind: LayoutKind) -> Self {
+        Self {
+            name: default_name(&kind).into(),
+            height,
+            width,
+            kind,
+        }
+    }
+
+    /// Creates a new free layout model
+    pub fn new_free(width: u32, height: u32) -> Self {
+        Self::new(
+            width,
+            height,
+            LayoutKind::Free {
+                free: FreeLayout::new(width, height),
+            },
+        )
+    }
+
+    /// Creates a new flex layout model
+    pub fn new_flex(width: u32, height: u32) -> Self {
+        Self::new(
+            width,
+            height,
+            LayoutKind::Flex {
+                flex: FlexLayout::new(width, height),
+            },
+        )
+    }
+
+    /// Creates a new grid layout model
+    ///
+    /// The column width is derived from the width, so that `GRID_COLUMNS` square cells fit into it
+    ///
+    /// # Arguments
+    /// * `width` - width of a layout in px
+    pub fn new_grid(width: u32) -> Self {
+        let row_height = width / GRID_COLUMNS as u32;
+        Self::new_grid_with(width, GRID_COLUMNS, row_height, 0)
+    }
+
+    /// Creates a new grid layout model with a custom column count, row height and gap
+    ///
+    /// # Arguments
+    /// * `width` - width of a layout in px
+    /// * `columns` - number of columns
+    /// * `row_height` - height of a row in px
+    /// * `gap` - space between columns and rows in px
+    pub fn new_grid_with(width: u32, columns: usize, row_height: u32, gap: u32) -> Self {
+        let metrics = GridMetrics::new(width, columns, row_height, gap);
+        let height = metrics.height_for_rows(GRID_ROWS);
+
+        Self::new(
+            width,
+            height,
+            LayoutKind::Grid {
+                metrics,
+                grid_data: Box::new(GridLayout::new(columns, GRID_ROWS)),
+            },
+        )
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_name(&mut self, name: &str) {
+        self.name = name.into();
+    }
+
+    pub fn kind(&self) -> &LayoutKind {
+        &self.kind
+    }
+
+    pub fn kind_mut(&mut self) -> &mut LayoutKind {
+        &mut self.kind
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Replace the layout kind, used to convert the layout
+    ///
+    /// A layout that still has the default name of its kind (`Grid` or a numbered `Grid 2`)
+    /// gets the one of the new kind, keeping the number
+    ///
+    /// # Arguments
+    /// * `kind` - new layout kind
+    /// * `height` - new height of a layout in px
+    pub fn set_kind(&mut self, kind: LayoutKind, height: u32, backend: &mut impl RenderBackend) {
+        if let Some(number) = self.name.strip_prefix(default_name(&self.kind)) {
+            let is_default = number.is_empty()
+                || number
+                    .strip_prefix(' ')
+                    .is_some_and(|n| n.parse::<u32>().is_ok());
+
+            if is_default {
+                self.name = format!("{}{}", default_name(&kind), number);
+            }
+        }
+
+        self.clear(backend);
+
+        self.kind = kind;
+        self.height = height;
+
+        self.render(backend);
+    }
+
+    /// Remove everything that `LayoutModel::render` added for the current layout kind
+    fn clear(&self, backend: &mut impl RenderBackend) {
+        match &self.kind {
+            LayoutKind::Free { .. } => {
+                backend.set_class("free", false);
+                backend.set_class("block", false);
+                backend.set_class("relative", false);
+            }
+            LayoutKind::Flex { flex } => {
+                backend.set_class("flex", false);
+                backend.set_class(flex.align(), false);
+                backend.set_class(flex.justify(), false);
+
+                for direction in FlexDirection::ALL.iter() {
+                    backend.set_class(direction.class(), false);
+                }
+                backend.set_class("flex-wrap", false);
+                backend.set_class("flex-nowrap", false);
+                backend.set_style("gap", "");
+            }
+            LayoutKind::Grid { .. } => {
+                backend.set_class("grid", false);
+                backend.set_class("justify-items-center", false);
+                backend.set_class("items-center", false);
+
+                backend.set_style("grid-template-columns", "");
+                backend.set_style("grid-template-rows", "");
+                backend.set_style("gap", "");
+            }
+        }
+    }
+
+    /// Render the initial state of the layout
+    pub fn render(&self, backend: &mut impl RenderBackend) {
+        match &self.kind {
+            LayoutKind::Free { .. } => {
+                backend.set_class("free", true);
+                backend.set_class("block", true);
+                backend.set_class("relative", true);
+            }
+            LayoutKind::Flex { flex } => {
+                backend.set_class("flex", true);
+                backend.set_class(flex.align(), true);
+                backend.set_class(flex.justify(), true);
+
+                flex.render(backend);
+            }
+            LayoutKind::Grid { metrics, grid_data } => {
+                backend.set_class("grid", true);
+                backend.set_class("justify-items-center", true);
+                backend.set_class("items-center", true);
+
+                render_grid_template(backend, grid_data, metrics);
+            }
+        }
+
+        // We don't handle width properly ATM
+        //
+        // backend.set_style("width", &format!("{}px", self.width));
+        backend.set_style("height", &format!("{}px", self.height));
+    }
+
+    /// Resize the layout
+    ///
+    /// Columns of a grid layout are stretched to the new width,
+    /// while its height is snapped to whole rows
+    ///
+    /// Returns `false` if the grid layout refused to shrink over occupied cells
+    ///
+    /// # Arguments
+    /// * `width` - new width of a layout in px
+    /// * `height` - new height of a layout in px
+    pub fn resize(
+        &mut self,
+        width: Option<u32>,
+        height: Option<u32>,
+        backend: &mut impl RenderBackend,
+    ) -> bool {
+        match &mut self.kind {
+            LayoutKind::Grid { grid_data, metrics } => {
+                let width = width.unwrap_or(self.width);
+                let new_metrics =
+                    GridMetrics::new(width, grid_data.width(), metrics.row_height, metrics.gap);
+
+                let rows = match height {
+                    Some(height) => new_metrics.rows_for_height(height),
+                    None => grid_data.height(),
+                };
+
+                if !grid_data.resize(grid_data.width(), rows) {
+                    return false;
+                }
+
+                self.width = width;
+                self.height = new_metrics.height_for_rows(rows);
+                *metrics = new_metrics;
+
+                backend.set_style("height", &format!("{}px", self.height));
+                render_grid_template(backend, grid_data, metrics);
+            }
+            _ => {
+                if let Some(width) = width {
+                    self.width = width;
+                }
+                if let Some(height) = height {
+                    self.height = height;
+
+                    backend.set_style("height", &format!("{}px", height));
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Find where an item moved into this layout without a point to drop it at ends up
+    ///
+    /// Grid layouts place it into the first free cells (below the last used row if there are none left),
+    /// flex layouts put it in front of `before`, or at the end
+    ///
+    /// Returns `None` if the item would stay where it is
+    ///
+    /// # Arguments
+    /// * `item` - moved item
+    /// * `items` - items of this layout in their order, they contain `item` if it is moved within the layout
+    /// * `before` - item of a flex layout to put the moved one in front of
+    /// * `span` - size of the item in cells of the grid with the given metrics
+    pub fn move_placement<T: PartialEq>(
+        &self,
+        item: &T,
+        items: &[T],
+        before: Option<&T>,
+        span: impl FnOnce(&GridMetrics) -> (usize, usize),
+    ) -> Option<MovePlacement> {
+        let current = items.iter().position(|i| i == item);
+
+        match &self.kind {
+            LayoutKind::Grid { .. } | LayoutKind::Free { .. } if current.is_some() => None,
+            LayoutKind::Grid { grid_data, metrics } => {
+                let (width, height) = span(metrics);
+                let block = grid_data.nearest_free_block(Block {
+                    x: 1,
+                    y: 1,
+                    width,
+                    height,
+                });
+
+                Some(MovePlacement::Grid {
+                    block,
+                    rows: (block.y + block.height - 1).max(grid_data.height()),
+                })
+            }
+            LayoutKind::Free { .. } => Some(MovePlacement::Free),
+            LayoutKind::Flex { .. } => {
+                let others: Vec<&T> = items.iter().filter(|i| *i != item).collect();
+                let order = before
+                    .and_then(|before| others.iter().position(|i| *i == before))
+                    .unwrap_or(others.len());
+
+                // Items put in front of themselves stay where they are too
+                Some(MovePlacement::Flex { order })
+                    .filter(|_| current != Some(order) && before != Some(item))
+            }
+        }
+    }
+
+    /// Find where an item dragged over this layout would be dropped
+    ///
+    /// Grid layouts place the top left cell of the item under its top left corner,
+    /// clamped so that the whole item fits into the grid,
+    /// free layouts snap the item, flex layouts put it in front of the first item past its center
+    ///
+    /// # Arguments
+    /// * `item` - dragged item
+    /// * `others` - rects of the other items of this layout, relative to the layout and in its order
+    pub fn drop_target(&self, item: &DraggedItem, others: &[Rect]) -> DropTarget {
+        let ((x, y), (width, height)) = item.rect;
+
+        match &self.kind {
+            LayoutKind::Grid { grid_data, metrics } => {
+                let size = item.grid_size.unwrap_or_else(|| {
+                    item.constraints
+                        .initial_span(metrics.cells_for_size((width, height)))
+                });
+                let (x, y) = grid_data.clamp_block_pos(metrics.cell_at_offset((x, y)), size);
+
+                DropTarget::Grid(grid_data.placeholder(
+                    item.grid_item,
+                    Block {
+                        x,
+                        y,
+                        width: size.0,
+                        height: size.1,
+                    },
+                ))
+            }
+            LayoutKind::Free { free } => {
+                let bounds = (self.width as f64, self.height as f64);
+                DropTarget::Free(free.snap(item.rect, others, bounds))
+            }
+            LayoutKind::Flex { flex } => {
+                let center = (x + width / 2.0, y + height / 2.0);
+                DropTarget::Flex {
+                    order: flex.drop_index(others, center),
+                }
+            }
+        }
+    }
+
+    /// Resize a block of a grid layout cell by cell, from the edges dragged by a resize handle
+    ///
+    /// An axis only changes once the mouse moved by a whole cell,
+    /// the block stays inside of the grid (growing grids have room for one more row) and within the span limits
+    ///
+    /// Returns `None` if this is not a grid layout, or if neither axis changed
+    ///
+    /// # Arguments
+    /// * `block` - current block of the item
+    /// * `edges` - dragged (horizontal, vertical) edges, -1 for the start one, 1 for the end one, 0 for none
+    /// * `delta` - mouse movement since the last step in px
+    /// * `limits` - span limits of the item
+    pub fn grid_resize_step(
+        &self,
+        block: Block,
+        edges: (i32, i32),
+        delta: (i32, i32),
+        limits: SpanLimits,
+    ) -> Option<GridResizeStep> {
+        let (grid_data, metrics) = match &self.kind {
+            LayoutKind::Grid { grid_data, metrics } => (grid_data, metrics),
+            _ => return None,
+        };
+
+        let horizontal = step_edge(
+            edges.0,
+            delta.0,
+            metrics.column_step() as i32,
+            block.x,
+            block.width,
+            grid_data.width(),
+        )
+        .filter(|(_, span)| limits.contains_axis(0, *span));
+
+        let max_rows = if grid_data.auto_grow() {
+            grid_data.height().max(block.y + block.height)
+        } else {
+            grid_data.height()
+        };
+        let vertical = step_edge(
+            edges.1,
+            delta.1,
+            metrics.row_step() as i32,
+            block.y,
+            block.height,
+            max_rows,
+        )
+        .filter(|(_, span)| limits.contains_axis(1, *span));
+
+        if horizontal.is_none() && vertical.is_none() {
+            return None;
+        }
+
+        let (x, width) = horizontal.unwrap_or((block.x, block.width));
+        let (y, height) = vertical.unwrap_or((block.y, block.height));
+
+        Some(GridResizeStep {
+            block: Block {
+                x,
+                y,
+                width,
+                height,
+            },
+            changed: (horizontal.is_some(), vertical.is_some()),
+        })
+    }
+
+    /// Change the column count, row height and gap of a grid layout
+    ///
+    /// Returns `false` if this is not a grid layout,
+    /// or if the grid refused to drop columns occupied by components
+    ///
+    /// # Arguments
+    /// * `columns` - number of columns
+    /// * `row_height` - height of a row in px
+    /// * `gap` - space between columns and rows in px
+    pub fn set_grid_geometry(
+        &mut self,
+        columns: usize,
+        row_height: u32,
+        gap: u32,
+        backend: &mut impl RenderBackend,
+    ) -> bool {
+        if let LayoutKind::Grid { grid_data, metrics } = &mut self.kind {
+            if columns == 0 || !grid_data.resize(columns, grid_data.height()) {
+                return false;
+            }
+
+            *metrics = GridMetrics::new(self.width, columns, row_height, gap);
+            self.height = metrics.height_for_rows(grid_data.height());
+
+            backend.set_style("height", &format!("{}px", self.height));
+            render_grid_template(backend, grid_data, metrics);
+
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Name of a newly created layout of a kind
+fn default_name(kind: &LayoutKind) -> &'static str {
+    match kind {
+        LayoutKind::Free { .. } => "Free",
+        LayoutKind::Flex { .. } => "Flex",
+        LayoutKind::Grid { .. } => "Grid",
+    }
+}
+
+/// Compute the new span of a grid item, resized cell by cell along one axis
+///
+/// Returns `None` if the mouse did not move by a whole cell yet,
+/// or if the item can not grow/shrink any further
+///
+/// # Arguments
+/// * `delta` - mouse movement since the last step in px
+/// * `cell_size` - distance between two neighbouring cells on the axis in px
+/// * `pos` - position of the item on the axis (in cells, starting from 1)
+/// * `span` - current span of the item on the axis (in cells)
+/// * `limit` - size of the grid on the axis (in cells)
+fn step_span(delta: i32, cell_size: i32, pos: usize, span: usize, limit: usize) -> Option<usize> {
+    if delta > cell_size && pos + span < limit + 1 {
+        Some(span + 1)
+    } else if delta < -cell_size && span > 1 {
+        Some(span - 1)
+    } else {
+        None
+    }
+}
+
+/// Compute the new position and span of a grid item, resized cell by cell from one of its edges
+///
+/// Dragging the start edge (left or top) moves the position, while the end edge stays in place
+///
+/// # Arguments
+/// * `edge` - dragged edge, -1 for the start one, 1 for the end one, 0 for none
+/// * other arguments are the same as in `step_span`
+fn step_edge(
+    edge: i32,
+    delta: i32,
+    cell_size: i32,
+    pos: usize,
+    span: usize,
+    limit: usize,
+) -> Option<(usize, usize)> {
+    match edge {
+        1 => step_span(delta, cell_size, pos, span, limit).map(|span| (pos, span)),
+        -1 => {
+            if delta < -cell_size && pos > 1 {
+                Some((pos - 1, span + 1))
+            } else if delta > cell_size && span > 1 {
+                Some((pos + 1, span - 1))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Render the css grid template of a grid layout
+fn render_grid_template(
+    backend: &mut impl RenderBackend,
+    grid_data: &GridLayout,
+    metrics: &GridMetrics,
+) {
+    backend.set_style(
+        "grid-template-columns",
+        &format!("repeat({}, {}px)", grid_data.width(), metrics.column_width),
+    );
+    backend.set_style(
+        "grid-template-rows",
+        &format!("repeat({}, {}px)", grid_data.height(), metrics.row_height),
+    );
+    backend.set_style("gap", &format!("{}px", metrics.gap));
+}
+
+#[cfg(test)]
+mod tests {
+    use generational_arena::Index;
+
+    use super::*;
+    use crate::backend::MemoryBackend;
+    use crate::page::layout::grid::GridItem;
+
+    fn grid_data(model: &LayoutModel) -> (&GridLayout, GridMetrics) {
+        match model.kind() {
+            LayoutKind::Grid { grid_data, metrics } => (grid_data, *metrics),
+            _ => panic!("Not a grid layout"),
+        }
+    }
+
+    #[test]
+    fn new_grid_derives_cells_from_width() {
+        let model = LayoutModel::new_grid(908);
+        let (grid, metrics) = grid_data(&model);
+
+        assert_eq!((metrics.column_width, metrics.row_height), (90, 90));
+        assert_eq!((grid.width(), grid.height()), (GRID_COLUMNS, GRID_ROWS));
+        assert_eq!(model.size(), (908, 270));
+        assert_eq!(model.name(), "Grid");
+
+        let mut backend = MemoryBackend::new();
+        model.render(&mut backend);
+
+        assert!(backend.has_class("grid"));
+        assert_eq!(backend.style("grid-template-rows"), Some("repeat(3, 90px)"));
+        assert_eq!(backend.style("height"), Some("270px"));
+    }
+
+    #[test]
+    fn set_kind_replaces_the_rendered_kind() {
+        let mut model = LayoutModel::new_grid(900);
+        let mut backend = MemoryBackend::new();
+        model.render(&mut backend);
+
+        model.set_kind(
+            LayoutKind::Flex {
+                flex: FlexLayout::new(900, 120),
+            },
+            120,
+            &mut backend,
+        );
+
+        assert_eq!(model.name(), "Flex");
+
+        model.set_name("Flex 3");
+        model.set_kind(
+            LayoutKind::Free {
+                free: FreeLayout::new(900, 120),
+            },
+            120,
+            &mut backend,
+        );
+        assert_eq!(model.name(), "Free 3");
+
+        model.set_name("Hero");
+        model.set_kind(
+            LayoutKind::Flex {
+                flex: FlexLayout::new(900, 120),
+            },
+            120,
+            &mut backend,
+        );
+        assert_eq!(model.name(), "Hero");
+        assert!(!backend.has_class("grid"));
+        assert!(backend.has_class("flex"));
+        assert!(backend.has_class("items-center"));
+        assert_eq!(backend.style("grid-template-rows"), None);
+        assert_eq!(backend.style("gap"), Some("0px"));
+        assert_eq!(backend.style("height"), Some("120px"));
+    }
+
+    #[test]
+    fn grid_height_is_snapped_to_cells() {
+        let mut model = LayoutModel::new_grid(900);
+        let mut backend = MemoryBackend::new();
+
+        assert!(model.resize(None, Some(460), &mut backend));
+
+        let (grid, _) = grid_data(&model);
+        assert_eq!(grid.height(), 5);
+        assert_eq!(backend.style("height"), Some("450px"));
+        assert_eq!(backend.style("grid-template-rows"), Some("repeat(5, 90px)"));
+    }
+
+    #[test]
+    fn grid_width_changes_column_width() {
+        let mut model = LayoutModel::new_grid(900);
+        let mut backend = MemoryBackend::new();
+
+        assert!(model.resize(Some(500), None, &mut backend));
+
+        let (grid, metrics) = grid_data(&model);
+        assert_eq!(metrics.column_width, 50);
+        // Rows keep their own height
+        assert_eq!(metrics.row_height, 90);
+        assert_eq!(grid.height(), GRID_ROWS);
+        assert_eq!(model.size(), (500, 270));
+        assert_eq!(
+            backend.style("grid-template-columns"),
+            Some("repeat(10, 50px)")
+        );
+    }
+
+    #[test]
+    fn grid_geometry_can_be_changed() {
+        let mut model = LayoutModel::new_grid(1136);
+        let mut backend = MemoryBackend::new();
+
+        assert!(model.set_grid_geometry(12, 40, 16, &mut backend));
+
+        let (grid, metrics) = grid_data(&model);
+        assert_eq!(grid.width(), 12);
+        assert_eq!(metrics.column_width, 80);
+        assert_eq!(model.size().1, 152);
+        assert_eq!(
+            backend.style("grid-template-columns"),
+            Some("repeat(12, 80px)")
+        );
+        assert_eq!(backend.style("grid-template-rows"), Some("repeat(3, 40px)"));
+        assert_eq!(backend.style("gap"), Some("16px"));
+
+        // Columns with components can not be removed
+        if let LayoutKind::Grid { grid_data, .. } = model.kind_mut() {
+            grid_data.insert_component(
+                GridItem::Component(Index::from_raw_parts(0, 0)),
+                (12, 1),
+                (1, 1),
+            );
+        }
+        assert!(!model.set_grid_geometry(10, 40, 16, &mut backend));
+        assert_eq!(grid_data(&model).0.width(), 12);
+    }
+
+    #[test]
+    fn grid_does_not_shrink_over_components() {
+        let mut model = LayoutModel::new_grid(900);
+        if let LayoutKind::Grid { grid_data, .. } = model.kind_mut() {
+            grid_data.insert_component(
+                GridItem::Component(Index::from_raw_parts(0, 0)),
+                (1, 3),
+                (1, 1),
+            );
+        }
+
+        let mut backend = MemoryBackend::new();
+        assert!(!model.resize(None, Some(180), &mut backend));
+
+        assert_eq!(grid_data(&model).0.height(), 3);
+        assert_eq!(model.size().1, 270);
+        assert_eq!(backend.style("height"), None);
+    }
+
+    fn item(n: usize) -> GridItem {
+        GridItem::Component(Index::from_raw_parts(n, 0))
+    }
+
+    fn block(x: usize, y: usize, width: usize, height: usize) -> Block {
+        Block {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn moved_items_take_the_first_free_cells() {
+        let mut model = LayoutModel::new_grid_with(400, 4, 100, 0);
+        if let LayoutKind::Grid { grid_data, .. } = model.kind_mut() {
+            grid_data.insert_component(item(0), (1, 1), (2, 1));
+            grid_data.insert_component(item(1), (1, 2), (4, 1));
+        }
+        let items = [item(0), item(1)];
+
+        assert_eq!(
+            model.move_placement(&item(2), &items, None, |_| (2, 1)),
+            Some(MovePlacement::Grid {
+                block: block(3, 1, 2, 1),
+                rows: 3
+            })
+        );
+        // Spans are measured with the metrics of the grid
+        assert_eq!(
+            model.move_placement(&item(2), &items, None, |metrics| metrics
+                .cells_for_size((350.0, 80.0))),
+            Some(MovePlacement::Grid {
+                block: block(1, 3, 4, 1),
+                rows: 3
+            })
+        );
+    }
+
+    #[test]
+    fn full_grids_grow_to_fit_moved_items() {
+        let mut model = LayoutModel::new_grid_with(400, 4, 100, 0);
+        if let LayoutKind::Grid { grid_data, .. } = model.kind_mut() {
+            grid_data.insert_component(item(0), (1, 1), (4, 3));
+        }
+
+        let placement = model.move_placement(&item(1), &[item(0)], None, |_| (2, 2));
+        assert_eq!(
+            placement,
+            Some(MovePlacement::Grid {
+                block: block(1, 4, 2, 2),
+                rows: 5
+            })
+        );
+
+        // The move grows the grid before the item is placed, so undoing it
+        // has to take the item out before the grid can shrink back
+        let mut backend = MemoryBackend::new();
+        assert!(model.resize(None, Some(500), &mut backend));
+        if let LayoutKind::Grid { grid_data, .. } = model.kind_mut() {
+            grid_data.insert_component(item(1), (1, 4), (2, 2));
+        }
+        assert!(!model.resize(None, Some(300), &mut backend));
+
+        if let LayoutKind::Grid { grid_data, .. } = model.kind_mut() {
+            grid_data.remove_component(item(1));
+        }
+        assert!(model.resize(None, Some(300), &mut backend));
+        assert_eq!(grid_data(&model).0.height(), 3);
+    }
+
+    #[test]
+    fn moved_flex_items_are_put_in_front_of_before() {
+        let model = LayoutModel::new_flex(900, 120);
+        let items = ["a", "b", "c"];
+
+        let placement = |item, before| model.move_placement(&item, &items, before, |_| (1, 1));
+
+        // Items from other layouts
+        assert_eq!(
+            placement("d", Some(&"b")),
+            Some(MovePlacement::Flex { order: 1 })
+        );
+        assert_eq!(placement("d", None), Some(MovePlacement::Flex { order: 3 }));
+        assert_eq!(
+            placement("d", Some(&"x")),
+            Some(MovePlacement::Flex { order: 3 })
+        );
+
+        // Items of the layout are reordered, the order does not count the moved item
+        assert_eq!(
+            placement("a", Some(&"c")),
+            Some(MovePlacement::Flex { order: 1 })
+        );
+        assert_eq!(placement("a", None), Some(MovePlacement::Flex { order: 2 }));
+        assert_eq!(
+            placement("c", Some(&"a")),
+            Some(MovePlacement::Flex { order: 0 })
+        );
+    }
+
+    #[test]
+    fn items_that_would_stay_in_place_are_not_moved() {
+        let flex = LayoutModel::new_flex(900, 120);
+        let items = ["a", "b", "c"];
+
+        assert_eq!(
+            flex.move_placement(&"a", &items, Some(&"b"), |_| (1, 1)),
+            None
+        );
+        assert_eq!(
+            flex.move_placement(&"b", &items, Some(&"c"), |_| (1, 1)),
+            None
+        );
+        assert_eq!(
+            flex.move_placement(&"b", &items, Some(&"b"), |_| (1, 1)),
+            None
+        );
+        assert_eq!(flex.move_placement(&"c", &items, None, |_| (1, 1)), None);
+
+        // Grid and free layouts do not have an order, items of the layout stay where they are
+        let grid = LayoutModel::new_grid(900);
+        assert_eq!(grid.move_placement(&"a", &items, None, |_| (1, 1)), None);
+
+        let free = LayoutModel::new_free(900, 120);
+        assert_eq!(free.move_placement(&"a", &items, None, |_| (1, 1)), None);
+        assert_eq!(
+            free.move_placement(&"d", &items, None, |_| (1, 1)),
+            Some(MovePlacement::Free)
+        );
+    }
+
+    #[test]
+    fn free_layout_resizes_freely() {
+        let mut model = LayoutModel::new_free(900, 76);
+        let mut backend = MemoryBackend::new();
+
+        assert!(model.resize(None, Some(313), &mut backend));
+        assert_eq!(model.size(), (900, 313));
+        assert_eq!(backend.style("height"), Some("313px"));
+    }
+
+    fn dragged(rect: Rect, grid_size: Option<(usize, usize)>) -> DraggedItem {
+        DraggedItem {
+            grid_item: item(9),
+            rect,
+            grid_size,
+            constraints: SizeConstraints::default(),
+        }
+    }
+
+    #[test]
+    fn grid_placeholders_are_clamped_into_the_grid() {
+        let mut model = LayoutModel::new_grid_with(400, 4, 100, 0);
+
+        // Two cells wide, but its top left corner is over the last column
+        let target = model.drop_target(&dragged(((350.0, -20.0), (150.0, 90.0)), None), &[]);
+        assert_eq!(
+            target,
+            DropTarget::Grid(Placeholder {
+                block: block(3, 1, 2, 1),
+                displaced: vec![],
+                rows: 3,
+                denied: false,
+            })
+        );
+
+        // Items already placed in a grid keep their span
+        let target = model.drop_target(&dragged(((10.0, 10.0), (150.0, 90.0)), Some((1, 2))), &[]);
+        assert!(
+            matches!(target, DropTarget::Grid(Placeholder { block: b, .. }) if b == block(1, 1, 1, 2))
+        );
+
+        if let LayoutKind::Grid { grid_data, .. } = model.kind_mut() {
+            grid_data.insert_component(item(0), (4, 1), (1, 1));
+        }
+        let target = model.drop_target(&dragged(((350.0, 0.0), (150.0, 90.0)), None), &[]);
+        assert_eq!(
+            target,
+            DropTarget::Grid(Placeholder {
+                block: block(3, 1, 2, 1),
+                displaced: vec![],
+                rows: 3,
+                denied: true,
+            })
+        );
+    }
+
+    #[test]
+    fn growing_grids_get_rows_for_the_placeholder() {
+        let mut model = LayoutModel::new_grid_with(400, 4, 100, 0);
+        if let LayoutKind::Grid { grid_data, .. } = model.kind_mut() {
+            grid_data.set_auto_grow(true);
+        }
+
+        let rows = match model.drop_target(&dragged(((0.0, 350.0), (90.0, 190.0)), None), &[]) {
+            DropTarget::Grid(placeholder) => {
+                assert_eq!(placeholder.block, block(1, 4, 1, 2));
+                placeholder.rows
+            }
+            target => panic!("Not a grid placeholder: {:?}", target),
+        };
+        assert_eq!(rows, 5);
+
+        let mut backend = MemoryBackend::new();
+        let (_, metrics) = grid_data(&model);
+        assert!(model.resize(None, Some(metrics.height_for_rows(rows)), &mut backend));
+        assert_eq!(
+            backend.style("grid-template-rows"),
+            Some("repeat(5, 100px)")
+        );
+        assert_eq!(backend.style("height"), Some("500px"));
+    }
+
+    #[test]
+    fn flex_and_free_drop_targets() {
+        let others = [((0.0, 0.0), (100.0, 50.0)), ((100.0, 0.0), (100.0, 50.0))];
+
+        let flex = LayoutModel::new_flex(200, 100);
+        let target = flex.drop_target(&dragged(((160.0, 0.0), (40.0, 40.0)), None), &others);
+        assert_eq!(target, DropTarget::Flex { order: 2 });
+        let target = flex.drop_target(&dragged(((60.0, 0.0), (40.0, 40.0)), None), &others);
+        assert_eq!(target, DropTarget::Flex { order: 1 });
+
+        // Close enough to the right edge of the first item to snap to it
+        let mut free = LayoutModel::new_free(400, 300);
+        if let LayoutKind::Free { free } = free.kind_mut() {
+            free.set_snap_grid(0);
+        }
+        match free.drop_target(&dragged(((103.0, 160.0), (40.0, 30.0)), None), &others[..1]) {
+            DropTarget::Free(snap) => assert_eq!(snap.position.0, 100.0),
+            target => panic!("Not a free drop target: {:?}", target),
+        }
+    }
+
+    #[test]
+    fn span_changes_only_after_a_whole_cell() {
+        assert_eq!(step_span(50, 90, 1, 2, 10), None);
+        assert_eq!(step_span(91, 90, 1, 2, 10), Some(3));
+        assert_eq!(step_span(-91, 90, 1, 2, 10), Some(1));
+    }
+
+    #[test]
+    fn span_stays_inside_of_the_grid() {
+        // Already touching the right edge
+        assert_eq!(step_span(200, 90, 9, 2, 10), None);
+        // Can not shrink below a single cell
+        assert_eq!(step_span(-200, 90, 1, 1, 10), None);
+    }
+
+    #[test]
+    fn start_edges_move_the_position() {
+        assert_eq!(step_edge(-1, -91, 90, 3, 2, 10), Some((2, 3)));
+        assert_eq!(step_edge(-1, 91, 90, 3, 2, 10), Some((4, 1)));
+        // Already at the first column
+        assert_eq!(step_edge(-1, -200, 90, 1, 2, 10), None);
+        assert_eq!(step_edge(0, 200, 90, 1, 2, 10), None);
+    }
+
+    #[test]
+    fn grid_resize_steps_respect_the_span_limits() {
+        let mut model = LayoutModel::new_grid_with(400, 4, 100, 0);
+        let limits = SpanLimits {
+            min: (1, 1),
+            max: Some((2, 3)),
+        };
+
+        assert_eq!(
+            model.grid_resize_step(block(1, 1, 1, 1), (1, 1), (101, 50), limits),
+            Some(GridResizeStep {
+                block: block(1, 1, 2, 1),
+                changed: (true, false),
+            })
+        );
+        // Already at the widest span, and the grid has no room for another row
+        assert_eq!(
+            model.grid_resize_step(block(1, 2, 2, 2), (1, 1), (101, 101), limits),
+            None
+        );
+
+        if let LayoutKind::Grid { grid_data, .. } = model.kind_mut() {
+            grid_data.set_auto_grow(true);
+        }
+        assert_eq!(
+            model.grid_resize_step(block(1, 2, 2, 2), (1, 1), (101, 101), limits),
+            Some(GridResizeStep {
+                block: block(1, 2, 2, 3),
+                changed: (false, true),
+            })
+        );
+
+        let free = LayoutModel::new_free(400, 300);
+        assert_eq!(
+            free.grid_resize_step(block(1, 1, 1, 1), (1, 1), (101, 101), limits),
+            None
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::unique_name;
+
+/// Information about a page that is not a part of its design, used when the page is exported
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PageMetadata {
+    /// Path that the page is served at, `None` to derive it from the page name
+    pub route: Option<String>,
+    /// Title of the document, `None` to use the page name
+    pub title: Option<String>,
+    /// Description of the page for search engines and link previews
+    pub description: String,
+}
+
+impl PageMetadata {
+    /// Path that the page is served at
+    ///
+    /// # Arguments
+    /// * `name` - name of the page, used when no route was set
+    pub fn route(&self, name: &str) -> String {
+        match &self.route {
+            Some(route) => route.clone(),
+            None => route_for_name(name),
+        }
+    }
+
+    /// Title of the document
+    ///
+    /// # Arguments
+    /// * `name` - name of the page, used when no title was set
+    pub fn title<'a>(&'a self, name: &'a str) -> &'a str {
+        self.title.as_deref().unwrap_or(name)
+    }
+}
+
+/// Route derived from a page name
+///
+/// `Home` is served at `/`, other pages at their lowercase name with words joined by `-`,
+/// for example `About Us` becomes `/about-us`
+pub fn route_for_name(name: &str) -> String {
+    if name.trim().eq_ignore_ascii_case("home") {
+        return "/".into();
+    }
+
+    let slug = name
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-");
+
+    format!("/{}", slug)
+}
+
+/// Name for a new page that is not taken yet, and that is not served at a taken route either
+///
+/// Names differing only in case or punctuation share a route, so `home` is numbered if there is a `Home` page
+///
+/// # Arguments
+/// * `names` - names of the other pages
+/// * `routes` - routes of the other pages
+pub fn unique_page_name<S: AsRef<str>>(name: &str, names: &[S], routes: &[S]) -> String {
+    let mut taken: Vec<String> = names.iter().map(|name| name.as_ref().to_owned()).collect();
+
+    loop {
+        let candidate = unique_name(name, &taken);
+        let route = route_for_name(&candidate);
+
+        if routes.iter().all(|taken| taken.as_ref() != route) {
+            return candidate;
+        }
+
+        // Numbers are tried in order, so the loop ends with the first free one
+        taken.push(candidate);
+    }
+}
+
+/// Clean up a route typed by the user
+///
+/// The route always starts with a `/`, never ends with one (unless it is the root)
+/// and whitespace inside of it is replaced by `-`.
+/// `.` and `..` segments are dropped, so that the route never points outside of the site
+pub fn normalize_route(route: &str) -> String {
+    let segments: Vec<String> = route
+        .split('/')
+        .map(|segment| segment.split_whitespace().collect::<Vec<_>>().join("-"))
+        .filter(|segment| !segment.is_empty() && segment != "." && segment != "..")
+        .collect();
+
+    format!("/{}", segments.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_follow_page_names() {
+        assert_eq!(route_for_name("Home"), "/");
+        assert_eq!(route_for_name("About Us"), "/about-us");
+        assert_eq!(route_for_name("  News & Events 2 "), "/news-events-2");
+    }
+
+    #[test]
+    fn typed_routes_are_normalized() {
+        assert_eq!(normalize_route("blog/first post/"), "/blog/first-post");
+        assert_eq!(normalize_route("//contact"), "/contact");
+        assert_eq!(normalize_route(" "), "/");
+        assert_eq!(normalize_route("../../etc/passwd"), "/etc/passwd");
+        assert_eq!(normalize_route("/blog/./../x"), "/blog/x");
+        assert_eq!(normalize_route(".."), "/");
+    }
+
+    #[test]
+    fn new_pages_do_not_share_routes() {
+        let names = ["Home", "About Us"];
+        let routes = ["/", "/about-us"];
+
+        assert_eq!(unique_page_name("Contact", &names, &routes), "Contact");
+        assert_eq!(unique_page_name("Home", &names, &routes), "Home 2");
+        assert_eq!(unique_page_name("home", &names, &routes), "home 2");
+        assert_eq!(unique_page_name("about-us", &names, &routes), "about-us 2");
+
+        // Routes set by hand are taken too
+        let routes = ["/", "/about-us", "/contact", "/contact-2"];
+        assert_eq!(
+            unique_page_name("Contact", &["Home", "About Us"], &routes),
+            "Contact 3"
+        );
+    }
+
+    #[test]
+    fn metadata_falls_back_to_the_name() {
+        let mut metadata = PageMetadata::default();
+        assert_eq!(metadata.route("Contact"), "/contact");
+        assert_eq!(metadata.title("Contact"), "Contact");
+
+        metadata.route = Some("/reach-us".into());
+        metadata.title = Some("Reach us".into());
+        assert_eq!(metadata.route("Contact"), "/reach-us");
+        assert_eq!(metadata.title("Contact"), "Reach us");
+    }
+}
@@ -6,19 +6,26 @@ use std::{
 use wasm_bindgen::JsCast;
 use web_sys::{Element, HtmlElement};
 
-use crate::{component::Component, editor::hierarchy::HierarchyItemData};
+use crate::{component::Component, document::PageDocument, editor::hierarchy::HierarchyItemData};
+
+pub mod breakpoint;
+use breakpoint::Breakpoint;
 
 pub mod layout;
-use layout::Layout;
+use layout::{Layout, StoredArrangement};
 
-struct Data {
-    /// The name of a page
-    ///
-    /// For example `Home`, `Contact`, `News`
-    name: RefCell<String>,
+pub mod metadata;
+use metadata::PageMetadata;
 
-    /// Width of a page
-    width: RefCell<u32>,
+pub mod model;
+use model::PageModel;
+
+/// Width in px of pages that are added without a width
+pub const DEFAULT_PAGE_WIDTH: u32 = 908;
+
+struct Data {
+    /// DOM independent state of a page (name, width)
+    model: RefCell<PageModel>,
 
     /// List of layouts inside of a page,
     /// laid out one under the other
@@ -26,8 +33,14 @@ struct Data {
 
     /// Hierarchy related data
     hierarchy_data: RefCell<HierarchyItemData>,
+}
 
-    grid_cell_size: RefCell<u32>,
+/// Breakpoint removed from a page, together with everything that its layouts stored for it
+#[derive(Clone)]
+pub struct RemovedBreakpoint {
+    pub breakpoint: Breakpoint,
+    /// Arrangements of the layouts of the page
+    arrangements: Vec<StoredArrangement>,
 }
 
 /// The representation of a Paga
@@ -48,31 +61,24 @@ pub struct Page {
 impl Page {
     /// Create a new page, with given name
     pub fn new(name: &str, width: u32) -> Self {
-        let name = name.to_owned();
+        let model = PageModel::new(name, width);
 
         let document = web_sys::window().unwrap().document().unwrap();
 
         let html_element = document.create_element("div").unwrap();
-        let html_element: HtmlElement = html_element.dyn_into().unwrap();
-
-        html_element
-            .style()
-            .set_property("width", &format!("{}px", width))
-            .unwrap();
+        let mut html_element: HtmlElement = html_element.dyn_into().unwrap();
 
-        html_element.class_list().add_2("page", "visible").unwrap();
+        model.render(&mut html_element);
 
         Self {
             html_element,
 
             data: Rc::new(Data {
-                name: RefCell::new(name),
-                width: RefCell::new(width),
+                model: RefCell::new(model),
 
                 layouts: Default::default(),
 
                 hierarchy_data: RefCell::new(HierarchyItemData::new()),
-                grid_cell_size: RefCell::new(76),
             }),
         }
     }
@@ -94,11 +100,39 @@ impl Page {
     }
 
     pub fn name(&self) -> Ref<str> {
-        Ref::map(self.data.name.borrow(), |name| name.as_ref())
+        Ref::map(self.data.model.borrow(), |model| model.name())
+    }
+
+    pub fn set_name(&mut self, name: &str) {
+        self.data.model.borrow_mut().set_name(name);
     }
 
     pub fn width(&self) -> u32 {
-        *self.data.width.borrow()
+        self.data.model.borrow().width()
+    }
+
+    /// Route, title and description of the page
+    pub fn metadata(&self) -> Ref<'_, PageMetadata> {
+        Ref::map(self.data.model.borrow(), |model| model.metadata())
+    }
+
+    pub fn set_metadata(&mut self, metadata: PageMetadata) {
+        self.data.model.borrow_mut().set_metadata(metadata);
+    }
+
+    /// Path that the page is served at, derived from its name unless it was set
+    pub fn route(&self) -> String {
+        self.metadata().route(&self.name())
+    }
+
+    /// Breakpoints of the page, sorted by width
+    pub fn breakpoints(&self) -> Ref<'_, [Breakpoint]> {
+        Ref::map(self.data.model.borrow(), |model| model.breakpoints())
+    }
+
+    /// The breakpoint that the page is currently shown at
+    pub fn active_breakpoint(&self) -> Ref<'_, Breakpoint> {
+        Ref::map(self.data.model.borrow(), |model| model.active_breakpoint())
     }
 
     #[allow(unused)]
@@ -115,14 +149,28 @@ impl Page {
         self.html_element.contains(Some(elm))
     }
 
-    ///  Fina layout on a page
+    ///  Fina the innermost layout on a page that contains the element
     pub fn find_layout_by_element(&self, elm: &Element) -> Option<Layout> {
         self.data
             .layouts
             .borrow()
             .iter()
-            .find(|layout| layout.contains(elm))
-            .cloned()
+            .find_map(|layout| layout.find_layout_by_element(elm))
+    }
+
+    /// All layouts of the page, including the nested ones (depth first)
+    pub fn all_layouts(&self) -> Vec<Layout> {
+        self.layouts()
+            .iter()
+            .flat_map(|layout| layout.tree())
+            .collect()
+    }
+
+    /// Find the layout that a nested layout is placed in, `None` for layouts placed directly in the page
+    pub fn find_parent_layout(&self, layout: &Layout) -> Option<Layout> {
+        self.layouts()
+            .iter()
+            .find_map(|root| root.find_parent(layout))
     }
 
     ///  Fina component on a page
@@ -183,18 +231,32 @@ impl Page {
         }
     }
 
+    /// Move a layout to a different position in the page, keeping the DOM in the same order
+    ///
+    /// Returns `false` if there is no layout at `from`
+    ///
+    /// # Arguments
+    /// * `to` - index of the layout after the move, out of bounds index moves it to the end
+    pub fn move_layout(&mut self, from: usize, to: usize) -> bool {
+        match self.remove_layout(from) {
+            Some(layout) => {
+                self.insert_layout(layout, Some(to));
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Resize the page
     ///
     /// # Arguments
     /// * `width` - width of a page in px
     pub fn resize(&mut self, width: u32) {
-        self.data.grid_cell_size.replace(width / 10);
-        self.data.width.replace(width);
-
-        self.html_element
-            .style()
-            .set_property("width", &format!("{}px", width))
-            .unwrap();
+        let mut html_element = self.html_element.clone();
+        self.data
+            .model
+            .borrow_mut()
+            .resize(width, &mut html_element);
 
         for layout in self.data.layouts.borrow_mut().iter_mut() {
             layout.resize(Some(width), None);
@@ -202,6 +264,127 @@ impl Page {
     }
 }
 
+// Breakpoint related methods:
+impl Page {
+    /// Add a new breakpoint, or change the width of an existing one
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        self.data.model.borrow_mut().add_breakpoint(breakpoint);
+    }
+
+    /// Remove a breakpoint together with the arrangements stored for it
+    ///
+    /// Returns `None` if it does not exist or is currently active
+    pub fn remove_breakpoint(&mut self, name: &str) -> Option<RemovedBreakpoint> {
+        let breakpoint = self.data.model.borrow_mut().remove_breakpoint(name)?;
+
+        let arrangements = self
+            .layouts()
+            .iter()
+            .map(|layout| layout.remove_breakpoint(name))
+            .collect();
+
+        Some(RemovedBreakpoint {
+            breakpoint,
+            arrangements,
+        })
+    }
+
+    /// Add a removed breakpoint back, together with the arrangements stored for it
+    pub fn restore_breakpoint(&mut self, removed: &RemovedBreakpoint) {
+        self.add_breakpoint(removed.breakpoint.clone());
+
+        for arrangement in removed.arrangements.iter() {
+            arrangement.restore(&removed.breakpoint.name);
+        }
+    }
+
+    /// Replace all of the breakpoints, used when loading a page
+    ///
+    /// Layouts are not rearranged, use `Page::apply_breakpoint` once the page is loaded
+    pub fn set_breakpoints(&mut self, breakpoints: Vec<Breakpoint>, active: Option<&str>) {
+        self.data
+            .model
+            .borrow_mut()
+            .set_breakpoints(breakpoints, active);
+    }
+
+    /// Remember the current arrangement of all layouts as the one of the active breakpoint
+    pub fn store_breakpoint(&self) {
+        let name = self.active_breakpoint().name.clone();
+
+        for layout in self.layouts().iter() {
+            layout.store_breakpoint(&name);
+        }
+    }
+
+    /// Arrange all layouts as they were stored for the active breakpoint
+    pub fn apply_breakpoint(&mut self) {
+        let name = self.active_breakpoint().name.clone();
+
+        for layout in self.layouts_mut().iter_mut() {
+            layout.apply_breakpoint(&name);
+        }
+    }
+
+    /// Show the page at a different breakpoint
+    ///
+    /// The current arrangement is stored for the active breakpoint,
+    /// then the page is resized to the new one and its layouts are rearranged
+    ///
+    /// Returns `false` if the breakpoint does not exist
+    pub fn set_breakpoint(&mut self, name: &str) -> bool {
+        self.store_breakpoint();
+
+        let width = self.data.model.borrow_mut().set_active_breakpoint(name);
+
+        match width {
+            Some(width) => {
+                self.resize(width);
+                self.apply_breakpoint();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Page {
+    /// Take a serializable snapshot of the page and all of its layouts
+    ///
+    /// The layouts are described as they are arranged at the active breakpoint,
+    /// arrangements of the other breakpoints are stored alongside them
+    pub fn to_document(&self) -> PageDocument {
+        self.store_breakpoint();
+
+        PageDocument {
+            name: self.name().to_owned(),
+            width: self.width(),
+            breakpoints: self.breakpoints().to_vec(),
+            breakpoint: Some(self.active_breakpoint().name.clone()),
+            metadata: self.metadata().clone(),
+            layouts: self
+                .layouts()
+                .iter()
+                .map(|layout| layout.to_document())
+                .collect(),
+        }
+    }
+}
+
+/// Index that a layout ends up at, when it is dropped in front of the layout at `drop`
+///
+/// Drop positions count the layouts that are still in the page,
+/// so dropping below its own position moves the layout one index less
+///
+/// # Arguments
+/// * `from` - current index of the dropped layout, `None` if it comes from a different page
+pub fn drop_index(from: Option<usize>, drop: usize) -> usize {
+    match from {
+        Some(from) if from < drop => drop - 1,
+        _ => drop,
+    }
+}
+
 impl PartialEq<HtmlElement> for Page {
     fn eq(&self, html_element: &HtmlElement) -> bool {
         &self.html_element == html_element
@@ -213,3 +396,19 @@ impl PartialEq<Page> for Page {
         self.html_element == layout.html_element
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drop_index_accounts_for_the_moved_layout() {
+        // Dropping right before or right after itself keeps the layout in place
+        assert_eq!(drop_index(Some(1), 1), 1);
+        assert_eq!(drop_index(Some(1), 2), 1);
+
+        assert_eq!(drop_index(Some(0), 3), 2);
+        assert_eq!(drop_index(Some(3), 0), 0);
+        assert_eq!(drop_index(None, 2), 2);
+    }
+}
@@ -0,0 +1,256 @@
+use crate::backend::RenderBackend;
+
+use super::{
+    breakpoint::{Breakpoint, DEFAULT_BREAKPOINT},
+    metadata::PageMetadata,
+};
+
+/// DOM independent state of a page
+pub struct PageModel {
+    /// The name of a page
+    ///
+    /// For example `Home`, `Contact`, `News`
+    name: String,
+
+    /// Width of a page
+    width: u32,
+
+    /// Breakpoints of a page, sorted by width
+    breakpoints: Vec<Breakpoint>,
+    /// Name of the breakpoint that the page is currently shown at
+    active_breakpoint: String,
+
+    /// Route, title and description of the page
+    metadata: PageMetadata,
+}
+
+impl PageModel {
+    /// Create a new page model, with given name
+    pub fn new(name: &str, width: u32) -> Self {
+        Self {
+            name: name.into(),
+            width,
+
+            breakpoints: vec![Breakpoint::new(DEFAULT_BREAKPOINT, width)],
+            active_breakpoint: DEFAULT_BREAKPOINT.into(),
+
+            metadata: PageMetadata::default(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_name(&mut self, name: &str) {
+        self.name = name.into();
+    }
+
+    /// Route, title and description of the page
+    pub fn metadata(&self) -> &PageMetadata {
+        &self.metadata
+    }
+
+    pub fn set_metadata(&mut self, metadata: PageMetadata) {
+        self.metadata = metadata;
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Breakpoints of the page, sorted by width
+    pub fn breakpoints(&self) -> &[Breakpoint] {
+        &self.breakpoints
+    }
+
+    /// The breakpoint that the page is currently shown at
+    pub fn active_breakpoint(&self) -> &Breakpoint {
+        // The active breakpoint can not be removed, so it is always there
+        self.breakpoints
+            .iter()
+            .find(|breakpoint| breakpoint.name == self.active_breakpoint)
+            .unwrap()
+    }
+
+    /// Add a new breakpoint, or change the width of an existing one
+    ///
+    /// The width of the active breakpoint can only be changed by resizing the page
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        if breakpoint.name == self.active_breakpoint {
+            return;
+        }
+
+        self.breakpoints.retain(|b| b.name != breakpoint.name);
+        self.breakpoints.push(breakpoint);
+        self.breakpoints.sort_by_key(|b| b.width);
+    }
+
+    /// Remove a breakpoint, returns `None` if it does not exist or is currently active
+    pub fn remove_breakpoint(&mut self, name: &str) -> Option<Breakpoint> {
+        if name == self.active_breakpoint {
+            return None;
+        }
+
+        let index = self.breakpoints.iter().position(|b| b.name == name)?;
+        Some(self.breakpoints.remove(index))
+    }
+
+    /// Replace all of the breakpoints, used when loading a page
+    ///
+    /// # Arguments
+    /// * `breakpoints` - new breakpoints, the page keeps the old ones if the list is empty
+    /// * `active` - name of the active breakpoint, falls back to the one matching the page width
+    pub fn set_breakpoints(&mut self, mut breakpoints: Vec<Breakpoint>, active: Option<&str>) {
+        let width = self.width;
+        let active = active
+            .filter(|name| breakpoints.iter().any(|b| b.name == *name))
+            .map(String::from)
+            .or_else(|| {
+                breakpoints
+                    .iter()
+                    .find(|b| b.width == width)
+                    .map(|b| b.name.clone())
+            });
+
+        if let Some(active) = active {
+            breakpoints.sort_by_key(|b| b.width);
+            self.breakpoints = breakpoints;
+            self.active_breakpoint = active;
+        }
+    }
+
+    /// Make a different breakpoint active
+    ///
+    /// Returns the width of the breakpoint, `None` if it does not exist.
+    /// The page has to be resized to the returned width separately
+    pub fn set_active_breakpoint(&mut self, name: &str) -> Option<u32> {
+        let width = self
+            .breakpoints
+            .iter()
+            .find(|breakpoint| breakpoint.name == name)
+            .map(|breakpoint| breakpoint.width)?;
+
+        self.active_breakpoint = name.into();
+        Some(width)
+    }
+
+    /// Render the initial state of the page
+    pub fn render(&self, backend: &mut impl RenderBackend) {
+        backend.set_style("width", &format!("{}px", self.width));
+
+        backend.set_class("page", true);
+        backend.set_class("visible", true);
+    }
+
+    /// Resize the page
+    ///
+    /// Layouts of the page have to be resized separately
+    ///
+    /// # Arguments
+    /// * `width` - width of a page in px
+    pub fn resize(&mut self, width: u32, backend: &mut impl RenderBackend) {
+        self.width = width;
+
+        // The page is shown at the active breakpoint, so it follows the page width
+        let active = self.active_breakpoint.clone();
+        if let Some(breakpoint) = self.breakpoints.iter_mut().find(|b| b.name == active) {
+            breakpoint.width = width;
+        }
+        self.breakpoints.sort_by_key(|b| b.width);
+
+        backend.set_style("width", &format!("{}px", width));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::MemoryBackend;
+
+    #[test]
+    fn resize_updates_width() {
+        let mut page = PageModel::new("Home", 908);
+        let mut backend = MemoryBackend::new();
+
+        page.render(&mut backend);
+        assert_eq!(backend.style("width"), Some("908px"));
+        assert!(backend.has_class("page"));
+
+        page.resize(1280, &mut backend);
+
+        assert_eq!(page.width(), 1280);
+        assert_eq!(backend.style("width"), Some("1280px"));
+    }
+
+    #[test]
+    fn breakpoints_are_sorted_by_width() {
+        let mut page = PageModel::new("Home", 1280);
+        page.add_breakpoint(Breakpoint::new("tablet", 768));
+        page.add_breakpoint(Breakpoint::new("mobile", 375));
+
+        let names: Vec<_> = page.breakpoints().iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, vec!["mobile", "tablet", DEFAULT_BREAKPOINT]);
+
+        // Adding an existing breakpoint changes its width
+        page.add_breakpoint(Breakpoint::new("tablet", 820));
+        assert_eq!(page.breakpoints()[1], Breakpoint::new("tablet", 820));
+        assert_eq!(page.breakpoints().len(), 3);
+    }
+
+    #[test]
+    fn active_breakpoint_follows_the_page_width() {
+        let mut page = PageModel::new("Home", 1280);
+        page.add_breakpoint(Breakpoint::new("mobile", 375));
+
+        assert_eq!(page.set_active_breakpoint("mobile"), Some(375));
+        assert_eq!(page.set_active_breakpoint("watch"), None);
+
+        let mut backend = MemoryBackend::new();
+        page.resize(390, &mut backend);
+        assert_eq!(page.active_breakpoint(), &Breakpoint::new("mobile", 390));
+
+        // The active breakpoint can not be removed
+        assert_eq!(page.remove_breakpoint("mobile"), None);
+        assert_eq!(
+            page.remove_breakpoint(DEFAULT_BREAKPOINT),
+            Some(Breakpoint::new(DEFAULT_BREAKPOINT, 1280))
+        );
+        assert_eq!(page.remove_breakpoint(DEFAULT_BREAKPOINT), None);
+        assert_eq!(page.breakpoints().len(), 1);
+    }
+
+    #[test]
+    fn removed_breakpoints_can_be_added_back() {
+        let mut page = PageModel::new("Home", 1280);
+        page.add_breakpoint(Breakpoint::new("mobile", 375));
+        page.add_breakpoint(Breakpoint::new("tablet", 768));
+        let breakpoints = page.breakpoints().to_vec();
+
+        // Undoing a removal adds the removed breakpoint back, redoing removes it again
+        let removed = page.remove_breakpoint("tablet").unwrap();
+        page.add_breakpoint(removed.clone());
+        assert_eq!(page.breakpoints(), &breakpoints[..]);
+
+        assert_eq!(page.remove_breakpoint("tablet"), Some(removed));
+        assert_eq!(page.breakpoints().len(), 2);
+    }
+
+    #[test]
+    fn loaded_breakpoints_need_an_active_one() {
+        let mut page = PageModel::new("Home", 768);
+
+        page.set_breakpoints(vec![Breakpoint::new("mobile", 375)], None);
+        assert_eq!(page.active_breakpoint().name, DEFAULT_BREAKPOINT);
+
+        page.set_breakpoints(
+            vec![
+                Breakpoint::new("tablet", 768),
+                Breakpoint::new("mobile", 375),
+            ],
+            None,
+        );
+        assert_eq!(page.active_breakpoint().name, "tablet");
+        assert_eq!(page.breakpoints()[0].name, "mobile");
+    }
+}
@@ -12,3 +12,69 @@ pub fn new_listener<
         .into_js_value()
         .unchecked_into()
 }
+
+/// Name that is not taken yet, `name` itself or `name` followed by the lowest free number
+///
+/// A number that the name already ends with is replaced, so copies of `Button 2` are named `Button 3`
+///
+/// ### Example:
+/// ```rs
+/// unique_name("Page", &["Page", "Page 2"]) // "Page 3"
+/// ```
+pub fn unique_name<S: AsRef<str>>(name: &str, taken: &[S]) -> String {
+    let is_taken = |candidate: &str| taken.iter().any(|name| name.as_ref() == candidate);
+
+    if !is_taken(name) {
+        return name.into();
+    }
+
+    let base = match name.rsplit_once(' ') {
+        Some((base, number)) if number.parse::<u32>().is_ok() => base,
+        _ => name,
+    };
+
+    (2..)
+        .map(|n| format!("{} {}", base, n))
+        .find(|candidate| !is_taken(candidate))
+        .unwrap()
+}
+
+/// Readable name derived from the tag name of a custom element, `launch-table` becomes `Launch Table`
+pub fn name_from_tag(tag_name: &str) -> String {
+    tag_name
+        .split(['-', '_'])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unique_name_takes_the_lowest_free_number() {
+        assert_eq!(unique_name("Page", &["Home"]), "Page");
+        assert_eq!(unique_name("Page", &["Page", "Page 3"]), "Page 2");
+        assert_eq!(unique_name("Page", &["Page", "Page 2"]), "Page 3");
+    }
+
+    #[test]
+    fn unique_name_continues_the_numbering_of_copies() {
+        assert_eq!(unique_name("Button 2", &["Button", "Button 2"]), "Button 3");
+        assert_eq!(unique_name("Button 2", &["Button"]), "Button 2");
+    }
+
+    #[test]
+    fn names_from_tags() {
+        assert_eq!(name_from_tag("launch-table"), "Launch Table");
+        assert_eq!(name_from_tag("x-button"), "X Button");
+    }
+}
@@ -43,7 +43,8 @@ export class LayoutContainer extends HTMLElement {
   connectedCallback() {
     this.classList.add("container");
 
-    {
+    // The callback runs again whenever the layout is moved, so the icons are only added once
+    if (!this.querySelector(":scope > .container__close-icon")) {
       const div = document.createElement("div");
       div.classList.add("container__close-icon");
 
@@ -55,6 +56,19 @@ export class LayoutContainer extends HTMLElement {
       this.prepend(div);
     }
 
+    if (!this.querySelector(":scope > .container__drag-handle")) {
+      const div = document.createElement("div");
+      div.classList.add("container__drag-handle");
+      div.draggable = true;
+
+      const img = document.createElement("img");
+      img.src = "/img/icons/move.svg";
+
+      div.appendChild(img);
+
+      this.prepend(div);
+    }
+
     if (this.classList.contains("grid")) {
       this.grid.mount(this);
     } else {
@@ -62,6 +76,18 @@ export class LayoutContainer extends HTMLElement {
     }
   }
 
+  // Convert the layout to a different kind, warning about components that did not fit
+  convert(editor: Editor, kind: string) {
+    const unplaced = editor.convert_layout(this, kind);
+
+    if (unplaced.length > 0) {
+      console.warn(
+        `${unplaced.length} component(s) could not be placed in the ${kind} layout and were hidden`,
+        unplaced
+      );
+    }
+  }
+
   open_contextmenu(editor: Editor, event) {
     const context_menu = document.querySelector("context-menu") as ContextMenu;
 
@@ -74,33 +100,24 @@ export class LayoutContainer extends HTMLElement {
     free_btn.innerText = "Free";
 
     free_btn.addEventListener("click", () => {
-      // this.classList.add("free");
-      // this.classList.remove("flex");
-      // this.classList.remove("grid");
-      // this.grid.remove();
-      // context_menu.close();
+      this.convert(editor, "free");
+      context_menu.close();
     });
 
     const flex_btn = document.createElement("button");
     flex_btn.innerText = "Flex";
 
     flex_btn.addEventListener("click", () => {
-      // this.classList.remove("free");
-      // this.classList.add("flex");
-      // this.classList.remove("grid");
-      // this.grid.remove();
-      // context_menu.close();
+      this.convert(editor, "flex");
+      context_menu.close();
     });
 
     const grid_btn = document.createElement("button");
     grid_btn.innerText = "Grid";
 
     grid_btn.addEventListener("click", () => {
-      // this.classList.remove("free");
-      // this.classList.remove("flex");
-      // this.classList.add("grid");
-      // this.grid.mount(this);
-      // context_menu.close();
+      this.convert(editor, "grid");
+      context_menu.close();
     });
 
     select.appendChild(free_btn);
@@ -109,6 +126,79 @@ export class LayoutContainer extends HTMLElement {
 
     root.appendChild(select);
 
+    {
+      const order = document.createElement("div");
+      order.className = "layout-select";
+      order.style.marginTop = "10px";
+
+      const up_btn = document.createElement("button");
+      up_btn.innerText = "Up";
+      up_btn.addEventListener("click", () => {
+        editor.shift_layout(this, -1);
+        context_menu.close();
+      });
+
+      const down_btn = document.createElement("button");
+      down_btn.innerText = "Down";
+      down_btn.addEventListener("click", () => {
+        editor.shift_layout(this, 1);
+        context_menu.close();
+      });
+
+      const duplicate_btn = document.createElement("button");
+      duplicate_btn.innerText = "Duplicate";
+      duplicate_btn.addEventListener("click", () => {
+        editor.duplicate_layout(this);
+        context_menu.close();
+      });
+
+      order.appendChild(up_btn);
+      order.appendChild(down_btn);
+      order.appendChild(duplicate_btn);
+
+      root.appendChild(order);
+    }
+
+    // Move the layout to the end of a different page
+    {
+      const pages = Array.from(document.querySelectorAll("#workspace .page"));
+      const current = this.closest("#workspace .page");
+
+      if (pages.length > 1) {
+        const mover = document.createElement("div");
+        mover.style.display = "flex";
+        mover.style.marginTop = "10px";
+
+        const label = document.createElement("span");
+        label.innerText = "Move to: ";
+        label.style.marginRight = "5px";
+
+        const page_select = document.createElement("select");
+
+        pages.forEach((page, id) => {
+          const option = document.createElement("option");
+          option.value = id.toString();
+          option.innerText =
+            editor.page_name(page as HTMLElement) || `Page ${id + 1}`;
+          option.selected = page == current;
+
+          page_select.appendChild(option);
+        });
+
+        page_select.addEventListener("change", () => {
+          const page = pages[parseInt(page_select.value)] as HTMLElement;
+
+          editor.move_layout(this, page, page.children.length);
+          context_menu.close();
+        });
+
+        mover.appendChild(label);
+        mover.appendChild(page_select);
+
+        root.appendChild(mover);
+      }
+    }
+
     if (this.classList.contains("free")) {
       free_btn.classList.add("active");
     } else if (this.classList.contains("flex")) {
@@ -136,11 +226,17 @@ export class LayoutContainer extends HTMLElement {
       const height = this.style.height.split("px")[0];
       input_y.value = height ? height : "75";
 
+      // A single drag of the slider is undone as one step
+      let session = editor.new_input_session();
+      input_y.addEventListener("change", () => {
+        session = editor.new_input_session();
+      });
+
       input_y.addEventListener("input", (event) => {
         const target = event.target as HTMLInputElement;
         const value = parseFloat(target.value);
 
-        editor.resize_layout(this, value);
+        editor.resize_layout(this, value, session);
       });
 
       sizer.appendChild(input_y);
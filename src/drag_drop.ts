@@ -12,6 +12,16 @@ export function connect(editor: Editor) {
       dragged = event.target as HTMLElement;
       dragged.style.opacity = "0.5";
 
+      // Pages, layouts and components are moved inside of the hierarchy tree, without the drop areas of the workspace
+      if (dragged.closest("#inspector-tree")) {
+        event.dataTransfer.setData("text/hierarchy-item", "");
+        return;
+      }
+
+      if (dragged.classList.contains("container__drag-handle")) {
+        event.dataTransfer.setData("text/layout-move", "");
+      }
+
       {
         const elements = document.querySelectorAll("#workspace .page.visible");
 
@@ -27,7 +37,7 @@ export function connect(editor: Editor) {
 
       {
         const elements = document.querySelectorAll(
-          "#workspace .page.visible layout-container"
+          "#workspace .page.visible > layout-container"
         );
 
         elements.forEach((elm, id) => {
@@ -114,10 +124,25 @@ export function connect(editor: Editor) {
             const data = event.dataTransfer.getData("text/layout-type");
             if (data) {
               editor.add_layout_to_page(parent, id, data);
+            } else if (dragged.classList.contains("container__drag-handle")) {
+              const layout = dragged.closest("layout-container") as HTMLElement;
+              editor.move_layout(layout, parent, id);
             }
           }
         }
         target.remove();
+      } else if (target.closest("#inspector-tree")) {
+        if (dragged.closest("#inspector-tree")) {
+          editor.drop_in_hierarchy(dragged, target);
+        }
+      } else {
+        // Dropping onto a layout nests the new layout inside of it
+        const layout = target.closest("#workspace layout-container");
+        const data = event.dataTransfer.getData("text/layout-type");
+
+        if (layout && data) {
+          editor.add_layout_to_layout(layout as HTMLElement, data);
+        }
       }
     },
     false
@@ -0,0 +1,65 @@
+import { Editor } from "./index";
+
+/// Replace the name of a hierarchy item with an input, and rename the item once it is confirmed
+///
+/// Enter or leaving the input renames the item, Escape cancels the rename
+function start_rename(editor: Editor, name: HTMLElement) {
+  const header = name.parentElement as HTMLElement;
+  const was_draggable = header.draggable;
+
+  const input = document.createElement("input");
+  input.type = "text";
+  input.className = "page-item__name-input";
+  input.value = name.innerText;
+
+  // Text can not be selected inside of a draggable element
+  header.draggable = false;
+
+  name.style.display = "none";
+  name.after(input);
+  input.focus();
+  input.select();
+
+  let done = false;
+  const finish = (rename: boolean) => {
+    if (done) {
+      return;
+    }
+    done = true;
+
+    header.draggable = was_draggable;
+    input.remove();
+    name.style.display = "";
+
+    // The tree is rebuilt after a rename, so the name does not have to be updated here
+    if (rename) {
+      editor.rename_in_hierarchy(name, input.value);
+    }
+  };
+
+  input.addEventListener("keydown", (event) => {
+    if (event.key == "Enter") {
+      finish(true);
+    } else if (event.key == "Escape") {
+      finish(false);
+    }
+  });
+  input.addEventListener("blur", () => finish(true));
+
+  // Clicks inside of the input must not select the item again
+  input.addEventListener("click", (event) => event.stopPropagation());
+}
+
+export function connect(editor: Editor) {
+  // The tree is replaced whenever it changes, so the listener is on its parent
+  const panel = document.getElementById("pages-panel");
+
+  panel.addEventListener("dblclick", (event) => {
+    const target = event.target as HTMLElement;
+    const name = target.closest("#inspector-tree .page-item__name") as HTMLElement;
+
+    if (name) {
+      start_rename(editor, name);
+    }
+  });
+}
@@ -6,6 +6,7 @@ import * as JsonPreview from "./json_preview";
 
 import * as ContextMenu from "./context_menu";
 import * as PageSettings from "./page_settings";
+import * as Hierarchy from "./hierarchy";
 
 import * as DragAndDrop from "./drag_drop";
 
@@ -18,6 +19,9 @@ export enum DataType {
   Number = "number",
   Boolean = "boolean",
   LayoutStyle = "layout_style",
+  Select = "select",
+  Url = "url",
+  Image = "image",
 }
 
 export async function run() {
@@ -45,6 +49,7 @@ export async function run() {
   // });
   editor.register_component({
     tag_name: "launch-button",
+    label: "Button",
     parameters: {
       innertext: DataType.Text,
       variant: DataType.Text,
@@ -64,6 +69,7 @@ export async function run() {
 
   editor.register_component({
     tag_name: "launch-text",
+    label: "Text",
     parameters: {
       innertext: DataType.Text,
       type: DataType.Text,
@@ -76,12 +82,16 @@ export async function run() {
   editor.register_component({
     tag_name: "launch-table",
     parameters: {},
+    constraints: {
+      default_span: [4, 2],
+      min_span: [2, 2],
+    },
   });
 
   editor.register_component({
     tag_name: "launch-pagination",
     parameters: {
-      pages: DataType.Number,
+      pages: { type: DataType.Number, default: 1, min: 1, step: 1 },
     },
   });
 
@@ -93,7 +103,8 @@ export async function run() {
   ContextMenu.connect(editor);
 
   Preview.connect();
-  JsonPreview.connect();
+  JsonPreview.connect(editor);
   PageSettings.connect(editor);
+  Hierarchy.connect(editor);
   DragAndDrop.connect(editor);
 }
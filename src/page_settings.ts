@@ -8,13 +8,22 @@ function connect_width_input(editor: Editor) {
   const minStr = target.getAttribute("min");
   const min = minStr ? parseInt(minStr) : 0;
 
+  // Resizes of one session are undone as a single step,
+  // it ends when the value is committed, or the pointer leaves the input after scrolling
+  let session = editor.new_input_session();
+  const end_session = () => {
+    session = editor.new_input_session();
+  };
+  target.addEventListener("change", end_session);
+  target.addEventListener("mouseleave", end_session);
+
   target.addEventListener("input", (event) => {
     const page = document.querySelector(".page") as HTMLElement;
 
     const value = parseInt(target.value);
 
     if (page && value >= min) {
-      editor.resize_page(page, value);
+      editor.resize_page(page, value, session);
     }
   });
 
@@ -27,7 +36,7 @@ function connect_width_input(editor: Editor) {
       if (page && value >= min) {
         target.value = value.toString();
 
-        editor.resize_page(page, value);
+        editor.resize_page(page, value, session);
       }
 
       event.preventDefault();
@@ -36,8 +45,151 @@ function connect_width_input(editor: Editor) {
   );
 }
 
+function update_breakpoint_select(editor: Editor, page: HTMLElement) {
+  const select = document.getElementById(
+    "page-breakpoint-select"
+  ) as HTMLSelectElement;
+
+  const breakpoints = JSON.parse(editor.breakpoints(page));
+  const active = editor.active_breakpoint(page);
+
+  select.innerHTML = "";
+  for (const breakpoint of breakpoints) {
+    const option = document.createElement("option");
+    option.value = breakpoint.name;
+    option.innerText = `${breakpoint.name} (${breakpoint.width}px)`;
+    option.selected = breakpoint.name == active;
+    select.appendChild(option);
+  }
+}
+
+function connect_breakpoint_select(editor: Editor) {
+  const select = document.getElementById(
+    "page-breakpoint-select"
+  ) as HTMLSelectElement;
+  const width_input = document.getElementById(
+    "page-width-input"
+  ) as HTMLInputElement;
+
+  // The page that the editor starts with has its default breakpoints already
+  const page = document.querySelector(".page") as HTMLElement;
+  if (page) {
+    update_breakpoint_select(editor, page);
+  }
+
+  select.addEventListener("change", () => {
+    const page = document.querySelector(".page.visible") as HTMLElement;
+
+    if (page && editor.set_breakpoint(page, select.value)) {
+      const breakpoint = JSON.parse(editor.breakpoints(page)).find(
+        (breakpoint) => breakpoint.name == select.value
+      );
+      width_input.value = breakpoint.width.toString();
+    }
+  });
+}
+
+function page_input(id: string) {
+  return document.getElementById(id) as HTMLInputElement;
+}
+
+/// Show the name and metadata of the visible page,
+/// route and title that follow the page name are shown as placeholders
+function update_page_inputs(editor: Editor) {
+  const page = document.querySelector(".page.visible") as HTMLElement;
+  if (!page) {
+    return;
+  }
+
+  const metadata = JSON.parse(editor.page_metadata(page));
+
+  page_input("page-name-input").value = editor.page_name(page) || "";
+
+  const route = page_input("page-route-input");
+  route.value = metadata.route || "";
+  route.placeholder = metadata.default_route;
+
+  const title = page_input("page-title-input");
+  title.value = metadata.title || "";
+  title.placeholder = metadata.default_title;
+
+  page_input("page-description-input").value = metadata.description;
+}
+
+function connect_page_inputs(editor: Editor) {
+  update_page_inputs(editor);
+
+  page_input("page-name-input").addEventListener("change", (event) => {
+    const page = document.querySelector(".page.visible") as HTMLElement;
+    const target = event.target as HTMLInputElement;
+
+    if (page) {
+      editor.rename_page(page, target.value);
+      update_page_inputs(editor);
+    }
+  });
+
+  const metadata_inputs = [
+    "page-route-input",
+    "page-title-input",
+    "page-description-input",
+  ];
+
+  for (const id of metadata_inputs) {
+    page_input(id).addEventListener("change", () => {
+      const page = document.querySelector(".page.visible") as HTMLElement;
+      if (!page) {
+        return;
+      }
+
+      const changed = editor.set_page_metadata(
+        page,
+        page_input("page-route-input").value,
+        page_input("page-title-input").value,
+        page_input("page-description-input").value
+      );
+
+      const route = page_input("page-route-input").value;
+      if (!changed && route) {
+        console.warn(`Route ${route} may already be used by a different page`);
+      }
+      update_page_inputs(editor);
+    });
+  }
+
+  document.getElementById("page-duplicate-btn").addEventListener("click", () => {
+    const page = document.querySelector(".page.visible") as HTMLElement;
+
+    if (page) {
+      editor.duplicate_page(page);
+      update_page_inputs(editor);
+    }
+  });
+
+  document.getElementById("page-delete-btn").addEventListener("click", () => {
+    const page = document.querySelector(".page.visible") as HTMLElement;
+
+    if (page) {
+      editor.remove_page(page);
+      update_page_inputs(editor);
+    }
+  });
+
+  // The visible page changes when a page is picked in the hierarchy, or a new one is added.
+  // Rust listeners are registered before this one, so the page is already switched
+  document.addEventListener("click", (event) => {
+    const target = event.target as HTMLElement;
+
+    if (target.closest("#inspector-tree, #add-page-btn")) {
+      update_page_inputs(editor);
+    }
+  });
+}
+
 export function connect(editor: Editor) {
   connect_width_input(editor);
+  connect_breakpoint_select(editor);
+  connect_page_inputs(editor);
 
   document
     .getElementById("page-fill-input")